@@ -0,0 +1,137 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A support library for procedural macro authors.
+//!
+//! Procedural macros are ordinary functions over token streams. They are
+//! defined at the root of a dylib crate with the `#[proc_macro]`,
+//! `#[proc_macro_attribute]` and `#[proc_macro_derive]` attributes:
+//!
+//! ```rust,ignore
+//! #![crate_type = "dylib"]
+//! #![feature(proc_macro, proc_macro_lib)]
+//!
+//! extern crate proc_macro;
+//!
+//! use proc_macro::TokenStream;
+//!
+//! // `sum!(a, b, c)` expands to `a + b + c`.
+//! #[proc_macro]
+//! pub fn sum(input: TokenStream) -> TokenStream {
+//!     input.to_string().replace(",", "+").parse().unwrap()
+//! }
+//! ```
+//!
+//! Other crates import them with `#[macro_use]` on the `extern crate` item of
+//! the defining crate. Unlike compiler plugins, procedural macros only see
+//! tokens, never the AST or other compiler internals.
+
+#![crate_name = "proc_macro"]
+#![unstable(feature = "proc_macro_lib", issue = "38356")]
+#![crate_type = "rlib"]
+#![crate_type = "dylib"]
+#![doc(html_logo_url = "https://www.rust-lang.org/logos/rust-logo-128x128-blk-v2.png",
+       html_favicon_url = "https://doc.rust-lang.org/favicon.ico",
+       html_root_url = "https://doc.rust-lang.org/nightly/")]
+#![cfg_attr(not(stage0), deny(warnings))]
+
+#![feature(rustc_private)]
+#![feature(staged_api)]
+
+extern crate syntax;
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+use syntax::tokenstream;
+
+/// The tokens a procedural macro is invoked on, or expands to.
+///
+/// Tokens taken from the input of a macro keep their spans, so errors in them
+/// point into the invocation. Tokens made up by the macro, e.g. by parsing a
+/// string, are given the span of the invocation.
+#[derive(Clone)]
+pub struct TokenStream {
+    inner: tokenstream::TokenStream,
+}
+
+/// The error returned when a string cannot be lexed into a `TokenStream`.
+#[derive(Debug)]
+pub struct LexError {
+    msg: String,
+}
+
+impl TokenStream {
+    /// Splits the stream into its token trees, i.e. single tokens and groups
+    /// of tokens in delimiters, which keep their spans.
+    pub fn trees(&self) -> Vec<TokenStream> {
+        self.inner.tts.iter().map(|tt| {
+            __internal::token_stream_wrap(tokenstream::TokenStream::from_tts(vec![tt.clone()]))
+        }).collect()
+    }
+}
+
+impl fmt::Display for TokenStream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl fmt::Debug for TokenStream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl FromStr for TokenStream {
+    type Err = LexError;
+
+    fn from_str(src: &str) -> Result<TokenStream, LexError> {
+        match src.parse() {
+            Ok(inner) => Ok(__internal::token_stream_wrap(inner)),
+            Err(msg) => Err(LexError { msg: msg }),
+        }
+    }
+}
+
+/// Concatenates token streams.
+impl FromIterator<TokenStream> for TokenStream {
+    fn from_iter<I: IntoIterator<Item = TokenStream>>(streams: I) -> TokenStream {
+        let mut tts = Vec::new();
+        for stream in streams {
+            tts.extend(stream.inner.tts);
+        }
+        __internal::token_stream_wrap(tokenstream::TokenStream::from_tts(tts))
+    }
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+/// Used by the compiler to hand token streams to procedural macros and to
+/// take them back. Not meant to be used by macros.
+#[doc(hidden)]
+pub mod __internal {
+    use syntax::tokenstream;
+
+    use super::TokenStream;
+
+    pub fn token_stream_wrap(inner: tokenstream::TokenStream) -> TokenStream {
+        TokenStream { inner: inner }
+    }
+
+    pub fn token_stream_inner(stream: TokenStream) -> tokenstream::TokenStream {
+        stream.inner
+    }
+}
@@ -1590,7 +1590,8 @@ impl Foo {
 ```
 
 * The `#[repr(C)]` attribute can only be placed on structs and enums.
-* The `#[repr(packed)]` and `#[repr(simd)]` attributes only work on structs.
+* The `#[repr(packed)]`, `#[repr(align(N))]` and `#[repr(simd)]` attributes
+  only work on structs.
 * The `#[repr(u8)]`, `#[repr(i16)]`, etc attributes only work on enums.
 
 These attributes do not work on typedefs, since typedefs are just aliases.
@@ -1609,6 +1610,9 @@ not apply to structs.
 representation of enums isn't strictly defined in Rust, and this attribute
 won't work on enums.
 
+`#[repr(align(N))]` raises the alignment of a struct to at least `N` bytes.
+Enums have no single layout for this hint to adjust.
+
 `#[repr(simd)]` will give a struct consisting of a homogenous series of machine
 types (i.e. `u8`, `i32`, etc) a representation that permits vectorization via
 SIMD. This doesn't make much sense for enums since they don't consist of a
@@ -1641,6 +1645,64 @@ each method; it is not possible to annotate the entire impl with an `#[inline]`
 attribute.
 "##,
 
+E0566: r##"
+The argument of a `#[repr(align(N))]` or `#[repr(packed(N))]` attribute was
+invalid.
+
+Erroneous code example:
+
+```compile_fail,E0566
+#![feature(repr_align)]
+
+#[repr(align(3))] // error: invalid `repr(align)` attribute: not a power of two
+struct Foo(u8);
+```
+
+The argument must be a single unsuffixed integer, which is an alignment in
+bytes. It has to be a power of two, no larger than 2^15 (32768):
+
+```
+#![feature(repr_align)]
+
+#[repr(align(4))]
+struct Foo(u8);
+```
+"##,
+
+E0567: r##"
+A struct was given both a `packed` and an `align` representation hint.
+
+Erroneous code example:
+
+```compile_fail,E0567
+#![feature(repr_align)]
+
+#[repr(packed, align(8))] // error: conflicting representation hints
+struct Foo {
+    a: u8,
+    b: u32,
+}
+```
+
+`#[repr(packed)]` lowers the alignment of a struct, while `#[repr(align(N))]`
+raises it, so the two hints cannot be combined. To get a struct without inner
+padding that is still placed on an aligned boundary, wrap the packed struct in
+an aligned one:
+
+```
+#![feature(repr_align)]
+
+#[repr(packed)]
+struct Packed {
+    a: u8,
+    b: u32,
+}
+
+#[repr(align(8))]
+struct Foo(Packed);
+```
+"##,
+
 E0522: r##"
 The lang attribute is intended for marking special items that are built-in to
 Rust itself. This includes special traits (like `Copy` and `Sized`) that affect
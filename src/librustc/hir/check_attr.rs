@@ -11,7 +11,9 @@
 use session::Session;
 
 use syntax::ast;
+use syntax::attr;
 use syntax::attr::AttrMetaMethods;
+use syntax::ptr::P;
 use syntax::visit;
 use syntax::visit::Visitor;
 
@@ -19,6 +21,7 @@ use syntax::visit::Visitor;
 enum Target {
     Fn,
     Struct,
+    Union,
     Enum,
     Other,
 }
@@ -28,6 +31,7 @@ impl Target {
         match item.node {
             ast::ItemKind::Fn(..) => Target::Fn,
             ast::ItemKind::Struct(..) => Target::Struct,
+            ast::ItemKind::Union(..) => Target::Union,
             ast::ItemKind::Enum(..) => Target::Enum,
             _ => Target::Other,
         }
@@ -53,16 +57,22 @@ impl<'a> CheckAttrVisitor<'a> {
             }
         };
         for word in words {
+            if let Some(args) = word.meta_item_list() {
+                self.check_repr_alignment(word, args);
+            }
             let word: &str = &word.name();
             let message = match word {
                 "C" => {
-                    if target != Target::Struct && target != Target::Enum {
+                    if target != Target::Struct &&
+                       target != Target::Union &&
+                       target != Target::Enum {
                             "attribute should be applied to struct or enum"
                     } else {
                         continue
                     }
                 }
                 "packed" |
+                "align" |
                 "simd" => {
                     if target != Target::Struct {
                         "attribute should be applied to struct"
@@ -85,6 +95,37 @@ impl<'a> CheckAttrVisitor<'a> {
         }
     }
 
+    fn check_repr_alignment(&self, hint: &ast::MetaItem, args: &[P<ast::MetaItem>]) {
+        let name: &str = &hint.name();
+        if name != "align" && name != "packed" {
+            return;
+        }
+        if let Err(msg) = attr::parse_repr_alignment(args) {
+            span_err!(self.sess, hint.span, E0566,
+                      "invalid `repr({})` attribute: {}", name, msg);
+        }
+    }
+
+    /// `packed` and `align` hints pull the alignment of a struct in opposite
+    /// directions, so they may not be combined, even across attributes.
+    fn check_repr_conflicts(&self, item: &ast::Item) {
+        let mut packed = false;
+        let mut align = None;
+        for attr in item.attrs.iter().filter(|attr| attr.check_name("repr")) {
+            for word in attr.meta_item_list().unwrap_or(&[]) {
+                match &*word.name() {
+                    "packed" => packed = true,
+                    "align" => align = Some(word.span),
+                    _ => {}
+                }
+            }
+        }
+        if let (true, Some(span)) = (packed, align) {
+            span_err!(self.sess, span, E0567,
+                      "conflicting representation hints: `packed` and `align`");
+        }
+    }
+
     fn check_attribute(&self, attr: &ast::Attribute, target: Target) {
         let name: &str = &attr.name();
         match name {
@@ -101,6 +142,7 @@ impl<'a> Visitor for CheckAttrVisitor<'a> {
         for attr in &item.attrs {
             self.check_attribute(attr, target);
         }
+        self.check_repr_conflicts(item);
         visit::walk_item(self, item);
     }
 }
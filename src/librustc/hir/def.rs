@@ -41,6 +41,7 @@ pub enum Def {
     // to NodeId of the struct itself.
     // If Def::Struct lives in value namespace (e.g. tuple struct, unit struct expressions)
     // it denotes a constructor and its DefId refers to NodeId of the struct's constructor.
+    // Unions are represented by Def::Struct as well, and only live in type namespace.
     Struct(DefId),
     Label(ast::NodeId),
     Method(DefId),
@@ -337,7 +337,7 @@ pub fn noop_fold_ty<T: Folder>(t: P<Ty>, fld: &mut T) -> P<Ty> {
         Ty {
             id: fld.new_id(id),
             node: match node {
-                TyInfer => node,
+                TyInfer | TyNever => node,
                 TyVec(ty) => TyVec(fld.fold_ty(ty)),
                 TyPtr(mt) => TyPtr(fld.fold_mt(mt)),
                 TyRptr(region, mt) => {
@@ -372,9 +372,15 @@ pub fn noop_fold_ty<T: Folder>(t: P<Ty>, fld: &mut T) -> P<Ty> {
                 TyTypeof(expr) => {
                     TyTypeof(fld.fold_expr(expr))
                 }
+                TyConstArg(expr) => {
+                    TyConstArg(fld.fold_expr(expr))
+                }
                 TyPolyTraitRef(bounds) => {
                     TyPolyTraitRef(bounds.move_map(|b| fld.fold_ty_param_bound(b)))
                 }
+                TyImplTrait(bounds) => {
+                    TyImplTrait(bounds.move_map(|b| fld.fold_ty_param_bound(b)))
+                }
             },
             span: fld.new_span(span),
         }
@@ -529,12 +535,13 @@ pub fn noop_fold_ty_param_bound<T>(tpb: TyParamBound, fld: &mut T) -> TyParamBou
 }
 
 pub fn noop_fold_ty_param<T: Folder>(tp: TyParam, fld: &mut T) -> TyParam {
-    let TyParam {id, name, bounds, default, span} = tp;
+    let TyParam {id, name, bounds, default, const_ty, span} = tp;
     TyParam {
         id: fld.new_id(id),
         name: name,
         bounds: fld.fold_bounds(bounds),
         default: default.map(|x| fld.fold_ty(x)),
+        const_ty: const_ty.map(|x| fld.fold_ty(x)),
         span: span,
     }
 }
@@ -746,10 +753,14 @@ pub fn noop_fold_item_underscore<T: Folder>(i: Item_, folder: &mut T) -> Item_ {
             let struct_def = folder.fold_variant_data(struct_def);
             ItemStruct(struct_def, folder.fold_generics(generics))
         }
+        ItemUnion(struct_def, generics) => {
+            let struct_def = folder.fold_variant_data(struct_def);
+            ItemUnion(struct_def, folder.fold_generics(generics))
+        }
         ItemDefaultImpl(unsafety, ref trait_ref) => {
             ItemDefaultImpl(unsafety, folder.fold_trait_ref((*trait_ref).clone()))
         }
-        ItemImpl(unsafety, polarity, generics, ifce, ty, impl_items) => {
+        ItemImpl(unsafety, polarity, defaultness, generics, ifce, ty, impl_items) => {
             let new_impl_items = impl_items
                 .move_map(|item| folder.fold_impl_item(item));
             let ifce = match ifce {
@@ -760,6 +771,7 @@ pub fn noop_fold_item_underscore<T: Folder>(i: Item_, folder: &mut T) -> Item_ {
             };
             ItemImpl(unsafety,
                      polarity,
+                     defaultness,
                      folder.fold_generics(generics),
                      ifce,
                      folder.fold_ty(ty),
@@ -788,8 +800,9 @@ pub fn noop_fold_trait_item<T: Folder>(i: TraitItem,
                 MethodTraitItem(noop_fold_method_sig(sig, folder),
                                 body.map(|x| folder.fold_block(x)))
             }
-            TypeTraitItem(bounds, default) => {
-                TypeTraitItem(folder.fold_bounds(bounds),
+            TypeTraitItem(generics, bounds, default) => {
+                TypeTraitItem(folder.fold_generics(generics),
+                              folder.fold_bounds(bounds),
                               default.map(|x| folder.fold_ty(x)))
             }
         },
@@ -811,7 +824,9 @@ pub fn noop_fold_impl_item<T: Folder>(i: ImplItem, folder: &mut T) -> ImplItem {
             ImplItemKind::Method(sig, body) => {
                 ImplItemKind::Method(noop_fold_method_sig(sig, folder), folder.fold_block(body))
             }
-            ImplItemKind::Type(ty) => ImplItemKind::Type(folder.fold_ty(ty)),
+            ImplItemKind::Type(generics, ty) => {
+                ImplItemKind::Type(folder.fold_generics(generics), folder.fold_ty(ty))
+            }
         },
         span: folder.new_span(i.span),
     }
@@ -325,13 +325,18 @@ pub fn walk_item<'v, V: Visitor<'v>>(visitor: &mut V, item: &'v Item) {
         ItemDefaultImpl(_, ref trait_ref) => {
             visitor.visit_trait_ref(trait_ref)
         }
-        ItemImpl(_, _, ref type_parameters, ref opt_trait_reference, ref typ, ref impl_items) => {
+        ItemImpl(_, _, _,
+                 ref type_parameters,
+                 ref opt_trait_reference,
+                 ref typ,
+                 ref impl_items) => {
             visitor.visit_generics(type_parameters);
             walk_list!(visitor, visit_trait_ref, opt_trait_reference);
             visitor.visit_ty(typ);
             walk_list!(visitor, visit_impl_item, impl_items);
         }
-        ItemStruct(ref struct_definition, ref generics) => {
+        ItemStruct(ref struct_definition, ref generics) |
+        ItemUnion(ref struct_definition, ref generics) => {
             visitor.visit_generics(generics);
             visitor.visit_variant_data(struct_definition, item.name, generics, item.id, item.span);
         }
@@ -405,10 +410,13 @@ pub fn walk_ty<'v, V: Visitor<'v>>(visitor: &mut V, typ: &'v Ty) {
         TyPolyTraitRef(ref bounds) => {
             walk_list!(visitor, visit_ty_param_bound, bounds);
         }
-        TyTypeof(ref expression) => {
+        TyImplTrait(ref bounds) => {
+            walk_list!(visitor, visit_ty_param_bound, bounds);
+        }
+        TyTypeof(ref expression) | TyConstArg(ref expression) => {
             visitor.visit_expr(expression)
         }
-        TyInfer => {}
+        TyInfer | TyNever => {}
     }
 }
 
@@ -529,6 +537,7 @@ pub fn walk_generics<'v, V: Visitor<'v>>(visitor: &mut V, generics: &'v Generics
         visitor.visit_name(param.span, param.name);
         walk_list!(visitor, visit_ty_param_bound, &param.bounds);
         walk_list!(visitor, visit_ty, &param.default);
+        walk_list!(visitor, visit_ty, &param.const_ty);
     }
     walk_list!(visitor, visit_lifetime_def, &generics.lifetimes);
     walk_list!(visitor, visit_where_predicate, &generics.where_clause.predicates);
@@ -628,7 +637,8 @@ pub fn walk_trait_item<'v, V: Visitor<'v>>(visitor: &mut V, trait_item: &'v Trai
                              trait_item.span,
                              trait_item.id);
         }
-        TypeTraitItem(ref bounds, ref default) => {
+        TypeTraitItem(ref generics, ref bounds, ref default) => {
+            visitor.visit_generics(generics);
             walk_list!(visitor, visit_ty_param_bound, bounds);
             walk_list!(visitor, visit_ty, default);
         }
@@ -654,7 +664,8 @@ pub fn walk_impl_item<'v, V: Visitor<'v>>(visitor: &mut V, impl_item: &'v ImplIt
                              impl_item.span,
                              impl_item.id);
         }
-        ImplItemKind::Type(ref ty) => {
+        ImplItemKind::Type(ref generics, ref ty) => {
+            visitor.visit_generics(generics);
             visitor.visit_ty(ty);
         }
     }
@@ -53,7 +53,7 @@ use syntax::ast::*;
 use syntax::errors;
 use syntax::ptr::P;
 use syntax::codemap::{respan, Spanned};
-use syntax::parse::token;
+use syntax::parse::token::{self, keywords};
 use syntax::std_inject;
 use syntax::visit::{self, Visitor};
 use syntax_pos::Span;
@@ -257,6 +257,7 @@ impl<'a> LoweringContext<'a> {
             id: t.id,
             node: match t.node {
                 Infer | ImplicitSelf => hir::TyInfer,
+                Never => hir::TyNever,
                 Vec(ref ty) => hir::TyVec(self.lower_ty(ty)),
                 Ptr(ref mt) => hir::TyPtr(self.lower_mt(mt)),
                 Rptr(ref region, ref mt) => {
@@ -292,10 +293,16 @@ impl<'a> LoweringContext<'a> {
                 Typeof(ref expr) => {
                     hir::TyTypeof(self.lower_expr(expr))
                 }
+                ConstArg(ref expr) => {
+                    hir::TyConstArg(self.lower_expr(expr))
+                }
                 PolyTraitRef(ref bounds) => {
                     let bounds = bounds.iter().map(|b| self.lower_ty_param_bound(b)).collect();
                     hir::TyPolyTraitRef(bounds)
                 }
+                ImplTrait(ref bounds) => {
+                    hir::TyImplTrait(self.lower_bounds(bounds))
+                }
                 Mac(_) => panic!("TyMac should have been expanded by now."),
             },
             span: t.span,
@@ -424,6 +431,7 @@ impl<'a> LoweringContext<'a> {
             name: tp.ident.name,
             bounds: self.lower_bounds(&tp.bounds),
             default: tp.default.as_ref().map(|x| self.lower_ty(x)),
+            const_ty: tp.const_ty.as_ref().map(|x| self.lower_ty(x)),
             span: tp.span,
         }
     }
@@ -641,17 +649,28 @@ impl<'a> LoweringContext<'a> {
                 let struct_def = self.lower_variant_data(struct_def);
                 hir::ItemStruct(struct_def, self.lower_generics(generics))
             }
+            ItemKind::Union(ref vdata, ref generics) => {
+                let vdata = self.lower_variant_data(vdata);
+                hir::ItemUnion(vdata, self.lower_generics(generics))
+            }
             ItemKind::DefaultImpl(unsafety, ref trait_ref) => {
                 hir::ItemDefaultImpl(self.lower_unsafety(unsafety),
                                      self.lower_trait_ref(trait_ref))
             }
-            ItemKind::Impl(unsafety, polarity, ref generics, ref ifce, ref ty, ref impl_items) => {
+            ItemKind::Impl(unsafety,
+                           polarity,
+                           defaultness,
+                           ref generics,
+                           ref ifce,
+                           ref ty,
+                           ref impl_items) => {
                 let new_impl_items = impl_items.iter()
                                                .map(|item| self.lower_impl_item(item))
                                                .collect();
                 let ifce = ifce.as_ref().map(|trait_ref| self.lower_trait_ref(trait_ref));
                 hir::ItemImpl(self.lower_unsafety(unsafety),
                               self.lower_impl_polarity(polarity),
+                              self.lower_defaultness(defaultness),
                               self.lower_generics(generics),
                               ifce,
                               self.lower_ty(ty),
@@ -684,8 +703,9 @@ impl<'a> LoweringContext<'a> {
                         hir::MethodTraitItem(this.lower_method_sig(sig),
                                              body.as_ref().map(|x| this.lower_block(x)))
                     }
-                    TraitItemKind::Type(ref bounds, ref default) => {
-                        hir::TypeTraitItem(this.lower_bounds(bounds),
+                    TraitItemKind::Type(ref generics, ref bounds, ref default) => {
+                        hir::TypeTraitItem(this.lower_generics(generics),
+                                           this.lower_bounds(bounds),
                                            default.as_ref().map(|x| this.lower_ty(x)))
                     }
                     TraitItemKind::Macro(..) => panic!("Shouldn't exist any more"),
@@ -711,7 +731,9 @@ impl<'a> LoweringContext<'a> {
                         hir::ImplItemKind::Method(this.lower_method_sig(sig),
                                                   this.lower_block(body))
                     }
-                    ImplItemKind::Type(ref ty) => hir::ImplItemKind::Type(this.lower_ty(ty)),
+                    ImplItemKind::Type(ref generics, ref ty) => {
+                        hir::ImplItemKind::Type(this.lower_generics(generics), this.lower_ty(ty))
+                    }
                     ImplItemKind::Macro(..) => panic!("Shouldn't exist any more"),
                 },
                 span: i.span,
@@ -1575,6 +1597,12 @@ impl<'a> LoweringContext<'a> {
                                            hir::MatchSource::TryDesugar);
                 }
 
+                // The resolver maps placeholders to `Def::Err`, so the type
+                // checker gives them the error type without complaining.
+                ExprKind::Err => {
+                    hir::ExprPath(None, self.path_ident(e.span, keywords::Invalid.name()))
+                }
+
                 ExprKind::Mac(_) => panic!("Shouldn't exist here"),
             },
             span: e.span,
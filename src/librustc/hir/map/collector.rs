@@ -200,6 +200,14 @@ impl<'ast> Visitor<'ast> for NodeCollector<'ast> {
         intravisit::walk_fn(self, fk, fd, b, s);
     }
 
+    fn visit_ty(&mut self, ty: &'ast Ty) {
+        self.insert(ty.id, NodeTy(ty));
+
+        self.with_parent(ty.id, |this| {
+            intravisit::walk_ty(this, ty);
+        });
+    }
+
     fn visit_block(&mut self, block: &'ast Block) {
         self.insert(block.id, NodeBlock(block));
         self.with_parent(block.id, |this| {
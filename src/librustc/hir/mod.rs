@@ -291,6 +291,8 @@ pub struct TyParam {
     pub id: NodeId,
     pub bounds: TyParamBounds,
     pub default: Option<P<Ty>>,
+    /// The type of a `const` parameter, e.g. `usize` in `const N: usize`.
+    pub const_ty: Option<P<Ty>>,
     pub span: Span,
 }
 
@@ -1006,9 +1008,9 @@ pub enum TraitItem_ {
     ConstTraitItem(P<Ty>, Option<P<Expr>>),
     /// A method with an optional body
     MethodTraitItem(MethodSig, Option<P<Block>>),
-    /// An associated type with (possibly empty) bounds and optional concrete
-    /// type
-    TypeTraitItem(TyParamBounds, Option<P<Ty>>),
+    /// An associated type with its own (possibly empty) generics, (possibly
+    /// empty) bounds and optional concrete type
+    TypeTraitItem(Generics, TyParamBounds, Option<P<Ty>>),
 }
 
 /// Represents anything within an `impl` block
@@ -1031,8 +1033,8 @@ pub enum ImplItemKind {
     Const(P<Ty>, P<Expr>),
     /// A method implementation with the given signature and body
     Method(MethodSig, P<Block>),
-    /// An associated type
-    Type(P<Ty>),
+    /// An associated type, with its own (possibly empty) generics
+    Type(Generics, P<Ty>),
 }
 
 // Bind a type to an associated type: `A=Foo`.
@@ -1089,6 +1091,8 @@ pub enum Ty_ {
     TyRptr(Option<Lifetime>, MutTy),
     /// A bare function (e.g. `fn(usize) -> bool`)
     TyBareFn(P<BareFnTy>),
+    /// The never type (`!`)
+    TyNever,
     /// A tuple (`(A, B, C, D,...)`)
     TyTup(HirVec<P<Ty>>),
     /// A path (`module::module::...::Type`), optionally
@@ -1100,8 +1104,14 @@ pub enum Ty_ {
     TyObjectSum(P<Ty>, TyParamBounds),
     /// A type like `for<'a> Foo<&'a Bar>`
     TyPolyTraitRef(TyParamBounds),
+    /// An `impl Bound1 + Bound2 + Bound3` type
+    /// where `Bound` is a trait or a lifetime.
+    TyImplTrait(TyParamBounds),
     /// Unused for now
     TyTypeof(P<Expr>),
+    /// A constant argument to a `const` type parameter, e.g. the `3` in
+    /// `Buf<3>` or the `{ 2 * 8 }` in `Buf<{ 2 * 8 }>`
+    TyConstArg(P<Expr>),
     /// TyInfer means the type should be inferred instead of it having been
     /// specified. This can appear anywhere in a type.
     TyInfer,
@@ -1509,6 +1519,8 @@ pub enum Item_ {
     ItemEnum(EnumDef, Generics),
     /// A struct definition, e.g. `struct Foo<A> {x: A}`
     ItemStruct(VariantData, Generics),
+    /// A union definition, e.g. `union Foo<A, B> {x: A, y: B}`
+    ItemUnion(VariantData, Generics),
     /// Represents a Trait Declaration
     ItemTrait(Unsafety, Generics, TyParamBounds, HirVec<TraitItem>),
 
@@ -1517,8 +1529,10 @@ pub enum Item_ {
     /// `impl Trait for .. {}`
     ItemDefaultImpl(Unsafety, TraitRef),
     /// An implementation, eg `impl<A> Trait for Foo { .. }`
+    /// or `default impl<A> Trait for Foo { .. }`
     ItemImpl(Unsafety,
              ImplPolarity,
+             Defaultness,
              Generics,
              Option<TraitRef>, // (optional) trait this impl implements
              P<Ty>, // self
@@ -1538,6 +1552,7 @@ impl Item_ {
             ItemTy(..) => "type alias",
             ItemEnum(..) => "enum",
             ItemStruct(..) => "struct",
+            ItemUnion(..) => "union",
             ItemTrait(..) => "trait",
             ItemImpl(..) |
             ItemDefaultImpl(..) => "item",
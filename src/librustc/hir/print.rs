@@ -504,6 +504,9 @@ impl<'a> State<'a> {
                 self.print_opt_lifetime(lifetime)?;
                 self.print_mt(mt)?;
             }
+            hir::TyNever => {
+                word(&mut self.s, "!")?;
+            }
             hir::TyTup(ref elts) => {
                 self.popen()?;
                 self.commasep(Inconsistent, &elts[..], |s, ty| s.print_type(&ty))?;
@@ -536,6 +539,9 @@ impl<'a> State<'a> {
             hir::TyPolyTraitRef(ref bounds) => {
                 self.print_bounds("", &bounds[..])?;
             }
+            hir::TyImplTrait(ref bounds) => {
+                self.print_bounds("impl", &bounds[..])?;
+            }
             hir::TyFixedLengthVec(ref ty, ref v) => {
                 word(&mut self.s, "[")?;
                 self.print_type(&ty)?;
@@ -548,6 +554,9 @@ impl<'a> State<'a> {
                 self.print_expr(&e)?;
                 word(&mut self.s, ")")?;
             }
+            hir::TyConstArg(ref e) => {
+                self.print_expr(&e)?;
+            }
             hir::TyInfer => {
                 word(&mut self.s, "_")?;
             }
@@ -609,14 +618,17 @@ impl<'a> State<'a> {
 
     fn print_associated_type(&mut self,
                              name: ast::Name,
+                             generics: &hir::Generics,
                              bounds: Option<&hir::TyParamBounds>,
                              ty: Option<&hir::Ty>)
                              -> io::Result<()> {
         self.word_space("type")?;
         self.print_name(name)?;
+        self.print_generics(generics)?;
         if let Some(bounds) = bounds {
             self.print_bounds(":", bounds)?;
         }
+        self.print_where_clause(&generics.where_clause)?;
         if let Some(ty) = ty {
             space(&mut self.s)?;
             self.word_space("=")?;
@@ -745,6 +757,10 @@ impl<'a> State<'a> {
                 self.head(&visibility_qualified(&item.vis, "struct"))?;
                 self.print_struct(struct_def, generics, item.name, item.span, true)?;
             }
+            hir::ItemUnion(ref struct_def, ref generics) => {
+                self.head(&visibility_qualified(&item.vis, "union"))?;
+                self.print_struct(struct_def, generics, item.name, item.span, true)?;
+            }
 
             hir::ItemDefaultImpl(unsafety, ref trait_ref) => {
                 self.head("")?;
@@ -760,12 +776,16 @@ impl<'a> State<'a> {
             }
             hir::ItemImpl(unsafety,
                           polarity,
+                          defaultness,
                           ref generics,
                           ref opt_trait,
                           ref ty,
                           ref impl_items) => {
                 self.head("")?;
                 self.print_visibility(&item.vis)?;
+                if let hir::Defaultness::Default = defaultness {
+                    self.word_nbsp("default")?;
+                }
                 self.print_unsafety(unsafety)?;
                 self.word_nbsp("impl")?;
 
@@ -997,8 +1017,9 @@ impl<'a> State<'a> {
                     word(&mut self.s, ";")?;
                 }
             }
-            hir::TypeTraitItem(ref bounds, ref default) => {
+            hir::TypeTraitItem(ref generics, ref bounds, ref default) => {
                 self.print_associated_type(ti.name,
+                                           generics,
                                            Some(bounds),
                                            default.as_ref().map(|ty| &**ty))?;
             }
@@ -1026,8 +1047,8 @@ impl<'a> State<'a> {
                 self.nbsp()?;
                 self.print_block_with_attrs(body, &ii.attrs)?;
             }
-            hir::ImplItemKind::Type(ref ty) => {
-                self.print_associated_type(ii.name, None, Some(ty))?;
+            hir::ImplItemKind::Type(ref generics, ref ty) => {
+                self.print_associated_type(ii.name, generics, None, Some(ty))?;
             }
         }
         self.ann.post(self, NodeSubItem(ii.id))
@@ -2045,6 +2066,12 @@ impl<'a> State<'a> {
     }
 
     pub fn print_ty_param(&mut self, param: &hir::TyParam) -> io::Result<()> {
+        if let Some(ref const_ty) = param.const_ty {
+            self.word_nbsp("const")?;
+            self.print_name(param.name)?;
+            self.word_space(":")?;
+            return self.print_type(&const_ty);
+        }
         self.print_name(param.name)?;
         self.print_bounds(":", &param.bounds)?;
         match param.default {
@@ -159,7 +159,10 @@ impl<'a, 'gcx, 'tcx> TypeFolder<'gcx, 'tcx> for TypeFreshener<'a, 'gcx, 'tcx> {
             ty::TyBox(..) |
             ty::TyStr |
             ty::TyError |
+            ty::TyNever |
+            ty::TyConst(_) |
             ty::TyArray(..) |
+            ty::TyGenericArray(..) |
             ty::TySlice(..) |
             ty::TyRawPtr(..) |
             ty::TyRef(..) |
@@ -170,6 +173,7 @@ impl<'a, 'gcx, 'tcx> TypeFolder<'gcx, 'tcx> for TypeFreshener<'a, 'gcx, 'tcx> {
             ty::TyClosure(..) |
             ty::TyTuple(..) |
             ty::TyProjection(..) |
+            ty::TyAnon(..) |
             ty::TyParam(..) => {
                 t.super_fold_with(self)
             }
@@ -19,7 +19,7 @@ use ty::MethodCall;
 
 use syntax::ast;
 use syntax_pos::Span;
-use hir;
+use hir::{self, PatKind};
 use hir::intravisit;
 use hir::intravisit::{FnKind, Visitor};
 
@@ -50,6 +50,13 @@ fn type_is_unsafe_function(ty: Ty) -> bool {
     }
 }
 
+fn type_is_union(ty: Ty) -> bool {
+    match ty.sty {
+        ty::TyStruct(def, _) => def.is_union(),
+        _ => false,
+    }
+}
+
 struct EffectCheckVisitor<'a, 'tcx: 'a> {
     tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
@@ -176,11 +183,45 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
                     self.require_unsafe(expr.span, "use of mutable static");
                 }
             }
+            hir::ExprField(ref base, field) => {
+                if type_is_union(self.tcx.expr_ty_adjusted(base)) {
+                    self.require_unsafe(field.span, "access to union field");
+                }
+            }
+            hir::ExprAssign(ref lhs, ref rhs) => {
+                // Writing a whole union field is safe, it can't observe
+                // the bytes of any other field. Unless the old value has to
+                // be dropped first, which reads the field.
+                if let hir::ExprField(ref base, field) = lhs.node {
+                    if type_is_union(self.tcx.expr_ty_adjusted(base)) {
+                        let field_ty = self.tcx.expr_ty(lhs);
+                        if field_ty.type_contents(self.tcx).needs_drop(self.tcx) {
+                            self.require_unsafe(field.span,
+                                                "assignment to union field that needs drop");
+                        }
+                        self.visit_expr(base);
+                        self.visit_expr(rhs);
+                        return;
+                    }
+                }
+            }
             _ => {}
         }
 
         intravisit::walk_expr(self, expr);
     }
+
+    fn visit_pat(&mut self, pat: &hir::Pat) {
+        if let PatKind::Struct(_, ref fields, _) = pat.node {
+            if type_is_union(self.tcx.pat_ty(pat)) {
+                for field in fields {
+                    self.require_unsafe(field.span, "matching on union field");
+                }
+            }
+        }
+
+        intravisit::walk_pat(self, pat);
+    }
 }
 
 pub fn check_crate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
@@ -155,8 +155,9 @@ impl<'a, 'tcx, 'v> Visitor<'v> for LifetimeContext<'a, 'tcx> {
                 hir::ItemTy(_, ref generics) |
                 hir::ItemEnum(_, ref generics) |
                 hir::ItemStruct(_, ref generics) |
+                hir::ItemUnion(_, ref generics) |
                 hir::ItemTrait(_, ref generics, _, _) |
-                hir::ItemImpl(_, _, ref generics, _, _, _) => {
+                hir::ItemImpl(_, _, _, ref generics, _, _, _) => {
                     // These kinds of items have only early bound lifetime parameters.
                     let lifetimes = &generics.lifetimes;
                     let early_scope = EarlyScope(subst::TypeSpace, lifetimes, &ROOT_SCOPE);
@@ -261,18 +262,32 @@ impl<'a, 'tcx, 'v> Visitor<'v> for LifetimeContext<'a, 'tcx> {
         // methods in an impl can reuse label names.
         let saved = replace(&mut self.labels_in_fn, vec![]);
 
-        if let hir::MethodTraitItem(ref sig, None) = trait_item.node {
-            self.visit_early_late(
-                trait_item.id, subst::FnSpace,
-                &sig.decl, &sig.generics,
-                |this| intravisit::walk_trait_item(this, trait_item))
-        } else {
-            intravisit::walk_trait_item(self, trait_item);
+        match trait_item.node {
+            hir::MethodTraitItem(ref sig, None) => {
+                self.visit_early_late(
+                    trait_item.id, subst::FnSpace,
+                    &sig.decl, &sig.generics,
+                    |this| intravisit::walk_trait_item(this, trait_item))
+            }
+            hir::TypeTraitItem(ref generics, _, _) => {
+                self.visit_associated_type(generics,
+                                           |this| intravisit::walk_trait_item(this, trait_item))
+            }
+            _ => intravisit::walk_trait_item(self, trait_item)
         }
 
         replace(&mut self.labels_in_fn, saved);
     }
 
+    fn visit_impl_item(&mut self, impl_item: &hir::ImplItem) {
+        if let hir::ImplItemKind::Type(ref generics, _) = impl_item.node {
+            self.visit_associated_type(generics,
+                                       |this| intravisit::walk_impl_item(this, impl_item))
+        } else {
+            intravisit::walk_impl_item(self, impl_item);
+        }
+    }
+
     fn visit_lifetime(&mut self, lifetime_ref: &hir::Lifetime) {
         if lifetime_ref.name == keywords::StaticLifetime.name() {
             self.insert_lifetime(lifetime_ref, DefStaticRegion);
@@ -584,6 +599,18 @@ impl<'a, 'tcx> LifetimeContext<'a, 'tcx> {
         });
     }
 
+    /// Visits an associated type, whose own lifetime parameters are all early
+    /// bound, in the same parameter space as those of a method.
+    fn visit_associated_type<F>(&mut self, generics: &hir::Generics, walk: F) where
+        F: FnOnce(&mut LifetimeContext),
+    {
+        let lifetimes = &generics.lifetimes;
+        self.with(EarlyScope(subst::FnSpace, lifetimes, self.scope), move |old_scope, this| {
+            this.check_lifetime_defs(old_scope, lifetimes);
+            walk(this);
+        });
+    }
+
     fn resolve_lifetime_ref(&mut self, lifetime_ref: &hir::Lifetime) {
         // Walk up the scope chain, tracking the number of fn scopes
         // that we pass through, until we find a lifetime with the
@@ -812,7 +839,8 @@ fn search_lifetimes<'a>(lifetimes: &'a [hir::LifetimeDef],
 ///
 /// A region declared on a fn is **late-bound** if:
 /// - it is constrained by an argument type;
-/// - it does not appear in a where-clause.
+/// - it does not appear in a where-clause;
+/// - it does not appear in an `impl Trait` in the return type.
 ///
 /// "Constrained" basically means that it appears in any type but
 /// not amongst the inputs to a projection.  In other words, `<&'a
@@ -857,6 +885,12 @@ fn insert_late_bound_lifetimes(map: &mut NamedRegionMap,
     debug!("insert_late_bound_lifetimes: appears_in_where_clause={:?}",
            appears_in_where_clause.regions);
 
+    // Lifetimes named in an `impl Trait` return type parameterize the
+    // anonymized type, which outlives the signature, so they must be
+    // early-bound too.
+    let mut appears_in_anon_type = AnonTypeCollector { regions: FnvHashSet() };
+    intravisit::walk_fn_ret_ty(&mut appears_in_anon_type, &decl.output);
+
     // Late bound regions are those that:
     // - appear in the inputs
     // - do not appear in the where-clauses
@@ -866,6 +900,9 @@ fn insert_late_bound_lifetimes(map: &mut NamedRegionMap,
         // appears in the where clauses? early-bound.
         if appears_in_where_clause.regions.contains(&name) { continue; }
 
+        // appears in an anonymized return type? early-bound.
+        if appears_in_anon_type.regions.contains(&name) { continue; }
+
         // does not appear in the inputs, but appears in the return
         // type? eventually this will be early-bound, but for now we
         // just mark it so we can issue warnings.
@@ -935,6 +972,22 @@ fn insert_late_bound_lifetimes(map: &mut NamedRegionMap,
             self.regions.insert(lifetime_ref.name);
         }
     }
+
+    struct AnonTypeCollector {
+        regions: FnvHashSet<ast::Name>,
+    }
+
+    impl<'v> Visitor<'v> for AnonTypeCollector {
+        fn visit_ty(&mut self, ty: &'v hir::Ty) {
+            if let hir::TyImplTrait(_) = ty.node {
+                let mut collector = AllCollector { regions: FnvHashSet() };
+                collector.visit_ty(ty);
+                self.regions.extend(collector.regions);
+            } else {
+                intravisit::walk_ty(self, ty);
+            }
+        }
+    }
 }
 
 impl<'a> fmt::Debug for ScopeChain<'a> {
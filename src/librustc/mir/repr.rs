@@ -947,7 +947,10 @@ pub enum CastKind {
 pub enum AggregateKind<'tcx> {
     Vec,
     Tuple,
-    Adt(AdtDef<'tcx>, usize, &'tcx Substs<'tcx>),
+    /// The second field is the variant index. The last field is the index
+    /// of the initialized field for unions, which only initialize one
+    /// field at a time.
+    Adt(AdtDef<'tcx>, usize, &'tcx Substs<'tcx>, Option<usize>),
     Closure(DefId, ClosureSubsts<'tcx>),
 }
 
@@ -1054,7 +1057,7 @@ impl<'tcx> Debug for Rvalue<'tcx> {
                         }
                     }
 
-                    Adt(adt_def, variant, substs) => {
+                    Adt(adt_def, variant, substs, active_field) => {
                         let variant_def = &adt_def.variants[variant];
 
                         ppaux::parameterized(fmt, substs, variant_def.did,
@@ -1068,8 +1071,13 @@ impl<'tcx> Debug for Rvalue<'tcx> {
                             ty::VariantKind::Tuple => fmt_tuple(fmt, lvs),
                             ty::VariantKind::Struct => {
                                 let mut struct_fmt = fmt.debug_struct("");
-                                for (field, lv) in variant_def.fields.iter().zip(lvs) {
-                                    struct_fmt.field(&field.name.as_str(), lv);
+                                if let Some(active_field) = active_field {
+                                    let field = &variant_def.fields[active_field];
+                                    struct_fmt.field(&field.name.as_str(), &lvs[0]);
+                                } else {
+                                    for (field, lv) in variant_def.fields.iter().zip(lvs) {
+                                        struct_fmt.field(&field.name.as_str(), lv);
+                                    }
                                 }
                                 struct_fmt.finish()
                             }
@@ -1141,6 +1149,11 @@ pub enum Literal<'tcx> {
         // Index into the `promoted` vector of `Mir`.
         index: Promoted
     },
+    // The value of a `const` type parameter, known once `param` is
+    // substituted with a `TyConst` during monomorphization.
+    ConstParam {
+        param: Ty<'tcx>,
+    },
 }
 
 impl<'tcx> Debug for Constant<'tcx> {
@@ -1165,6 +1178,9 @@ impl<'tcx> Debug for Literal<'tcx> {
             Promoted { index } => {
                 write!(fmt, "{:?}", index)
             }
+            ConstParam { param } => {
+                write!(fmt, "const {}", param)
+            }
         }
     }
 }
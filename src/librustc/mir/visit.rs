@@ -508,7 +508,8 @@ macro_rules! make_mir_visitor {
                             }
                             AggregateKind::Adt(_adt_def,
                                                _variant_index,
-                                               ref $($mutability)* substs) => {
+                                               ref $($mutability)* substs,
+                                               _active_field_index) => {
                                 self.visit_substs(substs);
                             }
                             AggregateKind::Closure(ref $($mutability)* def_id,
@@ -680,6 +681,9 @@ macro_rules! make_mir_visitor {
                         self.visit_const_val(value);
                     }
                     Literal::Promoted { index: _ } => {}
+                    Literal::ConstParam { ref $($mutability)* param } => {
+                        self.visit_ty(param);
+                    }
                 }
             }
 
@@ -28,6 +28,7 @@ use syntax::attr;
 use syntax::attr::AttrMetaMethods;
 use syntax::parse;
 use syntax::parse::token::InternedString;
+use syntax::ext::tt::macro_trace::MacroTraceFormat;
 use syntax::feature_gate::UnstableFeatures;
 
 use errors::{ColorConfig, Handler};
@@ -165,6 +166,8 @@ pub enum PrintRequest {
     CrateName,
     Cfg,
     TargetList,
+    CrateInfo,
+    CrateInfoJson,
 }
 
 pub enum Input {
@@ -325,7 +328,8 @@ impl Options {
     pub fn build_dep_graph(&self) -> bool {
         self.incremental.is_some() ||
             self.debugging_opts.dump_dep_graph ||
-            self.debugging_opts.query_dep_graph
+            self.debugging_opts.query_dep_graph ||
+            self.debugging_opts.save_analysis_incremental
     }
 }
 
@@ -466,11 +470,14 @@ macro_rules! options {
             Some("a number");
         pub const parse_panic_strategy: Option<&'static str> =
             Some("either `panic` or `abort`");
+        pub const parse_macro_trace_format: Option<&'static str> =
+            Some("either `tree` or `json`");
     }
 
     #[allow(dead_code)]
     mod $mod_set {
         use super::{$struct_name, Passes, SomePasses, AllPasses, PanicStrategy};
+        use super::MacroTraceFormat;
 
         $(
             pub fn $opt(cg: &mut $struct_name, v: Option<&str>) -> bool {
@@ -583,6 +590,16 @@ macro_rules! options {
             }
             true
         }
+
+        fn parse_macro_trace_format(slot: &mut Option<MacroTraceFormat>,
+                                    v: Option<&str>) -> bool {
+            match v {
+                Some("tree") => *slot = Some(MacroTraceFormat::Tree),
+                Some("json") => *slot = Some(MacroTraceFormat::Json),
+                _ => return false
+            }
+            true
+        }
     }
 ) }
 
@@ -690,10 +707,17 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "print the pre-expansion AST as JSON and halt"),
     ls: bool = (false, parse_bool,
         "list the symbols defined by a library crate"),
+    server: bool = (false, parse_bool,
+        "run a compiler server that answers queries about the crate as JSON-RPC over stdio"),
     save_analysis: bool = (false, parse_bool,
         "write syntax and type analysis (in JSON format) information in addition to normal output"),
     save_analysis_csv: bool = (false, parse_bool,
         "write syntax and type analysis (in CSV format) information in addition to normal output"),
+    save_analysis_api: bool = (false, parse_bool,
+        "write a description of the public interface of the crate (in JSON format) \
+         in addition to normal output"),
+    save_analysis_incremental: bool = (false, parse_bool,
+        "with -Z save-analysis, only write the analysis of items that changed since the last run"),
     print_move_fragments: bool = (false, parse_bool,
         "print out move-fragment data for every fn"),
     flowgraph_print_loans: bool = (false, parse_bool,
@@ -717,10 +741,14 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "attempt to recover from parse errors (experimental)"),
     incremental: Option<String> = (None, parse_opt_string,
           "enable incremental compilation (experimental)"),
+    incremental_info: bool = (false, parse_bool,
+          "print which dep-graph nodes were reused, and why the others were recomputed"),
     dump_dep_graph: bool = (false, parse_bool,
           "dump the dependency graph to $RUST_DEP_GRAPH (default: /tmp/dep_graph.gv)"),
     query_dep_graph: bool = (false, parse_bool,
           "enable queries of the dependency graph for regression testing"),
+    semver_check: Option<String> = (None, parse_opt_string,
+          "compare the input library against an older version of it at the given path"),
     no_analysis: bool = (false, parse_bool,
           "parse and expand the source, but run no analysis"),
     extra_plugins: Vec<String> = (Vec::new(), parse_list,
@@ -733,6 +761,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "force drop flag checks on or off"),
     trace_macros: bool = (false, parse_bool,
           "for every macro invocation, print its name and arguments"),
+    trace_macros_format: Option<MacroTraceFormat> = (None, parse_macro_trace_format,
+          "for every `macro_rules!` expansion, print the matched arm, the bindings \
+           of its variables and the resulting tokens (`tree` or `json`)"),
     enable_nonzeroing_move_hints: bool = (false, parse_bool,
           "force nonzeroing move optimization on"),
     keep_hygiene_data: bool = (false, parse_bool,
@@ -991,7 +1022,7 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
                  "[asm|llvm-bc|llvm-ir|obj|link|dep-info]"),
         opt::multi_s("", "print", "Comma separated list of compiler information to \
                                print on stdout",
-                 "[crate-name|file-names|sysroot|cfg|target-list]"),
+                 "[crate-name|file-names|sysroot|cfg|target-list|crate-info|crate-info-json]"),
         opt::flagmulti_s("g",  "",  "Equivalent to -C debuginfo=2"),
         opt::flagmulti_s("O", "", "Equivalent to -C opt-level=2"),
         opt::opt_s("o", "", "Write output to <filename>", "FILENAME"),
@@ -1043,8 +1074,9 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
                            valid types are any of the types for `--pretty`, as well as:
                            `flowgraph=<nodeid>` (graphviz formatted flowgraph for node),
                            `everybody_loops` (all function bodies replaced with `loop {}`),
-                           `hir` (the HIR), `hir,identified`, or
-                           `hir,typed` (HIR with types for each node).",
+                           `hir` (the HIR), `hir,identified`,
+                           `hir,typed` (HIR with types for each node), or
+                           `format[=<width>]` (the source reformatted, keeping comments).",
                           "TYPE"),
 
         // new options here should **not** use the `_ubnr` functions, all new
@@ -1281,6 +1313,8 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
             "sysroot" => PrintRequest::Sysroot,
             "cfg" => PrintRequest::Cfg,
             "target-list" => PrintRequest::TargetList,
+            "crate-info" => PrintRequest::CrateInfo,
+            "crate-info-json" => PrintRequest::CrateInfoJson,
             req => {
                 early_error(error_format, &format!("unknown print request `{}`", req))
             }
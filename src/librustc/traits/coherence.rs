@@ -247,14 +247,17 @@ fn ty_is_local_constructor(tcx: TyCtxt, ty: Ty, infer_is_local: InferIsLocal)->
         ty::TyUint(..) |
         ty::TyFloat(..) |
         ty::TyStr |
+        ty::TyNever |
         ty::TyFnDef(..) |
         ty::TyFnPtr(_) |
         ty::TyArray(..) |
+        ty::TyGenericArray(..) |
         ty::TySlice(..) |
         ty::TyRawPtr(..) |
         ty::TyRef(..) |
         ty::TyTuple(..) |
         ty::TyParam(..) |
+        ty::TyConst(_) |
         ty::TyProjection(..) => {
             false
         }
@@ -281,7 +284,7 @@ fn ty_is_local_constructor(tcx: TyCtxt, ty: Ty, infer_is_local: InferIsLocal)->
             true
         }
 
-        ty::TyClosure(..) => {
+        ty::TyClosure(..) | ty::TyAnon(..) => {
             bug!("ty_is_local invoked on unexpected type: {:?}", ty)
         }
     }
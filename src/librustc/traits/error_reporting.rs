@@ -204,13 +204,16 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
                 ty::TyEnum(..) => Some(5),
                 ty::TyStruct(..) => Some(6),
                 ty::TyBox(..) | ty::TyRef(..) | ty::TyRawPtr(..) => Some(7),
-                ty::TyArray(..) | ty::TySlice(..) => Some(8),
+                ty::TyArray(..) | ty::TyGenericArray(..) | ty::TySlice(..) => Some(8),
                 ty::TyFnDef(..) | ty::TyFnPtr(..) => Some(9),
                 ty::TyTrait(..) => Some(10),
                 ty::TyClosure(..) => Some(11),
                 ty::TyTuple(..) => Some(12),
                 ty::TyProjection(..) => Some(13),
                 ty::TyParam(..) => Some(14),
+                ty::TyAnon(..) => Some(15),
+                ty::TyNever => Some(16),
+                ty::TyConst(_) => Some(17),
                 ty::TyInfer(..) | ty::TyError => None
             }
         }
@@ -480,6 +483,14 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
                          method but not on the corresponding trait method",
                         obligation.predicate);
                     return;
+                } else if let ObligationCauseCode::CompareImplTypeObligation =
+                        obligation.cause.code {
+                    span_err!(
+                        self.tcx.sess, span, E0276,
+                        "the requirement `{}` appears on the impl associated \
+                         type but not on the corresponding trait associated type",
+                        obligation.predicate);
+                    return;
                 } else {
                     match obligation.predicate {
                         ty::Predicate::Trait(ref trait_predicate) => {
@@ -980,6 +991,12 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
                               but not on the corresponding trait method",
                              predicate));
             }
+            ObligationCauseCode::CompareImplTypeObligation => {
+                err.note(
+                    &format!("the requirement `{}` appears on the impl associated type \
+                              but not on the corresponding trait associated type",
+                             predicate));
+            }
         }
     }
 
@@ -28,7 +28,7 @@ use infer::{InferOk, TypeOrigin};
 use rustc_data_structures::snapshot_map::{Snapshot, SnapshotMap};
 use syntax::parse::token;
 use syntax::ast;
-use ty::subst::Subst;
+use ty::subst::{FnSpace, Subst, Substs};
 use ty::{self, ToPredicate, ToPolyTraitRef, Ty, TyCtxt};
 use ty::fold::{TypeFoldable, TypeFolder};
 use util::common::FN_OUTPUT_NAME;
@@ -348,6 +348,18 @@ impl<'a, 'b, 'gcx, 'tcx> TypeFolder<'gcx, 'tcx> for AssociatedTypeNormalizer<'a,
                 normalized_ty
             }
 
+            ty::TyAnon(def_id, substs) if !substs.has_escaping_regions() => { // (*)
+                // An anonymized type is only revealed after type-checking,
+                // which is when its concrete type is known, usually in trans.
+                if self.selcx.projection_mode().is_any() {
+                    let generic_ty = self.tcx().lookup_item_type(def_id).ty;
+                    let concrete_ty = generic_ty.subst(self.tcx(), substs);
+                    self.fold_ty(concrete_ty)
+                } else {
+                    ty
+                }
+            }
+
             _ => {
                 ty
             }
@@ -754,7 +766,8 @@ fn project_type<'cx, 'gcx, 'tcx>(
             Ok(ProjectedTy::NoProgress(
                 selcx.tcx().mk_projection(
                     obligation.predicate.trait_ref.clone(),
-                    obligation.predicate.item_name)))
+                    obligation.predicate.item_name,
+                    obligation.predicate.item_substs)))
         }
     }
 }
@@ -788,6 +801,9 @@ fn assemble_candidates_from_param_env<'cx, 'gcx, 'tcx>(
 /// ```
 ///
 /// Here, for example, we could conclude that the result is `i32`.
+///
+/// The bounds of an anonymized type are likewise all we know about
+/// `<impl Iterator<Item=i32> as Iterator>::Item`.
 fn assemble_candidates_from_trait_def<'cx, 'gcx, 'tcx>(
     selcx: &mut SelectionContext<'cx, 'gcx, 'tcx>,
     obligation: &ProjectionTyObligation<'tcx>,
@@ -796,9 +812,11 @@ fn assemble_candidates_from_trait_def<'cx, 'gcx, 'tcx>(
 {
     debug!("assemble_candidates_from_trait_def(..)");
 
-    // Check whether the self-type is itself a projection.
-    let trait_ref = match obligation_trait_ref.self_ty().sty {
-        ty::TyProjection(ref data) => data.trait_ref.clone(),
+    // Check whether the self-type is itself a projection
+    // or an anonymized type.
+    let (def_id, substs) = match obligation_trait_ref.self_ty().sty {
+        ty::TyProjection(ref data) => (data.trait_ref.def_id, data.trait_ref.substs),
+        ty::TyAnon(def_id, substs) => (def_id, substs),
         ty::TyInfer(ty::TyVar(_)) => {
             // If the self-type is an inference variable, then it MAY wind up
             // being a projected type, so induce an ambiguity.
@@ -809,8 +827,8 @@ fn assemble_candidates_from_trait_def<'cx, 'gcx, 'tcx>(
     };
 
     // If so, extract what we know from the trait and try to come up with a good answer.
-    let trait_predicates = selcx.tcx().lookup_predicates(trait_ref.def_id);
-    let bounds = trait_predicates.instantiate(selcx.tcx(), trait_ref.substs);
+    let trait_predicates = selcx.tcx().lookup_predicates(def_id);
+    let bounds = trait_predicates.instantiate(selcx.tcx(), substs);
     let bounds = elaborate_predicates(selcx.tcx(), bounds.predicates.into_vec());
     assemble_candidates_from_predicates(selcx,
                                         obligation,
@@ -1251,6 +1269,7 @@ fn confirm_callable_candidate<'cx, 'gcx, 'tcx>(
         projection_ty: ty::ProjectionTy {
             trait_ref: trait_ref,
             item_name: token::intern(FN_OUTPUT_NAME),
+            item_substs: tcx.mk_substs(Substs::empty()),
         },
         ty: ret_type
     });
@@ -1313,9 +1332,21 @@ fn confirm_impl_candidate<'cx, 'gcx, 'tcx>(
                        obligation.predicate.trait_ref);
                 tcx.types.err
             });
+            // The parameters of a generic associated type are in `FnSpace`. If
+            // the impl declares a different number of them than the trait,
+            // `compare_impl_type` reports the error.
+            let item_substs = obligation.predicate.item_substs;
+            let generics = &node_item.item.generics;
+            let ty = if generics.types.len(FnSpace) != item_substs.types.len(FnSpace) ||
+                        generics.regions.len(FnSpace) != item_substs.regions.len(FnSpace) {
+                tcx.types.err
+            } else {
+                ty
+            };
             let substs = translate_substs(selcx.infcx(), impl_def_id, substs, node_item.node);
+            let substs = substs.with_method_from(item_substs);
             Progress {
-                ty: ty.subst(tcx, substs),
+                ty: ty.subst(tcx, &substs),
                 obligations: nested,
                 cacheable: true
             }
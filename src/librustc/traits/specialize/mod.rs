@@ -118,7 +118,8 @@ pub fn specializes<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
     // The feature gate should prevent introducing new specializations, but not
     // taking advantage of upstream ones.
-    if !tcx.sess.features.borrow().specialization &&
+    let features = tcx.sess.features.borrow();
+    if !features.specialization && !features.min_specialization &&
         (impl1_def_id.is_local() || impl2_def_id.is_local()) {
         return false;
     }
@@ -178,6 +179,35 @@ pub fn specializes<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     result
 }
 
+/// Given that impl1 specializes impl2, find the substitution for the generics
+/// of impl2 under which it applies to everything impl1 applies to.
+///
+/// The substitution is expressed in terms of the free generics of impl1,
+/// which are returned as well, so that e.g. the predicates of both impls can
+/// be compared. `None` is returned if impl1 does not specialize impl2.
+pub fn specialized_impl_substs<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                         impl1_def_id: DefId,
+                                         impl2_def_id: DefId)
+                                         -> Option<(&'tcx Substs<'tcx>, &'tcx Substs<'tcx>)> {
+    let scheme = tcx.lookup_item_type(impl1_def_id);
+    let predicates = tcx.lookup_predicates(impl1_def_id);
+    let penv = tcx.construct_parameter_environment(DUMMY_SP,
+                                                   &scheme.generics,
+                                                   &predicates,
+                                                   region::DUMMY_CODE_EXTENT);
+    let free_substs = penv.free_substs;
+    let impl1_trait_ref = tcx.impl_trait_ref(impl1_def_id)
+                             .unwrap()
+                             .subst(tcx, &free_substs);
+
+    tcx.normalizing_infer_ctxt(ProjectionMode::Topmost).enter(|mut infcx| {
+        infcx.parameter_environment = penv;
+        fulfill_implication(&infcx, impl1_trait_ref, impl2_def_id).ok().and_then(|substs| {
+            tcx.lift_to_global(&substs)
+        }).map(|substs| (free_substs, substs))
+    })
+}
+
 /// Attempt to fulfill all obligations of `target_impl` after unification with
 /// `source_trait_ref`. If successful, returns a substitution for *all* the
 /// generics of `target_impl`, including both those needed to unify with
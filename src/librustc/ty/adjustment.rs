@@ -21,6 +21,7 @@ use hir;
 
 #[derive(Copy, Clone)]
 pub enum AutoAdjustment<'tcx> {
+    AdjustNeverToAny(Ty<'tcx>), // go from ! to any type
     AdjustReifyFnPointer,    // go from a fn-item type to a fn-pointer type
     AdjustUnsafeFnPointer,   // go from a safe fn pointer to an unsafe fn pointer
     AdjustMutToConstPointer, // go from a mut raw pointer to a const raw pointer
@@ -106,6 +107,7 @@ pub struct AutoDerefRef<'tcx> {
 impl<'tcx> AutoAdjustment<'tcx> {
     pub fn is_identity(&self) -> bool {
         match *self {
+            AdjustNeverToAny(..) |
             AdjustReifyFnPointer |
             AdjustUnsafeFnPointer |
             AdjustMutToConstPointer => false,
@@ -154,6 +156,8 @@ impl<'a, 'gcx, 'tcx> ty::TyS<'tcx> {
         return match adjustment {
             Some(adjustment) => {
                 match *adjustment {
+                    AdjustNeverToAny(ty) => ty,
+
                     AdjustReifyFnPointer => {
                         match self.sty {
                             ty::TyFnDef(_, _, f) => tcx.mk_fn_ptr(f),
@@ -186,7 +186,8 @@ impl<'a, 'tcx> ty::TyS<'tcx> {
                 // Scalar and unique types are sendable, and durable
                 ty::TyInfer(ty::FreshIntTy(_)) | ty::TyInfer(ty::FreshFloatTy(_)) |
                 ty::TyBool | ty::TyInt(_) | ty::TyUint(_) | ty::TyFloat(_) |
-                ty::TyFnDef(..) | ty::TyFnPtr(_) | ty::TyChar => {
+                ty::TyFnDef(..) | ty::TyFnPtr(_) | ty::TyChar | ty::TyNever |
+                ty::TyConst(_) => {
                     TC::None
                 }
 
@@ -206,7 +207,7 @@ impl<'a, 'tcx> ty::TyS<'tcx> {
                     TC::None
                 }
 
-                ty::TyArray(ty, _) => {
+                ty::TyArray(ty, _) | ty::TyGenericArray(ty, _) => {
                     tc_ty(tcx, ty, cache)
                 }
 
@@ -232,6 +233,12 @@ impl<'a, 'tcx> ty::TyS<'tcx> {
                             })
                         });
 
+                    if def.is_union() {
+                        // The fields of a union are never dropped, so
+                        // the union does not own what its fields own.
+                        res = res - TC::OwnsAll;
+                    }
+
                     if def.has_dtor() {
                         res = res | TC::OwnsDtor;
                     }
@@ -240,7 +247,8 @@ impl<'a, 'tcx> ty::TyS<'tcx> {
                 }
 
                 ty::TyProjection(..) |
-                ty::TyParam(_) => {
+                ty::TyParam(_) |
+                ty::TyAnon(..) => {
                     TC::All
                 }
 
@@ -189,6 +189,7 @@ pub struct CommonTypes<'tcx> {
     pub u64: Ty<'tcx>,
     pub f32: Ty<'tcx>,
     pub f64: Ty<'tcx>,
+    pub never: Ty<'tcx>,
     pub err: Ty<'tcx>,
 }
 
@@ -268,6 +269,7 @@ impl<'tcx> CommonTypes<'tcx> {
             u64: mk(TyUint(ast::UintTy::U64)),
             f32: mk(TyFloat(ast::FloatTy::F32)),
             f64: mk(TyFloat(ast::FloatTy::F64)),
+            never: mk(TyNever),
         }
     }
 }
@@ -964,7 +966,8 @@ macro_rules! sty_debug_print {
                 for &Interned(t) in tcx.interners.type_.borrow().iter() {
                     let variant = match t.sty {
                         ty::TyBool | ty::TyChar | ty::TyInt(..) | ty::TyUint(..) |
-                            ty::TyFloat(..) | ty::TyStr => continue,
+                            ty::TyFloat(..) | ty::TyStr | ty::TyNever |
+                            ty::TyConst(..) => continue,
                         ty::TyError => /* unimportant */ continue,
                         $(ty::$variant(..) => &mut $variant,)*
                     };
@@ -1004,8 +1007,9 @@ impl<'a, 'tcx> TyCtxt<'a, 'tcx, 'tcx> {
     pub fn print_debug_stats(self) {
         sty_debug_print!(
             self,
-            TyEnum, TyBox, TyArray, TySlice, TyRawPtr, TyRef, TyFnDef, TyFnPtr,
-            TyTrait, TyStruct, TyClosure, TyTuple, TyParam, TyInfer, TyProjection);
+            TyEnum, TyBox, TyArray, TyGenericArray, TySlice, TyRawPtr, TyRef, TyFnDef,
+            TyFnPtr, TyTrait, TyStruct, TyClosure, TyTuple, TyParam, TyInfer, TyProjection,
+            TyAnon);
 
         println!("Substs interner: #{}", self.interners.substs.borrow().len());
         println!("BareFnTy interner: #{}", self.interners.bare_fn.borrow().len());
@@ -1196,6 +1200,10 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
         self.mk_ty(TyStr)
     }
 
+    pub fn mk_never(self) -> Ty<'tcx> {
+        self.types.never
+    }
+
     pub fn mk_static_str(self) -> Ty<'tcx> {
         self.mk_imm_ref(self.mk_region(ty::ReStatic), self.mk_str())
     }
@@ -1241,6 +1249,17 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
         self.mk_ty(TyArray(ty, n))
     }
 
+    /// Creates the array type `[ty; len]`, where `len` is the type standing
+    /// for the value of a `const` type parameter. Arrays whose length is
+    /// already known are always represented as `TyArray`.
+    pub fn mk_generic_array(self, ty: Ty<'tcx>, len: Ty<'tcx>) -> Ty<'tcx> {
+        match len.sty {
+            TyConst(n) => self.mk_array(ty, n),
+            TyError => self.types.err,
+            _ => self.mk_ty(TyGenericArray(ty, len))
+        }
+    }
+
     pub fn mk_slice(self, ty: Ty<'tcx>) -> Ty<'tcx> {
         self.mk_ty(TySlice(ty))
     }
@@ -1283,13 +1302,22 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
 
     pub fn mk_projection(self,
                          trait_ref: TraitRef<'tcx>,
-                         item_name: Name)
+                         item_name: Name,
+                         item_substs: &'tcx Substs<'tcx>)
                          -> Ty<'tcx> {
         // take a copy of substs so that we own the vectors inside
-        let inner = ProjectionTy { trait_ref: trait_ref, item_name: item_name };
+        let inner = ProjectionTy {
+            trait_ref: trait_ref,
+            item_name: item_name,
+            item_substs: item_substs
+        };
         self.mk_ty(TyProjection(inner))
     }
 
+    pub fn mk_anon(self, def_id: DefId, substs: &'tcx Substs<'tcx>) -> Ty<'tcx> {
+        self.mk_ty(TyAnon(def_id, substs))
+    }
+
     pub fn mk_struct(self, def: AdtDef<'tcx>, substs: &'tcx Substs<'tcx>) -> Ty<'tcx> {
         // take a copy of substs so that we own the vectors inside
         self.mk_ty(TyStruct(def, substs))
@@ -1336,6 +1364,10 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
         self.mk_ty(TyParam(ParamTy { space: space, idx: index, name: name }))
     }
 
+    pub fn mk_const(self, n: usize) -> Ty<'tcx> {
+        self.mk_ty(TyConst(n))
+    }
+
     pub fn mk_self_type(self) -> Ty<'tcx> {
         self.mk_param(subst::SelfSpace, 0, keywords::SelfType.name())
     }
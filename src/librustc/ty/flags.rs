@@ -60,6 +60,8 @@ impl FlagComputation {
             &ty::TyInt(_) |
             &ty::TyFloat(_) |
             &ty::TyUint(_) |
+            &ty::TyNever |
+            &ty::TyConst(_) |
             &ty::TyStr => {
             }
 
@@ -110,6 +112,13 @@ impl FlagComputation {
                 self.add_projection_ty(data);
             }
 
+            &ty::TyAnon(_, substs) => {
+                // An anonymized type is normalized to its concrete type
+                // in trans, just like a projection.
+                self.add_flags(TypeFlags::HAS_PROJECTION);
+                self.add_substs(substs);
+            }
+
             &ty::TyTrait(box ty::TraitTy { ref principal, ref bounds }) => {
                 let mut computation = FlagComputation::new();
                 computation.add_substs(principal.0.substs);
@@ -127,6 +136,11 @@ impl FlagComputation {
                 self.add_ty(tt)
             }
 
+            &ty::TyGenericArray(tt, len) => {
+                self.add_ty(tt);
+                self.add_ty(len);
+            }
+
             &ty::TyRawPtr(ref m) => {
                 self.add_ty(m.ty);
             }
@@ -203,6 +217,7 @@ impl FlagComputation {
 
     fn add_projection_ty(&mut self, projection_ty: &ty::ProjectionTy) {
         self.add_substs(projection_ty.trait_ref.substs);
+        self.add_substs(projection_ty.item_substs);
     }
 
     fn add_substs(&mut self, substs: &subst::Substs) {
@@ -188,7 +188,8 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
             data @ DefPathData::Initializer |
             data @ DefPathData::MacroDef(..) |
             data @ DefPathData::ClosureExpr |
-            data @ DefPathData::Binding(..) => {
+            data @ DefPathData::Binding(..) |
+            data @ DefPathData::ImplTrait => {
                 let parent_def_id = self.parent_def_id(def_id).unwrap();
                 self.push_item_path(buffer, parent_def_id);
                 buffer.push(&data.as_interned_str());
@@ -275,7 +276,8 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
             ty::TyInt(_) |
             ty::TyUint(_) |
             ty::TyFloat(_) |
-            ty::TyStr => {
+            ty::TyStr |
+            ty::TyNever => {
                 buffer.push(&format!("{}", self_ty));
             }
 
@@ -324,6 +326,7 @@ pub fn characteristic_def_id_of_type(ty: Ty) -> Option<DefId> {
         ty::TyTrait(ref data) => Some(data.principal_def_id()),
 
         ty::TyArray(subty, _) |
+        ty::TyGenericArray(subty, _) |
         ty::TySlice(subty) |
         ty::TyBox(subty) => characteristic_def_id_of_type(subty),
 
@@ -342,9 +345,12 @@ pub fn characteristic_def_id_of_type(ty: Ty) -> Option<DefId> {
         ty::TyInt(_) |
         ty::TyUint(_) |
         ty::TyStr |
+        ty::TyNever |
         ty::TyFnPtr(_) |
         ty::TyProjection(_) |
         ty::TyParam(_) |
+        ty::TyConst(_) |
+        ty::TyAnon(..) |
         ty::TyInfer(_) |
         ty::TyError |
         ty::TyFloat(_) => None,
@@ -395,9 +395,12 @@ impl Integer {
                 }
             }
             attr::ReprAny => I8,
-            attr::ReprPacked => {
+            attr::ReprPacked(_) => {
                 bug!("Integer::repr_discr: found #[repr(packed)] on an enum");
             }
+            attr::ReprAlign(_) => {
+                bug!("Integer::repr_discr: found #[repr(align)] on an enum");
+            }
             attr::ReprSimd => {
                 bug!("Integer::repr_discr: found #[repr(simd)] on an enum");
             }
@@ -455,8 +458,9 @@ pub type FieldPath = Vec<u32>;
 pub struct Struct {
     pub align: Align,
 
-    /// If true, no alignment padding is used.
-    pub packed: bool,
+    /// If set, fields are aligned to at most this much, as requested
+    /// by `#[repr(packed)]` (byte aligned) or `#[repr(packed(N))]`.
+    pub pack: Option<Align>,
 
     /// If true, the size is exact, otherwise it's only a lower bound.
     pub sized: bool,
@@ -469,10 +473,10 @@ pub struct Struct {
 }
 
 impl<'a, 'gcx, 'tcx> Struct {
-    pub fn new(dl: &TargetDataLayout, packed: bool) -> Struct {
+    pub fn new(dl: &TargetDataLayout, pack: Option<Align>) -> Struct {
         Struct {
-            align: if packed { dl.i8_align } else { dl.aggregate_align },
-            packed: packed,
+            align: if pack.is_some() { dl.i8_align } else { dl.aggregate_align },
+            pack: pack,
             sized: true,
             offset_after_field: vec![]
         }
@@ -497,17 +501,18 @@ impl<'a, 'gcx, 'tcx> Struct {
                 self.sized = false;
             }
 
-            // Invariant: offset < dl.obj_size_bound() <= 1<<61
-            let mut offset = if !self.packed {
-                let align = field.align(dl);
-                self.align = self.align.max(align);
-                self.offset_after_field.last_mut().map_or(Size::from_bytes(0), |last| {
-                    *last = last.abi_align(align);
-                    *last
-                })
-            } else {
-                self.offset_after_field.last().map_or(Size::from_bytes(0), |&last| last)
+            let align = match self.pack {
+                Some(pack) => field.align(dl).min(pack),
+                None => field.align(dl)
             };
+            self.align = self.align.max(align);
+
+            // Invariant: offset < dl.obj_size_bound() <= 1<<61
+            let zero = Size::from_bytes(0);
+            let mut offset = self.offset_after_field.last_mut().map_or(zero, |last| {
+                *last = last.abi_align(align);
+                *last
+            });
 
             offset = offset.checked_add(field.size(dl), dl)
                            .map_or(Err(LayoutError::SizeOverflow(scapegoat)), Ok)?;
@@ -572,6 +577,10 @@ impl<'a, 'gcx, 'tcx> Struct {
                 }
             }
 
+            // The fields of a union overlap, so none of them
+            // is guaranteed to hold a valid value.
+            (&UntaggedUnion { .. }, _) => Ok(None),
+
             // Perhaps one of the fields of this struct is non-zero
             // let's recurse and find out
             (_, &ty::TyStruct(def, substs)) => {
@@ -626,6 +635,56 @@ pub const FAT_PTR_ADDR: usize = 0;
 /// - For a slice, this is the length.
 pub const FAT_PTR_EXTRA: usize = 1;
 
+/// An untagged union.
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct Union {
+    pub align: Align,
+
+    pub min_size: Size,
+
+    /// If true, no alignment padding is used.
+    pub packed: bool,
+}
+
+impl<'a, 'gcx, 'tcx> Union {
+    pub fn new(dl: &TargetDataLayout, packed: bool) -> Union {
+        Union {
+            align: if packed { dl.i8_align } else { dl.aggregate_align },
+            min_size: Size::from_bytes(0),
+            packed: packed,
+        }
+    }
+
+    /// Extend the Union with more fields.
+    /// All fields start at offset 0, so the union is as large
+    /// as its largest field and as aligned as its most aligned one.
+    pub fn extend<I>(&mut self, dl: &TargetDataLayout,
+                     fields: I,
+                     scapegoat: Ty<'gcx>)
+                     -> Result<(), LayoutError<'gcx>>
+    where I: Iterator<Item=Result<&'a Layout, LayoutError<'gcx>>> {
+        for (index, field) in fields.enumerate() {
+            let field = field?;
+            if field.is_unsized() {
+                bug!("Union::extend: field #{} of `{}` is unsized",
+                     index, scapegoat);
+            }
+
+            if !self.packed {
+                self.align = self.align.max(field.align(dl));
+            }
+            self.min_size = cmp::max(self.min_size, field.size(dl));
+        }
+
+        Ok(())
+    }
+
+    /// Get the size with trailing aligment padding.
+    pub fn stride(&self) -> Size {
+        self.min_size.abi_align(self.align)
+    }
+}
+
 /// Type layout, from which size and alignment can be cheaply computed.
 /// For ADTs, it also includes field placement and enum optimizations.
 /// NOTE: Because Layout is interned, redundant information should be
@@ -683,6 +742,11 @@ pub enum Layout {
         non_zero: bool
     },
 
+    /// Untagged unions.
+    UntaggedUnion {
+        variants: Union,
+    },
+
     /// General-case enums: for each case there is a struct, and they
     /// all start with a field for the discriminant.
     General {
@@ -833,14 +897,14 @@ impl<'a, 'gcx, 'tcx> Layout {
             }
 
             // Odd unit types.
-            ty::TyFnDef(..) => {
+            ty::TyFnDef(..) | ty::TyNever => {
                 Univariant {
-                    variant: Struct::new(dl, false),
+                    variant: Struct::new(dl, None),
                     non_zero: false
                 }
             }
             ty::TyTrait(_) => {
-                let mut unit = Struct::new(dl, false);
+                let mut unit = Struct::new(dl, None);
                 unit.sized = false;
                 Univariant { variant: unit, non_zero: false }
             }
@@ -848,12 +912,22 @@ impl<'a, 'gcx, 'tcx> Layout {
             // Tuples.
             ty::TyClosure(_, ty::ClosureSubsts { upvar_tys: tys, .. }) |
             ty::TyTuple(tys) => {
-                let mut st = Struct::new(dl, false);
+                let mut st = Struct::new(dl, None);
                 st.extend(dl, tys.iter().map(|ty| ty.layout(infcx)), ty)?;
                 Univariant { variant: st, non_zero: false }
             }
 
             // ADTs.
+            ty::TyStruct(def, substs) if def.is_union() => {
+                let fields = def.struct_variant().fields.iter().map(|field| {
+                    normalize_associated_type(infcx, field.ty(tcx, substs))
+                        .layout(infcx)
+                });
+                let packed = tcx.lookup_packed(def.did).is_some();
+                let mut un = Union::new(dl, packed);
+                un.extend(dl, fields, ty)?;
+                UntaggedUnion { variants: un }
+            }
             ty::TyStruct(def, substs) => {
                 if ty.is_simd() {
                     // SIMD vector types.
@@ -876,8 +950,10 @@ impl<'a, 'gcx, 'tcx> Layout {
                     normalize_associated_type(infcx, field.ty(tcx, substs))
                         .layout(infcx)
                 });
-                let packed = tcx.lookup_packed(def.did);
-                let mut st = Struct::new(dl, packed);
+                let pack = tcx.lookup_packed(def.did).map(|pack| {
+                    Align::from_bytes(pack as u64, pack as u64).unwrap()
+                });
+                let mut st = Struct::new(dl, pack);
                 st.extend(dl, fields, ty)?;
 
                 // FIXME(16758) don't add a drop flag to unsized structs, as it
@@ -891,6 +967,11 @@ impl<'a, 'gcx, 'tcx> Layout {
                         non_zero: false
                     })).into_iter(), ty)?;
                 }
+
+                if let Some(align) = tcx.lookup_align(def.did) {
+                    let align = Align::from_bytes(align as u64, align as u64).unwrap();
+                    st.align = st.align.max(align);
+                }
                 Univariant {
                     variant: st,
                     non_zero: Some(def.did) == tcx.lang_items.non_zero()
@@ -912,7 +993,7 @@ impl<'a, 'gcx, 'tcx> Layout {
                     // (Typechecking will reject discriminant-sizing attrs.)
                     assert_eq!(hint, attr::ReprAny);
 
-                    let mut st = Struct::new(dl, false);
+                    let mut st = Struct::new(dl, None);
                     st.extend(dl, drop_flag.iter().map(Ok), ty)?;
                     return Ok(Univariant { variant: st, non_zero: false });
                 }
@@ -953,7 +1034,7 @@ impl<'a, 'gcx, 'tcx> Layout {
                         normalize_associated_type(infcx, field.ty(tcx, substs))
                             .layout(infcx)
                     });
-                    let mut st = Struct::new(dl, false);
+                    let mut st = Struct::new(dl, None);
                     st.extend(dl, fields.chain(drop_flag.iter().map(Ok)), ty)?;
                     return Ok(Univariant { variant: st, non_zero: false });
                 }
@@ -997,7 +1078,7 @@ impl<'a, 'gcx, 'tcx> Layout {
 
                         path.push(0); // For GEP through a pointer.
                         path.reverse();
-                        let mut st = Struct::new(dl, false);
+                        let mut st = Struct::new(dl, None);
                         st.extend(dl, variants[discr].iter().map(|ty| {
                             ty.layout(infcx)
                         }), ty)?;
@@ -1038,7 +1119,7 @@ impl<'a, 'gcx, 'tcx> Layout {
                         }
                         Ok(field)
                     });
-                    let mut st = Struct::new(dl, false);
+                    let mut st = Struct::new(dl, None);
                     st.extend(dl, discr.iter().map(Ok).chain(fields)
                                               .chain(drop_flag.iter().map(Ok)), ty)?;
                     size = cmp::max(size, st.min_size());
@@ -1104,11 +1185,21 @@ impl<'a, 'gcx, 'tcx> Layout {
                 }
             }
 
+            // An anonymized type has the layout of its concrete type,
+            // which is only revealed in trans.
+            ty::TyAnon(..) => {
+                let normalized = normalize_associated_type(infcx, ty);
+                if ty == normalized {
+                    return Err(LayoutError::Unknown(ty));
+                }
+                return Layout::compute_uncached(normalized, infcx);
+            }
+
             // Types with no meaningful known layout.
-            ty::TyProjection(_) | ty::TyParam(_) => {
+            ty::TyProjection(_) | ty::TyParam(_) | ty::TyGenericArray(..) => {
                 return Err(LayoutError::Unknown(ty));
             }
-            ty::TyInfer(_) | ty::TyError => {
+            ty::TyConst(_) | ty::TyInfer(_) | ty::TyError => {
                 bug!("Layout::compute: unexpected type `{}`", ty)
             }
         };
@@ -1120,7 +1211,7 @@ impl<'a, 'gcx, 'tcx> Layout {
     pub fn is_unsized(&self) -> bool {
         match *self {
             Scalar {..} | Vector {..} | FatPointer {..} |
-            CEnum {..} | General {..} |
+            CEnum {..} | UntaggedUnion {..} | General {..} |
             RawNullablePointer {..} |
             StructWrappedNullablePointer {..} => false,
 
@@ -1159,6 +1250,8 @@ impl<'a, 'gcx, 'tcx> Layout {
             StructWrappedNullablePointer { nonnull: ref variant, .. } => {
                 variant.stride()
             }
+
+            UntaggedUnion { ref variants } => variants.stride()
         }
     }
 
@@ -1198,6 +1291,8 @@ impl<'a, 'gcx, 'tcx> Layout {
             StructWrappedNullablePointer { nonnull: ref variant, .. } => {
                 variant.align
             }
+
+            UntaggedUnion { ref variants } => variants.align
         }
     }
 }
@@ -1260,7 +1355,7 @@ impl<'a, 'gcx, 'tcx> SizeSkeleton<'gcx> {
 
             ty::TyStruct(def, substs) | ty::TyEnum(def, substs) => {
                 // Only newtypes and enums w/ nullable pointer optimization.
-                if def.variants.is_empty() || def.variants.len() > 2 {
+                if def.is_union() || def.variants.is_empty() || def.variants.len() > 2 {
                     return Err(err);
                 }
 
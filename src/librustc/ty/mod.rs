@@ -414,9 +414,13 @@ pub struct AssociatedConst<'tcx> {
     pub has_value: bool
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct AssociatedType<'tcx> {
     pub name: Name,
+    /// The associated type's own parameters (in `FnSpace`) and where
+    /// clauses; empty unless it is a generic associated type.
+    pub generics: Generics<'tcx>,
+    pub predicates: GenericPredicates<'tcx>,
     pub ty: Option<Ty<'tcx>>,
     pub vis: Visibility,
     pub defaultness: hir::Defaultness,
@@ -719,6 +723,10 @@ pub struct TypeParameterDef<'tcx> {
     pub default_def_id: DefId, // for use in error reporing about defaults
     pub default: Option<Ty<'tcx>>,
     pub object_lifetime_default: ObjectLifetimeDefault,
+    /// Whether this is a `const N: usize` parameter, whose arguments are
+    /// `TyConst` values (or parameters and inference variables standing
+    /// for them) rather than ordinary types.
+    pub is_const: bool,
 }
 
 #[derive(Clone)]
@@ -1282,8 +1290,24 @@ impl<'a, 'tcx> ParameterEnvironment<'tcx> {
         match tcx.map.find(id) {
             Some(ast_map::NodeImplItem(ref impl_item)) => {
                 match impl_item.node {
-                    hir::ImplItemKind::Type(_) | hir::ImplItemKind::Const(_, _) => {
-                        // associated types don't have their own entry (for some reason),
+                    hir::ImplItemKind::Type(..) => {
+                        let assoc_ty_def_id = tcx.map.local_def_id(id);
+                        match tcx.impl_or_trait_item(assoc_ty_def_id) {
+                            TypeTraitItem(ref assoc_ty) => {
+                                tcx.construct_parameter_environment(
+                                    impl_item.span,
+                                    &assoc_ty.generics,
+                                    &assoc_ty.predicates,
+                                    tcx.region_maps.item_extent(id))
+                            }
+                            _ => {
+                                bug!("ParameterEnvironment::for_item(): \
+                                      got non-type item from impl type?!")
+                            }
+                        }
+                    }
+                    hir::ImplItemKind::Const(_, _) => {
+                        // associated consts don't have their own entry (for some reason),
                         // so for now just grab environment for the impl
                         let impl_id = tcx.map.get_parent(id);
                         let impl_def_id = tcx.map.local_def_id(impl_id);
@@ -1316,8 +1340,24 @@ impl<'a, 'tcx> ParameterEnvironment<'tcx> {
             }
             Some(ast_map::NodeTraitItem(trait_item)) => {
                 match trait_item.node {
-                    hir::TypeTraitItem(..) | hir::ConstTraitItem(..) => {
-                        // associated types don't have their own entry (for some reason),
+                    hir::TypeTraitItem(..) => {
+                        let assoc_ty_def_id = tcx.map.local_def_id(id);
+                        match tcx.impl_or_trait_item(assoc_ty_def_id) {
+                            TypeTraitItem(ref assoc_ty) => {
+                                tcx.construct_parameter_environment(
+                                    trait_item.span,
+                                    &assoc_ty.generics,
+                                    &assoc_ty.predicates,
+                                    tcx.region_maps.item_extent(id))
+                            }
+                            _ => {
+                                bug!("ParameterEnvironment::for_item(): \
+                                      got non-type item from trait type?!")
+                            }
+                        }
+                    }
+                    hir::ConstTraitItem(..) => {
+                        // associated consts don't have their own entry (for some reason),
                         // so for now just grab environment for the trait
                         let trait_id = tcx.map.get_parent(id);
                         let trait_def_id = tcx.map.local_def_id(trait_id);
@@ -1375,6 +1415,7 @@ impl<'a, 'tcx> ParameterEnvironment<'tcx> {
                     }
                     hir::ItemEnum(..) |
                     hir::ItemStruct(..) |
+                    hir::ItemUnion(..) |
                     hir::ItemTy(..) |
                     hir::ItemImpl(..) |
                     hir::ItemConst(..) |
@@ -1461,6 +1502,7 @@ bitflags! {
         const IS_SIMD             = 1 << 4,
         const IS_FUNDAMENTAL      = 1 << 5,
         const IS_NO_DROP_FLAG     = 1 << 6,
+        const IS_UNION            = 1 << 7,
     }
 }
 
@@ -1496,7 +1538,7 @@ pub struct FieldDefData<'tcx, 'container: 'tcx> {
     ty: ivar::TyIVar<'tcx, 'container>
 }
 
-/// The definition of an abstract data type - a struct or enum.
+/// The definition of an abstract data type - a struct, union or enum.
 ///
 /// These are all interned (by intern_adt_def) into the adt_defs
 /// table.
@@ -1553,7 +1595,7 @@ impl<'tcx> Decodable for AdtDef<'tcx> {
 
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub enum AdtKind { Struct, Enum }
+pub enum AdtKind { Struct, Union, Enum }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, RustcEncodable, RustcDecodable)]
 pub enum VariantKind { Struct, Tuple, Unit }
@@ -1587,8 +1629,12 @@ impl<'a, 'gcx, 'tcx, 'container> AdtDefData<'gcx, 'container> {
         if Some(did) == tcx.lang_items.phantom_data() {
             flags = flags | AdtFlags::IS_PHANTOM_DATA;
         }
-        if let AdtKind::Enum = kind {
-            flags = flags | AdtFlags::IS_ENUM;
+        match kind {
+            AdtKind::Enum => flags = flags | AdtFlags::IS_ENUM,
+            // Unions never carry a drop flag; there is no place to put it
+            // that does not overlap the fields.
+            AdtKind::Union => flags = flags | AdtFlags::IS_UNION | AdtFlags::IS_NO_DROP_FLAG,
+            AdtKind::Struct => {}
         }
         AdtDefData {
             did: did,
@@ -1606,16 +1652,25 @@ impl<'a, 'gcx, 'tcx, 'container> AdtDefData<'gcx, 'container> {
         self.flags.set(self.flags.get() | AdtFlags::IS_DTORCK_VALID)
     }
 
-    /// Returns the kind of the ADT - Struct or Enum.
+    /// Returns the kind of the ADT - Struct, Union or Enum.
     #[inline]
     pub fn adt_kind(&self) -> AdtKind {
         if self.flags.get().intersects(AdtFlags::IS_ENUM) {
             AdtKind::Enum
+        } else if self.flags.get().intersects(AdtFlags::IS_UNION) {
+            AdtKind::Union
         } else {
             AdtKind::Struct
         }
     }
 
+    /// Returns true if this is a union, whose fields all share
+    /// the same storage.
+    #[inline]
+    pub fn is_union(&self) -> bool {
+        self.flags.get().intersects(AdtFlags::IS_UNION)
+    }
+
     /// Returns whether this is a dtorck type. If this returns
     /// true, this type being safe for destruction requires it to be
     /// alive; Otherwise, only the contents are required to be.
@@ -1645,6 +1700,13 @@ impl<'a, 'gcx, 'tcx, 'container> AdtDefData<'gcx, 'container> {
         self.flags.get().intersects(AdtFlags::IS_PHANTOM_DATA)
     }
 
+    /// Returns true if values of this type carry no drop flag, as with
+    /// unions and `#[unsafe_no_drop_flag]` types.
+    #[inline]
+    pub fn is_no_drop_flag(&self) -> bool {
+        self.flags.get().intersects(AdtFlags::IS_NO_DROP_FLAG)
+    }
+
     /// Returns whether this type has a destructor.
     pub fn has_dtor(&self) -> bool {
         match self.dtor_kind() {
@@ -1653,10 +1715,10 @@ impl<'a, 'gcx, 'tcx, 'container> AdtDefData<'gcx, 'container> {
         }
     }
 
-    /// Asserts this is a struct and returns the struct's unique
+    /// Asserts this is a struct or union and returns its unique
     /// variant.
     pub fn struct_variant(&self) -> &VariantDefData<'gcx, 'container> {
-        assert_eq!(self.adt_kind(), AdtKind::Struct);
+        assert!(self.adt_kind() != AdtKind::Enum);
         &self.variants[0]
     }
 
@@ -1845,7 +1907,7 @@ impl<'a, 'tcx> AdtDefData<'tcx, 'tcx> {
         let result = match ty.sty {
             TyBool | TyChar | TyInt(..) | TyUint(..) | TyFloat(..) |
             TyBox(..) | TyRawPtr(..) | TyRef(..) | TyFnDef(..) | TyFnPtr(_) |
-            TyArray(..) | TyClosure(..) => {
+            TyArray(..) | TyGenericArray(..) | TyClosure(..) | TyNever | TyConst(_) => {
                 vec![]
             }
 
@@ -1880,7 +1942,7 @@ impl<'a, 'tcx> AdtDefData<'tcx, 'tcx> {
                 }
             }
 
-            TyProjection(..) => {
+            TyProjection(..) | TyAnon(..) => {
                 // must calculate explicitly.
                 // FIXME: consider special-casing always-Sized projections
                 vec![ty]
@@ -2335,7 +2397,7 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
                         }
                     }).collect()
                 }
-                ItemImpl(_, _, _, _, _, ref iis) => {
+                ItemImpl(_, _, _, _, _, _, ref iis) => {
                     iis.iter().filter_map(|ii| {
                         if let hir::ImplItemKind::Const(_, _) = ii.node {
                             match self.impl_or_trait_item(self.map.local_def_id(ii.id)) {
@@ -2360,12 +2422,24 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
         }
     }
 
+    /// Returns the associated type named `name` declared by the trait
+    /// `trait_def_id`, if there is one.
+    pub fn associated_type_named(self, trait_def_id: DefId, name: Name)
+                                 -> Option<Rc<AssociatedType<'gcx>>> {
+        self.trait_items(trait_def_id).iter().filter_map(|item| {
+            match *item {
+                TypeTraitItem(ref assoc_ty) if assoc_ty.name == name => Some(assoc_ty.clone()),
+                _ => None
+            }
+        }).next()
+    }
+
     pub fn trait_impl_polarity(self, id: DefId) -> Option<hir::ImplPolarity> {
         if let Some(id) = self.map.as_local_node_id(id) {
             match self.map.find(id) {
                 Some(ast_map::NodeItem(item)) => {
                     match item.node {
-                        hir::ItemImpl(_, polarity, _, _, _, _) => Some(polarity),
+                        hir::ItemImpl(_, polarity, _, _, _, _, _) => Some(polarity),
                         _ => None
                     }
                 }
@@ -2376,6 +2450,24 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
         }
     }
 
+    /// Is this a `default impl`, which provides default items but does
+    /// not by itself implement its trait?
+    pub fn impl_is_default(self, id: DefId) -> bool {
+        if let Some(id) = self.map.as_local_node_id(id) {
+            match self.map.find(id) {
+                Some(ast_map::NodeItem(item)) => {
+                    match item.node {
+                        hir::ItemImpl(_, _, defaultness, _, _, _, _) => defaultness.is_default(),
+                        _ => false
+                    }
+                }
+                _ => false
+            }
+        } else {
+            self.sess.cstore.impl_defaultness(id).is_default()
+        }
+    }
+
     pub fn custom_coerce_unsized_kind(self, did: DefId) -> adjustment::CustomCoerceUnsized {
         self.custom_coerce_unsized_kinds.memoize(did, || {
             let (kind, src) = if did.krate != LOCAL_CRATE {
@@ -2446,6 +2538,16 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
         self.def_map.borrow().get(&id).map(|resolution| resolution.full_def())
     }
 
+    /// Returns the type parameter named by an expression, as with the `N`
+    /// in `[T; N]` or in `fn len() -> usize { N }`, if the expression is
+    /// not one to be evaluated.
+    pub fn const_param_def(self, expr: &hir::Expr) -> Option<Def> {
+        match self.def_map.borrow().get(&expr.id) {
+            Some(&PathResolution { base_def: def @ Def::TyParam(..), depth: 0 }) => Some(def),
+            _ => None
+        }
+    }
+
     // Returns `ty::VariantDef` if `def` refers to a struct,
     // or variant or their constructors, panics otherwise.
     pub fn expect_variant_def(self, def: Def) -> VariantDef<'tcx> {
@@ -2599,9 +2701,21 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
         self.get_attrs(did).iter().any(|item| item.check_name(attr))
     }
 
-    /// Determine whether an item is annotated with `#[repr(packed)]`
-    pub fn lookup_packed(self, did: DefId) -> bool {
-        self.lookup_repr_hints(did).contains(&attr::ReprPacked)
+    /// Get the maximum field alignment requested by `#[repr(packed)]`
+    /// (as 1) or `#[repr(packed(N))]`, if the item is packed at all.
+    pub fn lookup_packed(self, did: DefId) -> Option<u32> {
+        self.lookup_repr_hints(did).iter().filter_map(|hint| match *hint {
+            attr::ReprPacked(pack) => Some(pack),
+            _ => None
+        }).min()
+    }
+
+    /// Get the minimum alignment requested by `#[repr(align(N))]`, if any.
+    pub fn lookup_align(self, did: DefId) -> Option<u32> {
+        self.lookup_repr_hints(did).iter().filter_map(|hint| match *hint {
+            attr::ReprAlign(align) => Some(align),
+            _ => None
+        }).max()
     }
 
     /// Determine whether an item is annotated with `#[simd]`
@@ -175,7 +175,10 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
             ty::TyStruct(..) |      // OutlivesNominalType
             ty::TyBox(..) |         // OutlivesNominalType (ish)
             ty::TyStr |             // OutlivesScalar (ish)
+            ty::TyNever |           // ...
+            ty::TyConst(_) |        // OutlivesScalar
             ty::TyArray(..) |       // ...
+            ty::TyGenericArray(..) | // ...
             ty::TySlice(..) |       // ...
             ty::TyRawPtr(..) |      // ...
             ty::TyRef(..) |         // OutlivesReference
@@ -183,6 +186,7 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
             ty::TyFnDef(..) |       // OutlivesFunction (*)
             ty::TyFnPtr(_) |        // OutlivesFunction (*)
             ty::TyTrait(..) |       // OutlivesObject, OutlivesFragment (*)
+            ty::TyAnon(..) |        // OutlivesNominalType (ish)
             ty::TyError => {
                 // (*) Bare functions and traits are both binders. In the
                 // RFC, this means we would add the bound regions to the
@@ -330,7 +330,12 @@ impl<'tcx> Relate<'tcx> for ty::ProjectionTy<'tcx> {
                 expected_found(relation, &a.item_name, &b.item_name)))
         } else {
             let trait_ref = relation.relate(&a.trait_ref, &b.trait_ref)?;
-            Ok(ty::ProjectionTy { trait_ref: trait_ref, item_name: a.item_name })
+            let item_substs = relation.relate(&a.item_substs, &b.item_substs)?;
+            Ok(ty::ProjectionTy {
+                trait_ref: trait_ref,
+                item_name: a.item_name,
+                item_substs: item_substs
+            })
         }
     }
 }
@@ -467,6 +472,8 @@ pub fn super_relate_tys<'a, 'gcx, 'tcx, R>(relation: &mut R,
         (&ty::TyInt(_), _) |
         (&ty::TyUint(_), _) |
         (&ty::TyFloat(_), _) |
+        (&ty::TyNever, _) |
+        (&ty::TyConst(_), _) |
         (&ty::TyStr, _)
             if a == b =>
         {
@@ -540,6 +547,17 @@ pub fn super_relate_tys<'a, 'gcx, 'tcx, R>(relation: &mut R,
             }
         }
 
+        (&ty::TyGenericArray(..), &ty::TyArray(..)) |
+        (&ty::TyArray(..), &ty::TyGenericArray(..)) |
+        (&ty::TyGenericArray(..), &ty::TyGenericArray(..)) =>
+        {
+            let (a_t, a_len) = array_parts(tcx, a);
+            let (b_t, b_len) = array_parts(tcx, b);
+            let t = relation.relate(&a_t, &b_t)?;
+            let len = relation.relate_with_variance(ty::Invariant, &a_len, &b_len)?;
+            Ok(tcx.mk_generic_array(t, len))
+        }
+
         (&ty::TySlice(a_t), &ty::TySlice(b_t)) =>
         {
             let t = relation.relate(&a_t, &b_t)?;
@@ -579,7 +597,16 @@ pub fn super_relate_tys<'a, 'gcx, 'tcx, R>(relation: &mut R,
         (&ty::TyProjection(ref a_data), &ty::TyProjection(ref b_data)) =>
         {
             let projection_ty = relation.relate(a_data, b_data)?;
-            Ok(tcx.mk_projection(projection_ty.trait_ref, projection_ty.item_name))
+            Ok(tcx.mk_projection(projection_ty.trait_ref,
+                                 projection_ty.item_name,
+                                 projection_ty.item_substs))
+        }
+
+        (&ty::TyAnon(a_def_id, a_substs), &ty::TyAnon(b_def_id, b_substs))
+            if a_def_id == b_def_id =>
+        {
+            let substs = relate_substs(relation, None, a_substs, b_substs)?;
+            Ok(tcx.mk_anon(a_def_id, substs))
         }
 
         _ =>
@@ -589,6 +616,17 @@ pub fn super_relate_tys<'a, 'gcx, 'tcx, R>(relation: &mut R,
     }
 }
 
+/// Splits an array type into its element type and the type standing for
+/// its length, as it would appear in a `TyGenericArray`.
+fn array_parts<'a, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>, ty: Ty<'tcx>)
+                               -> (Ty<'tcx>, Ty<'tcx>) {
+    match ty.sty {
+        ty::TyArray(t, n) => (t, tcx.mk_const(n)),
+        ty::TyGenericArray(t, len) => (t, len),
+        _ => bug!("array_parts: `{:?}` is not an array", ty)
+    }
+}
+
 impl<'tcx> Relate<'tcx> for ty::ClosureSubsts<'tcx> {
     fn relate<'a, 'gcx, R>(relation: &mut R,
                            a: &ty::ClosureSubsts<'tcx>,
@@ -133,6 +133,12 @@ pub enum TypeVariants<'tcx> {
     /// An array with the given length. Written as `[T; n]`.
     TyArray(Ty<'tcx>, usize),
 
+    /// An array whose length is not yet known, written as `[T; N]` where
+    /// `N` is a `const` type parameter. The length is a `TyParam` or
+    /// `TyInfer` type; once it becomes a `TyConst`, `mk_generic_array`
+    /// turns the whole type into a `TyArray`.
+    TyGenericArray(Ty<'tcx>, Ty<'tcx>),
+
     /// The pointee of an array slice.  Written as `[T]`.
     TySlice(Ty<'tcx>),
 
@@ -159,6 +165,9 @@ pub enum TypeVariants<'tcx> {
     /// `|a| a`.
     TyClosure(DefId, ClosureSubsts<'tcx>),
 
+    /// The never type `!`, which has no values.
+    TyNever,
+
     /// A tuple type.  For example, `(i32, bool)`.
     TyTuple(&'tcx [Ty<'tcx>]),
 
@@ -166,9 +175,20 @@ pub enum TypeVariants<'tcx> {
     /// `<T as Trait<..>>::N`.
     TyProjection(ProjectionTy<'tcx>),
 
+    /// Anonymized (`impl Trait`) type found in a return type.
+    /// The DefId comes from the `impl Trait` ast::Ty node, and the
+    /// substitutions are for the generics of the function in question.
+    /// After typeck, the concrete type can be found in the `tcache` map.
+    TyAnon(DefId, &'tcx Substs<'tcx>),
+
     /// A type parameter; for example, `T` in `fn f<T>(x: T) {}
     TyParam(ParamTy),
 
+    /// The value of a `const` type parameter; for example, the `3` in
+    /// `Buf<3>`. This only appears as a type argument or as the length of
+    /// a `TyGenericArray`, never as the type of a value.
+    TyConst(usize),
+
     /// A type variable used during type-checking.
     TyInfer(InferTy),
 
@@ -325,7 +345,8 @@ impl<'a, 'gcx, 'tcx> TraitTy<'tcx> {
                                               substs);
                 let projection_ty = ty::ProjectionTy {
                     trait_ref: trait_ref,
-                    item_name: in_projection_ty.item_name
+                    item_name: in_projection_ty.item_name,
+                    item_substs: in_projection_ty.item_substs
                 };
                 ty::Binder(ty::ProjectionPredicate {
                     projection_ty: projection_ty,
@@ -438,7 +459,7 @@ impl fmt::Debug for TypeFlags {
 }
 
 /// Represents the projection of an associated type. In explicit UFCS
-/// form this would be written `<T as Trait<..>>::N`.
+/// form this would be written `<T as Trait<..>>::N<..>`.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct ProjectionTy<'tcx> {
     /// The trait reference `T as Trait<..>`.
@@ -446,12 +467,23 @@ pub struct ProjectionTy<'tcx> {
 
     /// The name `N` of the associated type.
     pub item_name: Name,
+
+    /// The arguments for the associated type's own generic parameters, in
+    /// `FnSpace` (like those of a method). Empty unless `N` is a generic
+    /// associated type.
+    pub item_substs: &'tcx Substs<'tcx>,
 }
 
-impl<'tcx> ProjectionTy<'tcx> {
+impl<'a, 'gcx, 'tcx> ProjectionTy<'tcx> {
     pub fn sort_key(&self) -> (DefId, Name) {
         (self.trait_ref.def_id, self.item_name)
     }
+
+    /// The substitutions for the generics of the associated type: those of
+    /// the trait followed by its own.
+    pub fn item_generics_substs(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>) -> &'tcx Substs<'tcx> {
+        tcx.mk_substs(self.trait_ref.substs.with_method_from(self.item_substs))
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -931,10 +963,18 @@ impl<'a, 'gcx, 'tcx> TyS<'tcx> {
         // FIXME(#24885): be smarter here
         match self.sty {
             TyEnum(def, _) | TyStruct(def, _) => def.is_empty(),
+            TyNever => true,
             _ => false
         }
     }
 
+    pub fn is_never(&self) -> bool {
+        match self.sty {
+            TyNever => true,
+            _ => false,
+        }
+    }
+
     pub fn is_primitive(&self) -> bool {
         match self.sty {
             TyBool | TyChar | TyInt(_) | TyUint(_) | TyFloat(_) => true,
@@ -1001,7 +1041,7 @@ impl<'a, 'gcx, 'tcx> TyS<'tcx> {
 
     pub fn sequence_element_type(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>) -> Ty<'tcx> {
         match self.sty {
-            TyArray(ty, _) | TySlice(ty) => ty,
+            TyArray(ty, _) | TyGenericArray(ty, _) | TySlice(ty) => ty,
             TyStr => tcx.mk_mach_uint(ast::UintTy::U8),
             _ => bug!("sequence_element_type called on non-sequence value: {}", self),
         }
@@ -1164,7 +1204,7 @@ impl<'a, 'gcx, 'tcx> TyS<'tcx> {
     // Returns the type of ty[i]
     pub fn builtin_index(&self) -> Option<Ty<'tcx>> {
         match self.sty {
-            TyArray(ty, _) | TySlice(ty) => Some(ty),
+            TyArray(ty, _) | TyGenericArray(ty, _) | TySlice(ty) => Some(ty),
             _ => None
         }
     }
@@ -1239,7 +1279,12 @@ impl<'a, 'gcx, 'tcx> TyS<'tcx> {
                 substs.func_substs.regions.as_slice().to_vec()
             }
             TyProjection(ref data) => {
-                data.trait_ref.substs.regions.as_slice().to_vec()
+                let mut v = data.trait_ref.substs.regions.as_slice().to_vec();
+                v.extend_from_slice(data.item_substs.regions.as_slice());
+                v
+            }
+            TyAnon(_, substs) => {
+                substs.regions.as_slice().to_vec()
             }
             TyFnDef(..) |
             TyFnPtr(_) |
@@ -1250,11 +1295,14 @@ impl<'a, 'gcx, 'tcx> TyS<'tcx> {
             TyFloat(_) |
             TyBox(_) |
             TyStr |
+            TyNever |
             TyArray(_, _) |
+            TyGenericArray(..) |
             TySlice(_) |
             TyRawPtr(_) |
             TyTuple(_) |
             TyParam(_) |
+            TyConst(_) |
             TyInfer(_) |
             TyError => {
                 vec![]
@@ -282,6 +282,32 @@ impl<'a, 'gcx, 'tcx> WfPredicates<'a, 'gcx, 'tcx> {
             let cause = self.cause(traits::ProjectionWf(data));
             self.out.push(traits::Obligation::new(cause, predicate));
         }
+
+        // A generic associated type also requires its arguments to be WF
+        // and its own where clauses to hold.
+        if !data.item_substs.is_noop() {
+            let cause = self.cause(traits::MiscObligation);
+            self.out.extend(
+                data.item_substs.types
+                                .as_slice()
+                                .iter()
+                                .filter(|ty| !ty.has_escaping_regions())
+                                .map(|ty| traits::Obligation::new(cause.clone(),
+                                                                  ty::Predicate::WellFormed(ty))));
+
+            let tcx = self.infcx.tcx;
+            if let Some(assoc_ty) = tcx.associated_type_named(data.trait_ref.def_id,
+                                                              data.item_name) {
+                let predicates = assoc_ty.predicates.instantiate(tcx,
+                                                                 data.item_generics_substs(tcx));
+                let cause = self.cause(traits::ItemObligation(assoc_ty.def_id));
+                self.out.extend(
+                    predicates.predicates
+                              .into_iter()
+                              .map(|pred| traits::Obligation::new(cause.clone(), pred))
+                              .filter(|pred| !pred.has_escaping_regions()));
+            }
+        }
     }
 
     fn require_sized(&mut self, subty: Ty<'tcx>, cause: traits::ObligationCauseCode<'tcx>,
@@ -321,12 +347,15 @@ impl<'a, 'gcx, 'tcx> WfPredicates<'a, 'gcx, 'tcx> {
                 ty::TyFloat(..) |
                 ty::TyError |
                 ty::TyStr |
+                ty::TyNever |
+                ty::TyConst(_) |
                 ty::TyParam(_) => {
                     // WfScalar, WfParameter, etc
                 }
 
                 ty::TySlice(subty) |
-                ty::TyArray(subty, _) => {
+                ty::TyArray(subty, _) |
+                ty::TyGenericArray(subty, _) => {
                     self.require_sized(subty, traits::SliceOrArrayElem, false);
                 }
 
@@ -383,6 +412,12 @@ impl<'a, 'gcx, 'tcx> WfPredicates<'a, 'gcx, 'tcx> {
                     // types appearing in the fn signature
                 }
 
+                ty::TyAnon(..) => {
+                    // all of the requirements on the type parameters
+                    // were checked by the function that returned the
+                    // anonymized type.
+                }
+
                 ty::TyTrait(ref data) => {
                     // WfObject
                     //
@@ -13,8 +13,8 @@ use hir::def_id::DefId;
 use ty::subst::{self, Subst};
 use ty::{BrAnon, BrEnv, BrFresh, BrNamed};
 use ty::{TyBool, TyChar, TyStruct, TyEnum};
-use ty::{TyError, TyStr, TyArray, TySlice, TyFloat, TyFnDef, TyFnPtr};
-use ty::{TyParam, TyRawPtr, TyRef, TyTuple};
+use ty::{TyError, TyStr, TyArray, TyGenericArray, TySlice, TyFloat, TyFnDef, TyFnPtr};
+use ty::{TyParam, TyConst, TyRawPtr, TyRef, TyNever, TyTuple};
 use ty::TyClosure;
 use ty::{TyBox, TyTrait, TyInt, TyUint, TyInfer};
 use ty::{self, Ty, TyCtxt, TypeFoldable};
@@ -429,6 +429,9 @@ impl<'tcx, 'container> fmt::Debug for ty::AdtDefData<'tcx, 'container> {
 impl<'tcx> fmt::Debug for ty::adjustment::AutoAdjustment<'tcx> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            ty::adjustment::AdjustNeverToAny(ref target) => {
+                write!(f, "AdjustNeverToAny({:?})", target)
+            }
             ty::adjustment::AdjustReifyFnPointer => {
                 write!(f, "AdjustReifyFnPointer")
             }
@@ -907,7 +910,59 @@ impl<'tcx> fmt::Display for ty::TypeVariants<'tcx> {
             }
             TyTrait(ref data) => write!(f, "{}", data),
             ty::TyProjection(ref data) => write!(f, "{}", data),
+            ty::TyAnon(def_id, substs) => ty::tls::with(|tcx| {
+                // Print the bounds of the `impl Trait`, which are all there
+                // is to know about the anonymized type.
+                let predicates = if def_id.is_local() {
+                    tcx.predicates.borrow().get(&def_id).cloned()
+                } else {
+                    Some(tcx.lookup_predicates(def_id))
+                };
+                let predicates = match (predicates, tcx.lift(&substs)) {
+                    (Some(predicates), Some(substs)) => {
+                        predicates.instantiate(tcx, substs).predicates.into_vec()
+                    }
+                    (Some(predicates), None) => predicates.predicates.into_vec(),
+                    (None, _) => return write!(f, "impl Trait"),
+                };
+
+                let mut first = true;
+                let mut is_sized = false;
+                write!(f, "impl")?;
+                for predicate in &predicates {
+                    match *predicate {
+                        ty::Predicate::Trait(ref data) => {
+                            let trait_ref = data.0.trait_ref;
+                            // `Sized` is implied, like for type parameters.
+                            if Some(trait_ref.def_id) == tcx.lang_items.sized_trait() {
+                                is_sized = true;
+                                continue;
+                            }
+                            let projections = predicates.iter().filter_map(|p| match *p {
+                                ty::Predicate::Projection(ref data)
+                                        if data.0.projection_ty.trait_ref.def_id ==
+                                           trait_ref.def_id => Some(data.0.clone()),
+                                _ => None,
+                            }).collect();
+                            write!(f, "{}", if first { " " } else { " + " })?;
+                            let tap = ty::Binder(TraitAndProjections(trait_ref, projections));
+                            in_binder(f, tcx, &ty::Binder(""), Some(tap))?;
+                        }
+                        ty::Predicate::TypeOutlives(ref data) => {
+                            let ty::OutlivesPredicate(_, ref region) = data.0;
+                            write!(f, "{}{}", if first { " " } else { " + " }, region)?;
+                        }
+                        _ => continue,
+                    }
+                    first = false;
+                }
+                if !is_sized {
+                    write!(f, "{}?Sized", if first { " " } else { " + " })?;
+                }
+                Ok(())
+            }),
             TyStr => write!(f, "str"),
+            TyNever => write!(f, "!"),
             TyClosure(did, substs) => ty::tls::with(|tcx| {
                 write!(f, "[closure")?;
 
@@ -940,6 +995,8 @@ impl<'tcx> fmt::Display for ty::TypeVariants<'tcx> {
                 write!(f, "]")
             }),
             TyArray(ty, sz) => write!(f, "[{}; {}]",  ty, sz),
+            TyGenericArray(ty, len) => write!(f, "[{}; {}]",  ty, len),
+            TyConst(n) => write!(f, "{}", n),
             TySlice(ty) => write!(f, "[{}]",  ty)
         }
     }
@@ -1055,7 +1112,20 @@ impl<'tcx> fmt::Display for ty::ProjectionTy<'tcx> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}::{}",
                self.trait_ref,
-               self.item_name)
+               self.item_name)?;
+
+        // The arguments of a generic associated type, if any.
+        let regions = self.item_substs.regions.get_slice(subst::FnSpace).iter().map(|r| {
+            r.to_string()
+        }).filter(|r| !r.is_empty());
+        let types = self.item_substs.types.get_slice(subst::FnSpace).iter().map(|t| {
+            t.to_string()
+        });
+        let args = regions.chain(types).collect::<Vec<_>>();
+        if !args.is_empty() {
+            write!(f, "<{}>", args.join(", "))?;
+        }
+        Ok(())
     }
 }
 
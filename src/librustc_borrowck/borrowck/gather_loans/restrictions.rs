@@ -99,8 +99,31 @@ impl<'a, 'tcx> RestrictionsContext<'a, 'tcx> {
                     Categorization::Downcast(_, variant_id) => Some(variant_id),
                     _ => None
                 };
+                let base_ty = cmt_base.ty;
                 let result = self.restrict(cmt_base);
-                self.extend(result, &cmt, LpInterior(opt_variant_id, i.cleaned()))
+                match base_ty.sty {
+                    ty::TyStruct(def, substs) if def.is_union() => {
+                        // Borrowing one union field borrows all of them,
+                        // since they occupy the same memory.
+                        match result {
+                            RestrictionResult::Safe => RestrictionResult::Safe,
+                            RestrictionResult::SafeIf(base_lp, mut base_vec) => {
+                                for field in &def.struct_variant().fields {
+                                    let name = InteriorKind::InteriorField(
+                                        mc::NamedField(field.name));
+                                    let sibling_kind = LpExtend(base_lp.clone(), cmt.mutbl,
+                                                                LpInterior(opt_variant_id, name));
+                                    let field_ty = field.ty(self.bccx.tcx, substs);
+                                    base_vec.push(Rc::new(LoanPath::new(sibling_kind, field_ty)));
+                                }
+                                let lp = new_lp(LpExtend(base_lp, cmt.mutbl,
+                                                         LpInterior(opt_variant_id, i.cleaned())));
+                                RestrictionResult::SafeIf(lp, base_vec)
+                            }
+                        }
+                    }
+                    _ => self.extend(result, &cmt, LpInterior(opt_variant_id, i.cleaned()))
+                }
             }
 
             Categorization::StaticItem => {
@@ -258,7 +258,8 @@ fn lvalue_contents_drop_state_cannot_differ<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx
                                                       lv: &repr::Lvalue<'tcx>) -> bool {
     let ty = mir.lvalue_ty(tcx, lv).to_ty(tcx);
     match ty.sty {
-        ty::TyArray(..) | ty::TySlice(..) | ty::TyRef(..) | ty::TyRawPtr(..) => {
+        ty::TyArray(..) | ty::TyGenericArray(..) | ty::TySlice(..) |
+        ty::TyRef(..) | ty::TyRawPtr(..) => {
             debug!("lvalue_contents_drop_state_cannot_differ lv: {:?} ty: {:?} refd => false",
                    lv, ty);
             true
@@ -268,6 +269,11 @@ fn lvalue_contents_drop_state_cannot_differ<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx
                    lv, ty);
             true
         }
+        ty::TyStruct(def, _) if def.is_union() => {
+            debug!("lvalue_contents_drop_state_cannot_differ lv: {:?} ty: {:?} union => true",
+                   lv, ty);
+            true
+        }
         _ => {
             false
         }
@@ -21,7 +21,8 @@ use rustc::middle::dataflow::DataFlowOperator;
 use rustc::middle::dataflow::KillFrom;
 use rustc::middle::expr_use_visitor as euv;
 use rustc::middle::expr_use_visitor::MutateMode;
-use rustc::ty::TyCtxt;
+use rustc::middle::mem_categorization as mc;
+use rustc::ty::{self, TyCtxt};
 use rustc::util::nodemap::{FnvHashMap, NodeSet};
 
 use std::cell::RefCell;
@@ -364,6 +365,16 @@ impl<'a, 'tcx> MoveData<'tcx> {
                     lp: Rc<LoanPath<'tcx>>,
                     id: ast::NodeId,
                     kind: MoveKind) {
+        // Moving one union field automatically moves all its fields.
+        for lp in union_field_paths(tcx, lp) {
+            self.add_move_helper(tcx, lp, id, kind);
+        }
+    }
+
+    fn add_move_helper(&self, tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                       lp: Rc<LoanPath<'tcx>>,
+                       id: ast::NodeId,
+                       kind: MoveKind) {
         debug!("add_move(lp={:?}, id={}, kind={:?})",
                lp,
                id,
@@ -393,6 +404,18 @@ impl<'a, 'tcx> MoveData<'tcx> {
                           span: Span,
                           assignee_id: ast::NodeId,
                           mode: euv::MutateMode) {
+        // Assigning to one union field automatically assigns to all its fields.
+        for lp in union_field_paths(tcx, lp) {
+            self.add_assignment_helper(tcx, lp, assign_id, span, assignee_id, mode);
+        }
+    }
+
+    fn add_assignment_helper(&self, tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                             lp: Rc<LoanPath<'tcx>>,
+                             assign_id: ast::NodeId,
+                             span: Span,
+                             assignee_id: ast::NodeId,
+                             mode: euv::MutateMode) {
         debug!("add_assignment(lp={:?}, assign_id={}, assignee_id={}",
                lp, assign_id, assignee_id);
 
@@ -590,6 +613,26 @@ impl<'a, 'tcx> MoveData<'tcx> {
     }
 }
 
+/// If `lp` is a field of a union, returns the paths of all the fields
+/// of that union, which share its memory. Otherwise returns just `lp`.
+fn union_field_paths<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                               lp: Rc<LoanPath<'tcx>>)
+                               -> Vec<Rc<LoanPath<'tcx>>> {
+    if let LpExtend(ref base_lp, mutbl, LpInterior(opt_variant_id, _)) = lp.kind {
+        if let ty::TyStruct(def, substs) = base_lp.ty.sty {
+            if def.is_union() {
+                return def.struct_variant().fields.iter().map(|field| {
+                    let name = InteriorKind::InteriorField(mc::NamedField(field.name));
+                    let kind = LpExtend(base_lp.clone(), mutbl,
+                                        LpInterior(opt_variant_id, name));
+                    Rc::new(LoanPath::new(kind, field.ty(tcx, substs)))
+                }).collect();
+            }
+        }
+    }
+    vec![lp]
+}
+
 impl<'a, 'tcx> FlowedMoveData<'a, 'tcx> {
     pub fn new(move_data: MoveData<'tcx>,
                tcx: TyCtxt<'a, 'tcx, 'tcx>,
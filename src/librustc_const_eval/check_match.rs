@@ -627,8 +627,9 @@ impl Constructor {
     }
 }
 
-fn missing_constructors(cx: &MatchCheckCtxt, &Matrix(ref rows): &Matrix,
-                       left_ty: Ty, max_slice_length: usize) -> Vec<Constructor> {
+fn missing_constructors<'a, 'tcx>(cx: &MatchCheckCtxt<'a, 'tcx>, &Matrix(ref rows): &Matrix,
+                                  left_ty: Ty<'tcx>, max_slice_length: usize)
+                                  -> Vec<Constructor> {
     let used_constructors: Vec<Constructor> = rows.iter()
         .flat_map(|row| pat_constructors(cx, row[0].0, left_ty, max_slice_length))
         .collect();
@@ -642,13 +643,23 @@ fn missing_constructors(cx: &MatchCheckCtxt, &Matrix(ref rows): &Matrix,
 /// values of type `left_ty`. For vectors, this would normally be an infinite set
 /// but is instead bounded by the maximum fixed length of slice patterns in
 /// the column of patterns being analyzed.
-fn all_constructors(_cx: &MatchCheckCtxt, left_ty: Ty,
-                    max_slice_length: usize) -> Vec<Constructor> {
+///
+/// With `#![feature(never_type)]`, enum variants with a field of an
+/// uninhabited type (such as `!`) can never be constructed, so they are
+/// left out.
+fn all_constructors<'a, 'tcx>(cx: &MatchCheckCtxt<'a, 'tcx>, left_ty: Ty<'tcx>,
+                              max_slice_length: usize) -> Vec<Constructor> {
     match left_ty.sty {
         ty::TyBool =>
             [true, false].iter().map(|b| ConstantValue(ConstVal::Bool(*b))).collect(),
         ty::TySlice(_) =>
             (0..max_slice_length+1).map(|length| Slice(length)).collect(),
+        ty::TyEnum(def, substs) if cx.tcx.sess.features.borrow().never_type => {
+            def.variants.iter()
+                .filter(|v| !v.fields.iter().any(|f| f.ty(cx.tcx, substs).is_empty(cx.tcx)))
+                .map(|v| Variant(v.did))
+                .collect()
+        }
         ty::TyEnum(def, _) => def.variants.iter().map(|v| Variant(v.did)).collect(),
         _ => vec![Single]
     }
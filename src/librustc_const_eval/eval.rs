@@ -835,6 +835,9 @@ pub fn eval_const_expr_partial<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                   }
               },
               Def::Method(id) | Def::Fn(id) => Function(id),
+              // The value of a `const` type parameter is only known once
+              // the item using it is monomorphized, in trans.
+              Def::TyParam(..) => signal!(e, UnimplementedConstVal("const parameters")),
               _ => signal!(e, NonConstPath),
           }
       }
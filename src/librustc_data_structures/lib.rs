@@ -26,6 +26,7 @@
 #![cfg_attr(not(stage0), deny(warnings))]
 
 #![feature(nonzero)]
+#![feature(libc)]
 #![feature(rustc_private)]
 #![feature(staged_api)]
 #![feature(unboxed_closures)]
@@ -37,6 +38,7 @@ extern crate core;
 #[macro_use]
 extern crate log;
 extern crate serialize as rustc_serialize; // used by deriving
+extern crate libc;
 
 pub mod bitvec;
 pub mod graph;
@@ -51,6 +53,7 @@ pub mod fnv;
 pub mod tuple_slice;
 pub mod veccell;
 pub mod control_flow_graph;
+pub mod flock;
 
 // See comments in src/librustc/lib.rs
 #[doc(hidden)]
@@ -0,0 +1,172 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `--print crate-info`, which describes a compiled library, and
+//! `-Z semver-check`, which compares two versions of one.
+//!
+//! Printing signatures requires a type context, so we load the library
+//! by compiling an otherwise empty crate that contains nothing but an
+//! `extern crate` item for it, and then dump the metadata of the crate
+//! that item resolved to.
+
+use driver;
+use rustc::dep_graph::DepGraph;
+use rustc::hir;
+use rustc::hir::map as hir_map;
+use rustc::lint;
+use rustc::middle::cstore::CrateStore;
+use rustc::session::{self, config, Session};
+use rustc::session::config::Input;
+use rustc::ty;
+use rustc_metadata::cstore::CStore;
+use rustc_metadata::crate_info::{self, CrateInfo};
+use rustc_metadata::loader;
+use rustc_metadata::semver;
+use rustc_lint;
+use rustc_resolve as resolve;
+use syntax::ast;
+use syntax::feature_gate::UnstableFeatures;
+use syntax::parse::token;
+use syntax::ptr::P;
+use syntax_pos::DUMMY_SP;
+
+use std::io;
+use std::path::Path;
+use std::rc::Rc;
+
+use super::{abort_on_err, diagnostics_registry};
+
+const CRATE_NAME: &'static str = "rustc_crate_info";
+
+pub fn print_crate_info(sess: &Session, path: &Path, json: bool) {
+    let info = load_crate_info(sess, path);
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let result = if json {
+        info.write_json(&mut out)
+    } else {
+        info.write_text(&mut out)
+    };
+    if let Err(err) = result {
+        sess.fatal(&format!("failed to print crate info: {}", err));
+    }
+}
+
+/// Compares the library at `new_path` against an older version of it
+/// at `old_path`, and prints the changes to its public interface along
+/// with the kind of release they require.
+pub fn check_semver(sess: &Session, old_path: &Path, new_path: &Path) {
+    let old = load_crate_info(sess, old_path);
+    let new = load_crate_info(sess, new_path);
+    if old.name != new.name {
+        sess.fatal(&format!("cannot compare different crates `{}` and `{}`",
+                            old.name, new.name));
+    }
+
+    let report = semver::compare(&old, &new);
+    let stdout = io::stdout();
+    if let Err(err) = report.write_text(&mut stdout.lock()) {
+        sess.fatal(&format!("failed to print semver report: {}", err));
+    }
+}
+
+/// Each library is loaded in a session of its own, so that two
+/// versions of the same crate can be loaded one after the other.
+fn load_crate_info(sess: &Session, path: &Path) -> CrateInfo {
+    let name = match loader::crate_name_from_file(&sess.target.target, path) {
+        Ok(name) => name,
+        Err(msg) => sess.fatal(&msg),
+    };
+
+    let mut externs = sess.opts.externs.clone();
+    externs.insert(name.clone(), vec![path.to_string_lossy().into_owned()]);
+    let sessopts = config::Options {
+        crate_types: vec![config::CrateTypeRlib],
+        externs: externs,
+        lint_cap: Some(lint::Allow),
+        prints: vec![],
+        incremental: None,
+        // We need `#![no_core]`, see below.
+        unstable_features: UnstableFeatures::Allow,
+        ..sess.opts.clone()
+    };
+
+    let dep_graph = DepGraph::new(false);
+    let _ignore = dep_graph.in_ignore();
+    let cstore = Rc::new(CStore::new(&dep_graph));
+    let sess = session::build_session(sessopts,
+                                      &dep_graph,
+                                      None,
+                                      diagnostics_registry(),
+                                      cstore.clone());
+    rustc_lint::register_builtins(&mut sess.lint_store.borrow_mut(), Some(&sess));
+    let cfg = config::build_configuration(&sess);
+
+    // The crate is `#![no_core]`, so that a library which is itself
+    // `core` or `std` is only loaded once.
+    let input = Input::Str {
+        name: format!("<{}>", CRATE_NAME),
+        input: "#![feature(no_core)] #![no_core]".to_string(),
+    };
+    let mut krate = match driver::phase_1_parse_input(&sess, cfg, &input) {
+        Ok(krate) => krate,
+        Err(mut parse_error) => {
+            parse_error.emit();
+            sess.fatal(&format!("failed to load `{}`", path.display()));
+        }
+    };
+
+    // Like the `extern crate std` item that is normally injected, this
+    // item has a dummy span, which keeps the stability checker from
+    // complaining about unstable libraries.
+    krate.module.items.push(P(ast::Item {
+        attrs: vec![],
+        vis: ast::Visibility::Inherited,
+        node: ast::ItemKind::ExternCrate(None),
+        ident: token::str_to_ident(&name),
+        id: ast::DUMMY_NODE_ID,
+        span: DUMMY_SP,
+    }));
+
+    let driver::ExpansionResult { defs, analysis, resolutions, mut hir_forest, .. } = {
+        abort_on_err(driver::phase_2_configure_and_expand(&sess,
+                                                          &cstore,
+                                                          krate,
+                                                          CRATE_NAME,
+                                                          None,
+                                                          resolve::MakeGlobMap::No,
+                                                          |_| Ok(())),
+                     &sess)
+    };
+
+    let arenas = ty::CtxtArenas::new();
+    let hir_map = hir_map::map_crate(&mut hir_forest, defs);
+
+    abort_on_err(driver::phase_3_run_analysis_passes(&sess,
+                                                     hir_map,
+                                                     analysis,
+                                                     resolutions,
+                                                     &arenas,
+                                                     CRATE_NAME,
+                                                     |tcx, _, _, result| {
+        if let Err(_) = result {
+            sess.fatal(&format!("failed to load `{}`", path.display()));
+        }
+
+        let cnum = tcx.map.krate().items.values().filter_map(|item| {
+            match item.node {
+                hir::ItemExternCrate(_) => cstore.extern_mod_stmt_cnum(item.id),
+                _ => None,
+            }
+        }).next().unwrap();
+
+        crate_info::crate_info(tcx, &cstore, cnum)
+    }), &sess)
+}
@@ -26,7 +26,7 @@ use rustc::util::nodemap::NodeSet;
 use rustc_back::sha2::{Sha256, Digest};
 use rustc_borrowck as borrowck;
 use rustc_incremental;
-use rustc_resolve::{MakeGlobMap, Resolver};
+use rustc_resolve::{self, MakeGlobMap, Resolver};
 use rustc_metadata::macro_import;
 use rustc_metadata::creader::read_local_crates;
 use rustc_metadata::cstore::CStore;
@@ -42,7 +42,7 @@ use super::Compilation;
 
 use serialize::json;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::ffi::{OsString, OsStr};
 use std::fs;
@@ -71,6 +71,25 @@ pub fn compile_input(sess: &Session,
                      output: &Option<PathBuf>,
                      addl_plugins: Option<Vec<String>>,
                      control: &CompileController) -> CompileResult {
+    let result = run_phases(sess, cstore, cfg, input, outdir, output, addl_plugins, control);
+
+    // Compilation can stop before the incremental compilation session
+    // directory gets finalized, because of errors or because the controller
+    // asked for it. The directory is incomplete then, so don't leave it
+    // behind.
+    rustc_incremental::delete_session_directory(sess);
+
+    result
+}
+
+fn run_phases(sess: &Session,
+              cstore: &CStore,
+              cfg: ast::CrateConfig,
+              input: &Input,
+              outdir: &Option<PathBuf>,
+              output: &Option<PathBuf>,
+              addl_plugins: Option<Vec<String>>,
+              control: &CompileController) -> CompileResult {
     macro_rules! controller_entry_point {
         ($point: ident, $tsess: expr, $make_state: expr, $phase_result: expr) => {{
             let state = &mut $make_state;
@@ -228,6 +247,10 @@ pub fn compile_input(sess: &Session,
 
     phase_6_link_output(sess, &trans, &outputs);
 
+    // Now that we won't touch anything in the incremental compilation directory
+    // any more, we can finalize it (which involves renaming it)
+    rustc_incremental::finalize_session_directory(sess, trans.link.crate_hash);
+
     controller_entry_point!(compilation_done,
                             sess,
                             CompileState::state_when_compilation_done(input, sess, outdir, output),
@@ -243,7 +266,8 @@ fn keep_hygiene_data(sess: &Session) -> bool {
 fn keep_ast(sess: &Session) -> bool {
     sess.opts.debugging_opts.keep_ast ||
     sess.opts.debugging_opts.save_analysis ||
-    sess.opts.debugging_opts.save_analysis_csv
+    sess.opts.debugging_opts.save_analysis_csv ||
+    sess.opts.debugging_opts.save_analysis_api
 }
 
 /// The name used for source code that doesn't originate in a file
@@ -599,6 +623,14 @@ pub fn phase_2_configure_and_expand<'a, F>(sess: &Session,
         }
 
         for registrar in registrars {
+            if let Some(ref import) = registrar.proc_macros {
+                // Anything but procedural macros needs the crate to be
+                // imported as a plugin.
+                let mut proc_macros = Registry::new(sess, &krate);
+                (registrar.fun)(&mut proc_macros);
+                registry.syntax_exts.extend(import.select(sess, proc_macros.syntax_exts));
+                continue;
+            }
             registry.args_hidden = Some(registrar.args);
             (registrar.fun)(&mut registry);
         }
@@ -632,7 +664,14 @@ pub fn phase_2_configure_and_expand<'a, F>(sess: &Session,
     }
     sess.track_errors(|| sess.lint_store.borrow_mut().process_command_line(sess))?;
 
-    krate = time(time_passes, "expansion", || {
+    // The spans of the imports of macros which were invoked, and the macros
+    // which can be imported by `use` from each extern crate.
+    let mut used_macro_imports = HashSet::new();
+    let mut extern_macros = HashMap::new();
+
+    // Expansion reports every erroneous macro invocation and carries on
+    // with a dummy result, which would only lead to more errors later.
+    krate = sess.track_errors(|| time(time_passes, "expansion", || {
         // Windows dlls do not have rpaths, so they don't know how to find their
         // dependencies. It's up to us to tell the system where to find all the
         // dependent dlls. Note that this uses cfg!(windows) as opposed to
@@ -664,23 +703,34 @@ pub fn phase_2_configure_and_expand<'a, F>(sess: &Session,
             features: Some(&features),
             recursion_limit: sess.recursion_limit.get(),
             trace_mac: sess.opts.debugging_opts.trace_macros,
+            trace_mac_format: sess.opts.debugging_opts.trace_macros_format,
             should_test: sess.opts.test,
         };
         let mut loader = macro_import::MacroLoader::new(sess, &cstore, crate_name);
+        let macro_imports = if features.use_extern_macros {
+            let (macro_imports, macros) =
+                rustc_resolve::resolve_macro_imports(sess, &krate, &mut loader);
+            extern_macros = macros;
+            macro_imports
+        } else {
+            HashMap::new()
+        };
         let mut ecx = syntax::ext::base::ExtCtxt::new(&sess.parse_sess,
                                                       krate.config.clone(),
                                                       cfg,
                                                       &mut loader);
+        ecx.macro_imports = macro_imports;
         syntax_ext::register_builtins(&mut ecx.syntax_env);
-        let (ret, macro_names) = syntax::ext::expand::expand_crate(ecx,
-                                                                   syntax_exts,
-                                                                   krate);
+        let (ret, macro_names, used_imports) = syntax::ext::expand::expand_crate(ecx,
+                                                                                 syntax_exts,
+                                                                                 krate);
         if cfg!(windows) {
             env::set_var("PATH", &old_path);
         }
         *sess.available_macros.borrow_mut() = macro_names;
+        used_macro_imports = used_imports;
         ret
-    });
+    }))?;
 
     krate = time(time_passes, "maybe building test harness", || {
         syntax::test::modify_for_testing(&sess.parse_sess,
@@ -689,8 +739,14 @@ pub fn phase_2_configure_and_expand<'a, F>(sess: &Session,
                                          sess.diagnostic())
     });
 
+    krate = time(time_passes, "maybe creating a procedural macro registrar", || {
+        syntax_ext::proc_macro_registrar::modify(&sess.parse_sess, krate, sess.diagnostic())
+    });
+
     let resolver_arenas = Resolver::arenas();
     let mut resolver = Resolver::new(sess, make_glob_map, &resolver_arenas);
+    resolver.used_macro_imports = used_macro_imports;
+    resolver.extern_macros = extern_macros;
 
     let krate = time(sess.time_passes(), "assigning node ids", || resolver.assign_node_ids(krate));
 
@@ -859,12 +915,23 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
                              name,
                              |tcx| {
         time(time_passes,
-             "load_dep_graph",
-             || rustc_incremental::load_dep_graph(tcx));
+             "incremental session directory",
+             || {
+                 // If the session directory cannot be set up, an error has
+                 // been reported already.
+                 let _ = rustc_incremental::prepare_session_directory(tcx);
+             });
 
         // passes are timed inside typeck
         try_with_f!(typeck::check_crate(tcx, trait_map), (tcx, None, analysis));
 
+        // The dep-graph is loaded after type collection, so that the
+        // signatures computed in this session can be compared against
+        // those of the previous session.
+        time(time_passes,
+             "load_dep_graph",
+             || rustc_incremental::load_dep_graph(tcx));
+
         time(time_passes,
              "const checking",
              || consts::check_crate(tcx));
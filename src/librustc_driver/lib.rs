@@ -85,7 +85,7 @@ use std::default::Default;
 use std::env;
 use std::io::{self, Read, Write};
 use std::iter::repeat;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
 use std::rc::Rc;
 use std::str;
@@ -106,6 +106,8 @@ use errors::snippet::FormatMode;
 pub mod test;
 
 pub mod driver;
+mod crate_info;
+mod server;
 pub mod pretty;
 pub mod target_features;
 
@@ -470,6 +472,14 @@ impl<'a> CompilerCalls<'a> for RustcDefaultCalls {
                      -> Compilation {
         RustcDefaultCalls::print_crate_info(sess, Some(input), odir, ofile)
             .and_then(|| RustcDefaultCalls::list_metadata(sess, matches, input))
+            .and_then(|| {
+                if sess.opts.debugging_opts.server {
+                    server::serve(sess, input);
+                    Compilation::Stop
+                } else {
+                    Compilation::Continue
+                }
+            })
     }
 
     fn build_controller(&mut self,
@@ -548,7 +558,8 @@ impl<'a> CompilerCalls<'a> for RustcDefaultCalls {
 
 fn save_analysis(sess: &Session) -> bool {
     sess.opts.debugging_opts.save_analysis ||
-    sess.opts.debugging_opts.save_analysis_csv
+    sess.opts.debugging_opts.save_analysis_csv ||
+    sess.opts.debugging_opts.save_analysis_api
 }
 
 fn save_analysis_format(sess: &Session) -> save::Format {
@@ -556,6 +567,8 @@ fn save_analysis_format(sess: &Session) -> save::Format {
         save::Format::Json
     } else if sess.opts.debugging_opts.save_analysis_csv {
         save::Format::Csv
+    } else if sess.opts.debugging_opts.save_analysis_api {
+        save::Format::JsonApi
     } else {
         unreachable!();
     }
@@ -564,6 +577,17 @@ fn save_analysis_format(sess: &Session) -> save::Format {
 impl RustcDefaultCalls {
     pub fn list_metadata(sess: &Session, matches: &getopts::Matches, input: &Input) -> Compilation {
         let r = matches.opt_strs("Z");
+        if let Some(ref old_path) = sess.opts.debugging_opts.semver_check {
+            match input {
+                &Input::File(ref ifile) => {
+                    crate_info::check_semver(sess, Path::new(old_path), ifile);
+                }
+                &Input::Str { .. } => {
+                    early_error(ErrorOutputType::default(), "cannot check semver for stdin");
+                }
+            }
+            return Compilation::Stop;
+        }
         if r.contains(&("ls".to_string())) {
             match input {
                 &Input::File(ref ifile) => {
@@ -593,9 +617,13 @@ impl RustcDefaultCalls {
             return Compilation::Continue;
         }
 
+        // Only the requests about the crate being compiled need its
+        // attributes; `crate-info` takes a library as its input instead.
+        let needs_attrs = sess.opts.prints.iter().any(|req| {
+            *req == PrintRequest::FileNames || *req == PrintRequest::CrateName
+        });
         let attrs = match input {
-            None => None,
-            Some(input) => {
+            Some(input) if needs_attrs => {
                 let result = parse_crate_attrs(sess, input);
                 match result {
                     Ok(attrs) => Some(attrs),
@@ -605,6 +633,7 @@ impl RustcDefaultCalls {
                     }
                 }
             }
+            _ => None,
         };
         for req in &sess.opts.prints {
             match *req {
@@ -636,6 +665,19 @@ impl RustcDefaultCalls {
                                       .to_string_lossy());
                     }
                 }
+                PrintRequest::CrateInfo |
+                PrintRequest::CrateInfoJson => {
+                    let path = match input {
+                        Some(&Input::File(ref path)) => path,
+                        Some(&Input::Str { .. }) => {
+                            early_error(ErrorOutputType::default(),
+                                        "cannot print crate info for stdin")
+                        }
+                        None => early_error(ErrorOutputType::default(), "no input file provided"),
+                    };
+                    let json = *req == PrintRequest::CrateInfoJson;
+                    crate_info::print_crate_info(sess, path, json);
+                }
                 PrintRequest::Cfg => {
                     let mut cfg = config::build_configuration(&sess);
                     target_features::add_configuration(&mut cfg, &sess);
@@ -81,6 +81,8 @@ pub enum PpMode {
     PpmFlowGraph(PpFlowGraphMode),
     PpmMir,
     PpmMirCFG,
+    /// Formats the source, wrapping lines at the given number of columns.
+    PpmFormat(usize),
 }
 
 impl PpMode {
@@ -90,6 +92,8 @@ impl PpMode {
             PpmSource(PpmEveryBodyLoops) |
             PpmSource(PpmIdentified) => opt_uii.is_some(),
 
+            PpmFormat(_) => false,
+
             PpmSource(PpmExpanded) |
             PpmSource(PpmExpandedIdentified) |
             PpmSource(PpmExpandedHygiene) |
@@ -130,12 +134,24 @@ pub fn parse_pretty(sess: &Session,
         ("mir-cfg", true) => PpmMirCFG,
         ("flowgraph", true) => PpmFlowGraph(PpFlowGraphMode::Default),
         ("flowgraph,unlabelled", true) => PpmFlowGraph(PpFlowGraphMode::UnlabelledEdges),
+        ("format", true) => {
+            let width = match opt_second {
+                None => pprust::DEFAULT_COLUMNS,
+                Some(s) => match s.parse::<usize>() {
+                    Ok(width) if width > 0 => width,
+                    _ => sess.fatal(&format!("the width given to `--unpretty format` must be \
+                                              a positive number; got {}", s)),
+                },
+            };
+            return (PpmFormat(width), None);
+        }
         _ => {
             if extended {
                 sess.fatal(&format!("argument to `unpretty` must be one of `normal`, \
                                      `expanded`, `flowgraph[,unlabelled]=<nodeid>`, \
                                      `identified`, `expanded,identified`, `everybody_loops`, \
-                                     `hir`, `hir,identified`, `hir,typed`, or `mir`; got {}",
+                                     `hir`, `hir,identified`, `hir,typed`, `mir`, or \
+                                     `format[=<width>]`; got {}",
                                     name));
             } else {
                 sess.fatal(&format!("argument to `pretty` must be one of `normal`, `expanded`, \
@@ -809,6 +825,14 @@ pub fn print_after_parsing(sess: &Session,
                                 annotation.pp_ann(),
                                 false)
         }).unwrap()
+    } else if let PpmFormat(width) = ppm {
+        pprust::format_crate(sess.codemap(),
+                             sess.diagnostic(),
+                             krate,
+                             src_name.to_string(),
+                             &mut rdr,
+                             box &mut out,
+                             width).unwrap()
     } else {
         unreachable!();
     };
@@ -0,0 +1,832 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The compiler server (`-Z server`), which answers queries about a crate
+//! for IDEs and other tools.
+//!
+//! The server reads JSON-RPC 2.0 requests from stdin, one per line, and
+//! writes the responses to stdout in the same way. Positions are given as
+//! `{"file": ..., "line": ..., "column": ...}`, with 1-based lines and
+//! columns, like the spans in save-analysis data. The methods are:
+//!
+//! - `didChange {file, text}`: the editor has unsaved changes to `file`,
+//!   which the server uses instead of the file on disk from now on.
+//! - `didClose {file}`: forget the unsaved changes to `file`.
+//! - `diagnostics`: the errors and warnings for the crate, in the same
+//!   format as `--error-format=json`.
+//! - `hover {position}`: the kind, name and type or signature of the item
+//!   at the position.
+//! - `definition {position}`: the span of the definition of the item at
+//!   the position.
+//! - `completion {position}`: the items whose names start with the
+//!   identifier that ends at the position.
+//! - `shutdown`: stop the server.
+//!
+//! The answers come from the save-analysis data of the crate. The analysis
+//! is run (as with `-Z no-trans`) the first time it is needed, and then
+//! reused until a file changes. The type context cannot outlive a
+//! compilation session, so after a change the crate is compiled again (with
+//! `-Z incremental`, using the incremental compilation directory like any
+//! other compilation), but the server keeps the save-analysis data of every
+//! item, along with the dep-graph of the analysis and the hashes of the HIR
+//! of the items. The items whose HIR changed are looked up in the dep-graph
+//! of the previous analysis, and only the items that depend on them, and the
+//! items that moved, are analyzed again; the data of all other items is
+//! reused. With `-Z incremental-info`, the server reports the items it
+//! analyzes again on stderr.
+
+use driver::{self, CompileController};
+use rustc::dep_graph::{DepGraph, DepGraphQuery, DepNode};
+use rustc::hir::def_id::DefId;
+use rustc::hir::map as hir_map;
+use rustc::session::{self, config};
+use rustc::session::config::Input;
+use rustc::session::Session;
+use rustc::ty::{self, TyCtxt};
+use rustc::util::nodemap::NodeSet;
+use rustc_incremental::SvhCalculate;
+use rustc_lint;
+use rustc_metadata::cstore::CStore;
+use rustc_resolve as resolve;
+use rustc_save_analysis as save;
+use rustc_save_analysis::external_data::*;
+use serialize::json::Json;
+use syntax::ast;
+use syntax::codemap::{CodeMap, FileLoader};
+use syntax::json::JsonEmitter;
+use syntax::visit::{self, Visitor};
+use errors;
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher, SipHasher};
+use std::io::{self, BufRead, Read, Write};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use super::{diagnostics_registry, target_features, Compilation};
+
+// The error codes defined by JSON-RPC.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+type RpcResult = Result<Json, (i64, String)>;
+
+/// Serves requests for the crate `input` until stdin is closed or a
+/// `shutdown` request is received.
+pub fn serve(sess: &Session, input: &Input) {
+    let path = match *input {
+        Input::File(ref path) => absolute(path),
+        Input::Str { .. } => sess.fatal("the compiler server requires a crate root file"),
+    };
+
+    let mut opts = sess.opts.clone();
+    opts.debugging_opts.server = false;
+    opts.debugging_opts.keep_ast = true;
+    opts.no_trans = true;
+    let mut server = Server {
+        opts: opts,
+        path: path,
+        files: Rc::new(RefCell::new(HashMap::new())),
+        index: None,
+        changed: false,
+        shutdown: false,
+    };
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => sess.fatal(&format!("failed to read request: {}", err)),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(response) = server.handle(&line) {
+            let mut out = stdout.lock();
+            if let Err(err) = write!(out, "{}\n", response).and_then(|_| out.flush()) {
+                sess.fatal(&format!("failed to write response: {}", err));
+            }
+        }
+
+        if server.shutdown {
+            break;
+        }
+    }
+}
+
+struct Server {
+    opts: config::Options,
+    path: PathBuf,
+    // The contents of files with unsaved changes, by absolute path.
+    files: Rc<RefCell<HashMap<PathBuf, String>>>,
+    // The analysis of the crate, if it has been run.
+    index: Option<Index>,
+    // Whether files changed since the analysis was run.
+    changed: bool,
+    shutdown: bool,
+}
+
+impl Server {
+    /// Handles one request, and returns the response, unless the request
+    /// is a notification.
+    fn handle(&mut self, line: &str) -> Option<Json> {
+        let request = match Json::from_str(line) {
+            Ok(Json::Object(request)) => request,
+            Ok(_) => return Some(error_response(Json::Null, INVALID_REQUEST, "not an object")),
+            Err(err) => return Some(error_response(Json::Null, PARSE_ERROR, &err.to_string())),
+        };
+
+        let id = request.get("id").cloned();
+        let result = match request.get("method").and_then(|method| method.as_string()) {
+            Some(method) => {
+                let params = request.get("params").cloned().unwrap_or(Json::Null);
+                self.call(method, &params)
+            }
+            None => Err((INVALID_REQUEST, "missing method".to_string())),
+        };
+
+        // Notifications are not answered, not even with errors.
+        let id = match id {
+            Some(id) => id,
+            None => return None,
+        };
+        Some(match result {
+            Ok(result) => response(id, "result", result),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    fn call(&mut self, method: &str, params: &Json) -> RpcResult {
+        match method {
+            "didChange" => {
+                let file = absolute(Path::new(string_param(params, "file")?));
+                let text = string_param(params, "text")?.to_string();
+                self.files.borrow_mut().insert(file, text);
+                self.changed = true;
+                Ok(Json::Null)
+            }
+            "didClose" => {
+                let file = absolute(Path::new(string_param(params, "file")?));
+                if self.files.borrow_mut().remove(&file).is_some() {
+                    self.changed = true;
+                }
+                Ok(Json::Null)
+            }
+            "diagnostics" => Ok(Json::Array(self.index()?.diagnostics.clone())),
+            "hover" => {
+                let position = Position::from_params(params)?;
+                Ok(self.index()?.hover(&position))
+            }
+            "definition" => {
+                let position = Position::from_params(params)?;
+                Ok(self.index()?.definition(&position))
+            }
+            "completion" => {
+                let position = Position::from_params(params)?;
+                let prefix = self.identifier_before(&position)?;
+                Ok(self.index()?.completion(&prefix))
+            }
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Json::Null)
+            }
+            _ => Err((METHOD_NOT_FOUND, format!("unknown method `{}`", method))),
+        }
+    }
+
+    fn index(&mut self) -> Result<&Index, (i64, String)> {
+        if self.index.is_none() || self.changed {
+            // The previous index is kept until the analysis succeeds, so it
+            // can still be reused by the next one if this one fails.
+            let index = self.analyze(self.index.as_ref())?;
+            self.index = Some(index);
+            self.changed = false;
+        }
+        Ok(self.index.as_ref().unwrap())
+    }
+
+    /// Runs the analysis, reusing what is still valid of the `previous` one.
+    fn analyze(&self, previous: Option<&Index>) -> Result<Index, (i64, String)> {
+        let diagnostics = Arc::new(Mutex::new(vec![]));
+        let index = RefCell::new(Index::default());
+
+        // Fatal errors unwind out of the compiler after they have been
+        // reported, so they just end the analysis early.
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            analyze_crate(self.opts.clone(), &self.path, &self.files, &diagnostics,
+                          previous, &index)
+        }));
+        if let Err(value) = result {
+            if !value.is::<errors::FatalError>() {
+                return Err((INTERNAL_ERROR, "the compiler panicked".to_string()));
+            }
+        }
+
+        let mut index = index.into_inner();
+        let diagnostics = String::from_utf8_lossy(&diagnostics.lock().unwrap()).into_owned();
+        index.diagnostics = diagnostics.lines()
+                                       .filter_map(|line| Json::from_str(line).ok())
+                                       .collect();
+        Ok(index)
+    }
+
+    /// The part of the identifier that ends at `position`.
+    fn identifier_before(&self, position: &Position) -> Result<String, (i64, String)> {
+        let text = match self.files.borrow().get(&position.file) {
+            Some(text) => text.clone(),
+            None => {
+                let mut text = String::new();
+                fs::File::open(&position.file)
+                         .and_then(|mut file| file.read_to_string(&mut text))
+                         .map_err(|err| (INVALID_PARAMS, err.to_string()))?;
+                text
+            }
+        };
+
+        let line = text.lines().nth(position.line - 1).unwrap_or("");
+        let before: Vec<char> = line.chars().take(position.column - 1).collect();
+        let start = before.iter()
+                          .rposition(|&c| !(c.is_alphanumeric() || c == '_'))
+                          .map_or(0, |i| i + 1);
+        Ok(before[start..].iter().cloned().collect())
+    }
+}
+
+fn analyze_crate(opts: config::Options,
+                 path: &Path,
+                 files: &Rc<RefCell<HashMap<PathBuf, String>>>,
+                 diagnostics: &Arc<Mutex<Vec<u8>>>,
+                 previous: Option<&Index>,
+                 index: &RefCell<Index>) {
+    // The dep-graph tells which items a change affects, so it is built
+    // even without `-Z incremental`.
+    let dep_graph = DepGraph::new(true);
+    let report = opts.debugging_opts.incremental_info;
+    let cstore = Rc::new(CStore::new(&dep_graph));
+    let codemap = Rc::new(CodeMap::with_file_loader(box OverlayFileLoader {
+        files: files.clone(),
+    }));
+    let emitter = box JsonEmitter::new(box Sink(diagnostics.clone()),
+                                       Some(diagnostics_registry()),
+                                       codemap.clone());
+    let handler = errors::Handler::with_emitter(true, false, emitter);
+    let sess = session::build_session_(opts,
+                                       &dep_graph,
+                                       Some(path.to_path_buf()),
+                                       handler,
+                                       codemap,
+                                       cstore.clone());
+    rustc_lint::register_builtins(&mut sess.lint_store.borrow_mut(), Some(&sess));
+    let mut cfg = config::build_configuration(&sess);
+    target_features::add_configuration(&mut cfg, &sess);
+
+    let mut control = CompileController::basic();
+    control.after_analysis.stop = Compilation::Stop;
+    control.after_analysis.run_callback_on_error = true;
+    control.make_glob_map = resolve::MakeGlobMap::Yes;
+    control.after_analysis.callback = box |state| {
+        *index.borrow_mut() = index_crate(state.tcx.unwrap(),
+                                          state.analysis.unwrap(),
+                                          state.expanded_crate.unwrap(),
+                                          previous,
+                                          report);
+    };
+
+    let input = Input::File(path.to_path_buf());
+    let _ = driver::compile_input(&sess, &cstore, cfg, &input, &None, &None, None, &control);
+}
+
+/// Indexes the analyzed crate, reusing the entries of the items of the
+/// `previous` index that are not affected by the changes since then.
+fn index_crate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                         analysis: &'a ty::CrateAnalysis<'a>,
+                         krate: &ast::Crate,
+                         previous: Option<&Index>,
+                         report: bool)
+                         -> Index {
+    let _ignore = tcx.dep_graph.in_ignore();
+
+    let hir_hashes: HashMap<_, _> = tcx.map.krate().items.keys().filter_map(|&id| {
+        let def_id = tcx.map.local_def_id(id);
+        save::stable_id(tcx, def_id).map(|stable_id| (stable_id, tcx.calculate_item_hash(def_id)))
+    }).collect();
+    let (affected, previous_items, external_items) = match previous {
+        Some(previous) => {
+            (previous.affected_items(&hir_hashes),
+             Some(&previous.items),
+             previous.external_items.clone())
+        }
+        None => (None, None, HashMap::new()),
+    };
+
+    let mut index = Index {
+        items: HashMap::new(),
+        modules: Entries::default(),
+        hir_hashes: hir_hashes,
+        dep_graph: Some(stable_dep_graph(tcx)),
+        external_items: external_items,
+        diagnostics: vec![],
+    };
+
+    let mut items = vec![];
+    module_items(tcx, &krate.module, &mut items);
+    let total = items.len();
+    let mut indexed = NodeSet();
+    let mut recomputed = vec![];
+    for (item, id) in items {
+        let location = item_location(tcx, item);
+        let reused = previous_items.and_then(|items| items.get(&id)).and_then(|previous| {
+            if previous.location == location && !is_affected(&affected, &id) {
+                Some(previous.entries.clone())
+            } else {
+                None
+            }
+        });
+        let entries = match reused {
+            Some(entries) => entries,
+            None => {
+                recomputed.push(tcx.node_path_str(item.id));
+                let mut recorder = Recorder::new(tcx, &mut index.external_items);
+                {
+                    let mut visitor = save::DumpVisitor::new(tcx,
+                                                             save::SaveContext::new(tcx),
+                                                             analysis,
+                                                             &mut recorder);
+                    visitor.visit_item(item);
+                }
+                recorder.entries
+            }
+        };
+        indexed.insert(item.id);
+        index.items.insert(id, ItemIndex { location: location, entries: entries });
+    }
+
+    // All other items have been indexed by now, so this only records the
+    // modules.
+    index.modules = {
+        let mut recorder = Recorder::new(tcx, &mut index.external_items);
+        {
+            let mut visitor = save::DumpVisitor::new(tcx,
+                                                     save::SaveContext::new(tcx),
+                                                     analysis,
+                                                     &mut recorder);
+            visitor.skip_items(indexed);
+            visit::walk_crate(&mut visitor, krate);
+        }
+        recorder.entries
+    };
+
+    if report {
+        recomputed.sort();
+        let stderr = io::stderr();
+        let mut stderr = stderr.lock();
+        let _ = writeln!(stderr, "server: reused {} items, recomputing {} items",
+                         total - recomputed.len(), recomputed.len());
+        for path in &recomputed {
+            let _ = writeln!(stderr, "server: recomputing: {}", path);
+        }
+    }
+
+    index
+}
+
+/// Collects the items that are directly contained in `module` and its
+/// submodules, other than the submodules themselves, with their stable ids.
+fn module_items<'a, 'tcx, 'k>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                              module: &'k ast::Mod,
+                              items: &mut Vec<(&'k ast::Item, String)>) {
+    for item in &module.items {
+        if let ast::ItemKind::Mod(ref module) = item.node {
+            module_items(tcx, module, items);
+            continue;
+        }
+
+        // Only items that made it into the HIR have a `DefId`.
+        match tcx.map.find(item.id) {
+            Some(hir_map::NodeItem(_)) => {}
+            _ => continue,
+        }
+        if let Some(id) = save::stable_id(tcx, tcx.map.local_def_id(item.id)) {
+            items.push((&**item, id));
+        }
+    }
+}
+
+/// A hash of the position and the source text of `item`. The spans that
+/// are recorded for an item are only valid as long as this does not change.
+fn item_location<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, item: &ast::Item) -> u64 {
+    let codemap = tcx.sess.codemap();
+    let mut state = SipHasher::new();
+    item.span.lo.0.hash(&mut state);
+    item.span.hi.0.hash(&mut state);
+    codemap.span_to_string(item.span).hash(&mut state);
+    codemap.span_to_snippet(item.span).ok().hash(&mut state);
+    state.finish()
+}
+
+/// Whether the item with the stable id `id`, or any definition in it, is
+/// among the `affected` ones (where `None` means that all are).
+fn is_affected(affected: &Option<HashSet<String>>, id: &str) -> bool {
+    match *affected {
+        Some(ref affected) => {
+            let prefix = format!("{}::", id);
+            affected.iter().any(|affected| affected == id || affected.starts_with(&prefix))
+        }
+        None => true,
+    }
+}
+
+/// The dep-graph of the analysis, with stable ids instead of `DefId`s, so
+/// that it can be compared with the next analysis.
+fn stable_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) -> DepGraphQuery<String> {
+    let query = tcx.dep_graph.query();
+    let stable = |node: &DepNode<DefId>| node.map_def(|&def_id| save::stable_id(tcx, def_id));
+    let nodes: Vec<_> = query.nodes().into_iter().filter_map(|node| stable(node)).collect();
+    let edges: Vec<_> = query.edges().into_iter().filter_map(|(source, target)| {
+        match (stable(source), stable(target)) {
+            (Some(source), Some(target)) => Some((source, target)),
+            _ => None,
+        }
+    }).collect();
+    DepGraphQuery::new(&nodes, &edges)
+}
+
+/// Loads files from disk, unless the editor has unsaved changes to them.
+struct OverlayFileLoader {
+    files: Rc<RefCell<HashMap<PathBuf, String>>>,
+}
+
+impl FileLoader for OverlayFileLoader {
+    fn file_exists(&self, path: &Path) -> bool {
+        self.files.borrow().contains_key(&absolute(path)) || fs::metadata(path).is_ok()
+    }
+
+    fn abs_path(&self, path: &Path) -> Option<PathBuf> {
+        Some(absolute(path))
+    }
+
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        if let Some(text) = self.files.borrow().get(&absolute(path)) {
+            return Ok(text.clone());
+        }
+        let mut text = String::new();
+        fs::File::open(path)?.read_to_string(&mut text)?;
+        Ok(text)
+    }
+}
+
+struct Sink(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Sink {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        Write::write(&mut *self.0.lock().unwrap(), data)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct Position {
+    file: PathBuf,
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    fn from_params(params: &Json) -> Result<Position, (i64, String)> {
+        let position = Position {
+            file: absolute(Path::new(string_param(params, "file")?)),
+            line: number_param(params, "line")? as usize,
+            column: number_param(params, "column")? as usize,
+        };
+        if position.line == 0 || position.column == 0 {
+            return Err((INVALID_PARAMS, "lines and columns start at 1".to_string()));
+        }
+        Ok(position)
+    }
+
+    fn is_in(&self, span: &SpanData) -> bool {
+        absolute(Path::new(&span.file_name)) == self.file &&
+        (span.line_start, span.column_start) <= (self.line, self.column) &&
+        (self.line, self.column) < (span.line_end, span.column_end)
+    }
+}
+
+/// What the server knows about the crate: the definitions and references
+/// recorded by save-analysis, and the diagnostics.
+///
+/// The definitions and references are kept by item, for the items that are
+/// directly contained in modules, so that the entries of the items that a
+/// change does not affect can be reused. To find those items, the index
+/// also keeps the dep-graph of the analysis and the hashes of the HIR of
+/// all items. Everything is keyed by stable ids, since `DefId`s are only
+/// valid within one compilation session.
+#[derive(Default)]
+struct Index {
+    // The entries of the items, by stable id.
+    items: HashMap<String, ItemIndex>,
+    // The entries of the modules, which are recorded anew by every analysis.
+    modules: Entries,
+    hir_hashes: HashMap<String, u64>,
+    dep_graph: Option<DepGraphQuery<String>>,
+    // The paths of the items in other crates that are referred to, by
+    // stable id.
+    external_items: HashMap<String, String>,
+    diagnostics: Vec<Json>,
+}
+
+struct ItemIndex {
+    // See `item_location`.
+    location: u64,
+    entries: Entries,
+}
+
+#[derive(Clone, Default)]
+struct Entries {
+    defs: Vec<Def>,
+    refs: Vec<Ref>,
+}
+
+#[derive(Clone)]
+struct Def {
+    id: String,
+    kind: &'static str,
+    name: String,
+    span: SpanData,
+    // The type or signature of the item.
+    detail: String,
+}
+
+#[derive(Clone)]
+struct Ref {
+    span: SpanData,
+    ref_id: String,
+}
+
+impl Index {
+    /// The stable ids of the items, and of the other definitions (such as
+    /// the closures and methods in items), whose analysis is affected by the
+    /// changes that gave the items the HIR hashes `hir_hashes`, or `None` if
+    /// all of them are.
+    fn affected_items(&self, hir_hashes: &HashMap<String, u64>) -> Option<HashSet<String>> {
+        let dep_graph = match self.dep_graph {
+            Some(ref dep_graph) => dep_graph,
+            None => return None,
+        };
+
+        // Name resolution is not tracked in the dep-graph, so once items
+        // are added or removed, any path may resolve differently.
+        if hir_hashes.len() != self.hir_hashes.len() ||
+           hir_hashes.keys().any(|id| !self.hir_hashes.contains_key(id)) {
+            return None;
+        }
+
+        let mut affected = HashSet::new();
+        for (id, hash) in hir_hashes {
+            if self.hir_hashes[id] == *hash {
+                continue;
+            }
+            affected.insert(id.clone());
+            // Everything that was computed from the HIR of the item has to
+            // be computed again.
+            for node in dep_graph.transitive_successors(&DepNode::Hir(id.clone())) {
+                node.map_def(|id| {
+                    affected.insert(id.clone());
+                    Some(())
+                });
+            }
+        }
+        Some(affected)
+    }
+
+    fn entries(&self) -> Vec<&Entries> {
+        let mut entries = vec![&self.modules];
+        entries.extend(self.items.values().map(|item| &item.entries));
+        entries
+    }
+
+    fn def(&self, id: &str) -> Option<&Def> {
+        self.entries().into_iter().flat_map(|entries| entries.defs.iter()).find(|def| def.id == id)
+    }
+
+    /// The innermost definition or reference at `position`, as the stable
+    /// id of the item it is about.
+    fn item_at(&self, position: &Position) -> Option<&str> {
+        let entries = self.entries();
+        let defs = entries.iter()
+                          .flat_map(|&entries| entries.defs.iter())
+                          .map(|def| (&def.span, &def.id[..]));
+        let refs = entries.iter()
+                          .flat_map(|&entries| entries.refs.iter())
+                          .map(|r| (&r.span, &r.ref_id[..]));
+        defs.chain(refs)
+            .filter(|&(span, _)| position.is_in(span))
+            .min_by_key(|&(span, _)| (span.byte_end - span.byte_start))
+            .map(|(_, id)| id)
+    }
+
+    fn hover(&self, position: &Position) -> Json {
+        let id = match self.item_at(position) {
+            Some(id) => id,
+            None => return Json::Null,
+        };
+        if let Some(def) = self.def(id) {
+            return object(vec![("kind", Json::String(def.kind.to_string())),
+                               ("name", Json::String(def.name.clone())),
+                               ("detail", Json::String(def.detail.clone()))]);
+        }
+        match self.external_items.get(id) {
+            Some(path) => object(vec![("kind", Json::String("external".to_string())),
+                                      ("name", Json::String(path.clone())),
+                                      ("detail", Json::String(String::new()))]),
+            None => Json::Null,
+        }
+    }
+
+    fn definition(&self, position: &Position) -> Json {
+        match self.item_at(position).and_then(|id| self.def(id)) {
+            Some(def) => span_to_json(&def.span),
+            None => Json::Null,
+        }
+    }
+
+    fn completion(&self, prefix: &str) -> Json {
+        // Local variables of other functions would only be noise.
+        let mut items = BTreeMap::new();
+        for def in self.entries().into_iter().flat_map(|entries| entries.defs.iter()) {
+            if def.kind == "local" || !def.name.starts_with(prefix) {
+                continue;
+            }
+            let item = object(vec![("kind", Json::String(def.kind.to_string())),
+                                   ("name", Json::String(def.name.clone())),
+                                   ("detail", Json::String(def.detail.clone()))]);
+            items.insert((def.name.clone(), def.kind, def.detail.clone()), item);
+        }
+        Json::Array(items.into_iter().map(|(_, item)| item).collect())
+    }
+}
+
+/// Records the definitions and references that save-analysis finds in a
+/// part of the crate.
+struct Recorder<'a, 'b, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    entries: Entries,
+    external_items: &'b mut HashMap<String, String>,
+}
+
+impl<'a, 'b, 'tcx> Recorder<'a, 'b, 'tcx> {
+    fn new(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+           external_items: &'b mut HashMap<String, String>)
+           -> Recorder<'a, 'b, 'tcx> {
+        Recorder {
+            tcx: tcx,
+            entries: Entries::default(),
+            external_items: external_items,
+        }
+    }
+
+    fn def(&mut self, kind: &'static str, id: DefId, name: String, span: SpanData, detail: String) {
+        if let Some(id) = save::stable_id(self.tcx, id) {
+            self.entries.defs.push(Def {
+                id: id,
+                kind: kind,
+                name: name,
+                span: span,
+                detail: detail,
+            });
+        }
+    }
+
+    fn reference(&mut self, span: SpanData, ref_id: Option<DefId>) {
+        let ref_id = match ref_id {
+            Some(ref_id) => ref_id,
+            None => return,
+        };
+        let id = match save::stable_id(self.tcx, ref_id) {
+            Some(id) => id,
+            None => return,
+        };
+        if !ref_id.is_local() && !self.external_items.contains_key(&id) {
+            self.external_items.insert(id.clone(), self.tcx.item_path_str(ref_id));
+        }
+        self.entries.refs.push(Ref { span: span, ref_id: id });
+    }
+}
+
+impl<'a, 'b, 'tcx> save::Dump for Recorder<'a, 'b, 'tcx> {
+    fn enum_data(&mut self, data: EnumData) {
+        self.def("enum", data.id, data.name, data.span, data.value);
+    }
+    fn tuple_variant(&mut self, data: TupleVariantData) {
+        self.def("variant", data.id, data.name, data.span, data.value);
+    }
+    fn struct_variant(&mut self, data: StructVariantData) {
+        self.def("variant", data.id, data.name, data.span, data.value);
+    }
+    fn struct_data(&mut self, data: StructData) {
+        self.def("struct", data.id, data.name, data.span, data.value);
+    }
+    fn trait_data(&mut self, data: TraitData) {
+        self.def("trait", data.id, data.name, data.span, data.value);
+    }
+    fn function(&mut self, data: FunctionData) {
+        self.def("function", data.id, data.name, data.span, data.value);
+    }
+    fn method(&mut self, data: MethodData) {
+        self.def("method", data.id, data.name, data.span, data.value);
+    }
+    fn mod_data(&mut self, data: ModData) {
+        self.def("mod", data.id, data.name, data.span, data.qualname);
+    }
+    fn typedef(&mut self, data: TypeDefData) {
+        self.def("type", data.id, data.name, data.span, data.value);
+    }
+    fn variable(&mut self, data: VariableData) {
+        let kind = match data.kind {
+            save::VariableKind::Static => "static",
+            save::VariableKind::Const => "const",
+            save::VariableKind::Local => "local",
+            save::VariableKind::Field => "field",
+        };
+        self.def(kind, data.id, data.name, data.span, data.type_value);
+    }
+
+    fn function_ref(&mut self, data: FunctionRefData) {
+        self.reference(data.span, Some(data.ref_id));
+    }
+    fn function_call(&mut self, data: FunctionCallData) {
+        self.reference(data.span, Some(data.ref_id));
+    }
+    fn method_call(&mut self, data: MethodCallData) {
+        self.reference(data.span, data.ref_id.or(data.decl_id));
+    }
+    fn mod_ref(&mut self, data: ModRefData) {
+        self.reference(data.span, data.ref_id);
+    }
+    fn type_ref(&mut self, data: TypeRefData) {
+        self.reference(data.span, data.ref_id);
+    }
+    fn variable_ref(&mut self, data: VariableRefData) {
+        self.reference(data.span, Some(data.ref_id));
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir().unwrap().join(path)
+    }
+}
+
+fn string_param<'a>(params: &'a Json, name: &str) -> Result<&'a str, (i64, String)> {
+    params.find(name)
+          .and_then(|param| param.as_string())
+          .ok_or_else(|| (INVALID_PARAMS, format!("expected a string `{}` parameter", name)))
+}
+
+fn number_param(params: &Json, name: &str) -> Result<u64, (i64, String)> {
+    params.find(name)
+          .and_then(|param| param.as_u64())
+          .ok_or_else(|| (INVALID_PARAMS, format!("expected a number `{}` parameter", name)))
+}
+
+fn object(fields: Vec<(&str, Json)>) -> Json {
+    Json::Object(fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect())
+}
+
+fn span_to_json(span: &SpanData) -> Json {
+    object(vec![("file_name", Json::String(span.file_name.clone())),
+                ("line_start", Json::U64(span.line_start as u64)),
+                ("column_start", Json::U64(span.column_start as u64)),
+                ("line_end", Json::U64(span.line_end as u64)),
+                ("column_end", Json::U64(span.column_end as u64))])
+}
+
+fn response(id: Json, field: &str, value: Json) -> Json {
+    object(vec![("jsonrpc", Json::String("2.0".to_string())),
+                ("id", id),
+                (field, value)])
+}
+
+fn error_response(id: Json, code: i64, message: &str) -> Json {
+    response(id, "error", object(vec![("code", Json::I64(code)),
+                                      ("message", Json::String(message.to_string()))]))
+}
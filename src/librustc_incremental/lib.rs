@@ -19,6 +19,7 @@
       html_root_url = "https://doc.rust-lang.org/nightly/")]
 #![cfg_attr(not(stage0), deny(warnings))]
 
+#![feature(rand)]
 #![feature(rustc_private)]
 #![feature(staged_api)]
 
@@ -38,5 +39,8 @@ mod persist;
 
 pub use assert_dep_graph::assert_dep_graph;
 pub use calculate_svh::SvhCalculate;
+pub use persist::delete_session_directory;
+pub use persist::finalize_session_directory;
 pub use persist::load_dep_graph;
+pub use persist::prepare_session_directory;
 pub use persist::save_dep_graph;
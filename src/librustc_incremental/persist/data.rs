@@ -41,6 +41,13 @@ pub struct SerializedDepGraph {
     /// but rather the `DefPathIndex`. This can then be retraced
     /// to find the current def-id.
     pub hashes: Vec<SerializedHash>,
+
+    /// Fingerprints of some of the results computed in this crate,
+    /// such as item signatures (see `fingerprint.rs`). When an input
+    /// of such a result changes in the next compile, we compare the
+    /// recomputed result's fingerprint against the one stored here;
+    /// if they are equal, the change does not propagate any further.
+    pub fingerprints: Vec<SerializedHash>,
 }
 
 pub type SerializedEdge = (DepNode<DefPathIndex>, DepNode<DefPathIndex>);
@@ -48,10 +55,11 @@ pub type SerializedEdge = (DepNode<DefPathIndex>, DepNode<DefPathIndex>);
 #[derive(Debug, RustcEncodable, RustcDecodable)]
 pub struct SerializedHash {
     /// node being hashed; either a Hir or MetaData variant, in
-    /// practice
+    /// practice, or one of the results handled by `fingerprint.rs`
     pub node: DepNode<DefPathIndex>,
 
-    /// the hash itself, computed by `calculate_item_hash`
+    /// the hash itself, computed by `calculate_item_hash` (or
+    /// `result_fingerprint`)
     pub hash: u64,
 }
 
@@ -86,6 +94,7 @@ pub struct SerializedMetadataHashes {
 pub struct SerializedMetadataHash {
     pub def_index: DefIndex,
 
-    /// the hash itself, computed by `calculate_item_hash`
+    /// the hash itself, computed by `calculate_item_hash` (or
+    /// `result_fingerprint`)
     pub hash: u64,
 }
@@ -11,20 +11,20 @@
 //! Debugging code to test the state of the dependency graph just
 //! after it is loaded from disk. For each node marked with
 //! `#[rustc_clean]` or `#[rustc_dirty]`, we will check that a
-//! suitable node for that item either appears or does not appear in
-//! the dep-graph, as appropriate:
+//! suitable node for that item either was or was not found to be
+//! clean when loading the dep-graph, as appropriate:
 //!
 //! - `#[rustc_dirty(label="TypeckItemBody", cfg="rev2")]` if we are
 //!   in `#[cfg(rev2)]`, then there MUST NOT be a node
-//!   `DepNode::TypeckItemBody(X)` where `X` is the def-id of the
-//!   current node.
+//!   clean `DepNode::TypeckItemBody(X)` where `X` is the def-id of
+//!   the current node.
 //! - `#[rustc_clean(label="TypeckItemBody", cfg="rev2")]` same as above,
 //!   except that the node MUST exist.
 //!
 //! Errors are reported if we are in the suitable configuration but
 //! the required condition is not met.
 
-use rustc::dep_graph::{DepGraphQuery, DepNode};
+use rustc::dep_graph::DepNode;
 use rustc::hir;
 use rustc::hir::def_id::DefId;
 use rustc::hir::intravisit::Visitor;
@@ -32,25 +32,31 @@ use syntax::ast::{self, Attribute, MetaItem};
 use syntax::attr::AttrMetaMethods;
 use syntax::parse::token::InternedString;
 use rustc::ty::TyCtxt;
+use rustc_data_structures::fnv::FnvHashSet;
 
 const DIRTY: &'static str = "rustc_dirty";
 const CLEAN: &'static str = "rustc_clean";
 const LABEL: &'static str = "label";
 const CFG: &'static str = "cfg";
 
-pub fn check_dirty_clean_annotations<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
+/// Checks the annotations against `clean_nodes`, the set of nodes
+/// that were found to be clean when loading the dep-graph. We can't
+/// just check the current dep-graph, since by the time the dep-graph
+/// is loaded it already contains the nodes from this session's type
+/// collection.
+pub fn check_dirty_clean_annotations<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                               clean_nodes: &FnvHashSet<DepNode<DefId>>) {
     let _ignore = tcx.dep_graph.in_ignore();
-    let query = tcx.dep_graph.query();
     let krate = tcx.map.krate();
     krate.visit_all_items(&mut DirtyCleanVisitor {
         tcx: tcx,
-        query: &query,
+        clean_nodes: clean_nodes,
     });
 }
 
 pub struct DirtyCleanVisitor<'a, 'tcx:'a> {
     tcx: TyCtxt<'a, 'tcx, 'tcx>,
-    query: &'a DepGraphQuery<DefId>,
+    clean_nodes: &'a FnvHashSet<DepNode<DefId>>,
 }
 
 impl<'a, 'tcx> DirtyCleanVisitor<'a, 'tcx> {
@@ -112,7 +118,7 @@ impl<'a, 'tcx> DirtyCleanVisitor<'a, 'tcx> {
     fn assert_dirty(&self, item: &hir::Item, dep_node: DepNode<DefId>) {
         debug!("assert_dirty({:?})", dep_node);
 
-        if self.query.contains_node(&dep_node) {
+        if self.clean_nodes.contains(&dep_node) {
             let dep_node_str = self.dep_node_str(dep_node);
             self.tcx.sess.span_err(
                 item.span,
@@ -123,7 +129,7 @@ impl<'a, 'tcx> DirtyCleanVisitor<'a, 'tcx> {
     fn assert_clean(&self, item: &hir::Item, dep_node: DepNode<DefId>) {
         debug!("assert_clean({:?})", dep_node);
 
-        if !self.query.contains_node(&dep_node) {
+        if !self.clean_nodes.contains(&dep_node) {
             let dep_node_str = self.dep_node_str(dep_node);
             self.tcx.sess.span_err(
                 item.span,
@@ -0,0 +1,226 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fingerprints of computed results. Whereas `hash.rs` hashes the
+//! *inputs* of the compilation (HIR and upstream metadata), this
+//! module hashes some of the *results* that are derived from them,
+//! such as the signature of an item. When an input changes, the
+//! results that depend on it are recomputed anyway; if such a result
+//! winds up with the same fingerprint as in the previous session,
+//! the change does not need to be propagated any further (see
+//! `load.rs`).
+//!
+//! Fingerprints must be comparable across compilation sessions, so
+//! they must not include anything that is session-specific, like
+//! `DefId`s or interned pointers. We therefore hash the
+//! pretty-printed form of the results, which refers to other items
+//! by path.
+//!
+//! A fingerprint must cover everything about a result that its
+//! dependents can observe, such as whether an ADT is a struct or a
+//! union and its `#[repr]`. Anything left out can change without
+//! changing the fingerprint, and then stale results are reused.
+
+use rustc::dep_graph::DepNode;
+use rustc::hir::def_id::DefId;
+use rustc::ty::{self, TyCtxt};
+use std::hash::{Hash, Hasher, SipHasher};
+
+/// Computes the fingerprint of the result represented by `dep_node`,
+/// if that is a kind of result we know how to fingerprint. Must be
+/// invoked in an ignore task, since it reads from dep-tracking maps.
+pub fn result_fingerprint<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                    dep_node: &DepNode<DefId>)
+                                    -> Option<u64> {
+    match *dep_node {
+        DepNode::ItemSignature(def_id) if def_id.is_local() => {
+            Some(item_signature_fingerprint(tcx, def_id))
+        }
+
+        DepNode::ImplOrTraitItems(def_id) if def_id.is_local() => {
+            tcx.impl_or_trait_items
+               .borrow()
+               .get(&def_id)
+               .map(|item| impl_or_trait_item_fingerprint(tcx, item))
+        }
+
+        DepNode::TraitItemDefIds(def_id) if def_id.is_local() => {
+            tcx.trait_item_def_ids
+               .borrow()
+               .get(&def_id)
+               .map(|ids| def_ids_fingerprint(tcx, ids.iter().map(|id| id.def_id())))
+        }
+
+        DepNode::ImplItems(def_id) if def_id.is_local() => {
+            tcx.impl_items
+               .borrow()
+               .get(&def_id)
+               .map(|ids| def_ids_fingerprint(tcx, ids.iter().map(|id| id.def_id())))
+        }
+
+        DepNode::InherentImpls(def_id) if def_id.is_local() => {
+            tcx.inherent_impls
+               .borrow()
+               .get(&def_id)
+               .map(|ids| def_ids_fingerprint(tcx, ids.iter().cloned()))
+        }
+
+        _ => None,
+    }
+}
+
+fn def_ids_fingerprint<'a, 'tcx, I>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_ids: I) -> u64
+    where I: Iterator<Item=DefId>
+{
+    let mut state = SipHasher::new();
+    for def_id in def_ids {
+        tcx.item_path_str(def_id).hash(&mut state);
+    }
+    state.finish()
+}
+
+/// The signature of an item is spread across a number of tables in
+/// the tcx, all of which map to the `ItemSignature` dep-node. We
+/// hash whatever each of them contains for the given item.
+fn item_signature_fingerprint<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId) -> u64 {
+    let mut state = SipHasher::new();
+
+    if let Some(scheme) = tcx.tcache.borrow().get(&def_id) {
+        "type".hash(&mut state);
+        hash_generics(&scheme.generics, &mut state);
+        scheme.ty.to_string().hash(&mut state);
+    }
+
+    if let Some(predicates) = tcx.predicates.borrow().get(&def_id) {
+        "predicates".hash(&mut state);
+        hash_predicates(predicates, &mut state);
+    }
+
+    if let Some(predicates) = tcx.super_predicates.borrow().get(&def_id) {
+        "super predicates".hash(&mut state);
+        hash_predicates(predicates, &mut state);
+    }
+
+    if let Some(trait_ref) = tcx.impl_trait_refs.borrow().get(&def_id) {
+        "impl trait ref".hash(&mut state);
+        trait_ref.map(|trait_ref| trait_ref.to_string()).hash(&mut state);
+    }
+
+    if let Some(trait_def) = tcx.trait_defs.borrow().get(&def_id) {
+        "trait".hash(&mut state);
+        trait_def.unsafety.hash(&mut state);
+        trait_def.paren_sugar.hash(&mut state);
+        hash_generics(&trait_def.generics, &mut state);
+        trait_def.trait_ref.to_string().hash(&mut state);
+        for name in &trait_def.associated_type_names {
+            name.as_str().hash(&mut state);
+        }
+    }
+
+    if let Some(adt_def) = tcx.adt_defs.borrow().get(&def_id) {
+        "adt".hash(&mut state);
+        format!("{:?}", adt_def.adt_kind()).hash(&mut state);
+        adt_def.is_fundamental().hash(&mut state);
+        adt_def.is_simd().hash(&mut state);
+        adt_def.is_phantom_data().hash(&mut state);
+        adt_def.is_no_drop_flag().hash(&mut state);
+        format!("{:?}", tcx.lookup_repr_hints(def_id)).hash(&mut state);
+        for variant in &adt_def.variants {
+            variant.name.as_str().hash(&mut state);
+            variant.kind.hash(&mut state);
+            variant.disr_val.to_u64_unchecked().hash(&mut state);
+            for field in &variant.fields {
+                field.name.as_str().hash(&mut state);
+                hash_visibility(tcx, field.vis, &mut state);
+                field.unsubst_ty().to_string().hash(&mut state);
+            }
+        }
+    }
+
+    if let Some(variances) = tcx.item_variance_map.borrow().get(&def_id) {
+        "variances".hash(&mut state);
+        format!("{:?}", variances).hash(&mut state);
+    }
+
+    state.finish()
+}
+
+/// Methods and associated types and consts each have their own
+/// generics, including generic associated types.
+fn impl_or_trait_item_fingerprint<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                            item: &ty::ImplOrTraitItem<'tcx>)
+                                            -> u64 {
+    let mut state = SipHasher::new();
+    item.name().as_str().hash(&mut state);
+    hash_visibility(tcx, item.vis(), &mut state);
+    match item.container() {
+        ty::TraitContainer(id) => ("trait", tcx.item_path_str(id)).hash(&mut state),
+        ty::ImplContainer(id) => ("impl", tcx.item_path_str(id)).hash(&mut state),
+    }
+
+    match *item {
+        ty::ConstTraitItem(ref assoc_const) => {
+            "const".hash(&mut state);
+            assoc_const.defaultness.hash(&mut state);
+            assoc_const.ty.to_string().hash(&mut state);
+            assoc_const.has_value.hash(&mut state);
+        }
+        ty::MethodTraitItem(ref method) => {
+            "method".hash(&mut state);
+            method.defaultness.hash(&mut state);
+            hash_generics(&method.generics, &mut state);
+            hash_predicates(&method.predicates, &mut state);
+            tcx.mk_fn_ptr(method.fty).to_string().hash(&mut state);
+            method.explicit_self.to_string().hash(&mut state);
+        }
+        ty::TypeTraitItem(ref assoc_ty) => {
+            "type".hash(&mut state);
+            assoc_ty.defaultness.hash(&mut state);
+            hash_generics(&assoc_ty.generics, &mut state);
+            hash_predicates(&assoc_ty.predicates, &mut state);
+            assoc_ty.ty.map(|ty| ty.to_string()).hash(&mut state);
+        }
+    }
+
+    state.finish()
+}
+
+fn hash_visibility(tcx: TyCtxt, vis: ty::Visibility, state: &mut SipHasher) {
+    match vis {
+        ty::Visibility::Public => "pub".hash(state),
+        ty::Visibility::Restricted(module) => tcx.node_path_str(module).hash(state),
+        ty::Visibility::PrivateExternal => "private external".hash(state),
+    }
+}
+
+fn hash_generics(generics: &ty::Generics, state: &mut SipHasher) {
+    for type_param in generics.types.iter() {
+        type_param.name.as_str().hash(state);
+        type_param.space.hash(state);
+        type_param.index.hash(state);
+        type_param.default.map(|ty| ty.to_string()).hash(state);
+        type_param.is_const.hash(state);
+    }
+
+    for region_param in generics.regions.iter() {
+        region_param.name.as_str().hash(state);
+        region_param.space.hash(state);
+        region_param.index.hash(state);
+        for bound in &region_param.bounds {
+            bound.to_string().hash(state);
+        }
+    }
+}
+
+fn hash_predicates(predicates: &ty::GenericPredicates, state: &mut SipHasher) {
+    for predicate in predicates.predicates.iter() {
+        predicate.to_string().hash(state);
+    }
+}
@@ -0,0 +1,981 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! This module manages how the incremental compilation cache is represented
+//! in the file system.
+//!
+//! Incremental compilation caches are managed according to a copy-on-write
+//! strategy: Once a complete, consistent cache version is finalized, it is
+//! never modified. Instead, when a subsequent compilation session is started,
+//! the compiler allocates a new, private session directory, copies (or
+//! hard-links) the files of the most recent finalized session into it, and
+//! only ever modifies this private copy. When compilation is done, the
+//! private directory is finalized by renaming it, which makes it available
+//! to later sessions. This way, concurrent compiler invocations (e.g. an IDE
+//! and a build running at the same time) never see each other's half-written
+//! data.
+//!
+//! The layout of the incremental compilation directory looks like this:
+//!
+//! ```ignore
+//! {incr-comp-dir}/
+//!     {crate-name}-{crate-disambiguator}/
+//!         s-{timestamp}-{random-number}-working/
+//!         s-{timestamp}-{random-number}.lock
+//!         s-{timestamp}-{random-number}-{svh}/
+//!         s-{timestamp}-{random-number}.lock
+//! ```
+//!
+//! Every session directory has a lock file next to it, whose name is the
+//! session directory name without the last component. A compilation session
+//! holds an exclusive lock on the lock file of its private directory for as
+//! long as it is working on it. A session that copies files out of a
+//! finalized directory holds a shared lock on that directory's lock file
+//! while doing so. Directories are only ever deleted by someone who holds
+//! an exclusive lock on them, which is how we make sure that no directory is
+//! deleted while it is still in use.
+//!
+//! A session directory goes through the following states:
+//!
+//! - `s-{timestamp}-{random-number}-working`: the directory has been
+//!   allocated by a compilation session, which is currently filling it with
+//!   data.
+//! - `s-{timestamp}-{random-number}-{svh}`: the compilation session that
+//!   owned the directory has completed successfully and renamed it. `{svh}`
+//!   is the SVH of the crate that was compiled; downstream crates use it to
+//!   find the metadata hashes that belong to the exact version of the crate
+//!   they were compiled against.
+//!
+//! If a compilation session has errors, or stops before its directory is
+//! finalized (e.g. because the compile controller stops after analysis), the
+//! directory is deleted when `compile_input` returns. If the compiler is
+//! killed or panics before it gets to finalize or delete its directory,
+//! the directory stays in the `-working` state with nobody holding its lock.
+//! Such directories are deleted by the garbage collection that runs at the
+//! end of every session. Garbage collection also deletes all
+//! finalized directories except for the most recent one, so that the cache
+//! does not keep growing.
+
+use rustc::hir::svh::Svh;
+use rustc::middle::cstore::LOCAL_CRATE;
+use rustc::session::Session;
+use rustc::ty::TyCtxt;
+use rustc::util::fs as fs_util;
+use rustc_data_structures::flock;
+use rustc_data_structures::fnv::{FnvHashSet, FnvHashMap};
+
+use std::fs as std_fs;
+use std::io;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::time::{UNIX_EPOCH, SystemTime, Duration};
+use std::__rand::{thread_rng, Rng};
+use syntax::ast;
+
+const LOCK_FILE_EXT: &'static str = ".lock";
+const DEP_GRAPH_FILENAME: &'static str = "dep-graph.bin";
+const METADATA_HASHES_FILENAME: &'static str = "metadata.bin";
+
+/// Session directories that are not finalized yet are only garbage
+/// collected once they are at least this old. This guards against deleting
+/// a directory in the small time window between its allocation and the
+/// acquisition of its lock.
+const MIN_SECONDS_BEFORE_COLLECTION: u64 = 10;
+
+pub fn dep_graph_path(sess: &Session) -> Option<PathBuf> {
+    in_incr_comp_dir_sess(sess, DEP_GRAPH_FILENAME)
+}
+
+pub fn metadata_hash_export_path(sess: &Session) -> Option<PathBuf> {
+    in_incr_comp_dir_sess(sess, METADATA_HASHES_FILENAME)
+}
+
+/// Returns the path of the file holding the metadata hashes of the given
+/// upstream crate, as they were saved when that crate was compiled in
+/// incremental mode.
+pub fn metadata_hash_import_path<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                           cnum: ast::CrateNum)
+                                           -> Option<PathBuf> {
+    find_metadata_hashes_for(tcx, cnum).map(|session_dir| {
+        session_dir.join(METADATA_HASHES_FILENAME)
+    })
+}
+
+fn in_incr_comp_dir_sess(sess: &Session, file_name: &str) -> Option<PathBuf> {
+    sess.incr_comp_session_dir_opt().map(|dir| dir.join(file_name))
+}
+
+/// Allocates the private session directory for this compilation session and
+/// copies the files of the most recent finalized session into it, so that
+/// they can be loaded from there. This has to be called before the
+/// dep-graph is loaded. If something goes wrong, an error is reported and
+/// `Err` is returned.
+pub fn prepare_session_directory<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) -> Result<(), ()> {
+    let sess = tcx.sess;
+    if sess.opts.incremental.is_none() {
+        return Ok(())
+    }
+
+    debug!("prepare_session_directory");
+
+    // {incr-comp-dir}/{crate-name}-{crate-disambiguator}
+    let crate_dir = crate_path_tcx(tcx, LOCAL_CRATE);
+    debug!("crate-dir: {}", crate_dir.display());
+    try!(create_dir(sess, &crate_dir, "crate"));
+
+    let mut source_directories_already_tried = FnvHashSet();
+
+    loop {
+        // Generate a session directory of the form:
+        // {incr-comp-dir}/{crate-name}-{crate-disambiguator}/s-{timestamp}-{random}-working
+        let session_dir = generate_session_dir_path(&crate_dir);
+        debug!("session-dir: {}", session_dir.display());
+
+        // Lock the new session directory. If this fails, return an
+        // error without retrying.
+        let (directory_lock, lock_file_path) = try!(lock_directory(sess, &session_dir));
+
+        // Now that we have the lock, we can actually create the session
+        // directory.
+        try!(create_dir(sess, &session_dir, "session"));
+
+        // Find a suitable source directory to copy from. Ignore those that we
+        // have already tried before.
+        let source_directory = find_source_directory(&crate_dir,
+                                                     &source_directories_already_tried);
+
+        let source_directory = if let Some(dir) = source_directory {
+            dir
+        } else {
+            // There's nowhere to copy from, we're done.
+            debug!("no source directory found. Continuing with empty session directory.");
+
+            sess.init_incr_comp_session(session_dir, directory_lock);
+            return Ok(())
+        };
+
+        debug!("attempting to copy data from source: {}",
+               source_directory.display());
+
+        // Try copying over all files from the source directory.
+        if copy_files(&session_dir, &source_directory).is_ok() {
+            debug!("successfully copied data from: {}",
+                   source_directory.display());
+
+            sess.init_incr_comp_session(session_dir, directory_lock);
+            return Ok(())
+        } else {
+            debug!("copying failed - trying next directory");
+
+            // Something went wrong while trying to copy/link files from the
+            // source directory. Try again with a different one.
+            source_directories_already_tried.insert(source_directory);
+
+            // Try to remove the session directory we just allocated. We don't
+            // know if there's any garbage in it from the failed copy action.
+            if let Err(err) = safe_remove_dir_all(&session_dir) {
+                sess.warn(&format!("Failed to delete partly initialized \
+                                    session dir `{}`: {}",
+                                   session_dir.display(),
+                                   err));
+            }
+
+            delete_session_dir_lock_file(sess, &lock_file_path);
+            mem::drop(directory_lock);
+        }
+    }
+}
+
+/// This function finalizes and thus 'publishes' the session directory by
+/// renaming it to `s-{timestamp}-{random-number}-{svh}` and releasing the file
+/// lock. If there have been compilation errors, the session directory is
+/// deleted instead. In both cases, the crate directory is garbage collected
+/// afterwards.
+pub fn finalize_session_directory(sess: &Session, svh: Svh) {
+    if sess.opts.incremental.is_none() {
+        return;
+    }
+
+    // If the session directory could not be set up, there's nothing to
+    // finalize.
+    let incr_comp_session_dir: PathBuf = match sess.incr_comp_session_dir_opt() {
+        Some(dir) => dir.clone(),
+        None => return,
+    };
+
+    debug!("finalize_session_directory() - session directory: {}",
+           incr_comp_session_dir.display());
+
+    if sess.has_errors() {
+        // If there have been any errors during compilation, we don't want to
+        // publish this session directory. Rather, we'll just delete it.
+        delete_session_directory(sess);
+        return
+    }
+
+    debug!("finalize_session_directory() - finalizing");
+
+    let old_sub_dir_name = incr_comp_session_dir.file_name()
+                                                .unwrap()
+                                                .to_string_lossy();
+    assert_no_characters_lost(&old_sub_dir_name);
+
+    // Keep the 's-{timestamp}-{random-number}' prefix, but replace the
+    // '-working' part with the SVH of the crate.
+    let dash_indices: Vec<_> = old_sub_dir_name.match_indices("-")
+                                               .map(|(idx, _)| idx)
+                                               .collect();
+    if dash_indices.len() != 3 {
+        bug!("Encountered incremental compilation session directory with \
+              malformed name: {}",
+             incr_comp_session_dir.display())
+    }
+
+    // State: "s-{timestamp}-{random-number}-"
+    let mut new_sub_dir_name = String::from(&old_sub_dir_name[.. dash_indices[2] + 1]);
+
+    // Append the svh
+    new_sub_dir_name.push_str(&encode_base_36(svh.as_u64()));
+
+    // Create the full path
+    let new_path = incr_comp_session_dir.parent().unwrap().join(new_sub_dir_name);
+    debug!("finalize_session_directory() - new path: {}", new_path.display());
+
+    match std_fs::rename(&incr_comp_session_dir, &new_path) {
+        Ok(_) => {
+            debug!("finalize_session_directory() - directory renamed successfully");
+
+            // This unlocks the directory
+            sess.finalize_incr_comp_session(new_path);
+        }
+        Err(e) => {
+            // Warn about the error. However, no need to abort compilation now.
+            sess.warn(&format!("Error finalizing incremental compilation \
+                                session directory `{}`: {}",
+                               incr_comp_session_dir.display(),
+                               e));
+
+            debug!("finalize_session_directory() - error, marking as invalid");
+            // Drop the file lock, so we can garage collect
+            sess.mark_incr_comp_session_as_invalid();
+        }
+    }
+
+    let _ = garbage_collect_session_directories(sess);
+}
+
+/// Deletes the session directory of this compilation session, if it has
+/// been allocated and is neither finalized nor deleted yet. This is how
+/// sessions that stop early, because of errors or because they were asked
+/// to, clean up after themselves.
+pub fn delete_session_directory(sess: &Session) {
+    if sess.opts.incremental.is_none() {
+        return;
+    }
+
+    let incr_comp_session_dir: PathBuf = match sess.incr_comp_session_dir_opt() {
+        Some(dir) => dir.clone(),
+        None => return,
+    };
+
+    debug!("delete_session_directory() - invalidating session directory: {}",
+           incr_comp_session_dir.display());
+
+    if let Err(err) = safe_remove_dir_all(&incr_comp_session_dir) {
+        sess.warn(&format!("Error deleting incremental compilation \
+                            session directory `{}`: {}",
+                           incr_comp_session_dir.display(),
+                           err));
+    }
+
+    let lock_file_path = lock_file_path(&incr_comp_session_dir);
+    delete_session_dir_lock_file(sess, &lock_file_path);
+    sess.mark_incr_comp_session_as_invalid();
+
+    let _ = garbage_collect_session_directories(sess);
+}
+
+fn copy_files(target_dir: &Path, source_dir: &Path) -> Result<(), ()> {
+    // We acquire a shared lock on the lock file of the directory, so that
+    // nobody deletes it out from under us while we are reading from it.
+    let lock_file_path = lock_file_path(source_dir);
+    let _lock = if let Ok(lock) = flock::Lock::new(&lock_file_path,
+                                                   false,   // don't wait,
+                                                   false,   // don't create
+                                                   false) { // not exclusive
+        lock
+    } else {
+        // Could not acquire the lock, don't try to copy from here
+        return Err(())
+    };
+
+    let source_dir_iterator = match source_dir.read_dir() {
+        Ok(it) => it,
+        Err(_) => return Err(())
+    };
+
+    for entry in source_dir_iterator {
+        match entry {
+            Ok(entry) => {
+                let file_name = entry.file_name();
+
+                let target_file_path = target_dir.join(file_name);
+                let source_path = entry.path();
+
+                debug!("copying into session dir: {}", source_path.display());
+                if fs_util::link_or_copy(&source_path, &target_file_path).is_err() {
+                    return Err(())
+                }
+            }
+            Err(_) => {
+                return Err(())
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate unique directory path of the form:
+/// {crate_dir}/s-{timestamp}-{random-number}-working
+fn generate_session_dir_path(crate_dir: &Path) -> PathBuf {
+    let timestamp = timestamp_to_string(SystemTime::now());
+    debug!("generate_session_dir_path: timestamp = {}", timestamp);
+    let random_number = thread_rng().next_u32();
+    debug!("generate_session_dir_path: random_number = {}", random_number);
+
+    let directory_name = format!("s-{}-{}-working",
+                                 timestamp,
+                                 encode_base_36(random_number as u64));
+    debug!("generate_session_dir_path: directory_name = {}", directory_name);
+    let directory_path = crate_dir.join(directory_name);
+    debug!("generate_session_dir_path: directory_path = {}", directory_path.display());
+    directory_path
+}
+
+fn create_dir(sess: &Session, path: &Path, dir_tag: &str) -> Result<(), ()> {
+    match create_dir_racy(path) {
+        Ok(()) => {
+            debug!("{} directory created successfully", dir_tag);
+            Ok(())
+        }
+        Err(err) => {
+            sess.err(&format!("Could not create incremental compilation {} \
+                               directory `{}`: {}",
+                              dir_tag,
+                              path.display(),
+                              err));
+            Err(())
+        }
+    }
+}
+
+/// Allocate the lock-file and lock it.
+fn lock_directory(sess: &Session,
+                  session_dir: &Path)
+                  -> Result<(flock::Lock, PathBuf), ()> {
+    let lock_file_path = lock_file_path(session_dir);
+    debug!("lock_directory() - lock_file: {}", lock_file_path.display());
+
+    match flock::Lock::new(&lock_file_path,
+                           false, // don't wait
+                           true,  // create the lock file
+                           true) { // the lock should be exclusive
+        Ok(lock) => Ok((lock, lock_file_path)),
+        Err(err) => {
+            sess.err(&format!("incremental compilation: could not create \
+                               session directory lock file: {}", err));
+            Err(())
+        }
+    }
+}
+
+fn delete_session_dir_lock_file(sess: &Session,
+                                lock_file_path: &Path) {
+    if let Err(err) = safe_remove_file(&lock_file_path) {
+        sess.warn(&format!("Error deleting lock file for incremental \
+                            compilation session directory `{}`: {}",
+                           lock_file_path.display(),
+                           err));
+    }
+}
+
+/// Find the most recent published session directory that is not in the
+/// ignore-list.
+fn find_source_directory(crate_dir: &Path,
+                         source_directories_already_tried: &FnvHashSet<PathBuf>)
+                         -> Option<PathBuf> {
+    let iter = match crate_dir.read_dir() {
+        Ok(iter) => iter,
+        Err(_) => return None,
+    };
+
+    find_source_directory_in_iter(iter.filter_map(|e| e.ok().map(|e| e.path())),
+                                  source_directories_already_tried)
+}
+
+fn find_source_directory_in_iter<I>(iter: I,
+                                    source_directories_already_tried: &FnvHashSet<PathBuf>)
+                                    -> Option<PathBuf>
+    where I: Iterator<Item=PathBuf>
+{
+    let mut best_candidate = (UNIX_EPOCH, None);
+
+    for session_dir in iter {
+        debug!("find_source_directory_in_iter - inspecting `{}`",
+               session_dir.display());
+
+        let directory_name = session_dir.file_name().unwrap().to_string_lossy();
+        assert_no_characters_lost(&directory_name);
+
+        if source_directories_already_tried.contains(&session_dir) ||
+           !is_session_directory(&directory_name) ||
+           !is_finalized(&directory_name) {
+            debug!("find_source_directory_in_iter - ignoring.");
+            continue
+        }
+
+        let timestamp = match extract_timestamp_from_session_dir(&directory_name) {
+            Ok(timestamp) => timestamp,
+            Err(()) => {
+                debug!("find_source_directory_in_iter - malformed name, ignoring.");
+                continue
+            }
+        };
+
+        if timestamp > best_candidate.0 {
+            best_candidate = (timestamp, Some(session_dir.clone()));
+        }
+    }
+
+    best_candidate.1
+}
+
+fn is_finalized(directory_name: &str) -> bool {
+    !directory_name.ends_with("-working")
+}
+
+fn is_session_directory(directory_name: &str) -> bool {
+    directory_name.starts_with("s-") &&
+    !directory_name.ends_with(LOCK_FILE_EXT)
+}
+
+fn is_session_directory_lock_file(file_name: &str) -> bool {
+    file_name.starts_with("s-") && file_name.ends_with(LOCK_FILE_EXT)
+}
+
+/// Extracts the timestamp from the name of a session directory or of a
+/// session directory lock file.
+fn extract_timestamp_from_session_dir(directory_name: &str) -> Result<SystemTime, ()> {
+    let mut components = directory_name.splitn(3, '-');
+
+    if components.next() != Some("s") {
+        return Err(())
+    }
+
+    match components.next() {
+        Some(timestamp) => string_to_timestamp(timestamp),
+        None => Err(()),
+    }
+}
+
+fn timestamp_to_string(timestamp: SystemTime) -> String {
+    let duration = timestamp.duration_since(UNIX_EPOCH).unwrap();
+    let micros = duration.as_secs() * 1_000_000 +
+                (duration.subsec_nanos() as u64) / 1000;
+    encode_base_36(micros)
+}
+
+fn string_to_timestamp(s: &str) -> Result<SystemTime, ()> {
+    let micros_since_unix_epoch = match u64::from_str_radix(s, 36) {
+        Ok(micros) => micros,
+        Err(_) => return Err(()),
+    };
+
+    let duration = Duration::new(micros_since_unix_epoch / 1_000_000,
+                                 1000 * (micros_since_unix_epoch % 1_000_000) as u32);
+    Ok(UNIX_EPOCH + duration)
+}
+
+fn encode_base_36(mut n: u64) -> String {
+    const DIGITS: &'static [u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    let mut s = Vec::with_capacity(13);
+    loop {
+        s.push(DIGITS[(n % 36) as usize]);
+        n /= 36;
+
+        if n == 0 {
+            break;
+        }
+    }
+    s.reverse();
+
+    String::from_utf8(s).unwrap()
+}
+
+fn crate_path_tcx<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, cnum: ast::CrateNum) -> PathBuf {
+    crate_path(tcx.sess, &tcx.crate_name(cnum), &tcx.crate_disambiguator(cnum))
+}
+
+/// Finds the session directory containing the metadata hashes for the given
+/// crate. This is the most recent finalized session directory whose SVH
+/// matches the SVH of the crate we are actually linking against.
+fn find_metadata_hashes_for<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                      cnum: ast::CrateNum)
+                                      -> Option<PathBuf> {
+    let crate_directory = crate_path_tcx(tcx, cnum);
+
+    if !crate_directory.exists() {
+        return None
+    }
+
+    let dir_entries = match crate_directory.read_dir() {
+        Ok(dir_entries) => dir_entries,
+        Err(e) => {
+            tcx.sess
+               .struct_warn(&format!("Could not read incremental compilation \
+                                      crate directory `{}`: {}",
+                                     crate_directory.display(),
+                                     e))
+               .emit();
+            return None
+        }
+    };
+
+    let target_svh = tcx.sess.cstore.crate_hash(cnum);
+    let target_svh = encode_base_36(target_svh.as_u64());
+
+    let sub_dir = find_metadata_hashes_iter(&target_svh, dir_entries.filter_map(|e| {
+        e.ok().map(|e| e.file_name().to_string_lossy().into_owned())
+    }));
+
+    sub_dir.map(|sub_dir_name| crate_directory.join(&sub_dir_name))
+}
+
+fn find_metadata_hashes_iter<I>(target_svh: &str, iter: I) -> Option<String>
+    where I: Iterator<Item=String>
+{
+    let mut best_candidate = (UNIX_EPOCH, None);
+    let svh_suffix = format!("-{}", target_svh);
+
+    for sub_dir_name in iter {
+        if !is_session_directory(&sub_dir_name) ||
+           !sub_dir_name.ends_with(&svh_suffix) {
+            continue
+        }
+
+        if let Ok(timestamp) = extract_timestamp_from_session_dir(&sub_dir_name) {
+            if timestamp > best_candidate.0 {
+                best_candidate = (timestamp, Some(sub_dir_name));
+            }
+        }
+    }
+
+    best_candidate.1
+}
+
+fn crate_path(sess: &Session,
+              crate_name: &str,
+              crate_disambiguator: &str)
+              -> PathBuf {
+    let incr_dir = sess.opts.incremental.as_ref().unwrap();
+    incr_dir.join(format!("{}-{}", crate_name, crate_disambiguator))
+}
+
+fn lock_file_path(session_dir: &Path) -> PathBuf {
+    let crate_dir = session_dir.parent().unwrap();
+
+    let directory_name = session_dir.file_name().unwrap().to_string_lossy();
+    assert_no_characters_lost(&directory_name);
+
+    let dash_indices: Vec<_> = directory_name.match_indices("-")
+                                             .map(|(idx, _)| idx)
+                                             .collect();
+    if dash_indices.len() != 3 {
+        bug!("Encountered incremental compilation session directory with \
+              malformed name: {}",
+             session_dir.display())
+    }
+
+    crate_dir.join(&directory_name[0 .. dash_indices[2]])
+             .with_extension(&LOCK_FILE_EXT[1..])
+}
+
+fn assert_no_characters_lost(s: &str) {
+    if s.contains('\u{FFFD}') {
+        bug!("Could not losslessly convert '{}'.", s)
+    }
+}
+
+fn is_old_enough_to_be_collected(timestamp: SystemTime) -> bool {
+    timestamp < SystemTime::now() - Duration::from_secs(MIN_SECONDS_BEFORE_COLLECTION)
+}
+
+/// Deletes stale and abandoned session directories from the crate directory
+/// of the current session: all finalized session directories except for the
+/// most recent one, and all `-working` directories whose owning compiler
+/// process has gone away without cleaning up. Directories that are locked by
+/// somebody else are left alone.
+pub fn garbage_collect_session_directories(sess: &Session) -> io::Result<()> {
+    debug!("garbage_collect_session_directories() - begin");
+
+    let session_directory = sess.incr_comp_session_dir().clone();
+    debug!("garbage_collect_session_directories() - session directory: {}",
+           session_directory.display());
+
+    let crate_directory = session_directory.parent().unwrap();
+    debug!("garbage_collect_session_directories() - crate directory: {}",
+           crate_directory.display());
+
+    // First do a pass over the crate directory, collecting lock files and
+    // session directories
+    let mut session_directories = FnvHashSet();
+    let mut lock_files = FnvHashSet();
+
+    for dir_entry in try!(crate_directory.read_dir()) {
+        let dir_entry = match dir_entry {
+            Ok(dir_entry) => dir_entry,
+            _ => {
+                // Ignore any errors
+                continue
+            }
+        };
+
+        let entry_name = dir_entry.file_name();
+        let entry_name = entry_name.to_string_lossy();
+
+        if is_session_directory_lock_file(&entry_name) {
+            assert_no_characters_lost(&entry_name);
+            lock_files.insert(entry_name.into_owned());
+        } else if is_session_directory(&entry_name) {
+            assert_no_characters_lost(&entry_name);
+            session_directories.insert(entry_name.into_owned());
+        } else {
+            // This is something we don't know, leave it alone
+        }
+    }
+
+    // Now map from lock files to session directories
+    let lock_file_to_session_dir: FnvHashMap<String, Option<String>> =
+        lock_files.into_iter()
+                  .map(|lock_file_name| {
+                        assert!(lock_file_name.ends_with(LOCK_FILE_EXT));
+                        let dir_prefix_end = lock_file_name.len() - LOCK_FILE_EXT.len();
+                        let session_dir = {
+                            let dir_prefix = format!("{}-", &lock_file_name[0 .. dir_prefix_end]);
+                            session_directories.iter()
+                                               .find(|dir_name| dir_name.starts_with(&dir_prefix))
+                        };
+                        (lock_file_name, session_dir.map(String::clone))
+                    })
+                  .collect();
+
+    // Delete all lock files, that don't have an associated directory. They must
+    // be some kind of leftover
+    for (lock_file_name, directory_name) in &lock_file_to_session_dir {
+        if directory_name.is_none() {
+            let timestamp = match extract_timestamp_from_session_dir(lock_file_name) {
+                Ok(timestamp) => timestamp,
+                Err(()) => {
+                    debug!("Found lock-file with malformed timestamp: {}",
+                           crate_directory.join(&lock_file_name).display());
+                    // Ignore it
+                    continue
+                }
+            };
+
+            let lock_file_path = crate_directory.join(&**lock_file_name);
+
+            if is_old_enough_to_be_collected(timestamp) {
+                debug!("garbage_collect_session_directories() - deleting \
+                        garbage lock file: {}", lock_file_path.display());
+                delete_session_dir_lock_file(sess, &lock_file_path);
+            } else {
+                debug!("garbage_collect_session_directories() - lock file with \
+                        no session dir not old enough to be collected: {}",
+                       lock_file_path.display());
+            }
+        }
+    }
+
+    // Filter out `None` directories
+    let lock_file_to_session_dir: FnvHashMap<String, String> =
+        lock_file_to_session_dir.into_iter()
+                                .filter_map(|(lock_file_name, directory_name)| {
+                                    directory_name.map(|n| (lock_file_name, n))
+                                })
+                                .collect();
+
+    let mut deletion_candidates = vec![];
+    let mut definitely_delete = vec![];
+
+    for (lock_file_name, directory_name) in &lock_file_to_session_dir {
+        debug!("garbage_collect_session_directories() - inspecting: {}",
+                directory_name);
+
+        let timestamp = match extract_timestamp_from_session_dir(directory_name) {
+            Ok(timestamp) => timestamp,
+            Err(()) => {
+                debug!("Found session-dir with malformed timestamp: {}",
+                       crate_directory.join(directory_name).display());
+                // Ignore it
+                continue
+            }
+        };
+
+        if is_finalized(directory_name) {
+            let lock_file_path = crate_directory.join(lock_file_name);
+            match flock::Lock::new(&lock_file_path,
+                                   false,  // don't wait
+                                   false,  // don't create the lock-file
+                                   true) { // get an exclusive lock
+                Ok(lock) => {
+                    debug!("garbage_collect_session_directories() - \
+                            successfully acquired lock");
+                    debug!("garbage_collect_session_directories() - adding \
+                            deletion candidate: {}", directory_name);
+
+                    // Note that we are holding on to the lock
+                    deletion_candidates.push((timestamp,
+                                              crate_directory.join(directory_name),
+                                              lock));
+                }
+                Err(_) => {
+                    debug!("garbage_collect_session_directories() - \
+                            not collecting, still in use");
+                }
+            }
+        } else if is_old_enough_to_be_collected(timestamp) {
+            // When cleaning out "-working" session directories, i.e.
+            // session directories that might still be in use by another
+            // compiler instance, we only look a directories that are
+            // at least ten seconds old. This is supposed to reduce the
+            // chance of deleting a directory in the time window where
+            // the process has allocated the directory but has not yet
+            // acquired the file-lock on it.
+
+            // Try to acquire the directory lock. If we can't, it
+            // means that the owning process is still alive and we
+            // leave this directory alone.
+            let lock_file_path = crate_directory.join(lock_file_name);
+            match flock::Lock::new(&lock_file_path,
+                                   false,  // don't wait
+                                   false,  // don't create the lock-file
+                                   true) { // get an exclusive lock
+                Ok(lock) => {
+                    debug!("garbage_collect_session_directories() - \
+                            successfully acquired lock");
+
+                    // Note that we are holding on to the lock
+                    definitely_delete.push((crate_directory.join(directory_name),
+                                            lock));
+                }
+                Err(_) => {
+                    debug!("garbage_collect_session_directories() - \
+                            not collecting, still in use");
+                }
+            }
+        } else {
+            debug!("garbage_collect_session_directories() - not finalized, not \
+                    old enough");
+        }
+    }
+
+    // Delete all but the most recent of the candidates
+    for (path, lock) in all_except_most_recent(deletion_candidates) {
+        debug!("garbage_collect_session_directories() - deleting `{}`",
+                path.display());
+
+        if let Err(err) = safe_remove_dir_all(&path) {
+            sess.warn(&format!("Failed to garbage collect finalized incremental \
+                                compilation session directory `{}`: {}",
+                               path.display(),
+                               err));
+        } else {
+            delete_session_dir_lock_file(sess, &lock_file_path(&path));
+        }
+
+        // Let's make it explicit that the file lock is released at this point,
+        // or rather, that we held on to it until here
+        mem::drop(lock);
+    }
+
+    for (path, lock) in definitely_delete {
+        debug!("garbage_collect_session_directories() - deleting `{}`",
+                path.display());
+
+        if let Err(err) = safe_remove_dir_all(&path) {
+            sess.warn(&format!("Failed to garbage collect incremental \
+                                compilation session directory `{}`: {}",
+                               path.display(),
+                               err));
+        } else {
+            delete_session_dir_lock_file(sess, &lock_file_path(&path));
+        }
+
+        // Let's make it explicit that the file lock is released at this point,
+        // or rather, that we held on to it until here
+        mem::drop(lock);
+    }
+
+    Ok(())
+}
+
+fn all_except_most_recent<T>(deletion_candidates: Vec<(SystemTime, PathBuf, T)>)
+                             -> Vec<(PathBuf, T)> {
+    let most_recent = deletion_candidates.iter()
+                                         .map(|&(timestamp, _, _)| timestamp)
+                                         .max();
+
+    if let Some(most_recent) = most_recent {
+        deletion_candidates.into_iter()
+                           .filter(|&(timestamp, _, _)| timestamp != most_recent)
+                           .map(|(_, path, lock)| (path, lock))
+                           .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Since paths of artifacts within session directories can get quite long, we
+/// need to support deleting files with very long paths. The regular
+/// WinApi functions only support paths up to 260 characters, however. In order
+/// to circumvent this limitation, we canonicalize the path of the directory
+/// before passing it to std::fs::remove_dir_all(). This will convert the path
+/// into the '\\?\' format, which supports much longer paths.
+fn safe_remove_dir_all(p: &Path) -> io::Result<()> {
+    if p.exists() {
+        let canonicalized = try!(p.canonicalize());
+        std_fs::remove_dir_all(canonicalized)
+    } else {
+        Ok(())
+    }
+}
+
+fn safe_remove_file(p: &Path) -> io::Result<()> {
+    if p.exists() {
+        let canonicalized = try!(p.canonicalize());
+        std_fs::remove_file(canonicalized)
+    } else {
+        Ok(())
+    }
+}
+
+// Like std::fs::create_dir_all, except handles concurrent calls among multiple
+// threads or processes.
+fn create_dir_racy(path: &Path) -> io::Result<()> {
+    match std_fs::create_dir(path) {
+        Ok(()) => return Ok(()),
+        Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => return Ok(()),
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    match path.parent() {
+        Some(p) => try!(create_dir_racy(p)),
+        None => return Err(io::Error::new(io::ErrorKind::Other,
+                                          "failed to create whole tree")),
+    }
+    match std_fs::create_dir(path) {
+        Ok(()) => Ok(()),
+        Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[test]
+fn test_all_except_most_recent() {
+    assert_eq!(all_except_most_recent(
+        vec![
+            (UNIX_EPOCH + Duration::new(4, 0), PathBuf::from("4"), ()),
+            (UNIX_EPOCH + Duration::new(1, 0), PathBuf::from("1"), ()),
+            (UNIX_EPOCH + Duration::new(5, 0), PathBuf::from("5"), ()),
+            (UNIX_EPOCH + Duration::new(3, 0), PathBuf::from("3"), ()),
+            (UNIX_EPOCH + Duration::new(2, 0), PathBuf::from("2"), ()),
+        ]).into_iter().map(|(path, _)| path).collect::<FnvHashSet<PathBuf>>(),
+        vec![
+            PathBuf::from("1"),
+            PathBuf::from("2"),
+            PathBuf::from("3"),
+            PathBuf::from("4"),
+        ].into_iter().collect::<FnvHashSet<PathBuf>>()
+    );
+
+    assert_eq!(all_except_most_recent::<()>(vec![]), vec![]);
+}
+
+#[test]
+fn test_timestamp_serialization() {
+    for i in 0 .. 1_000u64 {
+        let time = UNIX_EPOCH + Duration::new(i * 1_434_578, (i as u32) * 239_000);
+        let s = timestamp_to_string(time);
+        assert_eq!(Ok(time), string_to_timestamp(&s));
+    }
+}
+
+#[test]
+fn test_find_source_directory_in_iter() {
+    let already_visited = FnvHashSet();
+
+    // Find newest
+    assert_eq!(find_source_directory_in_iter(
+        vec![PathBuf::from("crate-dir/s-3234-0000-svh"),
+             PathBuf::from("crate-dir/s-2234-0000-svh"),
+             PathBuf::from("crate-dir/s-1234-0000-svh")].into_iter(), &already_visited),
+        Some(PathBuf::from("crate-dir/s-3234-0000-svh")));
+
+    // Filter out "-working"
+    assert_eq!(find_source_directory_in_iter(
+        vec![PathBuf::from("crate-dir/s-3234-0000-working"),
+             PathBuf::from("crate-dir/s-2234-0000-svh"),
+             PathBuf::from("crate-dir/s-1234-0000-svh")].into_iter(), &already_visited),
+        Some(PathBuf::from("crate-dir/s-2234-0000-svh")));
+
+    // Handle empty
+    assert_eq!(find_source_directory_in_iter(vec![].into_iter(), &already_visited),
+               None);
+
+    // Handle only working
+    assert_eq!(find_source_directory_in_iter(
+        vec![PathBuf::from("crate-dir/s-3234-0000-working"),
+             PathBuf::from("crate-dir/s-2123-0000-working"),
+             PathBuf::from("crate-dir/s-1234-0000-working")].into_iter(), &already_visited),
+        None);
+}
+
+#[test]
+fn test_find_metadata_hashes_iter() {
+    assert_eq!(find_metadata_hashes_iter("testsvh2",
+        vec![
+            String::from("s-timestamp1-rand1-testsvh1"),
+            String::from("s-timestamp2-rand2-testsvh2"),
+            String::from("s-timestamp3-rand3-testsvh3"),
+        ].into_iter()),
+        Some(String::from("s-timestamp2-rand2-testsvh2"))
+    );
+
+    assert_eq!(find_metadata_hashes_iter("testsvh2",
+        vec![
+            String::from("s-timestamp1-rand1-testsvh1"),
+            String::from("s-timestamp2-rand2-testsvh2"),
+            String::from("invalid-name"),
+        ].into_iter()),
+        Some(String::from("s-timestamp2-rand2-testsvh2"))
+    );
+
+    assert_eq!(find_metadata_hashes_iter("testsvh2",
+        vec![
+            String::from("s-timestamp1-rand1-testsvh1"),
+            String::from("s-timestamp2-rand2-testsvh2-working"),
+            String::from("s-timestamp3-rand3-testsvh3"),
+        ].into_iter()),
+        None
+    );
+}
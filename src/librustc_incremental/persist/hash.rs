@@ -22,7 +22,7 @@ use std::fs::File;
 use syntax::ast;
 
 use super::data::*;
-use super::util::*;
+use super::fs::*;
 
 pub struct HashContext<'a, 'tcx: 'a> {
     pub tcx: TyCtxt<'a, 'tcx, 'tcx>,
@@ -108,7 +108,7 @@ impl<'a, 'tcx> HashContext<'a, 'tcx> {
         debug!("load_data: svh={}", svh);
         assert!(old.is_none(), "loaded data for crate {:?} twice", cnum);
 
-        if let Some(path) = metadata_hash_path(self.tcx, cnum) {
+        if let Some(path) = metadata_hash_import_path(self.tcx, cnum) {
             debug!("load_data: path={:?}", path);
             let mut data = vec![];
             match
@@ -15,7 +15,7 @@ use rbml::opaque::Decoder;
 use rustc::dep_graph::DepNode;
 use rustc::hir::def_id::DefId;
 use rustc::ty::TyCtxt;
-use rustc_data_structures::fnv::FnvHashSet;
+use rustc_data_structures::fnv::{FnvHashMap, FnvHashSet};
 use rustc_serialize::Decodable as RustcDecodable;
 use std::io::Read;
 use std::fs::File;
@@ -24,32 +24,39 @@ use std::path::Path;
 use super::data::*;
 use super::directory::*;
 use super::dirty_clean;
+use super::fingerprint::result_fingerprint;
+use super::fs::*;
 use super::hash::*;
-use super::util::*;
+use super::report;
 
 type DirtyNodes = FnvHashSet<DepNode<DefId>>;
 
+type CleanNodes = FnvHashSet<DepNode<DefId>>;
+
 type CleanEdges = Vec<(DepNode<DefId>, DepNode<DefId>)>;
 
 /// If we are in incremental mode, and a previous dep-graph exists,
 /// then load up those nodes/edges that are still valid into the
-/// dep-graph for this session. (This is assumed to be running very
-/// early in compilation, before we've really done any work, but
-/// actually it doesn't matter all that much.) See `README.md` for
-/// more general overview.
+/// dep-graph for this session. This must run after type collection,
+/// because we compare the fingerprints that computed results like
+/// item signatures have in this session against the ones they had
+/// in the previous session (other than that, it doesn't matter all
+/// that much when it runs). See `README.md` for more general
+/// overview.
 pub fn load_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
     let _ignore = tcx.dep_graph.in_ignore();
 
-    if let Some(dep_graph) = dep_graph_path(tcx) {
-        // FIXME(#32754) lock file?
-        load_dep_graph_if_exists(tcx, &dep_graph);
-        dirty_clean::check_dirty_clean_annotations(tcx);
+    if let Some(dep_graph) = dep_graph_path(tcx.sess) {
+        let clean_nodes = load_dep_graph_if_exists(tcx, &dep_graph);
+        dirty_clean::check_dirty_clean_annotations(tcx, &clean_nodes);
     }
 }
 
-pub fn load_dep_graph_if_exists<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, path: &Path) {
+pub fn load_dep_graph_if_exists<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                          path: &Path)
+                                          -> CleanNodes {
     if !path.exists() {
-        return;
+        return FnvHashSet();
     }
 
     let mut data = vec![];
@@ -62,21 +69,24 @@ pub fn load_dep_graph_if_exists<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, path: &Pa
             tcx.sess.err(
                 &format!("could not load dep-graph from `{}`: {}",
                          path.display(), err));
-            return;
+            return FnvHashSet();
         }
     }
 
     match decode_dep_graph(tcx, &data) {
-        Ok(dirty) => dirty,
+        Ok(clean_nodes) => clean_nodes,
         Err(err) => {
             bug!("decoding error in dep-graph from `{}`: {}", path.display(), err);
         }
     }
 }
 
+/// Decodes the dep-graph of the previous session and adds the nodes
+/// and edges that are still valid to the current dep-graph. Returns
+/// the set of nodes that were found to be clean.
 pub fn decode_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                                   data: &[u8])
-                                  -> Result<(), Error>
+                                  -> Result<CleanNodes, Error>
 {
     // Deserialize the directory and dep-graph.
     let mut decoder = Decoder::new(data, 0);
@@ -97,37 +107,65 @@ pub fn decode_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
     debug!("decode_dep_graph: initial dirty_nodes = {:#?}", dirty_nodes);
 
-    // Find all DepNodes reachable from that core set. This loop
-    // iterates repeatedly over the list of edges whose source is not
-    // known to be dirty (`clean_edges`). If it finds an edge whose
-    // source is dirty, it removes it from that list and adds the
-    // target to `dirty_nodes`. It stops when it reaches a fixed
-    // point.
-    let clean_edges = compute_clean_edges(&serialized_dep_graph.edges,
+    // Remember the inputs that changed, for the reuse report.
+    let changed_inputs = if tcx.sess.opts.debugging_opts.incremental_info {
+        Some(dirty_nodes.clone())
+    } else {
+        None
+    };
+
+    // The fingerprints that computed results had in the previous
+    // session. For nodes found here, we can check whether their
+    // result actually changed instead of assuming that it did.
+    let old_fingerprints: FnvHashMap<_, _> =
+        serialized_dep_graph.fingerprints
+                            .iter()
+                            .filter_map(|fingerprint| {
+                                retraced.map(&fingerprint.node)
+                                        .map(|node| (node, fingerprint.hash))
+                            })
+                            .collect();
+
+    // Find all DepNodes reachable from that core set, stopping at
+    // nodes whose result hashes the same as before (see
+    // `compute_clean_edges` for details).
+    let clean_edges = compute_clean_edges(tcx,
+                                          &serialized_dep_graph.edges,
                                           &retraced,
+                                          &old_fingerprints,
                                           &mut dirty_nodes);
 
     // Add synthetic `foo->foo` edges for each clean node `foo` that
     // we had before. This is sort of a hack to create clean nodes in
     // the graph, since the existence of a node is a signal that the
     // work it represents need not be repeated.
-    let clean_nodes =
+    let clean_nodes: CleanNodes =
         serialized_dep_graph.nodes
                             .iter()
                             .filter_map(|node| retraced.map(node))
                             .filter(|node| !dirty_nodes.contains(node))
-                            .map(|node| (node.clone(), node));
+                            .collect();
+
+    if let Some(changed_inputs) = changed_inputs {
+        report::report_reuse(tcx,
+                             &serialized_dep_graph,
+                             &retraced,
+                             &changed_inputs,
+                             &dirty_nodes,
+                             &clean_nodes);
+    }
 
     // Add nodes and edges that are not dirty into our main graph.
     let dep_graph = tcx.dep_graph.clone();
-    for (source, target) in clean_edges.into_iter().chain(clean_nodes) {
+    let clean_self_edges = clean_nodes.iter().map(|node| (node.clone(), node.clone()));
+    for (source, target) in clean_edges.into_iter().chain(clean_self_edges) {
         let _task = dep_graph.in_task(target.clone());
         dep_graph.read(source.clone());
 
         debug!("decode_dep_graph: clean edge: {:?} -> {:?}", source, target);
     }
 
-    Ok(())
+    Ok(clean_nodes)
 }
 
 fn initial_dirty_nodes<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
@@ -163,53 +201,101 @@ fn initial_dirty_nodes<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     dirty_nodes
 }
 
-fn compute_clean_edges(serialized_edges: &[(SerializedEdge)],
-                       retraced: &RetracedDefIdDirectory,
-                       dirty_nodes: &mut DirtyNodes)
-                       -> CleanEdges {
+fn compute_clean_edges<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                 serialized_edges: &[(SerializedEdge)],
+                                 retraced: &RetracedDefIdDirectory,
+                                 old_fingerprints: &FnvHashMap<DepNode<DefId>, u64>,
+                                 dirty_nodes: &mut DirtyNodes)
+                                 -> CleanEdges {
     // Build up an initial list of edges. Include an edge (source,
     // target) if neither node has been removed. If the source has
-    // been removed, add target to the list of dirty nodes.
+    // been removed, the target may have changed.
     let mut clean_edges = Vec::with_capacity(serialized_edges.len());
+    let mut changed_nodes: Vec<_> = dirty_nodes.iter().cloned().collect();
+    let mut targets_of_removed_sources = vec![];
     for &(ref serialized_source, ref serialized_target) in serialized_edges {
         if let Some(target) = retraced.map(serialized_target) {
             if let Some(source) = retraced.map(serialized_source) {
                 clean_edges.push((source, target))
             } else {
-                // source removed, target must be dirty
-                dirty_nodes.insert(target);
+                // source removed, target may have changed
+                targets_of_removed_sources.push(target);
             }
         } else {
             // target removed, ignore the edge
         }
     }
 
-    debug!("compute_clean_edges: dirty_nodes={:#?}", dirty_nodes);
+    let mut successors = FnvHashMap();
+    for &(ref source, ref target) in &clean_edges {
+        successors.entry(source.clone())
+                  .or_insert_with(Vec::new)
+                  .push(target.clone());
+    }
 
-    // Propagate dirty marks by iterating repeatedly over
-    // `clean_edges`. If we find an edge `(source, target)` where
-    // `source` is dirty, add `target` to the list of dirty nodes and
-    // remove it. Keep doing this until we find no more dirty nodes.
-    let mut previous_size = 0;
-    while dirty_nodes.len() > previous_size {
-        debug!("compute_clean_edges: previous_size={}", previous_size);
-        previous_size = dirty_nodes.len();
-        let mut i = 0;
-        while i < clean_edges.len() {
-            if dirty_nodes.contains(&clean_edges[i].0) {
-                let (source, target) = clean_edges.swap_remove(i);
-                debug!("compute_clean_edges: dirty source {:?} -> {:?}",
-                       source, target);
-                dirty_nodes.insert(target);
-            } else if dirty_nodes.contains(&clean_edges[i].1) {
-                let (source, target) = clean_edges.swap_remove(i);
-                debug!("compute_clean_edges: dirty target {:?} -> {:?}",
-                       source, target);
-            } else {
-                i += 1;
+    // Propagate dirty marks along the edges. Results that we
+    // fingerprinted in the previous session are not marked dirty
+    // just because one of their inputs changed: they have been
+    // recomputed in this session, so we can check whether they
+    // actually changed. If a result hashes the same as before, it is
+    // clean ("green") and we stop propagating there; otherwise it is
+    // dirty ("red") and so is everything that depends on it.
+    let mut colors = FnvHashMap();
+    let mut mark_target = |target: DepNode<DefId>,
+                           dirty_nodes: &mut DirtyNodes,
+                           changed_nodes: &mut Vec<DepNode<DefId>>| {
+        if dirty_nodes.contains(&target) {
+            return;
+        }
+
+        let unchanged = *colors.entry(target.clone()).or_insert_with(|| {
+            result_unchanged(tcx, &target, old_fingerprints)
+        });
+
+        if unchanged {
+            debug!("compute_clean_edges: {:?} is green", target);
+        } else {
+            debug!("compute_clean_edges: {:?} is red", target);
+            dirty_nodes.insert(target.clone());
+            changed_nodes.push(target);
+        }
+    };
+
+    for target in targets_of_removed_sources {
+        mark_target(target, dirty_nodes, &mut changed_nodes);
+    }
+
+    while let Some(node) = changed_nodes.pop() {
+        if let Some(targets) = successors.get(&node) {
+            for target in targets {
+                mark_target(target.clone(), dirty_nodes, &mut changed_nodes);
             }
         }
     }
 
+    debug!("compute_clean_edges: dirty_nodes={:#?}", dirty_nodes);
+
+    // Only keep the edges between clean nodes.
+    clean_edges.retain(|&(ref source, ref target)| {
+        !dirty_nodes.contains(source) && !dirty_nodes.contains(target)
+    });
+
     clean_edges
 }
+
+/// True if `dep_node` is a result that was fingerprinted in the
+/// previous session and has the same fingerprint now.
+fn result_unchanged<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                              dep_node: &DepNode<DefId>,
+                              old_fingerprints: &FnvHashMap<DepNode<DefId>, u64>)
+                              -> bool {
+    match old_fingerprints.get(dep_node) {
+        Some(&old_fingerprint) => {
+            let new_fingerprint = result_fingerprint(tcx, dep_node);
+            debug!("result_unchanged: fingerprint of {:?} is {:?}, was {:?}",
+                   dep_node, new_fingerprint, old_fingerprint);
+            new_fingerprint == Some(old_fingerprint)
+        }
+        None => false,
+    }
+}
@@ -15,10 +15,15 @@
 mod data;
 mod directory;
 mod dirty_clean;
+mod fingerprint;
+mod fs;
 mod hash;
 mod load;
+mod report;
 mod save;
-mod util;
 
+pub use self::fs::delete_session_directory;
+pub use self::fs::finalize_session_directory;
+pub use self::fs::prepare_session_directory;
 pub use self::load::load_dep_graph;
 pub use self::save::save_dep_graph;
@@ -0,0 +1,128 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The report printed with `-Z incremental-info`. It summarizes how
+//! many nodes of the previous dep-graph could be reused, by kind of
+//! `DepNode`, and for each node that has to be recomputed it shows a
+//! chain of changed inputs that explains why.
+
+use rustc::dep_graph::{DepGraphQuery, DepNode};
+use rustc::hir::def_id::DefId;
+use rustc::ty::TyCtxt;
+use rustc_data_structures::fnv::{FnvHashMap, FnvHashSet};
+use std::collections::{BTreeMap, VecDeque};
+
+use super::data::*;
+use super::directory::*;
+
+type NodeSet = FnvHashSet<DepNode<DefId>>;
+
+pub fn report_reuse<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                              serialized_dep_graph: &SerializedDepGraph,
+                              retraced: &RetracedDefIdDirectory,
+                              changed_inputs: &NodeSet,
+                              dirty_nodes: &NodeSet,
+                              clean_nodes: &NodeSet) {
+    // Rebuild the previous graph, as far as it still exists, so that
+    // we can walk it.
+    let edges: Vec<_> =
+        serialized_dep_graph.edges
+                            .iter()
+                            .filter_map(|&(ref source, ref target)| {
+                                match (retraced.map(source), retraced.map(target)) {
+                                    (Some(source), Some(target)) => Some((source, target)),
+                                    _ => None,
+                                }
+                            })
+                            .collect();
+    let mut nodes: NodeSet =
+        serialized_dep_graph.nodes
+                            .iter()
+                            .filter_map(|node| retraced.map(node))
+                            .collect();
+    nodes.extend(changed_inputs.iter().cloned());
+    for &(ref source, ref target) in &edges {
+        nodes.insert(source.clone());
+        nodes.insert(target.clone());
+    }
+    let nodes: Vec<_> = nodes.into_iter().collect();
+    let query = DepGraphQuery::new(&nodes, &edges);
+
+    // Statistics, by kind of node.
+    let mut stats: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    for node in clean_nodes {
+        stats.entry(node_kind(node)).or_insert((0, 0)).0 += 1;
+    }
+    for node in dirty_nodes {
+        stats.entry(node_kind(node)).or_insert((0, 0)).1 += 1;
+    }
+
+    println!("incremental: reused {} nodes, recomputing {} nodes",
+             clean_nodes.len(), dirty_nodes.len());
+    println!("incremental: {:<24} {:>8} {:>10}", "kind", "reused", "recomputed");
+    for (kind, &(reused, recomputed)) in &stats {
+        println!("incremental: {:<24} {:>8} {:>10}", kind, reused, recomputed);
+    }
+
+    // Do a breadth-first walk from the changed inputs through the
+    // dirty part of the graph, remembering for each dirty node the
+    // node we reached it from. That gives us the shortest chain of
+    // changes leading to each dirty node.
+    let mut parents = FnvHashMap();
+    let mut queue: VecDeque<_> = changed_inputs.iter().collect();
+    while let Some(node) = queue.pop_front() {
+        for successor in query.immediate_successors(node) {
+            if dirty_nodes.contains(successor) &&
+               !changed_inputs.contains(successor) &&
+               !parents.contains_key(successor) {
+                parents.insert(successor, node);
+                queue.push_back(successor);
+            }
+        }
+    }
+
+    let mut reasons: Vec<_> =
+        dirty_nodes.iter()
+                   .filter(|node| !changed_inputs.contains(node))
+                   .map(|node| {
+                       let mut chain = vec![node_str(tcx, node)];
+                       let mut current = node;
+                       while let Some(&parent) = parents.get(current) {
+                           chain.push(node_str(tcx, parent));
+                           current = parent;
+                       }
+                       if !changed_inputs.contains(current) {
+                           // We did not get here from a changed input,
+                           // so one of the inputs must have been removed.
+                           chain.push("<removed input>".to_string());
+                       }
+                       chain.reverse();
+                       chain.join(" -> ")
+                   })
+                   .collect();
+    reasons.sort();
+
+    for reason in &reasons {
+        println!("incremental: recomputing: {}", reason);
+    }
+}
+
+fn node_kind(node: &DepNode<DefId>) -> String {
+    let string = format!("{:?}", node);
+    match string.find('(') {
+        Some(index) => string[..index].to_string(),
+        None => string,
+    }
+}
+
+fn node_str<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, node: &DepNode<DefId>) -> String {
+    let node = node.map_def(|&def_id| Some(tcx.item_path_str(def_id))).unwrap();
+    format!("{:?}", node)
+}
@@ -10,7 +10,6 @@
 
 use rbml::opaque::Encoder;
 use rustc::dep_graph::DepNode;
-use rustc::middle::cstore::LOCAL_CRATE;
 use rustc::ty::TyCtxt;
 use rustc_serialize::{Encodable as RustcEncodable};
 use std::hash::{Hasher, SipHasher};
@@ -20,14 +19,15 @@ use std::path::PathBuf;
 
 use super::data::*;
 use super::directory::*;
+use super::fingerprint::result_fingerprint;
+use super::fs::*;
 use super::hash::*;
-use super::util::*;
 
 pub fn save_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
     let _ignore = tcx.dep_graph.in_ignore();
     let mut hcx = HashContext::new(tcx);
-    save_in(&mut hcx, dep_graph_path(tcx), encode_dep_graph);
-    save_in(&mut hcx, metadata_hash_path(tcx, LOCAL_CRATE), encode_metadata_hashes);
+    save_in(&mut hcx, dep_graph_path(tcx.sess), encode_dep_graph);
+    save_in(&mut hcx, metadata_hash_export_path(tcx.sess), encode_metadata_hashes);
 }
 
 fn save_in<'a, 'tcx, F>(hcx: &mut HashContext<'a, 'tcx>,
@@ -42,9 +42,9 @@ fn save_in<'a, 'tcx, F>(hcx: &mut HashContext<'a, 'tcx>,
         None => return
     };
 
-    // FIXME(#32754) lock file?
-
-    // delete the old dep-graph, if any
+    // delete the old dep-graph, if any; note that the file might be a
+    // hard-link into a finalized session directory, so we must never write
+    // to it in place
     if path_buf.exists() {
         match fs::remove_file(&path_buf) {
             Ok(()) => { }
@@ -107,6 +107,19 @@ pub fn encode_dep_graph<'a, 'tcx>(hcx: &mut HashContext<'a, 'tcx>,
              })
              .collect();
 
+    // Create fingerprints for the results that support them.
+    let fingerprints =
+        query.nodes()
+             .into_iter()
+             .filter_map(|dep_node| {
+                 result_fingerprint(tcx, &dep_node)
+                    .map(|hash| {
+                        let node = builder.map(dep_node);
+                        SerializedHash { node: node, hash: hash }
+                    })
+             })
+             .collect();
+
     // Create the serialized dep-graph.
     let graph = SerializedDepGraph {
         nodes: query.nodes().into_iter()
@@ -120,6 +133,7 @@ pub fn encode_dep_graph<'a, 'tcx>(hcx: &mut HashContext<'a, 'tcx>,
                             })
                             .collect(),
         hashes: hashes,
+        fingerprints: fingerprints,
     };
 
     debug!("graph = {:#?}", graph);
@@ -253,6 +253,8 @@ pub fn rustc_version() -> String {
 
 pub const tag_panic_strategy: usize = 0x114;
 
+pub const tag_def_key_index: usize = 0x115; // top-level only
+
 // NB: increment this if you change the format of metadata such that
 // rustc_version can't be found.
-pub const metadata_encoding_version : &'static [u8] = &[b'r', b'u', b's', b't', 0, 0, 0, 2];
+pub const metadata_encoding_version : &'static [u8] = &[b'r', b'u', b's', b't', 0, 0, 0, 4];
@@ -0,0 +1,352 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Summarizes the metadata of a loaded crate for `--print crate-info`:
+//! its hash, the crates it depends on (with the hashes it was built
+//! against), its exported items with their signatures and stability,
+//! and the macros it exports.
+
+use cstore::CStore;
+use decoder;
+
+use rustc::hir::def::Def;
+use rustc::hir::def_id::{CRATE_DEF_INDEX, DefId};
+use rustc::middle::cstore::{ChildItem, CrateStore, DefLike};
+use rustc::ty::{self, TyCtxt};
+use rustc_data_structures::fnv::FnvHashSet;
+use rustc_serialize::json;
+
+use std::io;
+use syntax::ast;
+use syntax::attr;
+
+#[derive(RustcEncodable)]
+pub struct CrateInfo {
+    pub name: String,
+    pub hash: String,
+    pub disambiguator: String,
+    pub dependencies: Vec<DependencyInfo>,
+    pub items: Vec<ItemInfo>,
+    pub macros: Vec<String>,
+}
+
+#[derive(RustcEncodable)]
+pub struct DependencyInfo {
+    pub name: String,
+    /// The hash of the dependency that this crate was built against.
+    pub hash: String,
+    pub explicitly_linked: bool,
+}
+
+#[derive(RustcEncodable)]
+pub struct ItemInfo {
+    /// The path under which the item is exported, which is not
+    /// necessarily the path where it is defined.
+    pub path: String,
+    pub kind: String,
+    pub signature: Option<String>,
+    /// The where-clauses of the item, including the bounds declared
+    /// on its type parameters.
+    pub bounds: Vec<String>,
+    /// The variants of an enum, with their fields and the types of those.
+    pub variants: Vec<String>,
+    /// The public fields of a struct, with their types.
+    pub fields: Vec<String>,
+    pub has_private_fields: bool,
+    pub stability: Option<String>,
+    pub deprecation: Option<String>,
+}
+
+pub fn crate_info<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                            cstore: &CStore,
+                            cnum: ast::CrateNum)
+                            -> CrateInfo {
+    let cdata = cstore.get_crate_data(cnum);
+
+    let dependencies = decoder::get_crate_deps(cdata.data()).into_iter().map(|dep| {
+        DependencyInfo {
+            name: dep.name,
+            hash: dep.hash.to_string(),
+            explicitly_linked: dep.explicitly_linked,
+        }
+    }).collect();
+
+    let mut macros = vec![];
+    decoder::each_exported_macro(cdata.data(), |name, _, _, _| {
+        macros.push(name.to_string());
+        true
+    });
+
+    let mut collector = ItemCollector {
+        tcx: tcx,
+        cstore: cstore,
+        visited: FnvHashSet(),
+        items: vec![],
+    };
+    let krate = DefId { krate: cnum, index: CRATE_DEF_INDEX };
+    collector.visited.insert(krate);
+    collector.collect_children(cdata.name(), cstore.crate_top_level_items(cnum));
+
+    CrateInfo {
+        name: cdata.name().to_string(),
+        hash: cdata.hash().to_string(),
+        disambiguator: cdata.disambiguator().to_string(),
+        dependencies: dependencies,
+        items: collector.items,
+        macros: macros,
+    }
+}
+
+impl CrateInfo {
+    pub fn write_text(&self, out: &mut io::Write) -> io::Result<()> {
+        write!(out, "crate {} ({}-{})\n", self.name, self.hash, self.disambiguator)?;
+
+        write!(out, "\ndependencies:\n")?;
+        for dep in &self.dependencies {
+            write!(out, "    {}-{}{}\n",
+                   dep.name, dep.hash,
+                   if dep.explicitly_linked { "" } else { " (implicit)" })?;
+        }
+
+        write!(out, "\nitems:\n")?;
+        for item in &self.items {
+            write!(out, "    {} {}", item.kind, item.path)?;
+            if let Some(ref signature) = item.signature {
+                write!(out, ": {}", signature)?;
+            }
+            if let Some(ref stability) = item.stability {
+                write!(out, " [{}]", stability)?;
+            }
+            if let Some(ref deprecation) = item.deprecation {
+                write!(out, " [{}]", deprecation)?;
+            }
+            write!(out, "\n")?;
+            for bound in &item.bounds {
+                write!(out, "        where {}\n", bound)?;
+            }
+            for variant in &item.variants {
+                write!(out, "        variant {}\n", variant)?;
+            }
+            for field in &item.fields {
+                write!(out, "        field {}\n", field)?;
+            }
+            if item.has_private_fields {
+                write!(out, "        (private fields)\n")?;
+            }
+        }
+
+        write!(out, "\nmacros:\n")?;
+        for name in &self.macros {
+            write!(out, "    {}!\n", name)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn write_json(&self, out: &mut io::Write) -> io::Result<()> {
+        write!(out, "{}\n", json::as_pretty_json(self))
+    }
+}
+
+struct ItemCollector<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    cstore: &'a CStore,
+    visited: FnvHashSet<DefId>,
+    items: Vec<ItemInfo>,
+}
+
+impl<'a, 'tcx> ItemCollector<'a, 'tcx> {
+    fn collect_children(&mut self, prefix: &str, children: Vec<ChildItem>) {
+        for child in children {
+            if child.vis != ty::Visibility::Public {
+                continue;
+            }
+            let def = match child.def {
+                DefLike::DlDef(def) => def,
+                DefLike::DlImpl(..) | DefLike::DlField => continue,
+            };
+            // Tuple and unit structs are also exported in the value
+            // namespace, through their constructor; list them only once.
+            if let Def::Struct(def_id) = def {
+                if self.cstore.tuple_struct_definition_if_ctor(def_id).is_some() {
+                    continue;
+                }
+            }
+            let path = format!("{}::{}", prefix, child.name);
+            self.collect_item(path, def);
+        }
+    }
+
+    fn collect_item(&mut self, path: String, def: Def) {
+        let def_id = def.def_id();
+        let (kind, signature) = match def {
+            Def::Mod(_) => ("mod", None),
+            Def::Fn(_) => ("fn", Some(self.item_type(def_id))),
+            Def::Method(_) => ("method", Some(self.item_type(def_id))),
+            Def::Static(_, true) => ("static mut", Some(self.item_type(def_id))),
+            Def::Static(_, false) => ("static", Some(self.item_type(def_id))),
+            Def::Const(_) => ("const", Some(self.item_type(def_id))),
+            Def::Struct(_) => ("struct", Some(self.item_type(def_id))),
+            Def::Enum(_) => ("enum", Some(self.item_type(def_id))),
+            Def::TyAlias(_) => ("type", Some(self.item_type(def_id))),
+            Def::Variant(enum_def_id, _) => {
+                let adt_def = self.tcx.lookup_adt_def(enum_def_id);
+                ("variant", Some(variant_info(adt_def.variant_with_id(def_id))))
+            }
+            Def::Trait(_) => {
+                ("trait", Some(self.tcx.lookup_trait_def(def_id).trait_ref.to_string()))
+            }
+            _ => ("item", None),
+        };
+
+        let bounds = match def {
+            Def::Fn(_) | Def::Method(_) | Def::Struct(_) | Def::Enum(_) |
+            Def::TyAlias(_) | Def::Trait(_) => self.bounds(def_id),
+            _ => vec![],
+        };
+
+        let mut variants = vec![];
+        let mut fields = vec![];
+        let mut has_private_fields = false;
+        match def {
+            Def::Enum(_) => {
+                let adt_def = self.tcx.lookup_adt_def(def_id);
+                variants = adt_def.variants.iter().map(variant_info).collect();
+            }
+            Def::Struct(_) => {
+                let adt_def = self.tcx.lookup_adt_def(def_id);
+                for field in &adt_def.struct_variant().fields {
+                    if field.vis == ty::Visibility::Public {
+                        fields.push(format!("{}: {}", field.name, field.unsubst_ty()));
+                    } else {
+                        has_private_fields = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        self.items.push(ItemInfo {
+            path: path.clone(),
+            kind: kind.to_string(),
+            signature: signature,
+            bounds: bounds,
+            variants: variants,
+            fields: fields,
+            has_private_fields: has_private_fields,
+            stability: self.stability(def_id),
+            deprecation: self.deprecation(def_id),
+        });
+
+        match def {
+            // Modules can be reexported more than once, but we only list
+            // their contents the first time around.
+            Def::Mod(_) => {
+                if self.visited.insert(def_id) {
+                    let children = self.cstore.item_children(def_id);
+                    self.collect_children(&path, children);
+                }
+            }
+            Def::Trait(_) => self.collect_trait_items(&path, def_id),
+            _ => {}
+        }
+    }
+
+    fn collect_trait_items(&mut self, prefix: &str, trait_def_id: DefId) {
+        let provided: FnvHashSet<_> =
+            self.tcx.provided_trait_methods(trait_def_id)
+                    .iter()
+                    .map(|method| method.def_id)
+                    .collect();
+        for item in self.tcx.trait_items(trait_def_id).iter() {
+            let def_id = item.def_id();
+            let (kind, signature, bounds) = match *item {
+                ty::MethodTraitItem(ref method) => {
+                    let kind = if provided.contains(&method.def_id) {
+                        "provided method"
+                    } else {
+                        "required method"
+                    };
+                    (kind, Some(self.item_type(def_id)), predicate_strings(&method.predicates))
+                }
+                ty::ConstTraitItem(ref constant) => {
+                    let kind = if constant.has_value {
+                        "provided const"
+                    } else {
+                        "required const"
+                    };
+                    (kind, Some(constant.ty.to_string()), vec![])
+                }
+                ty::TypeTraitItem(ref assoc_ty) => {
+                    let kind = if assoc_ty.ty.is_some() {
+                        "provided type"
+                    } else {
+                        "required type"
+                    };
+                    (kind, assoc_ty.ty.map(|ty| ty.to_string()), vec![])
+                }
+            };
+            self.items.push(ItemInfo {
+                path: format!("{}::{}", prefix, item.name()),
+                kind: kind.to_string(),
+                signature: signature,
+                bounds: bounds,
+                variants: vec![],
+                fields: vec![],
+                has_private_fields: false,
+                stability: self.stability(def_id),
+                deprecation: self.deprecation(def_id),
+            });
+        }
+    }
+
+    fn bounds(&self, def_id: DefId) -> Vec<String> {
+        predicate_strings(&self.tcx.lookup_predicates(def_id))
+    }
+
+    fn stability(&self, def_id: DefId) -> Option<String> {
+        self.cstore.stability(def_id).map(|stab| {
+            match stab.level {
+                attr::Stable { ref since } => format!("stable since {}", since),
+                attr::Unstable { .. } => format!("unstable, feature `{}`", stab.feature),
+            }
+        })
+    }
+
+    fn deprecation(&self, def_id: DefId) -> Option<String> {
+        self.cstore.deprecation(def_id).map(|depr| {
+            match depr.since {
+                Some(ref since) => format!("deprecated since {}", since),
+                None => format!("deprecated"),
+            }
+        })
+    }
+
+    fn item_type(&self, def_id: DefId) -> String {
+        self.tcx.lookup_item_type(def_id).ty.to_string()
+    }
+}
+
+fn predicate_strings(predicates: &ty::GenericPredicates) -> Vec<String> {
+    predicates.predicates.iter().map(|predicate| predicate.to_string()).collect()
+}
+
+/// A variant with its fields, such as `A`, `B(u32)` or `C { x: u32 }`.
+fn variant_info(variant: &ty::VariantDefData) -> String {
+    let fields = variant.fields.iter().map(|field| match variant.kind {
+        ty::VariantKind::Struct => format!("{}: {}", field.name, field.unsubst_ty()),
+        _ => field.unsubst_ty().to_string(),
+    }).collect::<Vec<_>>().join(", ");
+    match variant.kind {
+        ty::VariantKind::Unit => variant.name.to_string(),
+        ty::VariantKind::Tuple => format!("{}({})", variant.name, fields),
+        ty::VariantKind::Struct => format!("{} {{ {} }}", variant.name, fields),
+    }
+}
@@ -305,7 +305,7 @@ impl<'a> CrateReader<'a> {
             extern_crate: Cell::new(None),
             index: decoder::load_index(metadata.as_slice()),
             xref_index: decoder::load_xrefs(metadata.as_slice()),
-            key_map: decoder::load_key_map(metadata.as_slice()),
+            def_key_index: decoder::load_def_key_index(metadata.as_slice()),
             data: metadata,
             cnum_map: RefCell::new(cnum_map),
             cnum: cnum,
@@ -643,6 +643,30 @@ impl<'a> CrateReader<'a> {
         }
     }
 
+    /// Looks for the plugin registrar of a crate imported with `#[macro_use]`
+    /// for its procedural macros. Unlike `find_plugin_registrar`, the crate
+    /// need not be a plugin, so `None` is returned rather than an error if it
+    /// is not one that can be loaded into the compiler.
+    pub fn find_proc_macro_registrar(&mut self, span: Span, name: &str)
+                                     -> Option<(PathBuf, Svh, DefIndex)> {
+        let ekrate = self.read_extension_crate(span, &CrateInfo {
+             name: name.to_string(),
+             ident: name.to_string(),
+             id: ast::DUMMY_NODE_ID,
+             should_link: false,
+        });
+        if ekrate.target_only {
+            return None;
+        }
+
+        let svh = decoder::get_crate_hash(ekrate.metadata.as_slice());
+        let registrar = decoder::get_plugin_registrar_fn(ekrate.metadata.as_slice());
+        match (ekrate.dylib, registrar) {
+            (Some(dylib), Some(reg)) => Some((dylib, svh, reg)),
+            _ => None,
+        }
+    }
+
     fn register_statically_included_foreign_items(&mut self) {
         let libs = self.cstore.get_used_libraries();
         for (lib, list) in self.foreign_item_map.iter() {
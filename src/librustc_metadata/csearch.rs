@@ -201,6 +201,13 @@ impl<'tcx> CrateStore<'tcx> for cstore::CStore {
         decoder::get_impl_polarity(&cdata, def.index)
     }
 
+    fn impl_defaultness(&self, def: DefId) -> hir::Defaultness
+    {
+        self.dep_graph.read(DepNode::MetaData(def));
+        let cdata = self.get_crate_data(def.krate);
+        decoder::get_impl_defaultness(&cdata, def.index)
+    }
+
     fn impl_trait_ref<'a>(&self, tcx: TyCtxt<'a, 'tcx, 'tcx>, def: DefId)
                           -> Option<ty::TraitRef<'tcx>>
     {
@@ -397,6 +404,17 @@ impl<'tcx> CrateStore<'tcx> for cstore::CStore {
         })
     }
 
+    fn exported_macro_names(&self, cnum: ast::CrateNum) -> Vec<ast::Name>
+    {
+        let cdata = self.get_crate_data(cnum);
+        let mut names = vec![];
+        decoder::each_exported_macro(cdata.data(), |name, _, _, _| {
+            names.push(name);
+            true
+        });
+        names
+    }
+
     fn native_libraries(&self, cnum: ast::CrateNum) -> Vec<(NativeLibraryKind, String)>
     {
         let cdata = self.get_crate_data(cnum);
@@ -414,7 +432,7 @@ impl<'tcx> CrateStore<'tcx> for cstore::CStore {
                              def: DefKey)
                              -> Option<DefIndex> {
         let cdata = self.get_crate_data(cnum);
-        cdata.key_map.get(&def).cloned()
+        decoder::def_index_for_def_key(&cdata, &def)
     }
 
     /// Returns the `DefKey` for a given `DefId`. This indicates the
@@ -22,8 +22,7 @@ use index;
 use loader;
 
 use rustc::dep_graph::DepGraph;
-use rustc::hir::def_id::{DefIndex, DefId};
-use rustc::hir::map::DefKey;
+use rustc::hir::def_id::DefId;
 use rustc::hir::svh::Svh;
 use rustc::middle::cstore::{ExternCrate};
 use rustc::session::config::PanicStrategy;
@@ -33,7 +32,6 @@ use rustc::util::nodemap::{FnvHashMap, NodeMap, NodeSet, DefIdMap};
 use std::cell::{RefCell, Ref, Cell};
 use std::rc::Rc;
 use std::path::PathBuf;
-use flate::Bytes;
 use syntax::ast;
 use syntax::attr;
 use syntax::codemap;
@@ -50,8 +48,8 @@ pub use middle::cstore::{CrateSource, LinkMeta};
 pub type CrateNumMap = IndexVec<ast::CrateNum, ast::CrateNum>;
 
 pub enum MetadataBlob {
-    MetadataVec(Bytes),
     MetadataArchive(loader::ArchiveMetadata),
+    MetadataObject(loader::ObjectMetadata),
 }
 
 /// Holds information about a syntax_pos::FileMap imported from another crate.
@@ -82,12 +80,12 @@ pub struct CrateMetadata {
     pub index: index::Index,
     pub xref_index: index::DenseIndex,
 
-    /// For each public item in this crate, we encode a key.  When the
-    /// crate is loaded, we read all the keys and put them in this
-    /// hashmap, which gives the reverse mapping.  This allows us to
-    /// quickly retrace a `DefPath`, which is needed for incremental
-    /// compilation support.
-    pub key_map: FnvHashMap<DefKey, DefIndex>,
+    /// For each public item in this crate, we encode a key. This index
+    /// gives the reverse mapping without reading all the keys when the
+    /// crate is loaded, by listing the items under each parent key.
+    /// This allows us to quickly retrace a `DefPath`, which is needed
+    /// for incremental compilation support.
+    pub def_key_index: index::ChildrenIndex,
 
     /// Flag if this crate is required by an rlib version of this crate, or in
     /// other words whether it was explicitly linked to. An example of a crate
@@ -327,8 +325,8 @@ impl CrateMetadata {
 impl MetadataBlob {
     pub fn as_slice_raw<'a>(&'a self) -> &'a [u8] {
         match *self {
-            MetadataVec(ref vec) => &vec[..],
             MetadataArchive(ref ar) => ar.as_slice(),
+            MetadataObject(ref of) => of.as_slice(),
         }
     }
 
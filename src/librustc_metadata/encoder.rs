@@ -688,6 +688,9 @@ fn encode_info_for_associated_type<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
 
     encode_def_id_and_key(ecx, rbml_w, associated_type.def_id);
     encode_name(rbml_w, associated_type.name);
+    encode_generics(rbml_w, ecx, index,
+                    &associated_type.generics, &associated_type.predicates,
+                    tag_method_ty_generics);
     encode_visibility(rbml_w, associated_type.vis);
     encode_family(rbml_w, 'y');
     encode_parent_item(rbml_w, ecx.tcx.map.local_def_id(parent_id));
@@ -1012,6 +1015,36 @@ fn encode_info_for_item<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
             encode_info_for_struct_ctor(ecx, rbml_w, item.name, struct_def, index, item.id);
         }
       }
+      hir::ItemUnion(..) => {
+        let _task = index.record(def_id, rbml_w);
+
+        let def = ecx.tcx.lookup_adt_def(def_id);
+        let variant = def.struct_variant();
+
+        rbml_w.start_tag(tag_items_data_item);
+        encode_def_id_and_key(ecx, rbml_w, def_id);
+        encode_family(rbml_w, 'U');
+        encode_bounds_and_type_for_item(rbml_w, ecx, index, item.id);
+
+        encode_item_variances(rbml_w, ecx, item.id);
+        encode_name(rbml_w, item.name);
+        encode_attributes(rbml_w, &item.attrs);
+        encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
+        encode_visibility(rbml_w, vis);
+        encode_repr_attrs(rbml_w, ecx, &item.attrs);
+
+        // Unions have no constructor, so there is nothing to inline;
+        // just record the fields and inherent impls.
+        encode_struct_fields(rbml_w, variant);
+        encode_inherent_implementations(ecx, rbml_w, def_id);
+
+        rbml_w.end_tag();
+
+        for field in &variant.fields {
+            encode_field(ecx, rbml_w, field, index);
+        }
+      }
       hir::ItemDefaultImpl(unsafety, _) => {
           let _task = index.record(def_id, rbml_w);
           rbml_w.start_tag(tag_items_data_item);
@@ -1024,7 +1057,7 @@ fn encode_info_for_item<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
           encode_trait_ref(rbml_w, ecx, trait_ref, tag_item_trait_ref);
           rbml_w.end_tag();
       }
-      hir::ItemImpl(unsafety, polarity, _, _, _, ref ast_items) => {
+      hir::ItemImpl(unsafety, polarity, defaultness, _, _, _, ref ast_items) => {
         let _task = index.record(def_id, rbml_w);
 
         // We need to encode information about the default methods we
@@ -1040,6 +1073,7 @@ fn encode_info_for_item<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
         encode_attributes(rbml_w, &item.attrs);
         encode_unsafety(rbml_w, unsafety);
         encode_polarity(rbml_w, polarity);
+        encode_defaultness(rbml_w, defaultness);
 
         match tcx.custom_coerce_unsized_kinds.borrow().get(&ecx.tcx.map.local_def_id(item.id)) {
             Some(&kind) => {
@@ -1233,6 +1267,9 @@ fn encode_info_for_item<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
                 ty::TypeTraitItem(associated_type) => {
                     encode_name(rbml_w, associated_type.name);
                     encode_def_id_and_key(ecx, rbml_w, associated_type.def_id);
+                    encode_generics(rbml_w, ecx, index,
+                                    &associated_type.generics, &associated_type.predicates,
+                                    tag_method_ty_generics);
                     encode_item_sort(rbml_w, 't');
                     encode_family(rbml_w, 'y');
 
@@ -1370,6 +1407,20 @@ fn my_visit_expr(expr: &hir::Expr,
     }
 }
 
+fn my_visit_ty<'a, 'tcx>(ty: &hir::Ty,
+                        rbml_w: &mut Encoder,
+                        ecx: &EncodeContext<'a, 'tcx>,
+                        index: &mut CrateIndex<'a, 'tcx>) {
+    if let hir::TyImplTrait(_) = ty.node {
+        let def_id = ecx.tcx.map.local_def_id(ty.id);
+        let _task = index.record(def_id, rbml_w);
+        rbml_w.start_tag(tag_items_data_item);
+        encode_def_id_and_key(ecx, rbml_w, def_id);
+        encode_bounds_and_type_for_item(rbml_w, ecx, index, ty.id);
+        rbml_w.end_tag();
+    }
+}
+
 struct EncodeVisitor<'a, 'b:'a, 'c:'a, 'tcx:'c> {
     rbml_w_for_visit_item: &'a mut Encoder<'b>,
     ecx: &'a EncodeContext<'c, 'tcx>,
@@ -1381,6 +1432,10 @@ impl<'a, 'b, 'c, 'tcx> Visitor<'tcx> for EncodeVisitor<'a, 'b, 'c, 'tcx> {
         intravisit::walk_expr(self, ex);
         my_visit_expr(ex, self.rbml_w_for_visit_item, self.ecx, self.index);
     }
+    fn visit_ty(&mut self, ty: &'tcx hir::Ty) {
+        intravisit::walk_ty(self, ty);
+        my_visit_ty(ty, self.rbml_w_for_visit_item, self.ecx, self.index);
+    }
     fn visit_item(&mut self, i: &'tcx hir::Item) {
         intravisit::walk_item(self, i);
         encode_info_for_item(self.ecx, self.rbml_w_for_visit_item, i, self.index);
@@ -1430,6 +1485,14 @@ fn encode_item_index(rbml_w: &mut Encoder, index: IndexData) {
     rbml_w.end_tag();
 }
 
+fn encode_def_key_index(ecx: &EncodeContext, rbml_w: &mut Encoder, index: &IndexData) {
+    rbml_w.start_tag(tag_def_key_index);
+    index.write_children_index(rbml_w.writer, |item| {
+        ecx.tcx.map.def_key(DefId::local(item)).parent
+    });
+    rbml_w.end_tag();
+}
+
 fn encode_meta_item(rbml_w: &mut Encoder, mi: &ast::MetaItem) {
     match mi.node {
       ast::MetaItemKind::Word(ref name) => {
@@ -1949,6 +2012,7 @@ fn encode_metadata_inner(rbml_w: &mut Encoder,
     rbml_w.end_tag();
 
     i = rbml_w.writer.seek(SeekFrom::Current(0)).unwrap();
+    encode_def_key_index(&ecx, rbml_w, &index.items);
     encode_item_index(rbml_w, index.items);
     stats.index_bytes = rbml_w.writer.seek(SeekFrom::Current(0)).unwrap() - i;
 
@@ -96,6 +96,75 @@ impl IndexData {
             write_be_u32(buf, position);
         }
     }
+
+    /// Writes the index read by `ChildrenIndex`, listing for each
+    /// DefIndex the recorded items whose parent (as given by `parent`)
+    /// it is.
+    pub fn write_children_index<F>(&self, buf: &mut Cursor<Vec<u8>>, mut parent: F)
+        where F: FnMut(DefIndex) -> Option<DefIndex>
+    {
+        let mut children = vec![Vec::new(); self.positions.len()];
+        for (item, &position) in self.positions.iter().enumerate() {
+            if position == u32::MAX {
+                continue;
+            }
+            let item = DefIndex::new(item);
+            if let Some(parent) = parent(item) {
+                children[parent.as_usize()].push(item.as_u32());
+            }
+        }
+
+        assert!(self.positions.len() < u32::MAX as usize);
+        write_be_u32(buf, self.positions.len() as u32);
+        let mut start = 0;
+        for item_children in &children {
+            write_be_u32(buf, start);
+            start += item_children.len() as u32;
+        }
+        write_be_u32(buf, start);
+        for item_children in children {
+            for child in item_children {
+                write_be_u32(buf, child);
+            }
+        }
+    }
+}
+
+/// The children of each DefIndex in the metadata, that is, the items
+/// whose `DefKey` has it as its parent. Items can be found from their
+/// `DefKey` by decoding only the keys of the children of its parent,
+/// rather than the keys of every item. The data starts with the number
+/// of DefIndices `n`, followed by `n + 1` offsets into the list of
+/// children that comes last, where the children of DefIndex `i` are
+/// the entries from offset `i` up to offset `i + 1`.
+pub struct ChildrenIndex {
+    data_start: usize,
+    data_end: usize,
+}
+
+impl ChildrenIndex {
+    pub fn from_rbml(index: rbml::Doc) -> ChildrenIndex {
+        ChildrenIndex { data_start: index.start, data_end: index.end }
+    }
+
+    /// Given the metadata, extract out the children of a particular
+    /// DefIndex.
+    pub fn lookup_children(&self, bytes: &[u8], parent: DefIndex) -> Vec<DefIndex> {
+        let words = bytes_to_words(&bytes[self.data_start..self.data_end]);
+        let len = u32::from_be(words[0]) as usize;
+        let index = parent.as_usize();
+        if index >= len {
+            return vec![];
+        }
+
+        let offsets = &words[1..len + 2];
+        let children = &words[len + 2..];
+        let start = u32::from_be(offsets[index]) as usize;
+        let end = u32::from_be(offsets[index + 1]) as usize;
+        children[start..end].iter().map(|&child| {
+            DefIndex::from_u32(u32::from_be(child))
+        }).collect()
+    }
 }
 
 /// A dense index with integer keys. Different API from IndexData (should
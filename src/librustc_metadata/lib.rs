@@ -29,7 +29,6 @@
 #[macro_use] extern crate syntax;
 #[macro_use] #[no_link] extern crate rustc_bitflags;
 extern crate syntax_pos;
-extern crate flate;
 extern crate rbml;
 extern crate serialize as rustc_serialize; // used by deriving
 extern crate rustc_errors as errors;
@@ -50,6 +49,7 @@ pub mod diagnostics;
 
 pub mod astencode;
 pub mod common;
+pub mod crate_info;
 pub mod def_key;
 pub mod tyencode;
 pub mod tydecode;
@@ -61,4 +61,5 @@ pub mod cstore;
 pub mod index;
 pub mod loader;
 pub mod macro_import;
+pub mod semver;
 pub mod tls_context;
@@ -212,7 +212,7 @@
 //! no means all of the necessary details. Take a look at the rest of
 //! metadata::loader or metadata::creader for all the juicy details!
 
-use cstore::{MetadataBlob, MetadataVec, MetadataArchive};
+use cstore::{MetadataBlob, MetadataArchive, MetadataObject};
 use common::{metadata_encoding_version, rustc_version};
 use decoder;
 
@@ -239,8 +239,6 @@ use std::ptr;
 use std::slice;
 use std::time::Instant;
 
-use flate;
-
 pub struct CrateMismatch {
     path: PathBuf,
     got: String,
@@ -276,6 +274,12 @@ pub struct ArchiveMetadata {
     data: *const [u8],
 }
 
+pub struct ObjectMetadata {
+    _object: ObjectFile,
+    // points into the metadata section of self._object
+    data: *const [u8],
+}
+
 pub struct CratePaths {
     pub ident: String,
     pub dylib: Option<PathBuf>,
@@ -759,6 +763,10 @@ impl ArchiveMetadata {
     pub fn as_slice<'a>(&'a self) -> &'a [u8] { unsafe { &*self.data } }
 }
 
+impl ObjectMetadata {
+    pub fn as_slice<'a>(&'a self) -> &'a [u8] { unsafe { &*self.data } }
+}
+
 fn verify_decompressed_encoding_version(blob: &MetadataBlob, filename: &Path)
                                         -> Result<(), String>
 {
@@ -847,18 +855,19 @@ fn get_metadata_section_imp(target: &Target, flavor: CrateFlavor, filename: &Pat
                                         filename.display())));
                 }
 
+                // The metadata is stored uncompressed, so we can use it
+                // right where it is. The object file is backed by a memory
+                // mapping of the library, so this does not even read the
+                // parts of the metadata that are never looked at.
                 let cvbuf1 = cvbuf.offset(vlen as isize);
-                debug!("inflating {} bytes of compressed metadata",
-                       csz - vlen);
-                let bytes = slice::from_raw_parts(cvbuf1, csz - vlen);
-                match flate::inflate_bytes(bytes) {
-                    Ok(inflated) => {
-                        let blob = MetadataVec(inflated);
-                        try!(verify_decompressed_encoding_version(&blob, filename));
-                        return Ok(blob);
-                    }
-                    Err(_) => {}
-                }
+                debug!("found {} bytes of metadata", csz - vlen);
+                let data = slice::from_raw_parts(cvbuf1, csz - vlen) as *const [u8];
+                let blob = MetadataObject(ObjectMetadata {
+                    _object: of,
+                    data: data,
+                });
+                try!(verify_decompressed_encoding_version(&blob, filename));
+                return Ok(blob);
             }
             llvm::LLVMMoveToNextSection(si.llsi);
         }
@@ -897,11 +906,24 @@ pub fn read_meta_section_name(target: &Target) -> &'static str {
     }
 }
 
+fn flavor_of_file(path: &Path) -> CrateFlavor {
+    let filename = path.file_name().unwrap().to_str().unwrap();
+    if filename.ends_with(".rlib") { CrateFlavor::Rlib } else { CrateFlavor::Dylib }
+}
+
+/// Reads the name of the crate stored in the library at `path`.
+pub fn crate_name_from_file(target: &Target, path: &Path) -> Result<String, String> {
+    let blob = try!(get_metadata_section(target, flavor_of_file(path), path));
+    match decoder::maybe_get_crate_name(blob.as_slice()) {
+        Some(name) => Ok(name.to_string()),
+        None => Err(format!("no crate name found in '{}'", path.display())),
+    }
+}
+
 // A diagnostic function for dumping crate metadata to an output stream
 pub fn list_file_metadata(target: &Target, path: &Path,
                           out: &mut io::Write) -> io::Result<()> {
-    let filename = path.file_name().unwrap().to_str().unwrap();
-    let flavor = if filename.ends_with(".rlib") { CrateFlavor::Rlib } else { CrateFlavor::Dylib };
+    let flavor = flavor_of_file(path);
     match get_metadata_section(target, flavor, path) {
         Ok(bytes) => decoder::list_crate_metadata(bytes.as_slice(), out),
         Err(msg) => {
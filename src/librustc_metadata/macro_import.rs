@@ -26,6 +26,9 @@ use syntax_pos::Span;
 pub struct MacroLoader<'a> {
     sess: &'a Session,
     reader: CrateReader<'a>,
+    /// The macros exported by the crates read so far, by the span of their
+    /// `extern crate` item.
+    exported_macros: HashMap<Span, Vec<ast::MacroDef>>,
 }
 
 impl<'a> MacroLoader<'a> {
@@ -33,8 +36,20 @@ impl<'a> MacroLoader<'a> {
         MacroLoader {
             sess: sess,
             reader: CrateReader::new(sess, cstore, crate_name),
+            exported_macros: HashMap::new(),
         }
     }
+
+    fn read_exported_macros(&mut self, extern_crate: &ast::Item) -> Vec<ast::MacroDef> {
+        let reader = &mut self.reader;
+        self.exported_macros.entry(extern_crate.span).or_insert_with(|| {
+            reader.read_exported_macros(extern_crate).into_iter().map(|mut def| {
+                def.allow_internal_unstable = attr::contains_name(&def.attrs,
+                                                                  "allow_internal_unstable");
+                def
+            }).collect()
+        }).clone()
+    }
 }
 
 pub fn call_bad_macro_reexport(a: &Session, b: Span) {
@@ -94,6 +109,10 @@ impl<'a> ext::base::MacroLoader for MacroLoader<'a> {
 
         self.load_macros(extern_crate, allows_macros, import, reexport)
     }
+
+    fn load_exported_macros(&mut self, extern_crate: &ast::Item) -> Vec<ast::MacroDef> {
+        self.read_exported_macros(extern_crate)
+    }
 }
 
 impl<'a> MacroLoader<'a> {
@@ -118,7 +137,7 @@ impl<'a> MacroLoader<'a> {
         let mut macros = Vec::new();
         let mut seen = HashSet::new();
 
-        for mut def in self.reader.read_exported_macros(vi) {
+        for mut def in self.read_exported_macros(vi) {
             let name = def.ident.name.as_str();
 
             def.use_locally = match import.as_ref() {
@@ -126,16 +145,17 @@ impl<'a> MacroLoader<'a> {
                 Some(sel) => sel.contains_key(&name),
             };
             def.export = reexport.contains_key(&name);
-            def.allow_internal_unstable = attr::contains_name(&def.attrs,
-                                                              "allow_internal_unstable");
             debug!("load_macros: loaded: {:?}", def);
             macros.push(def);
             seen.insert(name);
         }
 
         if let Some(sel) = import.as_ref() {
+            // Procedural macros were imported along with the plugins.
+            let proc_macros = self.sess.imported_proc_macros.borrow();
+            let proc_macros = proc_macros.get(&vi.ident.name).map_or(&[][..], |v| &v[..]);
             for (name, span) in sel {
-                if !seen.contains(&name) {
+                if !seen.contains(&name) && !proc_macros.iter().any(|m| m.as_str() == *name) {
                     span_err!(self.sess, *span, E0469,
                               "imported macro not found");
                 }
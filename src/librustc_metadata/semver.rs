@@ -0,0 +1,331 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Compares the public interfaces of two versions of a crate (as
+//! summarized by `crate_info`) and classifies the changes according
+//! to semantic versioning, for `-Z semver-check`.
+//!
+//! The classification is conservative: anything that could break a
+//! downstream crate is considered a major change, even if most users
+//! would not notice. For example, adding a variant to a public enum
+//! breaks exhaustive matches, and adding a public field to a struct
+//! whose fields were all public breaks struct literals.
+
+use crate_info::{CrateInfo, ItemInfo};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io;
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Level {
+    /// Nothing in the public interface changed.
+    Patch,
+    /// Things were added, but existing users are not affected.
+    Minor,
+    /// Existing users may be broken.
+    Major,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Level::Patch => "patch",
+            Level::Minor => "minor",
+            Level::Major => "major",
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Change {
+    pub level: Level,
+    pub path: String,
+    pub description: String,
+}
+
+pub struct Report {
+    pub changes: Vec<Change>,
+}
+
+impl Report {
+    /// The level of the release that the changes require.
+    pub fn level(&self) -> Level {
+        self.changes.iter().map(|change| change.level).max().unwrap_or(Level::Patch)
+    }
+
+    pub fn write_text(&self, out: &mut io::Write) -> io::Result<()> {
+        for change in &self.changes {
+            write!(out, "{}: {}: {}\n", change.level, change.path, change.description)?;
+        }
+        write!(out, "required version bump: {}\n", self.level())
+    }
+}
+
+pub fn compare(old: &CrateInfo, new: &CrateInfo) -> Report {
+    let mut changes = vec![];
+
+    let old_items = items_by_path(old);
+    let new_items = items_by_path(new);
+
+    for (path, old_item) in &old_items {
+        match new_items.get(path) {
+            Some(new_item) => compare_items(path, old_item, new_item, &mut changes),
+            None => changes.push(change(Level::Major, path, "removed")),
+        }
+    }
+
+    for (path, new_item) in &new_items {
+        if old_items.contains_key(path) {
+            continue;
+        }
+        // Implementors of a trait have to provide any item that is
+        // added to it without a default.
+        let parent = path.rsplitn(2, "::").nth(1).unwrap_or("");
+        let in_old_trait = old_items.get(parent).map_or(false, |item| item.kind == "trait");
+        let level = if in_old_trait && new_item.kind.starts_with("required") {
+            Level::Major
+        } else {
+            Level::Minor
+        };
+        changes.push(change(level, path, &format!("added {}", new_item.kind)));
+    }
+
+    let old_macros: BTreeSet<_> = old.macros.iter().collect();
+    let new_macros: BTreeSet<_> = new.macros.iter().collect();
+    for name in old_macros.difference(&new_macros) {
+        changes.push(change(Level::Major, &format!("{}!", name), "removed macro"));
+    }
+    for name in new_macros.difference(&old_macros) {
+        changes.push(change(Level::Minor, &format!("{}!", name), "added macro"));
+    }
+
+    Report { changes: changes }
+}
+
+fn items_by_path(info: &CrateInfo) -> BTreeMap<&str, &ItemInfo> {
+    info.items.iter().map(|item| (&item.path[..], item)).collect()
+}
+
+fn change(level: Level, path: &str, description: &str) -> Change {
+    Change {
+        level: level,
+        path: path.to_string(),
+        description: description.to_string(),
+    }
+}
+
+fn compare_items(path: &str, old: &ItemInfo, new: &ItemInfo, changes: &mut Vec<Change>) {
+    if old.kind != new.kind {
+        // Giving a required trait item a default is fine, the opposite
+        // is not.
+        let level = if old.kind.starts_with("required") && new.kind.starts_with("provided") {
+            Level::Minor
+        } else {
+            Level::Major
+        };
+        changes.push(change(level, path, &format!("changed from {} to {}", old.kind, new.kind)));
+        if level == Level::Major {
+            return;
+        }
+    }
+
+    if old.signature != new.signature {
+        let description = format!("changed signature from `{}` to `{}`",
+                                  old.signature.as_ref().map_or("", |s| &s[..]),
+                                  new.signature.as_ref().map_or("", |s| &s[..]));
+        changes.push(change(Level::Major, path, &description));
+    }
+
+    // Additional bounds may not be satisfied by existing users, but
+    // dropping bounds only accepts more.
+    for bound in added(&old.bounds, &new.bounds) {
+        changes.push(change(Level::Major, path, &format!("added bound `{}`", bound)));
+    }
+    for bound in added(&new.bounds, &old.bounds) {
+        changes.push(change(Level::Minor, path, &format!("removed bound `{}`", bound)));
+    }
+
+    // The variants are listed with their fields, so they are matched up
+    // by name. Any change to the fields of a variant breaks the users
+    // that construct or match it.
+    for variant in &new.variants {
+        match old.variants.iter().find(|v| variant_name(v) == variant_name(variant)) {
+            Some(old_variant) if old_variant != variant => {
+                let description = format!("changed variant `{}` to `{}`", old_variant, variant);
+                changes.push(change(Level::Major, path, &description));
+            }
+            Some(_) => {}
+            None => {
+                changes.push(change(Level::Major, path, &format!("added variant `{}`", variant)));
+            }
+        }
+    }
+    for variant in &old.variants {
+        if !new.variants.iter().any(|v| variant_name(v) == variant_name(variant)) {
+            changes.push(change(Level::Major, path, &format!("removed variant `{}`", variant)));
+        }
+    }
+
+    // The fields are listed with their types, so a field whose type
+    // changed is reported as removed and added.
+    for field in added(&new.fields, &old.fields) {
+        changes.push(change(Level::Major, path, &format!("removed field `{}`", field)));
+    }
+    for field in added(&old.fields, &new.fields) {
+        // As long as the struct has private fields, it cannot be
+        // constructed or exhaustively destructured outside its crate.
+        let level = if old.has_private_fields { Level::Minor } else { Level::Major };
+        changes.push(change(level, path, &format!("added field `{}`", field)));
+    }
+    if !old.has_private_fields && new.has_private_fields {
+        changes.push(change(Level::Major, path, "added private fields"));
+    }
+
+    if old.deprecation.is_none() && new.deprecation.is_some() {
+        changes.push(change(Level::Minor, path, "deprecated"));
+    }
+}
+
+/// The name of a variant as listed by `crate_info`, without its fields.
+fn variant_name(variant: &str) -> &str {
+    variant.split(|c| c == '(' || c == ' ').next().unwrap()
+}
+
+/// The elements of `new` that are not in `old`.
+fn added<'a>(old: &'a [String], new: &'a [String]) -> Vec<&'a String> {
+    new.iter().filter(|s| !old.contains(s)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate_info::{CrateInfo, ItemInfo};
+
+    fn item(path: &str, kind: &str, signature: &str) -> ItemInfo {
+        ItemInfo {
+            path: path.to_string(),
+            kind: kind.to_string(),
+            signature: Some(signature.to_string()),
+            bounds: vec![],
+            variants: vec![],
+            fields: vec![],
+            has_private_fields: false,
+            stability: None,
+            deprecation: None,
+        }
+    }
+
+    fn krate(items: Vec<ItemInfo>) -> CrateInfo {
+        CrateInfo {
+            name: "foo".to_string(),
+            hash: String::new(),
+            disambiguator: String::new(),
+            dependencies: vec![],
+            items: items,
+            macros: vec![],
+        }
+    }
+
+    #[test]
+    fn test_unchanged_is_patch() {
+        let old = krate(vec![item("foo::f", "fn", "fn()")]);
+        let new = krate(vec![item("foo::f", "fn", "fn()")]);
+        assert_eq!(compare(&old, &new).level(), Level::Patch);
+    }
+
+    #[test]
+    fn test_added_item_is_minor() {
+        let old = krate(vec![item("foo::f", "fn", "fn()")]);
+        let new = krate(vec![item("foo::f", "fn", "fn()"), item("foo::g", "fn", "fn()")]);
+        assert_eq!(compare(&old, &new).level(), Level::Minor);
+    }
+
+    #[test]
+    fn test_removed_or_changed_item_is_major() {
+        let old = krate(vec![item("foo::f", "fn", "fn()")]);
+        assert_eq!(compare(&old, &krate(vec![])).level(), Level::Major);
+        let new = krate(vec![item("foo::f", "fn", "fn(u32)")]);
+        assert_eq!(compare(&old, &new).level(), Level::Major);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut old_f = item("foo::f", "fn", "fn(T)");
+        old_f.bounds = vec!["T : std::marker::Sized".to_string()];
+        let mut new_f = item("foo::f", "fn", "fn(T)");
+        new_f.bounds = vec!["T : std::marker::Sized".to_string(),
+                            "T : std::clone::Clone".to_string()];
+        let old = krate(vec![old_f]);
+        let new = krate(vec![new_f]);
+        assert_eq!(compare(&old, &new).level(), Level::Major);
+        assert_eq!(compare(&new, &old).level(), Level::Minor);
+    }
+
+    #[test]
+    fn test_enum_variants() {
+        let mut old_e = item("foo::E", "enum", "foo::E");
+        old_e.variants = vec!["A".to_string()];
+        let mut new_e = item("foo::E", "enum", "foo::E");
+        new_e.variants = vec!["A".to_string(), "B".to_string()];
+        let report = compare(&krate(vec![old_e]), &krate(vec![new_e]));
+        assert_eq!(report.level(), Level::Major);
+        assert_eq!(report.changes[0].description, "added variant `B`");
+    }
+
+    #[test]
+    fn test_enum_variant_fields() {
+        let mut old_e = item("foo::E", "enum", "foo::E");
+        old_e.variants = vec!["A(u32)".to_string(), "B { x: u32 }".to_string()];
+        let mut new_e = item("foo::E", "enum", "foo::E");
+        new_e.variants = vec!["A(std::string::String)".to_string(), "B { x: u32 }".to_string()];
+        let report = compare(&krate(vec![old_e]), &krate(vec![new_e]));
+        assert_eq!(report.level(), Level::Major);
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(report.changes[0].description,
+                   "changed variant `A(u32)` to `A(std::string::String)`");
+
+        let mut old_e = item("foo::E", "enum", "foo::E");
+        old_e.variants = vec!["B { x: u32 }".to_string()];
+        let mut new_e = item("foo::E", "enum", "foo::E");
+        new_e.variants = vec!["B { x: u32, y: u32 }".to_string()];
+        let old = krate(vec![old_e, item("foo::B", "variant", "B { x: u32 }")]);
+        let new = krate(vec![new_e, item("foo::B", "variant", "B { x: u32, y: u32 }")]);
+        assert_eq!(compare(&old, &new).level(), Level::Major);
+    }
+
+    #[test]
+    fn test_struct_fields() {
+        let mut old_s = item("foo::S", "struct", "foo::S");
+        old_s.has_private_fields = true;
+        let mut new_s = item("foo::S", "struct", "foo::S");
+        new_s.has_private_fields = true;
+        new_s.fields = vec!["x: u32".to_string()];
+        assert_eq!(compare(&krate(vec![old_s]), &krate(vec![new_s])).level(), Level::Minor);
+
+        let old_s = item("foo::S", "struct", "foo::S");
+        let mut new_s = item("foo::S", "struct", "foo::S");
+        new_s.fields = vec!["x: u32".to_string()];
+        assert_eq!(compare(&krate(vec![old_s]), &krate(vec![new_s])).level(), Level::Major);
+    }
+
+    #[test]
+    fn test_trait_items() {
+        let old = krate(vec![item("foo::T", "trait", "<Self as foo::T>")]);
+        let provided = krate(vec![item("foo::T", "trait", "<Self as foo::T>"),
+                                  item("foo::T::f", "provided method", "fn()")]);
+        let required = krate(vec![item("foo::T", "trait", "<Self as foo::T>"),
+                                  item("foo::T::f", "required method", "fn()")]);
+        assert_eq!(compare(&old, &provided).level(), Level::Minor);
+        assert_eq!(compare(&old, &required).level(), Level::Major);
+        assert_eq!(compare(&required, &provided).level(), Level::Minor);
+        assert_eq!(compare(&provided, &required).level(), Level::Major);
+    }
+}
@@ -329,6 +329,7 @@ impl<'a,'tcx> TyDecoder<'a,'tcx> {
                 }
             }
             'c' => return tcx.types.char,
+            '!' => return tcx.types.never,
             't' => {
                 assert_eq!(self.next(), '[');
                 let did = self.parse_def();
@@ -353,6 +354,11 @@ impl<'a,'tcx> TyDecoder<'a,'tcx> {
                 let name = token::intern(&self.parse_str(']'));
                 return tcx.mk_param(space, index, name);
             }
+            'C' => {
+                let n = self.parse_uint();
+                assert_eq!(self.next(), '|');
+                return tcx.mk_const(n);
+            }
             '~' => return tcx.mk_box(self.parse_ty()),
             '*' => return tcx.mk_ptr(self.parse_mt()),
             '&' => {
@@ -367,6 +373,11 @@ impl<'a,'tcx> TyDecoder<'a,'tcx> {
                     None => tcx.mk_slice(t)
                 };
             }
+            'Y' => {
+                let t = self.parse_ty();
+                let len = self.parse_ty();
+                return tcx.mk_generic_array(t, len);
+            }
             'v' => {
                 return tcx.mk_str();
             }
@@ -442,8 +453,17 @@ impl<'a,'tcx> TyDecoder<'a,'tcx> {
             'P' => {
                 assert_eq!(self.next(), '[');
                 let trait_ref = self.parse_trait_ref();
-                let name = token::intern(&self.parse_str(']'));
-                return tcx.mk_projection(trait_ref, name);
+                let name = token::intern(&self.parse_str('|'));
+                let item_substs = self.parse_substs();
+                assert_eq!(self.next(), ']');
+                return tcx.mk_projection(trait_ref, name, tcx.mk_substs(item_substs));
+            }
+            'A' => {
+                assert_eq!(self.next(), '[');
+                let def_id = self.parse_def();
+                let substs = self.parse_substs();
+                assert_eq!(self.next(), ']');
+                return self.tcx.mk_anon(def_id, self.tcx.mk_substs(substs));
             }
             'e' => {
                 return tcx.types.err;
@@ -581,6 +601,7 @@ impl<'a,'tcx> TyDecoder<'a,'tcx> {
             projection_ty: ty::ProjectionTy {
                 trait_ref: self.parse_trait_ref(),
                 item_name: token::intern(&self.parse_str('|')),
+                item_substs: self.tcx.mk_substs(self.parse_substs()),
             },
             ty: self.parse_ty(),
         }
@@ -596,6 +617,11 @@ impl<'a,'tcx> TyDecoder<'a,'tcx> {
         let default_def_id = self.parse_def();
         let default = self.parse_opt(|this| this.parse_ty());
         let object_lifetime_default = self.parse_object_lifetime_default();
+        let is_const = match self.next() {
+            'y' => true,
+            'n' => false,
+            c => bug!("parse_type_param_def: bad const flag {}", c),
+        };
 
         ty::TypeParameterDef {
             name: name,
@@ -605,6 +631,7 @@ impl<'a,'tcx> TyDecoder<'a,'tcx> {
             default_def_id: default_def_id,
             default: default,
             object_lifetime_default: object_lifetime_default,
+            is_const: is_const,
         }
     }
 
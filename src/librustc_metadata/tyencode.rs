@@ -74,6 +74,7 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Cursor<Vec<u8>>, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx
     match t.sty {
         ty::TyBool => { write!(w, "b"); }
         ty::TyChar => { write!(w, "c"); }
+        ty::TyNever => { write!(w, "!"); }
         ty::TyInt(t) => {
             match t {
                 ast::IntTy::Is => write!(w, "is"),
@@ -127,6 +128,11 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Cursor<Vec<u8>>, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx
             enc_ty(w, cx, t);
             write!(w, "/{}|", sz);
         }
+        ty::TyGenericArray(t, len) => {
+            write!(w, "Y");
+            enc_ty(w, cx, t);
+            enc_ty(w, cx, len);
+        }
         ty::TySlice(t) => {
             write!(w, "V");
             enc_ty(w, cx, t);
@@ -151,6 +157,9 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Cursor<Vec<u8>>, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx
         ty::TyParam(ParamTy {space, idx, name}) => {
             write!(w, "p[{}|{}|{}]", idx, space.to_uint(), name);
         }
+        ty::TyConst(n) => {
+            write!(w, "C{}|", n);
+        }
         ty::TyStruct(def, substs) => {
             write!(w, "a[{}|", (cx.ds)(cx.tcx, def.did));
             enc_substs(w, cx, substs);
@@ -168,7 +177,14 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Cursor<Vec<u8>>, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx
         ty::TyProjection(ref data) => {
             write!(w, "P[");
             enc_trait_ref(w, cx, data.trait_ref);
-            write!(w, "{}]", data.item_name);
+            write!(w, "{}|", data.item_name);
+            enc_substs(w, cx, data.item_substs);
+            write!(w, "]");
+        }
+        ty::TyAnon(def_id, substs) => {
+            write!(w, "A[{}|", (cx.ds)(cx.tcx, def_id));
+            enc_substs(w, cx, substs);
+            write!(w, "]");
         }
         ty::TyError => {
             write!(w, "e");
@@ -429,6 +445,7 @@ pub fn enc_type_param_def<'a, 'tcx>(w: &mut Cursor<Vec<u8>>, cx: &ctxt<'a, 'tcx>
              v.space.to_uint(), v.index, (cx.ds)(cx.tcx, v.default_def_id));
     enc_opt(w, v.default, |w, t| enc_ty(w, cx, t));
     enc_object_lifetime_default(w, cx, v.object_lifetime_default);
+    write!(w, "{}", if v.is_const { 'y' } else { 'n' });
 }
 
 pub fn enc_region_param_def(w: &mut Cursor<Vec<u8>>, cx: &ctxt,
@@ -515,5 +532,6 @@ fn enc_projection_predicate<'a, 'tcx>(w: &mut Cursor<Vec<u8>>,
                                       data: &ty::ProjectionPredicate<'tcx>) {
     enc_trait_ref(w, cx, data.projection_ty.trait_ref);
     write!(w, "{}|", data.projection_ty.item_name);
+    enc_substs(w, cx, data.projection_ty.item_substs);
     enc_ty(w, cx, data.ty);
 }
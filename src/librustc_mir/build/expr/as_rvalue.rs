@@ -190,7 +190,15 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
 
                 let field_names = this.hir.all_fields(adt_def, variant_index);
 
-                let fields = if let Some(FruInfo { base, field_types }) = base {
+                // A union expression initializes exactly one field, and no
+                // base is allowed, so only that field's operand is kept.
+                let mut active_field_index = None;
+                let fields = if adt_def.is_union() {
+                    assert_eq!(fields_map.len(), 1);
+                    let (&name, operand) = fields_map.iter().next().unwrap();
+                    active_field_index = Some(name.index());
+                    vec![operand.clone()]
+                } else if let Some(FruInfo { base, field_types }) = base {
                     let base = unpack!(block = this.as_lvalue(block, base));
 
                     // MIR does not natively support FRU, so for each
@@ -207,8 +215,8 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
                     field_names.iter().map(|n| fields_map[n].clone()).collect()
                 };
 
-                block.and(Rvalue::Aggregate(AggregateKind::Adt(adt_def, variant_index, substs),
-                                            fields))
+                let adt = AggregateKind::Adt(adt_def, variant_index, substs, active_field_index);
+                block.and(Rvalue::Aggregate(adt, fields))
             }
             ExprKind::Assign { .. } |
             ExprKind::AssignOp { .. } => {
@@ -222,6 +230,7 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
             ExprKind::Loop { .. } |
             ExprKind::LogicalOp { .. } |
             ExprKind::Call { .. } |
+            ExprKind::NeverToAny { .. } |
             ExprKind::Field { .. } |
             ExprKind::Deref { .. } |
             ExprKind::Index { .. } |
@@ -187,6 +187,13 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
                 }
                 exit_block.unit()
             }
+            ExprKind::NeverToAny { source } => {
+                // Evaluate the source for its side effects; since it has type
+                // `!` it never produces a value and control can't reach the end.
+                unpack!(block = this.as_rvalue(block, source));
+                this.cfg.terminate(block, source_info, TerminatorKind::Unreachable);
+                this.cfg.start_new_block().unit()
+            }
             ExprKind::Call { ty, fun, args } => {
                 let diverges = match ty.sty {
                     ty::TyFnDef(_, _, ref f) | ty::TyFnPtr(ref f) => {
@@ -199,10 +199,16 @@ impl<'a, 'tcx> Visitor<'tcx> for BuildMir<'a, 'tcx> {
         intravisit::walk_expr(self, expr);
     }
 
-    // Array lengths, i.e. [T; constant].
+    // Array lengths, i.e. [T; constant], and constant generic arguments.
     fn visit_ty(&mut self, ty: &'tcx hir::Ty) {
-        if let hir::TyFixedLengthVec(_, ref length) = ty.node {
-            self.build_const_integer(length);
+        match ty.node {
+            hir::TyFixedLengthVec(_, ref length) => {
+                if self.tcx.const_param_def(length).is_none() {
+                    self.build_const_integer(length);
+                }
+            }
+            hir::TyConstArg(ref value) => self.build_const_integer(value),
+            _ => {}
         }
         intravisit::walk_ty(self, ty);
     }
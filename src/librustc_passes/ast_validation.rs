@@ -105,15 +105,23 @@ impl<'a> Visitor for AstValidator<'a> {
                                                             in import path");
                 }
             }
-            ItemKind::Impl(_, _, _, Some(..), _, ref impl_items) => {
+            ItemKind::Impl(_, polarity, defaultness, _, Some(..), _, ref impl_items) => {
                 self.invalid_visibility(&item.vis, item.span, None);
                 for impl_item in impl_items {
                     self.invalid_visibility(&impl_item.vis, impl_item.span, None);
                 }
+                if polarity == ImplPolarity::Negative && defaultness == Defaultness::Default {
+                    self.err_handler().span_err(item.span,
+                                                "negative impls cannot be default impls");
+                }
             }
-            ItemKind::Impl(_, _, _, None, _, _) => {
+            ItemKind::Impl(_, _, defaultness, _, None, _, _) => {
                 self.invalid_visibility(&item.vis, item.span, Some("place qualifiers on individual \
                                                                     impl items instead"));
+                if defaultness == Defaultness::Default {
+                    self.err_handler().span_err(item.span,
+                                                "inherent impls cannot be default");
+                }
             }
             ItemKind::DefaultImpl(..) => {
                 self.invalid_visibility(&item.vis, item.span, None);
@@ -129,6 +137,12 @@ impl<'a> Visitor for AstValidator<'a> {
                     }
                 }
             }
+            ItemKind::Union(ref vdata, _) => {
+                if vdata.fields().is_empty() {
+                    self.err_handler().span_err(item.span,
+                                                "unions cannot have zero fields");
+                }
+            }
             _ => {}
         }
 
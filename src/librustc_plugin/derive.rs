@@ -0,0 +1,156 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Custom derives defined as procedural macros, see
+//! `Registry::register_derive`.
+//!
+//! The item is handed to the derive function as a `TokenStream`, and the
+//! tokens it returns are parsed as items placed next to the annotated one.
+//! The input keeps the spans of the item in the source where it can, see
+//! `syntax::ext::expand::item_to_tts`. The output is lexed again from its
+//! source text in the current session, since the derive function may have
+//! made up its tokens in a session of its own (see `TokenStream::from_str`),
+//! and all of its tokens get the span of the `derive` attribute.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+use proc_macro::TokenStream;
+use proc_macro::__internal::{token_stream_inner, token_stream_wrap};
+
+use syntax::ast;
+use syntax::attr::AttrMetaMethods;
+use syntax::codemap::MacroAttribute;
+use syntax::ext::base::{Annotatable, ExtCtxt, MultiItemDecorator};
+use syntax::ext::expand::item_to_tts;
+use syntax::parse::{self, token};
+use syntax::tokenstream;
+use syntax_pos::Span;
+
+/// A custom derive: takes the tokens of the annotated item and returns the
+/// tokens of the items to add.
+pub type DeriveFn = fn(TokenStream) -> TokenStream;
+
+pub struct TokenDerive {
+    name: String,
+    expander: DeriveFn,
+}
+
+impl TokenDerive {
+    pub fn new(name: &str, expander: DeriveFn) -> TokenDerive {
+        TokenDerive {
+            name: name.to_string(),
+            expander: expander,
+        }
+    }
+}
+
+impl MultiItemDecorator for TokenDerive {
+    fn expand(&self,
+              cx: &mut ExtCtxt,
+              sp: Span,
+              _: &ast::MetaItem,
+              item: &Annotatable,
+              push: &mut FnMut(Annotatable)) {
+        let item = match *item {
+            Annotatable::Item(ref item) => item.clone(),
+            _ => {
+                cx.span_err(sp, &format!("`derive({})` can only be applied to items",
+                                         self.name));
+                return;
+            }
+        };
+
+        // The `#[derive_*]` attributes added by the expansion of `#[derive]`
+        // are not shown to the derive function. Attributes written by the
+        // user are, even if their name starts with `derive_`.
+        let item = item.map(|mut item| {
+            item.attrs.retain(|attr| !is_derive_attribute(cx, attr));
+            item
+        });
+        let input = match item_to_tts(cx, &format!("<derive({}) input>", self.name), &item) {
+            Some(input) => tokenstream::TokenStream::from_tts(input),
+            None => return,
+        };
+
+        let expander = self.expander;
+        let output = match panic::catch_unwind(AssertUnwindSafe(|| {
+            expander(token_stream_wrap(input))
+        })) {
+            Ok(output) => token_stream_inner(output),
+            Err(payload) => {
+                let mut err = cx.struct_span_err(sp, &format!("custom derive `{}` panicked",
+                                                              self.name));
+                if let Some(msg) = panic_message(&payload) {
+                    err.help(&format!("message: {}", msg));
+                }
+                err.emit();
+                return;
+            }
+        };
+
+        let output = match parse::parse_tts_with_span(format!("<derive({}) output>", self.name),
+                                                      output.to_string(),
+                                                      cx.parse_sess(),
+                                                      sp) {
+            Ok(output) => output,
+            Err(mut err) => {
+                err.emit();
+                return;
+            }
+        };
+        let mut parser = cx.new_parser_from_tts(&output);
+        while parser.token != token::Eof {
+            match parser.parse_item() {
+                Ok(Some(item)) => push(Annotatable::Item(item)),
+                Ok(None) => {
+                    let token_str = parser.this_token_to_string();
+                    cx.struct_span_err(sp, &format!("custom derive `{}` produced \
+                                                     something other than items",
+                                                    self.name))
+                      .span_note(parser.span, &format!("expected an item, found `{}`",
+                                                       token_str))
+                      .emit();
+                    return;
+                }
+                Err(mut err) => {
+                    err.emit();
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Whether `attr` is one of the `#[derive_Name]` attributes the expansion of
+/// `#[derive(Name)]` adds, which are given a span expanded from `derive(Name)`.
+fn is_derive_attribute(cx: &ExtCtxt, attr: &ast::Attribute) -> bool {
+    let name = attr.name();
+    if !name.starts_with("derive_") {
+        return false;
+    }
+    let format = format!("derive({})", &name["derive_".len()..]);
+    cx.codemap().with_expn_info(attr.span.expn_id, |info| {
+        match info.map(|info| &info.callee.format) {
+            Some(&MacroAttribute(ref expanded_from)) => *expanded_from.as_str() == format,
+            _ => false,
+        }
+    })
+}
+
+fn panic_message(payload: &Box<Any + Send>) -> Option<&str> {
+    if let Some(s) = payload.downcast_ref::<String>() {
+        Some(s)
+    } else if let Some(s) = payload.downcast_ref::<&'static str>() {
+        Some(s)
+    } else {
+        None
+    }
+}
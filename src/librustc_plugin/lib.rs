@@ -72,6 +72,7 @@ extern crate rustc_back;
 extern crate rustc_metadata;
 extern crate syntax_pos;
 extern crate rustc_errors as errors;
+extern crate proc_macro;
 
 pub use self::registry::Registry;
 
@@ -79,3 +80,4 @@ pub mod diagnostics;
 pub mod registry;
 pub mod load;
 pub mod build;
+pub mod derive;
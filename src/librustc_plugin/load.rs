@@ -16,10 +16,14 @@ use rustc_metadata::cstore::CStore;
 use registry::Registry;
 
 use std::borrow::ToOwned;
+use std::collections::HashSet;
 use std::env;
 use std::mem;
 use std::path::PathBuf;
 use syntax::ast;
+use syntax::ext::base::{NamedSyntaxExtension, ProcMacro, AttrProcMacro, CustomDerive};
+use syntax::feature_gate::{emit_feature_err, GateIssue, EXPLAIN_PROC_MACRO};
+use syntax::parse::token::{self, InternedString};
 use syntax::ptr::P;
 use syntax::attr::AttrMetaMethods;
 use syntax_pos::{Span, COMMAND_LINE_SP};
@@ -31,12 +35,66 @@ pub type PluginRegistrarFun =
 pub struct PluginRegistrar {
     pub fun: PluginRegistrarFun,
     pub args: Vec<P<ast::MetaItem>>,
+    /// Set if the crate was imported with `#[macro_use]` rather than as a
+    /// plugin, in which case only its procedural macros are to be used.
+    pub proc_macros: Option<ProcMacroImport>,
+}
+
+/// The procedural macros to import from a crate loaded for `#[macro_use]`.
+pub struct ProcMacroImport {
+    /// The name of the `extern crate` item.
+    pub item_name: ast::Name,
+    pub span: Span,
+    /// The names listed in `#[macro_use(...)]`, or `None` if all the macros
+    /// of the crate are imported.
+    pub names: Option<Vec<InternedString>>,
+}
+
+impl ProcMacroImport {
+    /// Picks the imported procedural macros out of the syntax extensions
+    /// registered by the crate. Custom derives, registered as `derive_Name`,
+    /// are imported as `Name`. The names of the macros are recorded, so that
+    /// `#[macro_use(...)]` does not report them as missing `macro_rules!`
+    /// macros.
+    pub fn select(&self,
+                  sess: &Session,
+                  exts: Vec<NamedSyntaxExtension>)
+                  -> Vec<NamedSyntaxExtension> {
+        let mut imported = Vec::new();
+        let exts: Vec<_> = exts.into_iter().filter(|&(name, ref ext)| {
+            let name = match *ext {
+                ProcMacro(..) | AttrProcMacro(..) => name,
+                CustomDerive(..) => token::intern(&name.as_str()["derive_".len()..]),
+                _ => return false,
+            };
+            let selected = match self.names {
+                Some(ref names) => names.iter().any(|n| *n == name.as_str()),
+                None => true,
+            };
+            if selected {
+                imported.push(name);
+            }
+            selected
+        }).collect();
+
+        if !exts.is_empty() && !sess.features.borrow().proc_macro {
+            emit_feature_err(&sess.parse_sess.span_diagnostic, "proc_macro", self.span,
+                             GateIssue::Language, EXPLAIN_PROC_MACRO);
+        }
+
+        sess.imported_proc_macros.borrow_mut()
+            .entry(self.item_name)
+            .or_insert_with(Vec::new)
+            .extend(imported);
+        exts
+    }
 }
 
 struct PluginLoader<'a> {
     sess: &'a Session,
     reader: CrateReader<'a>,
     plugins: Vec<PluginRegistrar>,
+    loaded: HashSet<String>,
 }
 
 fn call_malformed_plugin_attribute(a: &Session, b: Span) {
@@ -86,6 +144,41 @@ pub fn load_plugins(sess: &Session,
         }
     }
 
+    // The procedural macros of a crate are imported with `#[macro_use]`, like
+    // its `macro_rules!` macros, rather than as a plugin.
+    for item in &krate.module.items {
+        let name = match item.node {
+            ast::ItemKind::ExternCrate(orig_name) => orig_name.unwrap_or(item.ident.name),
+            _ => continue,
+        };
+        let macro_uses: Vec<_> = item.attrs.iter().filter(|attr| {
+            attr.check_name("macro_use")
+        }).collect();
+        if macro_uses.is_empty() || loader.loaded.contains(&*name.as_str()) {
+            continue;
+        }
+
+        // Malformed names are reported when the `macro_rules!` macros of the
+        // crate are imported.
+        let names = if macro_uses.iter().any(|attr| attr.meta_item_list().is_none()) {
+            None
+        } else {
+            Some(macro_uses.iter().flat_map(|attr| {
+                attr.meta_item_list().unwrap().iter().filter_map(|name| {
+                    match name.node {
+                        ast::MetaItemKind::Word(ref name) => Some(name.clone()),
+                        _ => None,
+                    }
+                })
+            }).collect())
+        };
+        loader.load_proc_macros(ProcMacroImport {
+            item_name: item.ident.name,
+            span: item.span,
+            names: names,
+        }, &name.as_str());
+    }
+
     loader.plugins
 }
 
@@ -95,6 +188,7 @@ impl<'a> PluginLoader<'a> {
             sess: sess,
             reader: CrateReader::new(sess, cstore, crate_name),
             plugins: vec![],
+            loaded: HashSet::new(),
         }
     }
 
@@ -107,6 +201,22 @@ impl<'a> PluginLoader<'a> {
             self.plugins.push(PluginRegistrar {
                 fun: fun,
                 args: args,
+                proc_macros: None,
+            });
+            self.loaded.insert(name.to_string());
+        }
+    }
+
+    fn load_proc_macros(&mut self, import: ProcMacroImport, name: &str) {
+        let registrar = self.reader.find_proc_macro_registrar(import.span, name);
+
+        if let Some((lib, svh, index)) = registrar {
+            let symbol = self.sess.generate_plugin_registrar_symbol(&svh, index);
+            let fun = self.dylink_registrar(import.span, lib, symbol);
+            self.plugins.push(PluginRegistrar {
+                fun: fun,
+                args: vec![],
+                proc_macros: Some(import),
             });
         }
     }
@@ -15,18 +15,29 @@ use rustc::session::Session;
 
 use rustc::mir::transform::MirMapPass;
 
+use derive::{DeriveFn, TokenDerive};
+
 use syntax::ext::base::{SyntaxExtension, NamedSyntaxExtension, NormalTT};
 use syntax::ext::base::{IdentTT, MultiModifier, MultiDecorator};
-use syntax::ext::base::{MacroExpanderFn, MacroRulesTT};
+use syntax::ext::base::{MacroExpanderFn, MacroRulesTT, ProcMacro, AttrProcMacro, CustomDerive};
 use syntax::parse::token;
 use syntax::ptr::P;
 use syntax::ast;
 use syntax::feature_gate::AttributeType;
 use syntax_pos::Span;
 
+use proc_macro::TokenStream;
+use proc_macro::__internal::{token_stream_inner, token_stream_wrap};
+
 use std::collections::HashMap;
 use std::borrow::ToOwned;
 
+/// A function-like procedural macro, see `Registry::register_proc_macro`.
+pub type ProcMacroFn = fn(TokenStream) -> TokenStream;
+
+/// An attribute-like procedural macro, see `Registry::register_attr_proc_macro`.
+pub type AttrProcMacroFn = fn(TokenStream, TokenStream) -> TokenStream;
+
 /// Structure used to register plugins.
 ///
 /// A plugin registrar function takes an `&mut Registry` and should call
@@ -112,6 +123,9 @@ impl<'a> Registry<'a> {
             }
             MultiDecorator(ext) => MultiDecorator(ext),
             MultiModifier(ext) => MultiModifier(ext),
+            ProcMacro(ext) => ProcMacro(ext),
+            AttrProcMacro(ext) => AttrProcMacro(ext),
+            CustomDerive(ext) => CustomDerive(ext),
             MacroRulesTT => {
                 self.sess.err("plugin tried to register a new MacroRulesTT");
                 return;
@@ -129,6 +143,50 @@ impl<'a> Registry<'a> {
                                        NormalTT(Box::new(expander), None, false));
     }
 
+    /// Register a custom derive defined as a procedural macro.
+    ///
+    /// `#[derive(name)]` calls `expander` with the tokens of the annotated
+    /// item, and the tokens it returns are parsed as items to add next to
+    /// it.
+    ///
+    /// This is called by the registrar generated for the
+    /// `#[proc_macro_derive(name)]` functions of a crate.
+    pub fn register_derive(&mut self, name: &str, expander: DeriveFn) {
+        self.register_syntax_extension(token::intern(&format!("derive_{}", name)),
+                                       CustomDerive(Box::new(TokenDerive::new(name, expander))));
+    }
+
+    /// Register a function-like procedural macro working on token streams.
+    ///
+    /// `name!(...)` calls `expander` with the tokens between the delimiters,
+    /// and the tokens it returns are the expansion of the macro. Tokens made
+    /// up by `expander` are given the span of the invocation.
+    ///
+    /// This is called by the registrar generated for the `#[proc_macro]`
+    /// functions of a crate.
+    pub fn register_proc_macro(&mut self, name: &str, expander: ProcMacroFn) {
+        let expander = move |input| {
+            token_stream_inner(expander(token_stream_wrap(input)))
+        };
+        self.register_syntax_extension(token::intern(name), ProcMacro(Box::new(expander)));
+    }
+
+    /// Register an attribute-like procedural macro working on token streams.
+    ///
+    /// `#[name(...)]` calls `expander` with the tokens between the parentheses
+    /// and the tokens of the annotated item, and the annotated item is
+    /// replaced with the items parsed from the tokens it returns.
+    ///
+    /// This is called by the registrar generated for the
+    /// `#[proc_macro_attribute]` functions of a crate.
+    pub fn register_attr_proc_macro(&mut self, name: &str, expander: AttrProcMacroFn) {
+        let expander = move |annotation, annotated| {
+            token_stream_inner(expander(token_stream_wrap(annotation),
+                                        token_stream_wrap(annotated)))
+        };
+        self.register_syntax_extension(token::intern(name), AttrProcMacro(Box::new(expander)));
+    }
+
     /// Register a compiler lint pass.
     pub fn register_early_lint_pass(&mut self, lint_pass: EarlyLintPassObject) {
         self.early_lint_passes.push(lint_pass);
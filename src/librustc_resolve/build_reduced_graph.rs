@@ -15,7 +15,7 @@
 
 use resolve_imports::ImportDirectiveSubclass::{self, GlobImport};
 use Module;
-use Namespace::{self, TypeNS, ValueNS};
+use Namespace::{self, TypeNS, ValueNS, MacroNS};
 use {NameBinding, NameBindingKind};
 use ParentLink::{ModuleParentLink, BlockParentLink};
 use Resolver;
@@ -26,7 +26,7 @@ use rustc::hir::def::*;
 use rustc::hir::def_id::{CRATE_DEF_INDEX, DefId};
 use rustc::ty::{self, VariantKind};
 
-use syntax::ast::Name;
+use syntax::ast::{MacroDef, Name};
 use syntax::attr;
 use syntax::parse::token;
 
@@ -34,11 +34,13 @@ use syntax::ast::{Block, Crate};
 use syntax::ast::{ForeignItem, ForeignItemKind, Item, ItemKind};
 use syntax::ast::{Mutability, PathListItemKind};
 use syntax::ast::{StmtKind, TraitItemKind};
-use syntax::ast::{Variant, ViewPathGlob, ViewPathList, ViewPathSimple};
+use syntax::ast::{Variant, ViewPath, ViewPathGlob, ViewPathList, ViewPathSimple};
 use syntax::visit::{self, Visitor};
 
 use syntax_pos::{Span, DUMMY_SP};
 
+use std::rc::Rc;
+
 trait ToNameBinding<'a> {
     fn to_name_binding(self) -> NameBinding<'a>;
 }
@@ -55,6 +57,12 @@ impl<'a> ToNameBinding<'a> for (Def, Span, ty::Visibility) {
     }
 }
 
+impl<'a> ToNameBinding<'a> for (Rc<MacroDef>, Span, ty::Visibility) {
+    fn to_name_binding(self) -> NameBinding<'a> {
+        NameBinding { kind: NameBindingKind::Macro(self.0), span: self.1, vis: self.2 }
+    }
+}
+
 impl<'b> Resolver<'b> {
     /// Constructs the reduced graph for the entire crate.
     pub fn build_reduced_graph(&mut self, krate: &Crate) {
@@ -92,111 +100,120 @@ impl<'b> Resolver<'b> {
         })
     }
 
-    /// Constructs the reduced graph for one item.
-    fn build_reduced_graph_for_item(&mut self, item: &Item, parent_ref: &mut Module<'b>) {
-        let parent = *parent_ref;
-        let name = item.ident.name;
-        let sp = item.span;
-        self.current_module = parent;
-        let vis = self.resolve_visibility(&item.vis);
+    /// Adds the import directives of the `use` item `item` to `parent`.
+    pub fn build_import_directives(&mut self,
+                                   item: &Item,
+                                   view_path: &ViewPath,
+                                   parent: Module<'b>,
+                                   vis: ty::Visibility) {
+        // Extract and intern the module part of the path. For
+        // globs and lists, the path is found directly in the AST;
+        // for simple paths we have to munge the path a little.
+        let module_path: Vec<Name> = match view_path.node {
+            ViewPathSimple(_, ref full_path) => {
+                full_path.segments
+                         .split_last()
+                         .unwrap()
+                         .1
+                         .iter()
+                         .map(|seg| seg.identifier.name)
+                         .collect()
+            }
 
-        match item.node {
-            ItemKind::Use(ref view_path) => {
-                // Extract and intern the module part of the path. For
-                // globs and lists, the path is found directly in the AST;
-                // for simple paths we have to munge the path a little.
-                let module_path: Vec<Name> = match view_path.node {
-                    ViewPathSimple(_, ref full_path) => {
-                        full_path.segments
-                                 .split_last()
-                                 .unwrap()
-                                 .1
+            ViewPathGlob(ref module_ident_path) |
+            ViewPathList(ref module_ident_path, _) => {
+                module_ident_path.segments
                                  .iter()
                                  .map(|seg| seg.identifier.name)
                                  .collect()
-                    }
+            }
+        };
 
-                    ViewPathGlob(ref module_ident_path) |
-                    ViewPathList(ref module_ident_path, _) => {
-                        module_ident_path.segments
-                                         .iter()
-                                         .map(|seg| seg.identifier.name)
-                                         .collect()
-                    }
-                };
-
-                // Build up the import directives.
-                let is_prelude = attr::contains_name(&item.attrs, "prelude_import");
-
-                match view_path.node {
-                    ViewPathSimple(binding, ref full_path) => {
-                        let source_name = full_path.segments.last().unwrap().identifier.name;
-                        if source_name.as_str() == "mod" || source_name.as_str() == "self" {
-                            resolve_error(self,
-                                          view_path.span,
-                                          ResolutionError::SelfImportsOnlyAllowedWithin);
-                        }
+        // Build up the import directives.
+        let is_prelude = attr::contains_name(&item.attrs, "prelude_import");
+
+        match view_path.node {
+            ViewPathSimple(binding, ref full_path) => {
+                let source_name = full_path.segments.last().unwrap().identifier.name;
+                if source_name.as_str() == "mod" || source_name.as_str() == "self" {
+                    resolve_error(self,
+                                  view_path.span,
+                                  ResolutionError::SelfImportsOnlyAllowedWithin);
+                }
 
-                        let subclass = ImportDirectiveSubclass::single(binding.name, source_name);
-                        let span = view_path.span;
-                        parent.add_import_directive(module_path, subclass, span, item.id, vis);
-                        self.unresolved_imports += 1;
+                let subclass = ImportDirectiveSubclass::single(binding.name, source_name);
+                let span = view_path.span;
+                parent.add_import_directive(module_path, subclass, span, item.id, vis);
+                self.unresolved_imports += 1;
+            }
+            ViewPathList(_, ref source_items) => {
+                // Make sure there's at most one `mod` import in the list.
+                let mod_spans = source_items.iter().filter_map(|item| {
+                    match item.node {
+                        PathListItemKind::Mod { .. } => Some(item.span),
+                        _ => None,
                     }
-                    ViewPathList(_, ref source_items) => {
-                        // Make sure there's at most one `mod` import in the list.
-                        let mod_spans = source_items.iter().filter_map(|item| {
-                            match item.node {
-                                PathListItemKind::Mod { .. } => Some(item.span),
-                                _ => None,
-                            }
-                        }).collect::<Vec<Span>>();
-
-                        if mod_spans.len() > 1 {
-                            let mut e = resolve_struct_error(self,
-                                          mod_spans[0],
-                                          ResolutionError::SelfImportCanOnlyAppearOnceInTheList);
-                            for other_span in mod_spans.iter().skip(1) {
-                                e.span_note(*other_span, "another `self` import appears here");
-                            }
-                            e.emit();
-                        }
+                }).collect::<Vec<Span>>();
+
+                if mod_spans.len() > 1 {
+                    let mut e = resolve_struct_error(self,
+                                  mod_spans[0],
+                                  ResolutionError::SelfImportCanOnlyAppearOnceInTheList);
+                    for other_span in mod_spans.iter().skip(1) {
+                        e.span_note(*other_span, "another `self` import appears here");
+                    }
+                    e.emit();
+                }
 
-                        for source_item in source_items {
-                            let (module_path, name, rename) = match source_item.node {
-                                PathListItemKind::Ident { name, rename, .. } =>
-                                    (module_path.clone(), name.name, rename.unwrap_or(name).name),
-                                PathListItemKind::Mod { rename, .. } => {
-                                    let name = match module_path.last() {
-                                        Some(name) => *name,
-                                        None => {
-                                            resolve_error(
-                                                self,
-                                                source_item.span,
-                                                ResolutionError::
-                                                SelfImportOnlyInImportListWithNonEmptyPrefix
-                                            );
-                                            continue;
-                                        }
-                                    };
-                                    let module_path = module_path.split_last().unwrap().1;
-                                    let rename = rename.map(|i| i.name).unwrap_or(name);
-                                    (module_path.to_vec(), name, rename)
+                for source_item in source_items {
+                    let (module_path, name, rename) = match source_item.node {
+                        PathListItemKind::Ident { name, rename, .. } =>
+                            (module_path.clone(), name.name, rename.unwrap_or(name).name),
+                        PathListItemKind::Mod { rename, .. } => {
+                            let name = match module_path.last() {
+                                Some(name) => *name,
+                                None => {
+                                    resolve_error(
+                                        self,
+                                        source_item.span,
+                                        ResolutionError::
+                                        SelfImportOnlyInImportListWithNonEmptyPrefix
+                                    );
+                                    continue;
                                 }
                             };
-                            let subclass = ImportDirectiveSubclass::single(rename, name);
-                            let (span, id) = (source_item.span, source_item.node.id());
-                            parent.add_import_directive(module_path, subclass, span, id, vis);
-                            self.unresolved_imports += 1;
+                            let module_path = module_path.split_last().unwrap().1;
+                            let rename = rename.map(|i| i.name).unwrap_or(name);
+                            (module_path.to_vec(), name, rename)
                         }
-                    }
-                    ViewPathGlob(_) => {
-                        let subclass = GlobImport { is_prelude: is_prelude };
-                        let span = view_path.span;
-                        parent.add_import_directive(module_path, subclass, span, item.id, vis);
-                        self.unresolved_imports += 1;
-                    }
+                    };
+                    let subclass = ImportDirectiveSubclass::single(rename, name);
+                    let (span, id) = (source_item.span, source_item.node.id());
+                    parent.add_import_directive(module_path, subclass, span, id, vis);
+                    self.unresolved_imports += 1;
                 }
             }
+            ViewPathGlob(_) => {
+                let subclass = GlobImport { is_prelude: is_prelude };
+                let span = view_path.span;
+                parent.add_import_directive(module_path, subclass, span, item.id, vis);
+                self.unresolved_imports += 1;
+            }
+        }
+    }
+
+    /// Constructs the reduced graph for one item.
+    fn build_reduced_graph_for_item(&mut self, item: &Item, parent_ref: &mut Module<'b>) {
+        let parent = *parent_ref;
+        let name = item.ident.name;
+        let sp = item.span;
+        self.current_module = parent;
+        let vis = self.resolve_visibility(&item.vis);
+
+        match item.node {
+            ItemKind::Use(ref view_path) => {
+                self.build_import_directives(item, view_path, parent, vis);
+            }
 
             ItemKind::ExternCrate(_) => {
                 // n.b. we don't need to look at the path option here, because cstore already
@@ -212,6 +229,14 @@ impl<'b> Resolver<'b> {
                     self.define(parent, name, TypeNS, (module, sp, vis));
 
                     self.build_reduced_graph_for_external_crate(module);
+
+                    // The macros exported by the crate, if they can be imported by `use`.
+                    if let Some(macros) = self.extern_macros.get(&item.span) {
+                        for def in macros {
+                            let binding = (def.clone(), def.span, ty::Visibility::Public);
+                            self.try_define(module, def.ident.name, MacroNS, binding);
+                        }
+                    }
                 }
             }
 
@@ -286,6 +311,21 @@ impl<'b> Resolver<'b> {
                 self.structs.insert(item_def_id, field_names);
             }
 
+            // Unions live only in the type namespace, they have no constructor.
+            ItemKind::Union(ref vdata, _) => {
+                let def = Def::Struct(self.definitions.local_def_id(item.id));
+                self.define(parent, name, TypeNS, (def, sp, vis));
+
+                // Record the def ID and fields of this union.
+                let field_names = vdata.fields().iter().enumerate().map(|(index, field)| {
+                    self.resolve_visibility(&field.vis);
+                    field.ident.map(|ident| ident.name)
+                               .unwrap_or_else(|| token::intern(&index.to_string()))
+                }).collect();
+                let item_def_id = self.definitions.local_def_id(item.id);
+                self.structs.insert(item_def_id, field_names);
+            }
+
             ItemKind::DefaultImpl(_, _) | ItemKind::Impl(..) => {}
 
             ItemKind::Trait(_, _, _, ref items) => {
@@ -22,7 +22,7 @@
 use std::ops::{Deref, DerefMut};
 
 use Resolver;
-use Namespace::{TypeNS, ValueNS};
+use Namespace::{TypeNS, ValueNS, MacroNS};
 
 use rustc::lint;
 use syntax::ast::{self, ViewPathGlob, ViewPathList, ViewPathSimple};
@@ -54,7 +54,8 @@ impl<'a, 'b> UnusedImportCheckVisitor<'a, 'b> {
     // used now. If an import is not used at all, we signal a lint error.
     fn check_import(&mut self, id: ast::NodeId, span: Span) {
         if !self.used_imports.contains(&(id, TypeNS)) &&
-           !self.used_imports.contains(&(id, ValueNS)) {
+           !self.used_imports.contains(&(id, ValueNS)) &&
+           !self.used_imports.contains(&(id, MacroNS)) {
             if self.maybe_unused_trait_imports.contains(&id) {
                 // Check later.
                 return;
@@ -73,9 +73,12 @@ use std::collections::{HashMap, HashSet};
 use std::cell::{Cell, RefCell};
 use std::fmt;
 use std::mem::replace;
+use std::rc::Rc;
 
 use resolve_imports::{ImportDirective, NameResolution};
 
+pub use macro_imports::{resolve_macro_imports, ExternMacros};
+
 // NB: This module needs to be declared first so diagnostics are
 // registered before they are used.
 mod diagnostics;
@@ -84,6 +87,7 @@ mod check_unused;
 mod build_reduced_graph;
 mod resolve_imports;
 mod assign_ids;
+mod macro_imports;
 
 enum SuggestionType {
     Macro(String),
@@ -497,6 +501,8 @@ impl PatternSource {
 pub enum Namespace {
     TypeNS,
     ValueNS,
+    // Macros exported by other crates, which are only imported by `use`.
+    MacroNS,
 }
 
 impl<'a> Visitor for Resolver<'a> {
@@ -810,7 +816,7 @@ impl<'a> fmt::Debug for ModuleS<'a> {
     }
 }
 
-// Records a possibly-private value, type, or module definition.
+// Records a possibly-private value, type, module, or macro definition.
 #[derive(Clone, Debug)]
 pub struct NameBinding<'a> {
     kind: NameBindingKind<'a>,
@@ -822,6 +828,7 @@ pub struct NameBinding<'a> {
 enum NameBindingKind<'a> {
     Def(Def),
     Module(Module<'a>),
+    Macro(Rc<ast::MacroDef>),
     Import {
         binding: &'a NameBinding<'a>,
         directive: &'a ImportDirective<'a>,
@@ -837,7 +844,7 @@ impl<'a> NameBinding<'a> {
     fn module(&self) -> Option<Module<'a>> {
         match self.kind {
             NameBindingKind::Module(module) => Some(module),
-            NameBindingKind::Def(_) => None,
+            NameBindingKind::Def(_) | NameBindingKind::Macro(_) => None,
             NameBindingKind::Import { binding, .. } => binding.module(),
         }
     }
@@ -846,10 +853,19 @@ impl<'a> NameBinding<'a> {
         match self.kind {
             NameBindingKind::Def(def) => Some(def),
             NameBindingKind::Module(module) => module.def,
+            NameBindingKind::Macro(_) => None,
             NameBindingKind::Import { binding, .. } => binding.def(),
         }
     }
 
+    fn macro_def(&self) -> Option<Rc<ast::MacroDef>> {
+        match self.kind {
+            NameBindingKind::Macro(ref def) => Some(def.clone()),
+            NameBindingKind::Import { binding, .. } => binding.macro_def(),
+            _ => None,
+        }
+    }
+
     fn is_pseudo_public(&self) -> bool {
         self.pseudo_vis() == ty::Visibility::Public
     }
@@ -885,8 +901,9 @@ impl<'a> NameBinding<'a> {
     }
 
     fn is_importable(&self) -> bool {
-        match self.def().unwrap() {
-            Def::AssociatedConst(..) | Def::Method(..) | Def::AssociatedTy(..) => false,
+        match self.def() {
+            Some(Def::AssociatedConst(..)) | Some(Def::Method(..)) |
+            Some(Def::AssociatedTy(..)) => false,
             _ => true,
         }
     }
@@ -1004,6 +1021,11 @@ pub struct Resolver<'a> {
     used_crates: HashSet<CrateNum>,
     pub maybe_unused_trait_imports: NodeSet,
 
+    // The spans of the imports whose macros were invoked during expansion.
+    pub used_macro_imports: HashSet<Span>,
+    // The macros exported by the crates of `extern crate` items, by the span of the item.
+    pub extern_macros: ExternMacros,
+
     privacy_errors: Vec<PrivacyError<'a>>,
 
     arenas: &'a ResolverArenas<'a>,
@@ -1158,6 +1180,9 @@ impl<'a> Resolver<'a> {
             used_crates: HashSet::new(),
             maybe_unused_trait_imports: NodeSet(),
 
+            used_macro_imports: HashSet::new(),
+            extern_macros: HashMap::new(),
+
             privacy_errors: Vec::new(),
 
             arenas: arenas,
@@ -1196,7 +1221,11 @@ impl<'a> Resolver<'a> {
     }
 
     fn get_ribs<'b>(&'b mut self, ns: Namespace) -> &'b mut Vec<Rib<'a>> {
-        match ns { ValueNS => &mut self.value_ribs, TypeNS => &mut self.type_ribs }
+        match ns {
+            ValueNS => &mut self.value_ribs,
+            TypeNS => &mut self.type_ribs,
+            MacroNS => bug!("there are no ribs in the macro namespace"),
+        }
     }
 
     #[inline]
@@ -1610,7 +1639,8 @@ impl<'a> Resolver<'a> {
         match item.node {
             ItemKind::Enum(_, ref generics) |
             ItemKind::Ty(_, ref generics) |
-            ItemKind::Struct(_, ref generics) => {
+            ItemKind::Struct(_, ref generics) |
+            ItemKind::Union(_, ref generics) => {
                 self.with_type_parameter_rib(HasTypeParameters(generics, TypeSpace, ItemRibKind),
                                              |this| visit::walk_item(this, item));
             }
@@ -1622,7 +1652,8 @@ impl<'a> Resolver<'a> {
             ItemKind::DefaultImpl(_, ref trait_ref) => {
                 self.with_optional_trait_ref(Some(trait_ref), |_, _| {});
             }
-            ItemKind::Impl(_, _, ref generics, ref opt_trait_ref, ref self_type, ref impl_items) =>
+            ItemKind::Impl(_, _, _, ref generics, ref opt_trait_ref, ref self_type,
+                           ref impl_items) =>
                 self.resolve_implementation(generics,
                                             opt_trait_ref,
                                             &self_type,
@@ -1663,8 +1694,10 @@ impl<'a> Resolver<'a> {
                                         visit::walk_trait_item(this, trait_item)
                                     });
                                 }
-                                TraitItemKind::Type(..) => {
-                                    this.with_type_parameter_rib(NoTypeParameters, |this| {
+                                TraitItemKind::Type(ref generics, _, _) => {
+                                    let type_parameters =
+                                        HasTypeParameters(generics, FnSpace, NormalRibKind);
+                                    this.with_type_parameter_rib(type_parameters, |this| {
                                         visit::walk_trait_item(this, trait_item)
                                     });
                                 }
@@ -1969,14 +2002,20 @@ impl<'a> Resolver<'a> {
                                         visit::walk_impl_item(this, impl_item);
                                     });
                                 }
-                                ImplItemKind::Type(ref ty) => {
+                                ImplItemKind::Type(ref generics, _) => {
                                     // If this is a trait impl, ensure the type
                                     // exists in trait
                                     this.check_trait_item(impl_item.ident.name,
                                                           impl_item.span,
                                         |n, s| ResolutionError::TypeNotMemberOfTrait(n, s));
 
-                                    this.visit_ty(ty);
+                                    // Generic associated types have their own
+                                    // type parameters.
+                                    let type_parameters =
+                                        HasTypeParameters(generics, FnSpace, NormalRibKind);
+                                    this.with_type_parameter_rib(type_parameters, |this| {
+                                        visit::walk_impl_item(this, impl_item);
+                                    });
                                 }
                                 ImplItemKind::Macro(_) => panic!("unexpanded macro in resolve!"),
                             }
@@ -2203,12 +2242,44 @@ impl<'a> Resolver<'a> {
                     }
                 }
             }
+            TyKind::FixedLengthVec(ref element, ref length) => {
+                // The length of `[T; N]` may name a `const` type parameter,
+                // which lives in the type namespace rather than the value one.
+                if let Some(resolution) = self.resolve_const_param(length) {
+                    self.record_def(length.id, resolution);
+                    self.visit_ty(element);
+                    return;
+                }
+            }
             _ => {}
         }
         // Resolve embedded types.
         visit::walk_ty(self, ty);
     }
 
+    /// Resolves a single-segment path expression that names a type
+    /// parameter, as const parameters are used in array lengths and values.
+    fn resolve_const_param(&mut self, expr: &Expr) -> Option<PathResolution> {
+        let ident = match expr.node {
+            ExprKind::Path(None, ref path) if !path.global && path.segments.len() == 1 &&
+                                              path.segments[0].parameters.is_empty() => {
+                path.segments[0].identifier
+            }
+            _ => return None,
+        };
+        match self.resolve_ident_in_lexical_scope(ident, TypeNS, true) {
+            Some(LexicalScopeBinding::LocalDef(local_def)) => {
+                if let Def::TyParam(..) = local_def.def {
+                    let def = self.adjust_local_def(local_def, expr.span);
+                    Some(def.map_or_else(err_path_resolution, PathResolution::new))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
     fn fresh_binding(&mut self,
                      ident: &ast::SpannedIdent,
                      pat_id: NodeId,
@@ -2860,6 +2931,10 @@ impl<'a> Resolver<'a> {
 
                         self.record_def(expr.id, path_res);
                     }
+                } else if let Some(resolution) = self.resolve_const_param(expr) {
+                    // A `const` type parameter used as a value. Whether the
+                    // parameter really is `const` is checked during typeck.
+                    self.record_def(expr.id, resolution);
                 } else {
                     // Be helpful if the name refers to a struct
                     // (The pattern matching def_tys where the id is in self.structs
@@ -3076,6 +3151,11 @@ impl<'a> Resolver<'a> {
                 }
             }
 
+            ExprKind::Err => {
+                // A placeholder left by the parser; the error was reported.
+                self.record_def(expr.id, err_path_resolution());
+            }
+
             _ => {
                 visit::walk_expr(self, expr);
             }
@@ -3322,6 +3402,17 @@ impl<'a> Resolver<'a> {
         vis.is_accessible_from(node_id, self)
     }
 
+    /// Whether `name` is a macro exported by the external crate whose root is
+    /// `module`, which `use` can only import with the `use_extern_macros` feature.
+    fn is_exported_macro(&self, module: Module<'a>, name: Name) -> bool {
+        match module.def {
+            Some(Def::Mod(def_id)) if def_id.index == CRATE_DEF_INDEX && !def_id.is_local() => {
+                self.session.cstore.exported_macro_names(def_id.krate).contains(&name)
+            }
+            _ => false,
+        }
+    }
+
     fn check_privacy(&mut self, name: Name, binding: &'a NameBinding<'a>, span: Span) {
         if !self.is_accessible(binding.vis) {
             self.privacy_errors.push(PrivacyError(span, name, binding));
@@ -3339,8 +3430,8 @@ impl<'a> Resolver<'a> {
                 let msg = format!("extern crate `{}` is private", name);
                 self.session.add_lint(lint::builtin::INACCESSIBLE_EXTERN_CRATE, node_id, span, msg);
             } else {
-                let def = binding.def().unwrap();
-                self.session.span_err(span, &format!("{} `{}` is private", def.kind_name(), name));
+                let kind = binding.def().map_or("macro", |def| def.kind_name());
+                self.session.span_err(span, &format!("{} `{}` is private", kind, name));
             }
         }
     }
@@ -3351,6 +3442,10 @@ impl<'a> Resolver<'a> {
                        ns: Namespace,
                        binding: &NameBinding,
                        old_binding: &NameBinding) {
+        if !self.emit_errors {
+            return;
+        }
+
         // Error on the second of two conflicting names
         if old_binding.span.lo > binding.span.lo {
             return self.report_conflict(parent, name, ns, old_binding, binding);
@@ -3376,6 +3471,7 @@ impl<'a> Resolver<'a> {
                 (TypeNS, Some(module)) if module.is_normal() => "a module",
                 (TypeNS, Some(module)) if module.is_trait() => "a trait",
                 (TypeNS, _) => "a type",
+                (MacroNS, _) => "a macro",
             };
             format!("{} named `{}` has already been {} in this {}",
                     kind, name, participle, container)
@@ -0,0 +1,213 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Resolution of the macros imported by `use`
+//!
+//! Macros are expanded before imports are resolved, so the macros imported by
+//! `use` items are resolved ahead of expansion. We build a reduced graph of the
+//! unexpanded crate out of its modules, `extern crate` items and `use` items,
+//! define the macros exported by each extern crate in the macro namespace of
+//! its module, and resolve the imports of that graph. Items produced by macro
+//! invocations are not seen here, so imports expanded from macros do not import
+//! macros. All imports are resolved again after expansion, which is when their
+//! errors are reported.
+
+use {MakeGlobMap, Module, NameBinding, NameBindingKind, Resolver};
+use Namespace::{TypeNS, MacroNS};
+use ParentLink::{ModuleParentLink, BlockParentLink};
+
+use rustc::hir::def::Def;
+use rustc::hir::def_id::{CRATE_DEF_INDEX, DefId};
+use rustc::hir::map::DefPathData;
+use rustc::session::Session;
+use rustc::ty;
+
+use syntax::ast::{self, Block, Item, ItemKind, NodeId, StmtKind, CRATE_NODE_ID, DUMMY_NODE_ID};
+use syntax::ext::base::{MacroImport, MacroImports, MacroLoader};
+use syntax::visit::{self, Visitor};
+
+use syntax_pos::Span;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// The macros exported by the crates of `extern crate` items, by the span of the item.
+pub type ExternMacros = HashMap<Span, Vec<Rc<ast::MacroDef>>>;
+
+/// Resolves the macros imported by `use` in the unexpanded `krate`, loading the
+/// macros exported by its extern crates with `loader`. Returns the macros imported
+/// in each module and block, along with the macros of each extern crate.
+pub fn resolve_macro_imports(session: &Session, krate: &ast::Crate, loader: &mut MacroLoader)
+                             -> (MacroImports, ExternMacros) {
+    let arenas = Resolver::arenas();
+    let mut resolver = Resolver::new(session, MakeGlobMap::No, &arenas);
+    resolver.emit_errors = false;
+    resolver.definitions.create_def_with_parent(None, CRATE_NODE_ID, DefPathData::CrateRoot);
+
+    let graph_root = resolver.graph_root;
+    let (scopes, extern_macros) = {
+        let mut collector = MacroImportCollector {
+            resolver: &mut resolver,
+            loader: loader,
+            parent: graph_root,
+            last_node_id: CRATE_NODE_ID,
+            scopes: vec![(krate.module.inner, graph_root)],
+            extern_macros: HashMap::new(),
+        };
+        visit::walk_crate(&mut collector, krate);
+        (collector.scopes, collector.extern_macros)
+    };
+
+    resolver.resolve_imports();
+
+    let mut macro_imports = HashMap::new();
+    for (scope, module) in scopes {
+        let resolutions = module.resolutions.borrow();
+        let mut bindings = resolutions.iter().filter_map(|(&(name, ns), resolution)| {
+            match resolution.borrow().binding {
+                Some(binding) if ns == MacroNS && binding.is_import() => Some((name, binding)),
+                _ => None,
+            }
+        }).collect::<Vec<_>>();
+        if bindings.is_empty() {
+            continue;
+        }
+        bindings.sort_by_key(|&(name, _)| name.as_str());
+
+        let imports = bindings.into_iter().filter_map(|(name, binding)| {
+            binding.macro_def().map(|def| {
+                let mut def = (*def).clone();
+                def.ident = ast::Ident::with_empty_ctxt(name);
+                def.use_locally = true;
+                // `pub use` at the crate root reexports the macro.
+                def.export = module.def_id() == graph_root.def_id() &&
+                             binding.vis == ty::Visibility::Public;
+                MacroImport { def: def, span: binding.span }
+            })
+        }).collect();
+        macro_imports.insert(scope, imports);
+    }
+
+    (macro_imports, extern_macros)
+}
+
+struct MacroImportCollector<'a, 'b: 'a> {
+    resolver: &'a mut Resolver<'b>,
+    loader: &'a mut MacroLoader,
+    parent: Module<'b>,
+    last_node_id: NodeId,
+    // The modules and anonymous modules of the graph, by the span of their contents.
+    scopes: Vec<(Span, Module<'b>)>,
+    extern_macros: ExternMacros,
+}
+
+impl<'a, 'b> MacroImportCollector<'a, 'b> {
+    // The unexpanded crate has no node ids yet, so the graph uses its own.
+    fn next_node_id(&mut self) -> NodeId {
+        self.last_node_id += 1;
+        self.last_node_id
+    }
+
+    fn visibility(&mut self, vis: &ast::Visibility) -> ty::Visibility {
+        match *vis {
+            // The path is only resolved after expansion.
+            ast::Visibility::Restricted { .. } => ty::Visibility::Restricted(CRATE_NODE_ID),
+            _ => {
+                self.resolver.current_module = self.parent;
+                self.resolver.resolve_visibility(vis)
+            }
+        }
+    }
+
+    fn define_module(&mut self, item: &Item, module: Module<'b>) {
+        let binding = NameBinding {
+            kind: NameBindingKind::Module(module),
+            span: item.span,
+            vis: self.visibility(&item.vis),
+        };
+        let _ = self.parent.try_define_child(item.ident.name, TypeNS, binding);
+    }
+}
+
+impl<'a, 'b> Visitor for MacroImportCollector<'a, 'b> {
+    fn visit_item(&mut self, item: &Item) {
+        let old_parent = self.parent;
+        match item.node {
+            ItemKind::Use(ref view_path) => {
+                let vis = self.visibility(&item.vis);
+                self.resolver.build_import_directives(item, view_path, self.parent, vis);
+            }
+
+            ItemKind::ExternCrate(_) => {
+                let macros = self.loader.load_exported_macros(item).into_iter()
+                                        .map(Rc::new).collect::<Vec<_>>();
+
+                // The crate is only registered with the crate store after expansion,
+                // so its root gets a placeholder `DefId`, which is never looked up.
+                let def = Def::Mod(DefId { krate: !0, index: CRATE_DEF_INDEX });
+                let parent_link = ModuleParentLink(self.parent, item.ident.name);
+                let module = self.resolver.new_extern_crate_module(parent_link, def, DUMMY_NODE_ID);
+                for def in &macros {
+                    let binding = NameBinding {
+                        kind: NameBindingKind::Macro(def.clone()),
+                        span: def.span,
+                        vis: ty::Visibility::Public,
+                    };
+                    let _ = module.try_define_child(def.ident.name, MacroNS, binding);
+                }
+                self.define_module(item, module);
+                self.extern_macros.insert(item.span, macros);
+            }
+
+            ItemKind::Mod(ref contents) => {
+                let id = self.next_node_id();
+                let parent_def_id = self.resolver
+                                        .get_nearest_normal_module_parent_or_self(self.parent)
+                                        .def_id()
+                                        .unwrap();
+                let index = self.resolver.definitions.create_def_with_parent(
+                    Some(parent_def_id.index), id, DefPathData::Module(item.ident.name));
+
+                let parent_link = ModuleParentLink(self.parent, item.ident.name);
+                let def = Def::Mod(DefId::local(index));
+                let module = self.resolver.new_module(parent_link, Some(def), false);
+                self.define_module(item, module);
+                self.resolver.module_map.insert(id, module);
+                self.scopes.push((contents.inner, module));
+                self.parent = module;
+            }
+
+            _ => {}
+        }
+
+        visit::walk_item(self, item);
+        self.parent = old_parent;
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        let old_parent = self.parent;
+        let has_items = block.stmts.iter().any(|stmt| match stmt.node {
+            StmtKind::Item(_) => true,
+            _ => false,
+        });
+        if has_items {
+            let id = self.next_node_id();
+            let module = self.resolver.new_module(BlockParentLink(self.parent, id), None, false);
+            self.resolver.module_map.insert(id, module);
+            self.scopes.push((block.span, module));
+            self.parent = module;
+        }
+
+        visit::walk_block(self, block);
+        self.parent = old_parent;
+    }
+
+    fn visit_mac(&mut self, _: &ast::Mac) {}
+}
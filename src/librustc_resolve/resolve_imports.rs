@@ -11,7 +11,7 @@
 use self::ImportDirectiveSubclass::*;
 
 use Module;
-use Namespace::{self, TypeNS, ValueNS};
+use Namespace::{self, TypeNS, ValueNS, MacroNS};
 use {NameBinding, NameBindingKind, PrivacyError};
 use ResolveResult;
 use ResolveResult::*;
@@ -25,6 +25,7 @@ use rustc::lint;
 use rustc::hir::def::*;
 
 use syntax::ast::{NodeId, Name};
+use syntax::feature_gate::{emit_feature_err, GateIssue, EXPLAIN_USE_EXTERN_MACROS};
 use syntax::util::lev_distance::find_best_match_for_name;
 use syntax_pos::{Span, DUMMY_SP};
 
@@ -44,6 +45,7 @@ pub enum ImportDirectiveSubclass {
         source: Name,
         type_determined: Cell<bool>,
         value_determined: Cell<bool>,
+        macro_determined: Cell<bool>,
     },
     GlobImport { is_prelude: bool },
 }
@@ -55,6 +57,7 @@ impl ImportDirectiveSubclass {
             source: source,
             type_determined: Cell::new(false),
             value_determined: Cell::new(false),
+            macro_determined: Cell::new(false),
         }
     }
 }
@@ -289,7 +292,7 @@ impl<'a> ::ModuleS<'a> {
         self.unresolved_imports.borrow_mut().push(directive);
         match directive.subclass {
             SingleImport { target, .. } => {
-                for &ns in &[ValueNS, TypeNS] {
+                for &ns in &[ValueNS, TypeNS, MacroNS] {
                     self.resolution(target, ns).borrow_mut().single_imports
                                                             .add_directive(directive);
                 }
@@ -481,21 +484,26 @@ impl<'a, 'b:'a> ImportResolver<'a, 'b> {
         };
 
         directive.target_module.set(Some(target_module));
-        let (source, target, value_determined, type_determined) = match directive.subclass {
-            SingleImport { source, target, ref value_determined, ref type_determined } =>
-                (source, target, value_determined, type_determined),
-            GlobImport { .. } => return self.resolve_glob_import(target_module, directive),
-        };
+        let (source, target, value_determined, type_determined, macro_determined) =
+            match directive.subclass {
+                SingleImport { source, target, ref value_determined, ref type_determined,
+                               ref macro_determined } =>
+                    (source, target, value_determined, type_determined, macro_determined),
+                GlobImport { .. } => return self.resolve_glob_import(target_module, directive),
+            };
 
-        // We need to resolve both namespaces for this to succeed.
+        // We need to resolve all namespaces for this to succeed.
         let value_result =
             self.resolver.resolve_name_in_module(target_module, source, ValueNS, false, true);
         let type_result =
             self.resolver.resolve_name_in_module(target_module, source, TypeNS, false, true);
+        let macro_result =
+            self.resolver.resolve_name_in_module(target_module, source, MacroNS, false, true);
 
         let module_ = self.resolver.current_module;
         for &(ns, result, determined) in &[(ValueNS, &value_result, value_determined),
-                                           (TypeNS, &type_result, type_determined)] {
+                                           (TypeNS, &type_result, type_determined),
+                                           (MacroNS, &macro_result, macro_determined)] {
             if determined.get() { continue }
             if let Indeterminate = *result { continue }
 
@@ -529,9 +537,21 @@ impl<'a, 'b:'a> ImportResolver<'a, 'b> {
             }
         }
 
-        match (&value_result, &type_result) {
-            (&Indeterminate, _) | (_, &Indeterminate) => return Indeterminate,
-            (&Failed(_), &Failed(_)) => {
+        match (&value_result, &type_result, &macro_result) {
+            (&Indeterminate, _, _) | (_, &Indeterminate, _) | (_, _, &Indeterminate) =>
+                return Indeterminate,
+            (&Failed(_), &Failed(_), &Failed(_))
+                    if !self.resolver.session.features.borrow().use_extern_macros &&
+                       self.resolver.is_exported_macro(target_module, source) => {
+                emit_feature_err(&self.resolver.session.parse_sess.span_diagnostic,
+                                 "use_extern_macros",
+                                 directive.span,
+                                 GateIssue::Language,
+                                 EXPLAIN_USE_EXTERN_MACROS);
+                self.import_dummy_binding(module_, directive);
+                return Success(());
+            }
+            (&Failed(_), &Failed(_), &Failed(_)) => {
                 let resolutions = target_module.resolutions.borrow();
                 let names = resolutions.iter().filter_map(|(&(ref name, _), resolution)| {
                     if *name == source { return None; } // Never suggest the same name
@@ -556,7 +576,13 @@ impl<'a, 'b:'a> ImportResolver<'a, 'b> {
             _ => (),
         }
 
+        if let Success(_) = macro_result {
+            self.record_macro_import_use(directive);
+        }
+
         match (&value_result, &type_result) {
+            _ if !self.resolver.emit_errors => {}
+
             (&Success(binding), _) if !binding.pseudo_vis()
                                               .is_at_least(directive.vis, self.resolver) &&
                                       self.resolver.is_accessible(binding.vis) => {
@@ -594,7 +620,7 @@ impl<'a, 'b:'a> ImportResolver<'a, 'b> {
 
         // Report a privacy error here if all successful namespaces are privacy errors.
         let mut privacy_error = None;
-        for &ns in &[ValueNS, TypeNS] {
+        for &ns in &[ValueNS, TypeNS, MacroNS] {
             privacy_error = match module_.resolve_name(target, ns, true) {
                 Success(&NameBinding {
                     kind: NameBindingKind::Import { ref privacy_error, .. }, ..
@@ -607,13 +633,13 @@ impl<'a, 'b:'a> ImportResolver<'a, 'b> {
 
         // Record what this import resolves to for later uses in documentation,
         // this may resolve to either a value or a type, but for documentation
-        // purposes it's good enough to just favor one over the other.
-        let def = match type_result.success().and_then(NameBinding::def) {
-            Some(def) => def,
-            None => value_result.success().and_then(NameBinding::def).unwrap(),
-        };
-        let path_resolution = PathResolution::new(def);
-        self.resolver.def_map.insert(directive.id, path_resolution);
+        // purposes it's good enough to just favor one over the other. An import
+        // of a macro alone has no definition to record.
+        let def = type_result.success().and_then(NameBinding::def)
+                             .or_else(|| value_result.success().and_then(NameBinding::def));
+        if let Some(def) = def {
+            self.resolver.def_map.insert(directive.id, PathResolution::new(def));
+        }
 
         debug!("(resolving single import) successfully resolved import");
         return Success(());
@@ -655,6 +681,7 @@ impl<'a, 'b:'a> ImportResolver<'a, 'b> {
                 let _ = module_.try_define_child(name, ns, directive.import(binding, None));
             }
         }
+        self.record_macro_import_use(directive);
 
         // Record the destination of this import
         if let Some(did) = target_module.def_id() {
@@ -666,6 +693,14 @@ impl<'a, 'b:'a> ImportResolver<'a, 'b> {
         return Success(());
     }
 
+    // Macros are invoked before imports are resolved, so whether an import is used
+    // in the macro namespace is recorded by expansion, by the span of the import.
+    fn record_macro_import_use(&mut self, directive: &'b ImportDirective<'b>) {
+        if self.resolver.used_macro_imports.contains(&directive.span) {
+            self.resolver.used_imports.insert((directive.id, MacroNS));
+        }
+    }
+
     // Miscellaneous post-processing, including recording reexports, reporting conflicts,
     // reporting the PRIVATE_IN_PUBLIC lint, and reporting unresolved imports.
     fn finalize_resolutions_in(&mut self, module: Module<'b>, report_unresolved_imports: bool) {
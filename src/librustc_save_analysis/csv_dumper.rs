@@ -40,6 +40,7 @@ impl<'b, W: Write + 'b> Dump for CsvDumper<'b, W> {
     fn crate_prelude(&mut self, data: CratePreludeData) {
         let values = make_values_str(&[
             ("name", &data.crate_name),
+            ("disambiguator", &data.disambiguator),
             ("crate_root", &data.crate_root)
         ]);
 
@@ -50,6 +51,7 @@ impl<'b, W: Write + 'b> Dump for CsvDumper<'b, W> {
             let values = make_values_str(&[
                 ("name", &c.name),
                 ("crate", &num),
+                ("disambiguator", &c.disambiguator),
                 ("file_name", &c.file_name)
             ]);
 
@@ -80,6 +80,7 @@ pub enum Data {
 #[derive(Debug, RustcEncodable)]
 pub struct CratePreludeData {
     pub crate_name: String,
+    pub disambiguator: String,
     pub crate_root: String,
     pub external_crates: Vec<ExternalCrateData>,
     pub span: Span,
@@ -90,6 +91,7 @@ pub struct CratePreludeData {
 pub struct ExternalCrateData {
     pub name: String,
     pub num: CrateNum,
+    pub disambiguator: String,
     pub file_name: String,
 }
 
@@ -30,8 +30,10 @@
 use rustc::hir::def::Def;
 use rustc::hir::def_id::DefId;
 use rustc::hir::map::Node;
+use rustc::middle::cstore::LOCAL_CRATE;
 use rustc::session::Session;
 use rustc::ty::{self, TyCtxt, ImplOrTraitItem, ImplOrTraitItemContainer};
+use rustc::util::nodemap::NodeSet;
 
 use std::collections::HashSet;
 use std::hash::*;
@@ -78,6 +80,10 @@ pub struct DumpVisitor<'l, 'tcx: 'l, 'll, D: 'll> {
     // one macro use per unique callsite span.
     mac_defs: HashSet<Span>,
     mac_uses: HashSet<Span>,
+
+    // Items which are not dumped, because their data is unchanged since
+    // the previous (incremental) dump.
+    skipped_items: NodeSet,
 }
 
 impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
@@ -97,9 +103,14 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
             cur_scope: 0,
             mac_defs: HashSet::new(),
             mac_uses: HashSet::new(),
+            skipped_items: NodeSet(),
         }
     }
 
+    pub fn skip_items(&mut self, items: NodeSet) {
+        self.skipped_items = items;
+    }
+
     fn nest<F>(&mut self, scope_id: NodeId, f: F)
         where F: FnOnce(&mut DumpVisitor<'l, 'tcx, 'll, D>)
     {
@@ -124,6 +135,7 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
             ExternalCrateData {
                 name: c.name,
                 num: c.number,
+                disambiguator: self.tcx.crate_disambiguator(c.number).to_string(),
                 file_name: SpanUtils::make_path_string(&lo_loc.file.name),
             }
         }).collect();
@@ -131,6 +143,7 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
         // The current crate.
         let data = CratePreludeData {
             crate_name: name.into(),
+            disambiguator: self.tcx.crate_disambiguator(LOCAL_CRATE).to_string(),
             crate_root: crate_root.unwrap_or("<no source>".to_owned()),
             external_crates: external_crates,
             span: krate.span,
@@ -563,10 +576,12 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
         let name = item.ident.to_string();
         let qualname = format!("::{}", self.tcx.node_path_str(item.id));
 
-        let sub_span = self.span.sub_span_after_keyword(item.span, keywords::Struct);
-        let (val, fields) =
-            if let ast::ItemKind::Struct(ast::VariantData::Struct(ref fields, _), _) = item.node
-        {
+        let kind = match item.node {
+            ast::ItemKind::Union(..) => keywords::Union,
+            _ => keywords::Struct,
+        };
+        let sub_span = self.span.sub_span_after_keyword(item.span, kind);
+        let (val, fields) = if let ast::VariantData::Struct(ref fields, _) = *def {
             let fields_str = fields.iter()
                                    .enumerate()
                                    .map(|(i, f)| f.ident.map(|i| i.to_string())
@@ -1042,6 +1057,9 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
 impl<'l, 'tcx: 'l, 'll, D: Dump +'ll> Visitor for DumpVisitor<'l, 'tcx, 'll, D> {
     fn visit_item(&mut self, item: &ast::Item) {
         use syntax::ast::ItemKind::*;
+        if self.skipped_items.contains(&item.id) {
+            return;
+        }
         self.process_macro_use(item.span, item.id);
         match item.node {
             Use(ref use_item) => {
@@ -1149,9 +1167,10 @@ impl<'l, 'tcx: 'l, 'll, D: Dump +'ll> Visitor for DumpVisitor<'l, 'tcx, 'll, D>
                 self.process_static_or_const_item(item, typ, expr),
             Const(ref typ, ref expr) =>
                 self.process_static_or_const_item(item, &typ, &expr),
-            Struct(ref def, ref ty_params) => self.process_struct(item, def, ty_params),
+            Struct(ref def, ref ty_params) |
+            Union(ref def, ref ty_params) => self.process_struct(item, def, ty_params),
             Enum(ref def, ref ty_params) => self.process_enum(item, def, ty_params),
-            Impl(_, _,
+            Impl(_, _, _,
                           ref ty_params,
                           ref trait_ref,
                           ref typ,
@@ -1239,7 +1258,7 @@ impl<'l, 'tcx: 'l, 'll, D: Dump +'ll> Visitor for DumpVisitor<'l, 'tcx, 'll, D>
                                     impl_item.ident.name,
                                     impl_item.span);
             }
-            ast::ImplItemKind::Type(_) |
+            ast::ImplItemKind::Type(..) |
             ast::ImplItemKind::Macro(_) => {}
         }
     }
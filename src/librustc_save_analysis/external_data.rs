@@ -64,6 +64,7 @@ impl SpanData {
 #[derive(Debug, RustcEncodable)]
 pub struct CratePreludeData {
     pub crate_name: String,
+    pub disambiguator: String,
     pub crate_root: String,
     pub external_crates: Vec<data::ExternalCrateData>,
     pub span: SpanData,
@@ -75,6 +76,7 @@ impl Lower for data::CratePreludeData {
     fn lower(self, tcx: TyCtxt) -> CratePreludeData {
         CratePreludeData {
             crate_name: self.crate_name,
+            disambiguator: self.disambiguator,
             crate_root: self.crate_root,
             external_crates: self.external_crates,
             span: SpanData::from_span(self.span, tcx.sess.codemap()),
@@ -0,0 +1,220 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Support for incremental dumps (`-Z save-analysis-incremental`).
+//!
+//! Next to the analysis we store a hash of every item that is directly
+//! contained in a module, keyed by the stable id of the item. On the next
+//! run, items whose hash did not change are left out of the dump, and only
+//! their stable ids are listed, so that consumers can carry over the data
+//! from the previous dump.
+//!
+//! The hash covers the HIR of the item and its span, so an item is dumped
+//! again if it is edited or moved. The data of an item also depends on
+//! other items, though: the types inferred for its variables, the methods
+//! its calls resolve to and the traits it implements all do. So the hash
+//! also covers the inputs that the analysis of the item was computed from,
+//! according to the dep-graph (which is built for this purpose): the HIR of
+//! the local items, and the hashes of the other crates. Modules are never
+//! skipped, since the items they contain are hashed separately.
+
+use rustc::dep_graph::DepNode;
+use rustc::hir::def_id::DefId;
+use rustc::hir::map as hir_map;
+use rustc::ty::TyCtxt;
+use rustc::util::nodemap::{DefIdMap, DefIdSet, NodeSet};
+use rustc_incremental::SvhCalculate;
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::hash::{Hash, Hasher, SipHasher};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use syntax::ast;
+
+use stable_id;
+
+pub struct ItemHashes {
+    /// The items which did not change since the previous dump.
+    pub unchanged: NodeSet,
+    /// The stable ids of the unchanged items.
+    pub unchanged_ids: Vec<String>,
+    hashes: BTreeMap<String, u64>,
+}
+
+impl ItemHashes {
+    /// Hashes the items of `krate` and compares them to the hashes stored
+    /// at `path` by the previous dump, if any.
+    pub fn compute<'l, 'tcx>(tcx: TyCtxt<'l, 'tcx, 'tcx>,
+                             krate: &ast::Crate,
+                             path: &Path)
+                             -> ItemHashes {
+        let previous = match load_hashes(path) {
+            Ok(hashes) => hashes,
+            Err(err) => {
+                debug!("no item hashes loaded from {}: {}", path.display(), err);
+                BTreeMap::new()
+            }
+        };
+
+        let _ignore = tcx.dep_graph.in_ignore();
+
+        let mut items = vec![];
+        module_items(tcx, &krate.module, &mut items);
+        let item_ids = items.iter().map(|&(def_id, _)| def_id).collect();
+        let inputs = item_inputs(tcx, &item_ids);
+
+        let mut item_hashes = ItemHashes {
+            unchanged: NodeSet(),
+            unchanged_ids: vec![],
+            hashes: BTreeMap::new(),
+        };
+        for (def_id, item) in items {
+            let id = match stable_id(tcx, def_id) {
+                Some(id) => id,
+                None => continue,
+            };
+
+            let mut state = SipHasher::new();
+            tcx.calculate_item_hash(def_id).hash(&mut state);
+            item.span.lo.0.hash(&mut state);
+            item.span.hi.0.hash(&mut state);
+            if let Some(inputs) = inputs.get(&def_id) {
+                for (input, hash) in inputs {
+                    input.hash(&mut state);
+                    hash.hash(&mut state);
+                }
+            }
+            let hash = state.finish();
+
+            if previous.get(&id) == Some(&hash) {
+                item_hashes.unchanged.insert(item.id);
+                item_hashes.unchanged_ids.push(id.clone());
+            }
+            item_hashes.hashes.insert(id, hash);
+        }
+        item_hashes
+    }
+
+    /// Stores the hashes for the next dump.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut file = File::create(path)?;
+        for (id, hash) in &self.hashes {
+            write!(file, "{:016x} {}\n", hash, id)?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects the items that are directly contained in `module` and its
+/// submodules, other than the submodules themselves.
+fn module_items<'l, 'tcx, 'k>(tcx: TyCtxt<'l, 'tcx, 'tcx>,
+                              module: &'k ast::Mod,
+                              items: &mut Vec<(DefId, &'k ast::Item)>) {
+    for item in &module.items {
+        if let ast::ItemKind::Mod(ref module) = item.node {
+            module_items(tcx, module, items);
+            continue;
+        }
+
+        // Only items that made it into the HIR can be hashed.
+        match tcx.map.find(item.id) {
+            Some(hir_map::NodeItem(_)) => {}
+            _ => continue,
+        }
+        items.push((tcx.map.local_def_id(item.id), &**item));
+    }
+}
+
+/// The inputs that the results about each of `items` (or about the
+/// definitions in it) were computed from, with their hashes, by stable id.
+fn item_inputs<'l, 'tcx>(tcx: TyCtxt<'l, 'tcx, 'tcx>,
+                         items: &DefIdSet)
+                         -> DefIdMap<BTreeMap<String, u64>> {
+    let query = tcx.dep_graph.query();
+    let mut hir_hashes = DefIdMap();
+    let mut inputs = DefIdMap();
+    for node in query.nodes() {
+        let mut owners = vec![];
+        node.map_def(|&def_id| {
+            if let Some(owner) = owner(tcx, items, def_id) {
+                owners.push(owner);
+            }
+            Some(())
+        });
+        if owners.is_empty() {
+            continue;
+        }
+
+        for input in query.transitive_predecessors(node) {
+            let (def_id, hash) = match *input {
+                DepNode::Hir(def_id) => {
+                    let hash = *hir_hashes.entry(def_id).or_insert_with(|| {
+                        tcx.calculate_item_hash(def_id)
+                    });
+                    (def_id, hash)
+                }
+                // We do not know which items of another crate changed,
+                // only whether the crate did.
+                DepNode::MetaData(def_id) if !def_id.is_local() => {
+                    (def_id, tcx.sess.cstore.crate_hash(def_id.krate).as_u64())
+                }
+                _ => continue,
+            };
+            if let Some(id) = stable_id(tcx, def_id) {
+                for owner in &owners {
+                    inputs.entry(*owner)
+                          .or_insert_with(BTreeMap::new)
+                          .insert(id.clone(), hash);
+                }
+            }
+        }
+    }
+    inputs
+}
+
+/// The item among `items` that contains the definition `def_id`, if any.
+fn owner<'l, 'tcx>(tcx: TyCtxt<'l, 'tcx, 'tcx>, items: &DefIdSet, def_id: DefId)
+                   -> Option<DefId> {
+    if !def_id.is_local() {
+        return None;
+    }
+    let mut def_id = def_id;
+    loop {
+        if items.contains(&def_id) {
+            return Some(def_id);
+        }
+        match tcx.def_key(def_id).parent {
+            Some(parent) => def_id.index = parent,
+            None => return None,
+        }
+    }
+}
+
+fn load_hashes(path: &Path) -> io::Result<BTreeMap<String, u64>> {
+    let mut hashes = BTreeMap::new();
+    let file = File::open(path)?;
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let mut parts = line.splitn(2, ' ');
+        let hash = parts.next().and_then(|hash| u64::from_str_radix(hash, 16).ok());
+        match (hash, parts.next()) {
+            (Some(hash), Some(id)) => {
+                hashes.insert(id.to_string(), hash);
+            }
+            _ => {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          format!("malformed line `{}`", line)));
+            }
+        }
+    }
+    Ok(hashes)
+}
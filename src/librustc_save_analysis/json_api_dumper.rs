@@ -0,0 +1,551 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use rustc::hir::def_id::{DefId, DefIndex};
+use rustc::ty::TyCtxt;
+use rustc::util::nodemap::DefIdMap;
+use rustc_serialize::json::as_json;
+
+use external_data::*;
+use data::VariableKind;
+use dump::Dump;
+
+// A dumper for the public interface of a crate: unlike `JsonDumper`, it
+// ignores references and anything that is not visible from outside the
+// crate, but it keeps the relationships between items (which module,
+// trait or impl an item belongs to, which impls exist for which types
+// and traits, and which supertraits a trait has), along with the
+// documentation of each item.
+pub struct JsonApiDumper<'b, 'tcx: 'b, W: Write + 'b> {
+    tcx: TyCtxt<'b, 'tcx, 'tcx>,
+    output: &'b mut W,
+    result: Analysis,
+    // The public items of the crate, mapped to their documentation.
+    public_items: DefIdMap<String>,
+}
+
+impl<'b, 'tcx, W: Write> JsonApiDumper<'b, 'tcx, W> {
+    pub fn new(writer: &'b mut W,
+               tcx: TyCtxt<'b, 'tcx, 'tcx>,
+               public_items: DefIdMap<String>)
+               -> JsonApiDumper<'b, 'tcx, W> {
+        JsonApiDumper {
+            tcx: tcx,
+            output: writer,
+            result: Analysis::new(),
+            public_items: public_items,
+        }
+    }
+
+    fn docs(&self, id: DefId) -> Option<String> {
+        self.public_items.get(&id).cloned()
+    }
+
+    fn public_children(&self, ids: Vec<DefId>) -> Vec<Id> {
+        ids.into_iter()
+           .filter(|id| self.public_items.contains_key(id))
+           .map(|id| From::from(id))
+           .collect()
+    }
+}
+
+impl<'b, 'tcx, W: Write> Drop for JsonApiDumper<'b, 'tcx, W> {
+    fn drop(&mut self) {
+        let tcx = self.tcx;
+        self.result.stable_ids = self.result.ids().into_iter().filter_map(|id| {
+            ::stable_id(tcx, id.def_id()).map(|stable_id| {
+                StableId { id: id, stable_id: stable_id }
+            })
+        }).collect();
+
+        if let Err(_) = write!(self.output, "{}", as_json(&self.result)) {
+            error!("Error writing output");
+        }
+    }
+}
+
+// Only public items are recorded, and only their public children.
+macro_rules! impl_fn {
+    ($fn_name: ident, $data_type: ident) => {
+        fn $fn_name(&mut self, data: $data_type) {
+            if let Some(docs) = self.docs(data.id) {
+                let mut def: Def = From::from(data);
+                def.docs = docs;
+                self.result.defs.push(def);
+            }
+        }
+    };
+    ($fn_name: ident, $data_type: ident, $children: ident) => {
+        fn $fn_name(&mut self, mut data: $data_type) {
+            if let Some(docs) = self.docs(data.id) {
+                let children = ::std::mem::replace(&mut data.$children, vec![]);
+                let mut def: Def = From::from(data);
+                def.docs = docs;
+                def.children = self.public_children(children);
+                self.result.defs.push(def);
+            }
+        }
+    }
+}
+
+impl<'b, 'tcx, W: Write + 'b> Dump for JsonApiDumper<'b, 'tcx, W> {
+    fn crate_prelude(&mut self, data: CratePreludeData) {
+        self.result.prelude = Some(data)
+    }
+
+    fn extern_crate(&mut self, data: ExternCrateData) {
+        if self.public_items.contains_key(&data.id) {
+            self.result.reexports.push(From::from(data));
+        }
+    }
+    fn use_data(&mut self, data: UseData) {
+        if self.public_items.contains_key(&data.id) {
+            self.result.reexports.push(From::from(data));
+        }
+    }
+    fn use_glob(&mut self, data: UseGlobData) {
+        if self.public_items.contains_key(&data.id) {
+            self.result.reexports.push(From::from(data));
+        }
+    }
+
+    impl_fn!(enum_data, EnumData, variants);
+    impl_fn!(tuple_variant, TupleVariantData);
+    impl_fn!(struct_variant, StructVariantData);
+    impl_fn!(struct_data, StructData, fields);
+    impl_fn!(trait_data, TraitData, items);
+    impl_fn!(function, FunctionData);
+    impl_fn!(method, MethodData);
+    impl_fn!(mod_data, ModData, items);
+    impl_fn!(typedef, TypeDefData);
+    impl_fn!(variable, VariableData);
+
+    fn impl_data(&mut self, data: ImplData) {
+        if self.public_items.contains_key(&data.id) {
+            self.result.impls.push(From::from(data));
+        }
+    }
+    fn inheritance(&mut self, data: InheritanceData) {
+        if self.public_items.contains_key(&data.deriv_id) {
+            self.result.relations.push(From::from(data));
+        }
+    }
+}
+
+#[derive(Debug, RustcEncodable)]
+struct Analysis {
+    prelude: Option<CratePreludeData>,
+    reexports: Vec<Reexport>,
+    defs: Vec<Def>,
+    impls: Vec<Impl>,
+    relations: Vec<Relation>,
+    stable_ids: Vec<StableId>,
+}
+
+impl Analysis {
+    fn new() -> Analysis {
+        Analysis {
+            prelude: None,
+            reexports: vec![],
+            defs: vec![],
+            impls: vec![],
+            relations: vec![],
+            stable_ids: vec![],
+        }
+    }
+
+    // All the ids that occur in the analysis.
+    fn ids(&self) -> BTreeSet<Id> {
+        let mut ids = BTreeSet::new();
+        for reexport in &self.reexports {
+            ids.insert(reexport.id);
+            ids.extend(reexport.parent);
+            ids.extend(reexport.ref_id);
+        }
+        for def in &self.defs {
+            ids.insert(def.id);
+            ids.extend(def.parent);
+            ids.extend(def.children.iter().cloned());
+            ids.extend(def.decl_id);
+        }
+        for imp in &self.impls {
+            ids.insert(imp.id);
+            ids.extend(imp.parent);
+            ids.extend(imp.trait_id);
+            ids.extend(imp.self_id);
+        }
+        for relation in &self.relations {
+            ids.insert(relation.from);
+            ids.insert(relation.to);
+        }
+        ids
+    }
+}
+
+// DefId::index is a newtype and so the JSON serialisation is ugly. Therefore
+// we use our own Id which is the same, but without the newtype.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, RustcEncodable)]
+struct Id {
+    krate: u32,
+    index: u32,
+}
+
+impl Id {
+    fn def_id(&self) -> DefId {
+        DefId { krate: self.krate, index: DefIndex::from_u32(self.index) }
+    }
+}
+
+// See the `StableId` of `JsonDumper`.
+#[derive(Debug, RustcEncodable)]
+struct StableId {
+    id: Id,
+    stable_id: String,
+}
+
+impl From<DefId> for Id {
+    fn from(id: DefId) -> Id {
+        Id {
+            krate: id.krate,
+            index: id.index.as_u32(),
+        }
+    }
+}
+
+#[derive(Debug, RustcEncodable)]
+struct Reexport {
+    kind: ReexportKind,
+    id: Id,
+    span: SpanData,
+    name: String,
+    // The module the reexport is in.
+    parent: Option<Id>,
+    // The reexported item, if known.
+    ref_id: Option<Id>,
+    value: String,
+}
+
+#[derive(Debug, RustcEncodable)]
+enum ReexportKind {
+    ExternCrate,
+    Use,
+    GlobUse,
+}
+
+impl From<ExternCrateData> for Reexport {
+    fn from(data: ExternCrateData) -> Reexport {
+        Reexport {
+            kind: ReexportKind::ExternCrate,
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            parent: Some(From::from(data.scope)),
+            ref_id: None,
+            value: data.location,
+        }
+    }
+}
+impl From<UseData> for Reexport {
+    fn from(data: UseData) -> Reexport {
+        Reexport {
+            kind: ReexportKind::Use,
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            parent: Some(From::from(data.scope)),
+            ref_id: data.mod_id.map(|id| From::from(id)),
+            value: String::new(),
+        }
+    }
+}
+impl From<UseGlobData> for Reexport {
+    fn from(data: UseGlobData) -> Reexport {
+        Reexport {
+            kind: ReexportKind::GlobUse,
+            id: From::from(data.id),
+            span: data.span,
+            name: "*".to_owned(),
+            parent: Some(From::from(data.scope)),
+            ref_id: None,
+            value: data.names.join(", "),
+        }
+    }
+}
+
+#[derive(Debug, RustcEncodable)]
+struct Def {
+    kind: DefKind,
+    id: Id,
+    span: SpanData,
+    name: String,
+    qualname: String,
+    value: String,
+    // The module, trait or impl that contains the item.
+    parent: Option<Id>,
+    // The public items contained in this one.
+    children: Vec<Id>,
+    decl_id: Option<Id>,
+    docs: String,
+}
+
+#[derive(Debug, RustcEncodable)]
+enum DefKind {
+    // value = variant names
+    Enum,
+    // value = enum name + variant name + types
+    Tuple,
+    // value = [enum name +] name + fields
+    Struct,
+    // value = signature
+    Trait,
+    // value = type + generics
+    Function,
+    // value = type + generics
+    Method,
+    // value = file_name
+    Mod,
+    // value = aliased type
+    Type,
+    // value = type and init expression (for all variable kinds).
+    Static,
+    Const,
+    Field,
+}
+
+impl From<EnumData> for Def {
+    fn from(data: EnumData) -> Def {
+        Def {
+            kind: DefKind::Enum,
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            qualname: data.qualname,
+            value: data.value,
+            parent: Some(From::from(data.scope)),
+            children: vec![],
+            decl_id: None,
+            docs: String::new(),
+        }
+    }
+}
+
+impl From<TupleVariantData> for Def {
+    fn from(data: TupleVariantData) -> Def {
+        Def {
+            kind: DefKind::Tuple,
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            qualname: data.qualname,
+            value: data.value,
+            parent: Some(From::from(data.scope)),
+            children: vec![],
+            decl_id: None,
+            docs: String::new(),
+        }
+    }
+}
+impl From<StructVariantData> for Def {
+    fn from(data: StructVariantData) -> Def {
+        Def {
+            kind: DefKind::Struct,
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            qualname: data.qualname,
+            value: data.value,
+            parent: Some(From::from(data.scope)),
+            children: vec![],
+            decl_id: None,
+            docs: String::new(),
+        }
+    }
+}
+impl From<StructData> for Def {
+    fn from(data: StructData) -> Def {
+        Def {
+            kind: DefKind::Struct,
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            qualname: data.qualname,
+            value: data.value,
+            parent: Some(From::from(data.scope)),
+            children: vec![],
+            decl_id: None,
+            docs: String::new(),
+        }
+    }
+}
+impl From<TraitData> for Def {
+    fn from(data: TraitData) -> Def {
+        Def {
+            kind: DefKind::Trait,
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            qualname: data.qualname,
+            value: data.value,
+            parent: Some(From::from(data.scope)),
+            children: vec![],
+            decl_id: None,
+            docs: String::new(),
+        }
+    }
+}
+impl From<FunctionData> for Def {
+    fn from(data: FunctionData) -> Def {
+        Def {
+            kind: DefKind::Function,
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            qualname: data.qualname,
+            value: data.value,
+            parent: Some(From::from(data.scope)),
+            children: vec![],
+            decl_id: data.declaration.map(|id| From::from(id)),
+            docs: String::new(),
+        }
+    }
+}
+impl From<MethodData> for Def {
+    fn from(data: MethodData) -> Def {
+        Def {
+            kind: DefKind::Method,
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            qualname: data.qualname,
+            value: data.value,
+            parent: Some(From::from(data.scope)),
+            children: vec![],
+            decl_id: data.decl_id.map(|id| From::from(id)),
+            docs: String::new(),
+        }
+    }
+}
+impl From<ModData> for Def {
+    fn from(data: ModData) -> Def {
+        Def {
+            kind: DefKind::Mod,
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            qualname: data.qualname,
+            value: data.filename,
+            parent: Some(From::from(data.scope)),
+            children: vec![],
+            decl_id: None,
+            docs: String::new(),
+        }
+    }
+}
+impl From<TypeDefData> for Def {
+    fn from(data: TypeDefData) -> Def {
+        Def {
+            kind: DefKind::Type,
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            qualname: data.qualname,
+            value: data.value,
+            parent: None,
+            children: vec![],
+            decl_id: None,
+            docs: String::new(),
+        }
+    }
+}
+impl From<VariableData> for Def {
+    fn from(data: VariableData) -> Def {
+        Def {
+            kind: match data.kind {
+                VariableKind::Static => DefKind::Static,
+                VariableKind::Const => DefKind::Const,
+                VariableKind::Field => DefKind::Field,
+                // Locals are never public.
+                VariableKind::Local => bug!("public local variable"),
+            },
+            id: From::from(data.id),
+            span: data.span,
+            name: data.name,
+            qualname: data.qualname,
+            value: data.value,
+            parent: Some(From::from(data.scope)),
+            children: vec![],
+            decl_id: None,
+            docs: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, RustcEncodable)]
+struct Impl {
+    kind: ImplKind,
+    id: Id,
+    span: SpanData,
+    // The module that contains the impl.
+    parent: Option<Id>,
+    // The implemented trait, for trait impls.
+    trait_id: Option<Id>,
+    // The implementing type, if it is a nominal type.
+    self_id: Option<Id>,
+}
+
+#[derive(Debug, RustcEncodable)]
+enum ImplKind {
+    Inherent,
+    Direct,
+}
+
+impl From<ImplData> for Impl {
+    fn from(data: ImplData) -> Impl {
+        Impl {
+            kind: match data.trait_ref {
+                Some(_) => ImplKind::Direct,
+                None => ImplKind::Inherent,
+            },
+            id: From::from(data.id),
+            span: data.span,
+            parent: Some(From::from(data.scope)),
+            trait_id: data.trait_ref.map(|id| From::from(id)),
+            self_id: data.self_ref.map(|id| From::from(id)),
+        }
+    }
+}
+
+#[derive(Debug, RustcEncodable)]
+struct Relation {
+    kind: RelationKind,
+    span: SpanData,
+    from: Id,
+    to: Id,
+}
+
+#[derive(Debug, RustcEncodable)]
+enum RelationKind {
+    // `from` is a supertrait of `to`.
+    SuperTrait,
+}
+
+impl From<InheritanceData> for Relation {
+    fn from(data: InheritanceData) -> Relation {
+        Relation {
+            kind: RelationKind::SuperTrait,
+            span: data.span,
+            from: From::from(data.base_id),
+            to: From::from(data.deriv_id),
+        }
+    }
+}
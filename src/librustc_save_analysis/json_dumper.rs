@@ -8,28 +8,46 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::collections::BTreeSet;
 use std::io::Write;
 
-use rustc::hir::def_id::DefId;
+use rustc::hir::def_id::{DefId, DefIndex};
+use rustc::ty::TyCtxt;
 use rustc_serialize::json::as_json;
 
 use external_data::*;
 use data::VariableKind;
 use dump::Dump;
 
-pub struct JsonDumper<'b, W: Write + 'b> {
+pub struct JsonDumper<'b, 'tcx: 'b, W: Write + 'b> {
+    tcx: TyCtxt<'b, 'tcx, 'tcx>,
     output: &'b mut W,
     result: Analysis,
 }
 
-impl<'b, W: Write> JsonDumper<'b, W> {
-    pub fn new(writer: &'b mut W) -> JsonDumper<'b, W> {
-        JsonDumper { output: writer, result: Analysis::new() }
+impl<'b, 'tcx, W: Write> JsonDumper<'b, 'tcx, W> {
+    pub fn new(writer: &'b mut W, tcx: TyCtxt<'b, 'tcx, 'tcx>) -> JsonDumper<'b, 'tcx, W> {
+        JsonDumper { tcx: tcx, output: writer, result: Analysis::new() }
+    }
+
+    /// Makes this an incremental dump, which only has data for items that
+    /// changed since the previous dump. `ids` are the stable ids of the
+    /// items that did not change, whose data should be taken from the
+    /// previous dump.
+    pub fn set_unchanged_items(&mut self, ids: Vec<String>) {
+        self.result.unchanged_items = Some(ids);
     }
 }
 
-impl<'b, W: Write> Drop for JsonDumper<'b, W> {
+impl<'b, 'tcx, W: Write> Drop for JsonDumper<'b, 'tcx, W> {
     fn drop(&mut self) {
+        let tcx = self.tcx;
+        self.result.stable_ids = self.result.ids().into_iter().filter_map(|id| {
+            ::stable_id(tcx, id.def_id()).map(|stable_id| {
+                StableId { id: id, stable_id: stable_id }
+            })
+        }).collect();
+
         if let Err(_) = write!(self.output, "{}", as_json(&self.result)) {
             error!("Error writing output");
         }
@@ -44,7 +62,7 @@ macro_rules! impl_fn {
     }
 }
 
-impl<'b, W: Write + 'b> Dump for JsonDumper<'b, W> {
+impl<'b, 'tcx, W: Write + 'b> Dump for JsonDumper<'b, 'tcx, W> {
     fn crate_prelude(&mut self, data: CratePreludeData) {
         self.result.prelude = Some(data)
     }
@@ -92,6 +110,10 @@ struct Analysis {
     defs: Vec<Def>,
     refs: Vec<Ref>,
     macro_refs: Vec<MacroRef>,
+    stable_ids: Vec<StableId>,
+    // For incremental dumps, the items whose data is unchanged since the
+    // previous dump, and which are therefore missing from this one.
+    unchanged_items: Option<Vec<String>>,
 }
 
 impl Analysis {
@@ -102,18 +124,48 @@ impl Analysis {
             defs: vec![],
             refs: vec![],
             macro_refs: vec![],
+            stable_ids: vec![],
+            unchanged_items: None,
         }
     }
+
+    // All the ids that occur in the analysis.
+    fn ids(&self) -> BTreeSet<Id> {
+        let mut ids = BTreeSet::new();
+        ids.extend(self.imports.iter().map(|import| import.id));
+        for def in &self.defs {
+            ids.insert(def.id);
+            ids.extend(def.children.iter().cloned());
+            ids.extend(def.decl_id);
+        }
+        ids.extend(self.refs.iter().map(|r| r.ref_id));
+        ids
+    }
 }
 
 // DefId::index is a newtype and so the JSON serialisation is ugly. Therefore
 // we use our own Id which is the same, but without the newtype.
-#[derive(Debug, RustcEncodable)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, RustcEncodable)]
 struct Id {
     krate: u32,
     index: u32,
 }
 
+impl Id {
+    fn def_id(&self) -> DefId {
+        DefId { krate: self.krate, index: DefIndex::from_u32(self.index) }
+    }
+}
+
+// Ids are only meaningful within one compilation of one crate, so we also
+// map them to ids that can be used to find the same item in the analysis
+// of other crates, or of other compilations of this crate.
+#[derive(Debug, RustcEncodable)]
+struct StableId {
+    id: Id,
+    stable_id: String,
+}
+
 impl From<DefId> for Id {
     fn from(id: DefId) -> Id {
         Id {
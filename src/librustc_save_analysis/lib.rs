@@ -26,11 +26,14 @@
 
 #[macro_use] extern crate log;
 #[macro_use] extern crate syntax;
+extern crate rustc_incremental;
 extern crate serialize as rustc_serialize;
 extern crate syntax_pos;
 
 mod csv_dumper;
 mod json_dumper;
+mod json_api_dumper;
+mod incremental;
 mod data;
 mod dump;
 mod dump_visitor;
@@ -43,13 +46,17 @@ use rustc::hir::map::{Node, NodeItem};
 use rustc::hir::def::Def;
 use rustc::hir::def_id::DefId;
 use rustc::session::config::CrateType::CrateTypeExecutable;
+use rustc::middle::privacy::AccessLevels;
 use rustc::ty::{self, TyCtxt};
+use rustc::util::nodemap::{DefIdMap, NodeSet};
 
 use std::env;
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 
 use syntax::ast::{self, NodeId, PatKind};
+use syntax::attr::AttrMetaMethods;
+use syntax::parse::lexer::comments::strip_doc_comment_decoration;
 use syntax::parse::token::{self, keywords};
 use syntax::visit::{self, Visitor};
 use syntax::print::pprust::{ty_to_string, arg_to_string};
@@ -58,9 +65,11 @@ use syntax_pos::*;
 
 pub use self::csv_dumper::CsvDumper;
 pub use self::json_dumper::JsonDumper;
+pub use self::json_api_dumper::JsonApiDumper;
 pub use self::data::*;
 pub use self::dump::Dump;
 pub use self::dump_visitor::DumpVisitor;
+use self::incremental::ItemHashes;
 use self::span_utils::SpanUtils;
 
 // FIXME this is legacy code and should be removed
@@ -217,7 +226,7 @@ impl<'l, 'tcx: 'l> SaveContext<'l, 'tcx> {
                     variants: def.variants.iter().map(|v| v.node.data.id()).collect(),
                 }))
             }
-            ast::ItemKind::Impl(_, _, _, ref trait_ref, ref typ, _) => {
+            ast::ItemKind::Impl(_, _, _, _, ref trait_ref, ref typ, _) => {
                 let mut type_data = None;
                 let sub_span;
 
@@ -295,7 +304,7 @@ impl<'l, 'tcx: 'l> SaveContext<'l, 'tcx> {
             Some(impl_id) => match self.tcx.map.get_if_local(impl_id) {
                 Some(NodeItem(item)) => {
                     match item.node {
-                        hir::ItemImpl(_, _, _, _, ref ty, _) => {
+                        hir::ItemImpl(_, _, _, _, _, ref ty, _) => {
                             let mut result = String::from("<");
                             result.push_str(&rustc::hir::print::ty_to_string(&ty));
 
@@ -729,6 +738,8 @@ impl Visitor for PathCollector {
 pub enum Format {
     Csv,
     Json,
+    // Only the public interface of the crate, see `JsonApiDumper`.
+    JsonApi,
 }
 
 impl Format {
@@ -736,6 +747,7 @@ impl Format {
         match *self {
             Format::Csv => ".csv",
             Format::Json => ".json",
+            Format::JsonApi => ".api.json",
         }
     }
 }
@@ -793,9 +805,13 @@ pub fn process_crate<'l, 'tcx>(tcx: TyCtxt<'l, 'tcx, 'tcx>,
     let save_ctxt = SaveContext::new(tcx);
 
     macro_rules! dump {
-        ($new_dumper: expr) => {{
+        ($new_dumper: expr) => {
+            dump!($new_dumper, NodeSet())
+        };
+        ($new_dumper: expr, $skipped_items: expr) => {{
             let mut dumper = $new_dumper;
             let mut visitor = DumpVisitor::new(tcx, save_ctxt, analysis, &mut dumper);
+            visitor.skip_items($skipped_items);
 
             visitor.dump_crate_info(cratename, krate);
             visit::walk_crate(&mut visitor, krate);
@@ -804,12 +820,163 @@ pub fn process_crate<'l, 'tcx>(tcx: TyCtxt<'l, 'tcx, 'tcx>,
 
     match format {
         Format::Csv => dump!(CsvDumper::new(output)),
-        Format::Json => dump!(JsonDumper::new(output)),
+        Format::Json if tcx.sess.opts.debugging_opts.save_analysis_incremental => {
+            let hashes_path = root_path.join(format!("{}.hashes", out_name));
+            let item_hashes = ItemHashes::compute(tcx, krate, &hashes_path);
+
+            let mut dumper = JsonDumper::new(output, tcx);
+            dumper.set_unchanged_items(item_hashes.unchanged_ids.clone());
+            dump!(dumper, item_hashes.unchanged.clone());
+
+            if let Err(e) = item_hashes.save(&hashes_path) {
+                tcx.sess.err(&format!("Could not write {}: {}", hashes_path.display(), e));
+            }
+        }
+        Format::Json => dump!(JsonDumper::new(output, tcx)),
+        Format::JsonApi => {
+            let public_items = public_items(tcx, krate, &analysis.access_levels);
+            dump!(JsonApiDumper::new(output, tcx, public_items))
+        }
+    }
+}
+
+// Finds the items which are visible outside the crate, and maps them to
+// their documentation.
+fn public_items<'l, 'tcx>(tcx: TyCtxt<'l, 'tcx, 'tcx>,
+                          krate: &ast::Crate,
+                          access_levels: &AccessLevels)
+                          -> DefIdMap<String> {
+    let mut collector = PublicItemCollector {
+        tcx: tcx,
+        access_levels: access_levels,
+        cur_mod: ast::CRATE_NODE_ID,
+        items: DefIdMap(),
+    };
+    collector.record(ast::CRATE_NODE_ID, &krate.attrs);
+    visit::walk_crate(&mut collector, krate);
+    collector.items
+}
+
+struct PublicItemCollector<'l, 'tcx: 'l> {
+    tcx: TyCtxt<'l, 'tcx, 'tcx>,
+    access_levels: &'l AccessLevels,
+    cur_mod: NodeId,
+    items: DefIdMap<String>,
+}
+
+impl<'l, 'tcx> PublicItemCollector<'l, 'tcx> {
+    fn record(&mut self, id: NodeId, attrs: &[ast::Attribute]) {
+        if let Some(def_id) = self.tcx.map.opt_local_def_id(id) {
+            self.items.insert(def_id, docs_for_attrs(attrs));
+        }
+    }
+
+    fn record_if_public(&mut self, id: NodeId, attrs: &[ast::Attribute]) {
+        if self.access_levels.is_public(id) {
+            self.record(id, attrs);
+        }
+    }
+}
+
+impl<'l, 'tcx> Visitor for PublicItemCollector<'l, 'tcx> {
+    fn visit_item(&mut self, item: &ast::Item) {
+        match item.node {
+            // Imports do not get an access level of their own, but a `pub`
+            // import in a public module reexports whatever it imports.
+            ast::ItemKind::Use(_) | ast::ItemKind::ExternCrate(_) => {
+                if item.vis == ast::Visibility::Public &&
+                   self.access_levels.is_public(self.cur_mod) {
+                    self.record(item.id, &item.attrs);
+                }
+            }
+            _ => self.record_if_public(item.id, &item.attrs),
+        }
+
+        if let ast::ItemKind::Mod(_) = item.node {
+            let parent_mod = self.cur_mod;
+            self.cur_mod = item.id;
+            visit::walk_item(self, item);
+            self.cur_mod = parent_mod;
+        } else {
+            visit::walk_item(self, item);
+        }
+    }
+
+    fn visit_foreign_item(&mut self, item: &ast::ForeignItem) {
+        self.record_if_public(item.id, &item.attrs);
+        visit::walk_foreign_item(self, item);
+    }
+
+    fn visit_trait_item(&mut self, item: &ast::TraitItem) {
+        self.record_if_public(item.id, &item.attrs);
+        visit::walk_trait_item(self, item);
+    }
+
+    fn visit_impl_item(&mut self, item: &ast::ImplItem) {
+        self.record_if_public(item.id, &item.attrs);
+        visit::walk_impl_item(self, item);
+    }
+
+    fn visit_variant(&mut self, variant: &ast::Variant, generics: &ast::Generics, id: NodeId) {
+        self.record_if_public(variant.node.data.id(), &variant.node.attrs);
+        visit::walk_variant(self, variant, generics, id);
     }
+
+    fn visit_struct_field(&mut self, field: &ast::StructField) {
+        self.record_if_public(field.id, &field.attrs);
+        visit::walk_struct_field(self, field);
+    }
+
+    // Items in function bodies are never public, so there is no need to
+    // walk them.
+    fn visit_block(&mut self, _: &ast::Block) {}
+    fn visit_expr(&mut self, _: &ast::Expr) {}
+}
+
+fn docs_for_attrs(attrs: &[ast::Attribute]) -> String {
+    let mut result = String::new();
+    for attr in attrs {
+        if attr.check_name("doc") {
+            if let Some(value) = attr.value_str() {
+                if attr.node.is_sugared_doc {
+                    result.push_str(&strip_doc_comment_decoration(&value));
+                } else {
+                    result.push_str(&value);
+                }
+                result.push('\n');
+            }
+        }
+    }
+    result
 }
 
 // Utility functions for the module.
 
+/// A stable identifier for `def_id`. Unlike the crate number and index of
+/// the `DefId`, it is the same in the analysis of every crate that refers
+/// to the item, and does not change when unrelated items are added or
+/// removed. It has the form `crate/disambiguator::path[0]::to[0]::item[0]`,
+/// like the def paths that symbol names are built from.
+pub fn stable_id<'l, 'tcx>(tcx: TyCtxt<'l, 'tcx, 'tcx>, def_id: DefId) -> Option<String> {
+    if def_id == external_data::null_def_id() {
+        return None;
+    }
+
+    let def_path = tcx.def_path(def_id);
+    let mut id = if def_path.is_local() {
+        tcx.crate_name(def_path.krate).to_string()
+    } else {
+        tcx.sess.cstore.original_crate_name(def_path.krate).to_string()
+    };
+    id.push('/');
+    id.push_str(&tcx.crate_disambiguator(def_path.krate));
+    for component in &def_path.data {
+        id.push_str(&format!("::{}[{}]", component.data.as_interned_str(),
+                             component.disambiguator));
+    }
+    Some(id)
+}
+
 // Helper function to escape quotes in a string
 fn escape(s: String) -> String {
     s.replace("\"", "\"\"")
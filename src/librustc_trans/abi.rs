@@ -23,6 +23,7 @@ use cabi_powerpc64;
 use cabi_mips;
 use cabi_asmjs;
 use machine::{llalign_of_min, llsize_of, llsize_of_real, llsize_of_store};
+use monomorphize;
 use type_::Type;
 use type_of;
 
@@ -76,6 +77,10 @@ pub struct ArgType {
     pub cast: Option<Type>,
     /// Dummy argument, which is emitted before the real argument
     pub pad: Option<Type>,
+    /// For unions, a literal struct of the sizing types of all fields.
+    /// `ty` only holds the most aligned field, but C classifies a union
+    /// by every one of its fields, so the `cabi_*` modules look at these.
+    pub union_fields: Option<Type>,
     /// LLVM attributes of argument
     pub attrs: llvm::Attributes
 }
@@ -89,6 +94,7 @@ impl ArgType {
             signedness: None,
             cast: None,
             pad: None,
+            union_fields: None,
             attrs: llvm::Attributes::default()
         }
     }
@@ -315,6 +321,15 @@ impl FnType {
                 if ty.is_integral() {
                     arg.signedness = Some(ty.is_signed());
                 }
+                if let ty::TyStruct(def, substs) = ty.sty {
+                    if def.is_union() {
+                        let fields = def.struct_variant().fields.iter().map(|field| {
+                            let field_ty = monomorphize::field_ty(ccx.tcx(), substs, field);
+                            type_of::sizing_type_of(ccx, field_ty)
+                        }).collect::<Vec<_>>();
+                        arg.union_fields = Some(Type::struct_(ccx, &fields, false));
+                    }
+                }
                 if llsize_of_real(ccx, arg.ty) == 0 {
                     // For some forsaken reason, x86_64-pc-windows-gnu
                     // doesn't ignore zero-sized struct arguments.
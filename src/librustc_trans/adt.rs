@@ -47,7 +47,7 @@ use super::Disr;
 use std;
 use std::rc::Rc;
 
-use llvm::{ValueRef, True, IntEQ, IntNE};
+use llvm::{ValueRef, True, IntEQ, IntNE, IntULT};
 use rustc::ty::subst;
 use rustc::ty::{self, Ty, TyCtxt};
 use syntax::ast;
@@ -104,6 +104,9 @@ pub enum Repr<'tcx> {
     /// (The flag if nonzero, represents the initialization value to use;
     ///  if zero, then use no flag at all.)
     Univariant(Struct<'tcx>, u8),
+    /// Untagged unions: all fields start at offset 0 and the storage is
+    /// large enough, and aligned enough, to hold any one of them.
+    UntaggedUnion(Union<'tcx>),
     /// General-case enums: for each case there is a struct, and they
     /// all start with a field for the discriminant.
     ///
@@ -149,6 +152,22 @@ pub struct Struct<'tcx> {
     pub align: u32,
     pub sized: bool,
     pub packed: bool,
+    // If nonzero, fields are aligned to at most this many bytes, as with
+    // `#[repr(packed(N))]`; over-aligned fields are laid out as arrays
+    // of a less aligned type, see `pack_llfield`.
+    pub pack: u32,
+    // If nonzero, the struct is padded out to this alignment, as with
+    // `#[repr(align(N))]`, by a trailing zero-sized field.
+    pub min_align: u32,
+    pub fields: Vec<Ty<'tcx>>,
+}
+
+/// For untagged unions.
+#[derive(Eq, PartialEq, Debug)]
+pub struct Union<'tcx> {
+    pub min_size: u64,
+    pub align: u32,
+    pub packed: bool,
     pub fields: Vec<Ty<'tcx>>,
 }
 
@@ -250,11 +269,19 @@ fn represent_type_uncached<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
         ty::TyTuple(ref elems) => {
             Univariant(mk_struct(cx, &elems[..], false, t), 0)
         }
+        ty::TyStruct(def, substs) if def.is_union() => {
+            let ftys = def.struct_variant().fields.iter().map(|field| {
+                monomorphize::field_ty(cx.tcx(), substs, field)
+            }).collect::<Vec<_>>();
+            let packed = cx.tcx().lookup_packed(def.did).is_some();
+            UntaggedUnion(mk_union(cx, &ftys[..], packed, t))
+        }
         ty::TyStruct(def, substs) => {
             let mut ftys = def.struct_variant().fields.iter().map(|field| {
                 monomorphize::field_ty(cx.tcx(), substs, field)
             }).collect::<Vec<_>>();
-            let packed = cx.tcx().lookup_packed(def.did);
+            let pack = cx.tcx().lookup_packed(def.did);
+            let align = cx.tcx().lookup_align(def.did);
             // FIXME(16758) don't add a drop flag to unsized structs, as it
             // won't actually be in the location we say it is because it'll be after
             // the unsized field. Several other pieces of code assume that the unsized
@@ -264,7 +291,8 @@ fn represent_type_uncached<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                 ftys.push(cx.tcx().dtor_type());
             }
 
-            Univariant(mk_struct(cx, &ftys[..], packed, t), dtor_to_init_u8(dtor))
+            Univariant(mk_struct_with_hints(cx, &ftys[..], pack, align, t),
+                       dtor_to_init_u8(dtor))
         }
         ty::TyClosure(_, ref substs) => {
             Univariant(mk_struct(cx, &substs.upvar_tys, false, t), 0)
@@ -558,22 +586,116 @@ fn mk_struct<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                        tys: &[Ty<'tcx>], packed: bool,
                        scapegoat: Ty<'tcx>)
                        -> Struct<'tcx> {
+    let pack = if packed { Some(1) } else { None };
+    mk_struct_with_hints(cx, tys, pack, None, scapegoat)
+}
+
+/// Like `mk_struct`, but honouring the `#[repr(packed(N))]` and
+/// `#[repr(align(N))]` hints of a struct definition.
+fn mk_struct_with_hints<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
+                                  tys: &[Ty<'tcx>],
+                                  pack: Option<u32>,
+                                  align: Option<u32>,
+                                  scapegoat: Ty<'tcx>)
+                                  -> Struct<'tcx> {
+    // Packing to a single byte is what LLVM packed structs already do.
+    let packed = pack == Some(1);
+    let pack = match pack {
+        Some(pack) if pack > 1 => pack,
+        _ => 0
+    };
+
     let sized = tys.iter().all(|&ty| type_is_sized(cx.tcx(), ty));
     let lltys : Vec<Type> = if sized {
-        tys.iter().map(|&ty| type_of::sizing_type_of(cx, ty)).collect()
+        tys.iter().map(|&ty| pack_llfield(cx, type_of::sizing_type_of(cx, ty), pack)).collect()
     } else {
         tys.iter().filter(|&ty| type_is_sized(cx.tcx(), *ty))
-           .map(|&ty| type_of::sizing_type_of(cx, ty)).collect()
+           .map(|&ty| pack_llfield(cx, type_of::sizing_type_of(cx, ty), pack)).collect()
     };
 
     ensure_struct_fits_in_address_space(cx, &lltys[..], packed, scapegoat);
 
     let llty_rec = Type::struct_(cx, &lltys[..], packed);
+    let mut size = machine::llsize_of_alloc(cx, llty_rec);
+    let mut llalign = machine::llalign_of_min(cx, llty_rec);
+
+    // FIXME: unsized structs don't get the extra alignment, as the padding
+    // field would have to follow the unsized one.
+    let min_align = match align {
+        Some(align) if sized && align > llalign => align,
+        _ => 0
+    };
+    if min_align > 0 {
+        size = roundup(size, min_align);
+        llalign = min_align;
+    }
+
     Struct {
-        size: machine::llsize_of_alloc(cx, llty_rec),
-        align: machine::llalign_of_min(cx, llty_rec),
+        size: size,
+        align: llalign,
         sized: sized,
         packed: packed,
+        pack: pack,
+        min_align: min_align,
+        fields: tys.to_vec(),
+    }
+}
+
+/// Get an LLVM type whose size and alignment are both `align` bytes.
+fn llty_with_align(cx: &CrateContext, align: u32) -> Type {
+    let candidates = [Type::i8(cx), Type::i16(cx), Type::i32(cx), Type::i64(cx), Type::f64(cx)];
+    let vector = Type::vector(&Type::i8(cx), align as u64);
+    let found = candidates.iter().chain(Some(&vector)).cloned().find(|&llty| {
+        machine::llalign_of_min(cx, llty) == align &&
+            machine::llsize_of_alloc(cx, llty) == align as u64
+    });
+    match found {
+        Some(llty) => llty,
+        None => cx.sess().fatal(&format!("no LLVM type has an alignment of {} bytes \
+                                          on this target", align))
+    }
+}
+
+/// Get the type a field of LLVM type `llty` takes in a struct whose fields
+/// are aligned to at most `pack` bytes (no limit if `pack` is 0).
+///
+/// A field that is aligned more than that is replaced by an array of the
+/// same size, made of `pack`-aligned elements, so that LLVM places it at
+/// the lower alignment. Accesses to it need to cast the field pointer.
+fn pack_llfield(cx: &CrateContext, llty: Type, pack: u32) -> Type {
+    if pack == 0 || machine::llalign_of_min(cx, llty) <= pack {
+        return llty;
+    }
+    let unit = llty_with_align(cx, pack);
+    Type::array(&unit, machine::llsize_of_alloc(cx, llty) / pack as u64)
+}
+
+fn mk_union<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
+                      tys: &[Ty<'tcx>], packed: bool,
+                      scapegoat: Ty<'tcx>)
+                      -> Union<'tcx> {
+    let mut min_size = 0;
+    let mut align = 0;
+    for llty in tys.iter().map(|&ty| type_of::sizing_type_of(cx, ty)) {
+        let field_size = machine::llsize_of_alloc(cx, llty);
+        if min_size < field_size {
+            min_size = field_size;
+        }
+        let field_align = machine::llalign_of_min(cx, llty);
+        if align < field_align {
+            align = field_align;
+        }
+    }
+    let align = if packed { 1 } else { align };
+
+    if roundup(min_size, align) >= cx.obj_size_bound() {
+        cx.report_overbig_object(scapegoat);
+    }
+
+    Union {
+        min_size: min_size,
+        align: align,
+        packed: packed,
         fields: tys.to_vec(),
     }
 }
@@ -628,9 +750,12 @@ fn range_to_inttype(cx: &CrateContext, hint: Hint, bounds: &IntBounds) -> IntTyp
         attr::ReprAny => {
             attempts = choose_shortest;
         },
-        attr::ReprPacked => {
+        attr::ReprPacked(_) => {
             bug!("range_to_inttype: found ReprPacked on an enum");
         }
+        attr::ReprAlign(_) => {
+            bug!("range_to_inttype: found ReprAlign on an enum");
+        }
         attr::ReprSimd => {
             bug!("range_to_inttype: found ReprSimd on an enum");
         }
@@ -751,6 +876,8 @@ pub fn finish_type_of<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                                 r: &Repr<'tcx>, llty: &mut Type) {
     match *r {
         CEnum(..) | General(..) | RawNullablePointer { .. } => { }
+        UntaggedUnion(ref un) =>
+            llty.set_struct_body(&union_llfields(cx, un, false), un.packed),
         Univariant(ref st, _) | StructWrappedNullablePointer { nonnull: ref st, .. } =>
             llty.set_struct_body(&struct_llfields(cx, st, false, false),
                                  st.packed)
@@ -803,6 +930,18 @@ fn generic_type_of<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                 }
             }
         }
+        UntaggedUnion(ref un) => {
+            match name {
+                None => {
+                    TypeContext::direct(
+                        Type::struct_(cx, &union_llfields(cx, un, sizing), un.packed))
+                }
+                Some(name) => {
+                    assert_eq!(sizing, false);
+                    TypeContext::direct(Type::named_struct(cx, name))
+                }
+            }
+        }
         General(ity, ref sts, dtor_needed) => {
             let dtor_needed = dtor_needed != 0;
             // We need a representation that has:
@@ -861,14 +1000,47 @@ fn generic_type_of<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
     }
 }
 
+/// The LLVM fields of a union: its most aligned field (the largest one
+/// on ties), followed by enough padding to cover the whole union. This is
+/// the layout clang uses, but it is not enough to pass the union the way
+/// C does, so `FnType` also records every field for the `cabi_*` modules.
+fn union_llfields<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>, un: &Union<'tcx>,
+                            sizing: bool) -> Vec<Type> {
+    let lltys = un.fields.iter().map(|&ty| {
+        if sizing {
+            type_of::sizing_type_of(cx, ty)
+        } else {
+            type_of::in_memory_type_of(cx, ty)
+        }
+    });
+    let llty = lltys.max_by_key(|&llty| {
+        (machine::llalign_of_min(cx, llty), machine::llsize_of_alloc(cx, llty))
+    }).unwrap();
+
+    let size = roundup(un.min_size, un.align);
+    let llty_size = machine::llsize_of_alloc(cx, llty);
+    if llty_size == size {
+        vec![llty]
+    } else {
+        vec![llty, Type::array(&Type::i8(cx), size - llty_size)]
+    }
+}
+
 fn struct_llfields<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>, st: &Struct<'tcx>,
                              sizing: bool, dst: bool) -> Vec<Type> {
-    if sizing {
+    let mut fields: Vec<Type> = if sizing {
         st.fields.iter().filter(|&ty| !dst || type_is_sized(cx.tcx(), *ty))
             .map(|&ty| type_of::sizing_type_of(cx, ty)).collect()
     } else {
         st.fields.iter().map(|&ty| type_of::in_memory_type_of(cx, ty)).collect()
+    };
+    if st.pack > 0 {
+        fields = fields.into_iter().map(|llty| pack_llfield(cx, llty, st.pack)).collect();
+    }
+    if st.min_align > 0 {
+        fields.push(Type::array(&llty_with_align(cx, st.min_align), 0));
     }
+    fields
 }
 
 /// Obtain a representation of the discriminant sufficient to translate
@@ -886,7 +1058,7 @@ pub fn trans_switch<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             (_match::Switch, Some(trans_get_discr(bcx, r, scrutinee, None,
                                                   range_assert)))
         }
-        Univariant(..) => {
+        Univariant(..) | UntaggedUnion(..) => {
             // N.B.: Univariant means <= 1 enum variants (*not* == 1 variants).
             (_match::Single, None)
         }
@@ -897,7 +1069,7 @@ pub fn is_discr_signed<'tcx>(r: &Repr<'tcx>) -> bool {
     match *r {
         CEnum(ity, _, _) => ity.is_signed(),
         General(ity, _, _) => ity.is_signed(),
-        Univariant(..) => false,
+        Univariant(..) | UntaggedUnion(..) => false,
         RawNullablePointer { .. } => false,
         StructWrappedNullablePointer { .. } => false,
     }
@@ -918,7 +1090,7 @@ pub fn trans_get_discr<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, r: &Repr<'tcx>,
             load_discr(bcx, ity, ptr, Disr(0), Disr(cases.len() as u64 - 1),
                        range_assert)
         }
-        Univariant(..) => C_u8(bcx.ccx(), 0),
+        Univariant(..) | UntaggedUnion(..) => C_u8(bcx.ccx(), 0),
         RawNullablePointer { nndiscr, nnty, .. } =>  {
             let cmp = if nndiscr == Disr(0) { IntEQ } else { IntNE };
             let llptrty = type_of::sizing_type_of(bcx.ccx(), nnty);
@@ -980,8 +1152,8 @@ pub fn trans_case<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, r: &Repr, discr: Disr)
         General(ity, _, _) => {
             C_integral(ll_inttype(bcx.ccx(), ity), discr.0, true)
         }
-        Univariant(..) => {
-            bug!("no cases for univariants or structs")
+        Univariant(..) | UntaggedUnion(..) => {
+            bug!("no cases for univariants, structs or unions")
         }
         RawNullablePointer { .. } |
         StructWrappedNullablePointer { .. } => {
@@ -1017,6 +1189,9 @@ pub fn trans_set_discr<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, r: &Repr<'tcx>,
                       StructGEP(bcx, val, st.fields.len() - 1));
             }
         }
+        UntaggedUnion(..) => {
+            assert_eq!(discr, Disr(0));
+        }
         RawNullablePointer { nndiscr, nnty, ..} => {
             if discr != nndiscr {
                 let llptrty = type_of::sizing_type_of(bcx.ccx(), nnty);
@@ -1055,6 +1230,10 @@ pub fn num_args(r: &Repr, discr: Disr) -> usize {
             assert_eq!(discr, Disr(0));
             st.fields.len() - (if dtor_active(dtor) { 1 } else { 0 })
         }
+        UntaggedUnion(ref un) => {
+            assert_eq!(discr, Disr(0));
+            un.fields.len()
+        }
         General(_, ref cases, dtor) => {
             cases[discr.0 as usize].fields.len() - 1 - (if dtor_active(dtor) { 1 } else { 0 })
         }
@@ -1091,6 +1270,13 @@ pub fn trans_field_ptr_builder<'blk, 'tcx>(bcx: &BlockAndBuilder<'blk, 'tcx>,
             assert_eq!(discr, Disr(0));
             struct_field_ptr(bcx, st, val, ix, false)
         }
+        UntaggedUnion(ref un) => {
+            // Every field of a union lives at offset 0.
+            assert_eq!(discr, Disr(0));
+            let ty = type_of::in_memory_type_of(bcx.ccx(), un.fields[ix]);
+            if bcx.is_unreachable() { return C_undef(ty.ptr_to()); }
+            bcx.pointercast(val.value, ty.ptr_to())
+        }
         General(_, ref cases, _) => {
             struct_field_ptr(bcx, &cases[discr.0 as usize], val, ix + 1, true)
         }
@@ -1130,28 +1316,37 @@ fn struct_field_ptr<'blk, 'tcx>(bcx: &BlockAndBuilder<'blk, 'tcx>,
     }
 
     let ptr_val = if needs_cast {
-        let fields = st.fields.iter().map(|&ty| {
-            type_of::in_memory_type_of(ccx, ty)
-        }).collect::<Vec<_>>();
+        let fields = struct_llfields(ccx, st, false, false);
         let real_ty = Type::struct_(ccx, &fields[..], st.packed);
         bcx.pointercast(val.value, real_ty.ptr_to())
     } else {
         val.value
     };
 
+    // Fields of a `#[repr(packed(N))]` struct may be stored as arrays,
+    // see `pack_llfield`, so cast back to the type of the field.
+    let field_gep = |ptr_val: ValueRef| {
+        let field_ptr = bcx.struct_gep(ptr_val, ix);
+        if st.pack > 0 {
+            bcx.pointercast(field_ptr, ll_fty.ptr_to())
+        } else {
+            field_ptr
+        }
+    };
+
     // Simple case - we can just GEP the field
     //   * First field - Always aligned properly
     //   * Packed struct - There is no alignment padding
     //   * Field is sized - pointer is properly aligned already
     if ix == 0 || st.packed || type_is_sized(bcx.tcx(), fty) {
-        return bcx.struct_gep(ptr_val, ix);
+        return field_gep(ptr_val);
     }
 
     // If the type of the last field is [T] or str, then we don't need to do
     // any adjusments
     match fty.sty {
         ty::TySlice(..) | ty::TyStr => {
-            return bcx.struct_gep(ptr_val, ix);
+            return field_gep(ptr_val);
         }
         _ => ()
     }
@@ -1160,7 +1355,7 @@ fn struct_field_ptr<'blk, 'tcx>(bcx: &BlockAndBuilder<'blk, 'tcx>,
     if !val.has_meta() {
         debug!("Unsized field `{}`, of `{:?}` has no metadata for adjustment",
                ix, Value(ptr_val));
-        return bcx.struct_gep(ptr_val, ix);
+        return field_gep(ptr_val);
     }
 
     let dbloc = DebugLoc::None;
@@ -1186,14 +1381,18 @@ fn struct_field_ptr<'blk, 'tcx>(bcx: &BlockAndBuilder<'blk, 'tcx>,
     let mut offset = 0;
     for &ty in &st.fields[0..ix] {
         let llty = type_of::sizing_type_of(ccx, ty);
-        let type_align = type_of::align_of(ccx, ty);
+        let type_align = packed_align(st, type_of::align_of(ccx, ty));
         offset = roundup(offset, type_align);
         offset += machine::llsize_of_alloc(ccx, llty);
     }
     let unaligned_offset = C_uint(bcx.ccx(), offset);
 
     // Get the alignment of the field
-    let (_, align) = glue::size_and_align_of_dst(bcx, fty, meta);
+    let (_, mut align) = glue::size_and_align_of_dst(bcx, fty, meta);
+    if st.pack > 0 {
+        let pack = C_uint(bcx.ccx(), st.pack);
+        align = bcx.select(bcx.icmp(IntULT, align, pack), align, pack);
+    }
 
     // Bump the unaligned offset up to the appropriate alignment using the
     // following expression:
@@ -1360,7 +1559,19 @@ pub fn trans_const<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>, r: &Repr<'tcx>, discr
         Univariant(ref st, _dro) => {
             assert_eq!(discr, Disr(0));
             let contents = build_const_struct(ccx, st, vals);
-            C_struct(ccx, &contents[..], st.packed)
+            C_struct(ccx, &contents[..], st.packed || st.pack > 0)
+        }
+        UntaggedUnion(ref un) => {
+            // `vals` holds only the value of the initialized field.
+            assert_eq!(discr, Disr(0));
+            assert_eq!(vals.len(), 1);
+            let size = roundup(un.min_size, un.align);
+            let val_size = machine::llsize_of_alloc(ccx, val_ty(vals[0]));
+            let mut contents = vec![vals[0]];
+            if val_size != size {
+                contents.push(padding(ccx, size - val_size));
+            }
+            C_struct(ccx, &contents[..], un.packed)
         }
         RawNullablePointer { nndiscr, nnty, .. } => {
             if discr == nndiscr {
@@ -1400,7 +1611,7 @@ fn compute_struct_field_offsets<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
     for &ty in &st.fields {
         let llty = type_of::sizing_type_of(ccx, ty);
         if !st.packed {
-            let type_align = type_of::align_of(ccx, ty);
+            let type_align = packed_align(st, type_of::align_of(ccx, ty));
             offset = roundup(offset, type_align);
         }
         offsets.push(offset);
@@ -1430,7 +1641,7 @@ fn build_const_struct<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
     let mut cfields = Vec::new();
     for (&val, target_offset) in vals.iter().zip(target_offsets) {
         if !st.packed {
-            let val_align = machine::llalign_of_min(ccx, val_ty(val));
+            let val_align = packed_align(st, machine::llalign_of_min(ccx, val_ty(val)));
             offset = roundup(offset, val_align);
         }
         if offset != target_offset {
@@ -1450,6 +1661,11 @@ fn build_const_struct<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
     cfields
 }
 
+/// Clamp the alignment of a field to the `#[repr(packed(N))]` of its struct.
+fn packed_align(st: &Struct, align: u32) -> u32 {
+    if st.pack > 0 { std::cmp::min(align, st.pack) } else { align }
+}
+
 fn padding(ccx: &CrateContext, size: u64) -> ValueRef {
     C_undef(Type::array(&Type::i8(ccx), size))
 }
@@ -1473,7 +1689,7 @@ pub fn const_get_discrim(r: &Repr, val: ValueRef) -> Disr {
                 attr::UnsignedInt(..) => Disr(const_to_uint(const_get_elt(val, &[0])))
             }
         }
-        Univariant(..) => Disr(0),
+        Univariant(..) | UntaggedUnion(..) => Disr(0),
         RawNullablePointer { .. } | StructWrappedNullablePointer { .. } => {
             bug!("const discrim access of non c-like enum")
         }
@@ -1490,6 +1706,7 @@ pub fn const_get_field(r: &Repr, val: ValueRef, _discr: Disr,
     match *r {
         CEnum(..) => bug!("element access in C-like enum const"),
         Univariant(..) => const_struct_field(val, ix),
+        UntaggedUnion(..) => const_struct_field(val, 0),
         General(..) => const_struct_field(val, ix + 1),
         RawNullablePointer { .. } => {
             assert_eq!(ix, 0);
@@ -433,6 +433,10 @@ pub fn iter_structural_ty<'blk, 'tcx, F>(cx: Block<'blk, 'tcx>,
 
     let mut cx = cx;
     match t.sty {
+        ty::TyStruct(def, _) if def.is_union() => {
+            // The fields of a union overlap and are never dropped,
+            // so there is nothing to iterate over.
+        }
         ty::TyStruct(..) => {
             let repr = adt::represent_type(cx.ccx(), t);
             let VariantInfo { fields, discr } = VariantInfo::from_ty(cx.tcx(), t, None);
@@ -2217,8 +2221,6 @@ fn contains_null(s: &str) -> bool {
 
 fn write_metadata(cx: &SharedCrateContext,
                   reachable_ids: &NodeSet) -> Vec<u8> {
-    use flate;
-
     let any_library = cx.sess()
                         .crate_types
                         .borrow()
@@ -2235,10 +2237,12 @@ fn write_metadata(cx: &SharedCrateContext,
                                           reachable_ids,
                                           cx.mir_map(),
                                           cx.tcx().map.krate());
-    let mut compressed = cstore.metadata_encoding_version().to_vec();
-    compressed.extend_from_slice(&flate::deflate_bytes(&metadata));
+    // The metadata is not compressed, so that the loader can use it
+    // directly from the mapped library instead of inflating all of it.
+    let mut section = cstore.metadata_encoding_version().to_vec();
+    section.extend_from_slice(&metadata);
 
-    let llmeta = C_bytes_in_context(cx.metadata_llcx(), &compressed[..]);
+    let llmeta = C_bytes_in_context(cx.metadata_llcx(), &section[..]);
     let llconst = C_struct_in_context(cx.metadata_llcx(), &[llmeta], false);
     let name = cx.metadata_symbol_name();
     let buf = CString::new(name).unwrap();
@@ -151,6 +151,38 @@ fn is_homogenous_aggregate_ty(ty: Type) -> Option<(Type, u64)> {
     })
 }
 
+fn is_homogenous_aggregate(arg: &ArgType) -> Option<(Type, u64)> {
+    let fields = match arg.union_fields {
+        Some(fields) => fields.field_types(),
+        None => return is_homogenous_aggregate_ty(arg.ty)
+    };
+
+    // A union is an HFA/HVA only if all of its fields are, with the same
+    // base type; it then has as many members as its largest field.
+    let mut homog_agg = None;
+    for field in fields {
+        match (homog_agg, is_homogenous_aggregate_ty(field)) {
+            (_, None) => return None,
+            (None, Some(field_homog_agg)) => homog_agg = Some(field_homog_agg),
+            (Some((base_ty, members)), Some((field_ty, field_members))) => {
+                if base_ty != field_ty {
+                    return None;
+                }
+                homog_agg = Some((base_ty, cmp::max(members, field_members)));
+            }
+        }
+    }
+
+    // Ensure there is no padding.
+    homog_agg.and_then(|(base_ty, members)| {
+        if ty_size(arg.ty) == ty_size(base_ty) * (members as usize) {
+            Some((base_ty, members))
+        } else {
+            None
+        }
+    })
+}
+
 fn classify_ret_ty(ccx: &CrateContext, ret: &mut ArgType) {
     if is_reg_ty(ret.ty) {
         ret.extend_integer_width_to(64);
@@ -162,7 +194,7 @@ fn classify_ret_ty(ccx: &CrateContext, ret: &mut ArgType) {
         ret.make_indirect(ccx);
     }
 
-    if let Some((base_ty, members)) = is_homogenous_aggregate_ty(ret.ty) {
+    if let Some((base_ty, members)) = is_homogenous_aggregate(ret) {
         ret.cast = Some(Type::array(&base_ty, members));
         return;
     }
@@ -192,7 +224,7 @@ fn classify_arg_ty(ccx: &CrateContext, arg: &mut ArgType) {
         return;
     }
 
-    if let Some((base_ty, members)) = is_homogenous_aggregate_ty(arg.ty) {
+    if let Some((base_ty, members)) = is_homogenous_aggregate(arg) {
         arg.cast = Some(Type::array(&base_ty, members));
         return;
     }
@@ -83,7 +83,7 @@ impl ClassList for [RegClass] {
     }
 }
 
-fn classify_ty(ty: Type) -> Vec<RegClass> {
+fn classify_ty(ty: Type, union_fields: Option<Type>) -> Vec<RegClass> {
     fn align(off: usize, ty: Type) -> usize {
         let a = ty_align(ty);
         return (off + a - 1) / a * a;
@@ -323,7 +323,16 @@ fn classify_ty(ty: Type) -> Vec<RegClass> {
         all_mem(&mut cls);
         return cls;
     }
-    classify(ty, &mut cls, 0, 0);
+    match union_fields {
+        // Every field of a union starts at offset 0, and each eightbyte
+        // gets the merged class of all the fields overlapping it.
+        Some(fields) => {
+            for field in fields.field_types() {
+                classify(field, &mut cls, 0, 0);
+            }
+        }
+        None => classify(ty, &mut cls, 0, 0)
+    }
     fixup(ty, &mut cls);
     return cls;
 }
@@ -390,8 +399,8 @@ pub fn compute_abi_info(ccx: &CrateContext, fty: &mut FnType) {
                     ind_attr: Option<Attribute>)
         where F: FnOnce(&[RegClass]) -> bool
     {
-        if !arg.ty.is_reg_ty() {
-            let cls = classify_ty(arg.ty);
+        if arg.union_fields.is_some() || !arg.ty.is_reg_ty() {
+            let cls = classify_ty(arg.ty, arg.union_fields);
             if is_mem_cls(&cls) {
                 arg.make_indirect(ccx);
                 if let Some(attr) = ind_attr {
@@ -748,11 +748,13 @@ fn find_drop_glue_neighbors<'a, 'tcx>(scx: &SharedCrateContext<'a, 'tcx>,
         ty::TyInt(_)    |
         ty::TyUint(_)   |
         ty::TyStr       |
+        ty::TyNever     |
         ty::TyFloat(_)  |
         ty::TyRawPtr(_) |
         ty::TyRef(..)   |
         ty::TyFnDef(..) |
         ty::TyFnPtr(_)  |
+        ty::TyConst(_)  |
         ty::TyTrait(_)  => {
             /* nothing to do */
         }
@@ -794,7 +796,9 @@ fn find_drop_glue_neighbors<'a, 'tcx>(scx: &SharedCrateContext<'a, 'tcx>,
             }
         }
         ty::TyProjection(_) |
+        ty::TyAnon(..)      |
         ty::TyParam(_)      |
+        ty::TyGenericArray(..) |
         ty::TyInfer(_)      |
         ty::TyError         => {
             bug!("encountered unexpected type");
@@ -1092,7 +1096,8 @@ impl<'b, 'a, 'v> hir_visit::Visitor<'v> for RootCollector<'b, 'a, 'v> {
             }
 
             hir::ItemEnum(_, ref generics)        |
-            hir::ItemStruct(_, ref generics)      => {
+            hir::ItemStruct(_, ref generics)      |
+            hir::ItemUnion(_, ref generics)       => {
                 if !generics.is_parameterized() {
                     let ty = {
                         let tables = self.scx.tcx().tables.borrow();
@@ -1148,7 +1153,7 @@ impl<'b, 'a, 'v> hir_visit::Visitor<'v> for RootCollector<'b, 'a, 'v> {
                 let parent_node_id = hir_map.get_parent_node(ii.id);
                 let is_impl_generic = match hir_map.expect_item(parent_node_id) {
                     &hir::Item {
-                        node: hir::ItemImpl(_, _, ref generics, _, _, _),
+                        node: hir::ItemImpl(_, _, _, ref generics, _, _, _),
                         ..
                     } => {
                         generics.is_type_parameterized()
@@ -1180,6 +1185,7 @@ fn create_trans_items_for_default_impls<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                                                   output: &mut Vec<TransItem<'tcx>>) {
     match item.node {
         hir::ItemImpl(_,
+                      _,
                       _,
                       ref generics,
                       _,
@@ -146,6 +146,12 @@ pub fn type_is_imm_pair<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>, ty: Ty<'tcx>)
                 return false;
             }
 
+            // With `#[repr(packed(N))]`, the fields may not have the
+            // LLVM types they would be loaded as.
+            if variant.pack.map_or(false, |pack| pack.abi() > 1) {
+                return false;
+            }
+
             match type_pair_fields(ccx, ty) {
                 Some([a, b]) => {
                     type_is_immediate(ccx, a) && type_is_immediate(ccx, b)
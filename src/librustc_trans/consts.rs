@@ -34,7 +34,7 @@ use type_of;
 use value::Value;
 use Disr;
 use rustc::ty::subst::Substs;
-use rustc::ty::adjustment::{AdjustDerefRef, AdjustReifyFnPointer};
+use rustc::ty::adjustment::{AdjustNeverToAny, AdjustDerefRef, AdjustReifyFnPointer};
 use rustc::ty::adjustment::{AdjustUnsafeFnPointer, AdjustMutToConstPointer};
 use rustc::ty::{self, Ty, TyCtxt};
 use rustc::ty::cast::{CastTy,IntTy};
@@ -348,6 +348,7 @@ pub fn const_expr<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                                                             &cx.tcx().expr_ty_adjusted(e));
     let opt_adj = cx.tcx().tables.borrow().adjustments.get(&e.id).cloned();
     match opt_adj {
+        Some(AdjustNeverToAny(..)) => span_bug!(e.span, "const expression of type ! encountered"),
         Some(AdjustReifyFnPointer) => {
             match ety.sty {
                 ty::TyFnDef(def_id, substs, _) => {
@@ -834,18 +835,25 @@ fn const_expr_unadjusted<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
             };
 
             let VariantInfo { discr, fields } = VariantInfo::of_node(cx.tcx(), ety, e.id);
-            let cs = fields.iter().enumerate().map(|(ix, &Field(f_name, _))| {
-                match (fs.iter().find(|f| f_name == f.name.node), base_val) {
-                    (Some(ref f), _) => {
-                        const_expr(cx, &f.expr, param_substs, fn_args, trueconst).map(|(l, _)| l)
-                    },
-                    (_, Some((bv, _))) => Ok(adt::const_get_field(&repr, bv, discr, ix)),
-                    (_, None) => span_bug!(e.span, "missing struct field"),
-                }
-            })
-            .collect::<Vec<Result<_, ConstEvalFailure>>>()
-            .into_iter()
-            .collect::<Result<Vec<_>,ConstEvalFailure>>();
+            let cs = if ety.ty_adt_def().map_or(false, |def| def.is_union()) {
+                // A union expression has exactly one field and no base.
+                const_expr(cx, &fs[0].expr, param_substs, fn_args, trueconst)
+                    .map(|(l, _)| vec![l])
+            } else {
+                fields.iter().enumerate().map(|(ix, &Field(f_name, _))| {
+                    match (fs.iter().find(|f| f_name == f.name.node), base_val) {
+                        (Some(ref f), _) => {
+                            const_expr(cx, &f.expr, param_substs, fn_args, trueconst)
+                                .map(|(l, _)| l)
+                        },
+                        (_, Some((bv, _))) => Ok(adt::const_get_field(&repr, bv, discr, ix)),
+                        (_, None) => span_bug!(e.span, "missing struct field"),
+                    }
+                })
+                .collect::<Vec<Result<_, ConstEvalFailure>>>()
+                .into_iter()
+                .collect::<Result<Vec<_>,ConstEvalFailure>>()
+            };
             let cs = cs?;
             if ety.is_simd() {
                 C_vector(&cs[..])
@@ -171,6 +171,7 @@ impl<'tcx> TypeMap<'tcx> {
         unique_type_id.push('{');
 
         match type_.sty {
+            ty::TyNever    |
             ty::TyBool     |
             ty::TyChar     |
             ty::TyStr      |
@@ -704,6 +705,7 @@ pub fn type_metadata<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
 
     let sty = &t.sty;
     let MetadataCreationResult { metadata, already_stored_in_typemap } = match *sty {
+        ty::TyNever    |
         ty::TyBool     |
         ty::TyChar     |
         ty::TyInt(_)   |
@@ -931,6 +933,7 @@ fn basic_type_metadata<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
     debug!("basic_type_metadata: {:?}", t);
 
     let (name, encoding) = match t.sty {
+        ty::TyNever => ("!", DW_ATE_unsigned),
         ty::TyTuple(ref elements) if elements.is_empty() =>
             ("()", DW_ATE_unsigned),
         ty::TyBool => ("bool", DW_ATE_boolean),
@@ -1103,6 +1106,7 @@ struct StructMemberDescriptionFactory<'tcx> {
     variant: ty::VariantDef<'tcx>,
     substs: &'tcx subst::Substs<'tcx>,
     is_simd: bool,
+    is_union: bool,
     span: Span,
 }
 
@@ -1135,6 +1139,9 @@ impl<'tcx> StructMemberDescriptionFactory<'tcx> {
 
             let offset = if self.is_simd {
                 FixedMemberOffset { bytes: i * field_size.unwrap() }
+            } else if self.is_union {
+                // All fields of a union overlap at its start.
+                FixedMemberOffset { bytes: 0 }
             } else {
                 ComputedMemberOffset
             };
@@ -1159,12 +1166,12 @@ fn prepare_struct_metadata<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
     let struct_name = compute_debuginfo_type_name(cx, struct_type, false);
     let struct_llvm_type = type_of::in_memory_type_of(cx, struct_type);
 
-    let (struct_def_id, variant, substs) = match struct_type.sty {
-        ty::TyStruct(def, substs) => (def.did, def.struct_variant(), substs),
+    let (struct_def, variant, substs) = match struct_type.sty {
+        ty::TyStruct(def, substs) => (def, def.struct_variant(), substs),
         _ => bug!("prepare_struct_metadata on a non-struct")
     };
 
-    let (containing_scope, _) = get_namespace_and_span_for_item(cx, struct_def_id);
+    let (containing_scope, _) = get_namespace_and_span_for_item(cx, struct_def.did);
 
     let struct_metadata_stub = create_struct_stub(cx,
                                                   struct_llvm_type,
@@ -1182,6 +1189,7 @@ fn prepare_struct_metadata<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
             variant: variant,
             substs: substs,
             is_simd: struct_type.is_simd(),
+            is_union: struct_def.is_union(),
             span: span,
         })
     )
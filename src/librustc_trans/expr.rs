@@ -69,7 +69,7 @@ use tvec;
 use type_of;
 use value::Value;
 use Disr;
-use rustc::ty::adjustment::{AdjustDerefRef, AdjustReifyFnPointer};
+use rustc::ty::adjustment::{AdjustNeverToAny, AdjustDerefRef, AdjustReifyFnPointer};
 use rustc::ty::adjustment::{AdjustUnsafeFnPointer, AdjustMutToConstPointer};
 use rustc::ty::adjustment::CustomCoerceUnsized;
 use rustc::ty::{self, Ty, TyCtxt};
@@ -348,6 +348,7 @@ fn adjustment_required<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     }
 
     match adjustment {
+        AdjustNeverToAny(..) => true,
         AdjustReifyFnPointer => true,
         AdjustUnsafeFnPointer | AdjustMutToConstPointer => {
             // purely a type-level thing
@@ -380,6 +381,15 @@ fn apply_adjustments<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     debug!("unadjusted datum for expr {:?}: {:?} adjustment={:?}",
            expr, datum, adjustment);
     match adjustment {
+        AdjustNeverToAny(ref target) => {
+            // A value of type `!` is never produced, so the rest of the
+            // block is dead; hand back a dummy of the target type.
+            let target = bcx.monomorphize(target);
+            let llty = type_of::type_of(bcx.ccx(), target);
+            Unreachable(bcx);
+            let dummy = C_undef(llty.ptr_to());
+            datum = Datum::new(dummy, target, RvalueExpr(Rvalue::new(ByRef)));
+        }
         AdjustReifyFnPointer => {
             match datum.ty.sty {
                 ty::TyFnDef(def_id, substs, _) => {
@@ -652,8 +662,22 @@ fn trans_datum_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             trans(bcx, &e)
         }
         hir::ExprPath(..) => {
-            let var = trans_var(bcx, bcx.tcx().expect_def(expr.id));
-            DatumBlock::new(bcx, var.to_expr_datum())
+            match bcx.tcx().expect_def(expr.id) {
+                // A `const` type parameter used as a value.
+                Def::TyParam(space, index, _, name) => {
+                    let param = monomorphize_type(bcx, bcx.tcx().mk_param(space, index, name));
+                    let value = match param.sty {
+                        ty::TyConst(value) => value,
+                        _ => bug!("const parameter `{:?}` was not substituted", param)
+                    };
+                    let ty = expr_ty(bcx, expr);
+                    immediate_rvalue_bcx(bcx, C_uint(bcx.ccx(), value), ty).to_expr_datumblock()
+                }
+                def => {
+                    let var = trans_var(bcx, def);
+                    DatumBlock::new(bcx, var.to_expr_datum())
+                }
+            }
         }
         hir::ExprField(ref base, name) => {
             trans_rec_field(bcx, &base, name.node)
@@ -1295,7 +1319,9 @@ fn trans_struct<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                                  fields: leftovers })
         }
         None => {
-            if need_base.iter().any(|b| *b) {
+            // Union expressions initialize only one of their fields.
+            let is_union = ty.ty_adt_def().map_or(false, |def| def.is_union());
+            if !is_union && need_base.iter().any(|b| *b) {
                 span_bug!(expr_span, "missing fields and no base expr")
             }
             None
@@ -2403,7 +2429,8 @@ fn expr_kind<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, expr: &hir::Expr) -> ExprKin
                 Def::Local(..) => ExprKind::Lvalue,
 
                 Def::Const(..) |
-                Def::AssociatedConst(..) => ExprKind::RvalueDatum,
+                Def::AssociatedConst(..) |
+                Def::TyParam(..) => ExprKind::RvalueDatum,
 
                 def => {
                     span_bug!(
@@ -103,6 +103,18 @@ impl<'tcx> Const<'tcx> {
         Const::new(val, ty)
     }
 
+    /// Translate the value of a `const` type parameter, once `param`
+    /// has been monomorphized into a `TyConst`.
+    pub fn from_const_param<'a>(ccx: &CrateContext<'a, 'tcx>,
+                                param: Ty<'tcx>,
+                                ty: Ty<'tcx>)
+                                -> Const<'tcx> {
+        match param.sty {
+            ty::TyConst(value) => Const::new(C_uint(ccx, value), ty),
+            _ => bug!("MIR const parameter `{:?}` was not substituted", param)
+        }
+    }
+
     fn get_pair(&self) -> (ValueRef, ValueRef) {
         (const_get_elt(self.llval, &[0]),
          const_get_elt(self.llval, &[1]))
@@ -488,6 +500,10 @@ impl<'a, 'tcx> MirConstContext<'a, 'tcx> {
                     mir::Literal::Value { value } => {
                         Ok(Const::from_constval(self.ccx, value, ty))
                     }
+                    mir::Literal::ConstParam { param } => {
+                        let param = self.monomorphize(&param);
+                        Ok(Const::from_const_param(self.ccx, param, ty))
+                    }
                 }
             }
         }
@@ -552,7 +568,7 @@ impl<'a, 'tcx> MirConstContext<'a, 'tcx> {
                                                 self.monomorphize(&substs));
                 }
 
-                let val = if let mir::AggregateKind::Adt(adt_def, index, _) = *kind {
+                let val = if let mir::AggregateKind::Adt(adt_def, index, _, _) = *kind {
                     let repr = adt::represent_type(self.ccx, dest_ty);
                     let disr = Disr::from(adt_def.variants[index].disr_val);
                     adt::trans_const(self.ccx, &repr, disr, &fields)
@@ -914,6 +930,10 @@ impl<'bcx, 'tcx> MirContext<'bcx, 'tcx> {
             mir::Literal::Value { value } => {
                 Ok(Const::from_constval(bcx.ccx(), value, ty))
             }
+            mir::Literal::ConstParam { param } => {
+                let param = bcx.monomorphize(&param);
+                Ok(Const::from_const_param(bcx.ccx(), param, ty))
+            }
         };
 
         match result {
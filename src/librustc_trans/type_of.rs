@@ -75,7 +75,7 @@ pub fn sizing_type_of<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>, t: Ty<'tcx>) -> Typ
             }
         }
 
-        ty::TyFnDef(..) => Type::nil(cx),
+        ty::TyFnDef(..) | ty::TyNever => Type::nil(cx),
         ty::TyFnPtr(_) => Type::i8p(cx),
 
         ty::TyArray(ty, size) => {
@@ -112,7 +112,8 @@ pub fn sizing_type_of<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>, t: Ty<'tcx>) -> Typ
             }
         }
 
-        ty::TyProjection(..) | ty::TyInfer(..) | ty::TyParam(..) | ty::TyError => {
+        ty::TyProjection(..) | ty::TyInfer(..) | ty::TyParam(..) |
+        ty::TyAnon(..) | ty::TyError | ty::TyGenericArray(..) | ty::TyConst(_) => {
             bug!("fictitious type {:?} in sizing_type_of()", t)
         }
         ty::TySlice(_) | ty::TyTrait(..) | ty::TyStr => bug!()
@@ -305,7 +306,7 @@ pub fn in_memory_type_of<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>, t: Ty<'tcx>) ->
       ty::TySlice(ty) => in_memory_type_of(cx, ty),
       ty::TyStr | ty::TyTrait(..) => Type::i8(cx),
 
-      ty::TyFnDef(..) => Type::nil(cx),
+      ty::TyFnDef(..) | ty::TyNever => Type::nil(cx),
       ty::TyFnPtr(f) => {
         let sig = cx.tcx().erase_late_bound_regions(&f.sig);
         let sig = cx.tcx().normalize_associated_type(&sig);
@@ -341,7 +342,10 @@ pub fn in_memory_type_of<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>, t: Ty<'tcx>) ->
 
       ty::TyInfer(..) => bug!("type_of with TyInfer"),
       ty::TyProjection(..) => bug!("type_of with TyProjection"),
+      ty::TyAnon(..) => bug!("type_of with TyAnon"),
       ty::TyParam(..) => bug!("type_of with ty_param"),
+      ty::TyGenericArray(..) => bug!("type_of with TyGenericArray"),
+      ty::TyConst(_) => bug!("type_of with TyConst"),
       ty::TyError => bug!("type_of with TyError"),
     };
 
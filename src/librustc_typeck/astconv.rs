@@ -52,10 +52,12 @@ use rustc_const_eval::eval_length;
 use hir::{self, SelfKind};
 use hir::def::{Def, PathResolution};
 use hir::def_id::DefId;
+use hir::map as hir_map;
 use hir::print as pprust;
 use middle::resolve_lifetime as rl;
 use rustc::lint;
 use rustc::ty::subst::{FnSpace, TypeSpace, SelfSpace, Subst, Substs, ParamSpace};
+use rustc::ty::subst::VecPerParamSpace;
 use rustc::traits;
 use rustc::ty::{self, Ty, TyCtxt, ToPredicate, TypeFoldable};
 use rustc::ty::wf::object_region_bounds;
@@ -63,7 +65,7 @@ use rustc_back::slice;
 use require_c_abi_if_variadic;
 use rscope::{self, UnelidableRscope, RegionScope, ElidableRscope,
              ObjectLifetimeDefaultRscope, ShiftedRscope, BindingRscope,
-             ElisionFailureInfo, ElidedLifetime};
+             ElisionFailureInfo, ElidedLifetime, AnonTypeScope, MaybeWithAnonTypes};
 use util::common::{ErrorReported, FN_OUTPUT_NAME};
 use util::nodemap::{NodeMap, FnvHashSet};
 
@@ -107,12 +109,23 @@ pub trait AstConv<'gcx, 'tcx> {
     fn trait_defines_associated_type_named(&self, trait_def_id: DefId, name: ast::Name)
                                            -> bool;
 
+    /// Returns the number of lifetime and type parameters declared by the
+    /// associated type `name` of the trait with id `trait_def_id`; both are
+    /// zero unless it is a generic associated type.
+    fn associated_type_param_counts(&self, trait_def_id: DefId, name: ast::Name)
+                                    -> (usize, usize);
+
     /// Return an (optional) substitution to convert bound type parameters that
     /// are in scope into free ones. This function should only return Some
     /// within a fn body.
     /// See ParameterEnvironment::free_substs for more information.
     fn get_free_substs(&self) -> Option<&Substs<'tcx>>;
 
+    /// Returns the identity substitutions of the fn or method
+    /// `enclosing_item`, which also parameterize the anonymized types
+    /// (`impl Trait`) in its return type.
+    fn get_anon_type_substs(&self, span: Span, enclosing_item: DefId) -> &'tcx Substs<'tcx>;
+
     /// What type should we use when a type is omitted?
     fn ty_infer(&self,
                 param_and_substs: Option<ty::TypeParameterDef<'tcx>>,
@@ -130,7 +143,8 @@ pub trait AstConv<'gcx, 'tcx> {
     fn projected_ty_from_poly_trait_ref(&self,
                                         span: Span,
                                         poly_trait_ref: ty::PolyTraitRef<'tcx>,
-                                        item_name: ast::Name)
+                                        item_name: ast::Name,
+                                        item_substs: &'tcx Substs<'tcx>)
                                         -> Ty<'tcx>;
 
     /// Project an associated type from a non-higher-ranked trait reference.
@@ -138,7 +152,8 @@ pub trait AstConv<'gcx, 'tcx> {
     fn projected_ty(&self,
                     span: Span,
                     _trait_ref: ty::TraitRef<'tcx>,
-                    _item_name: ast::Name)
+                    _item_name: ast::Name,
+                    _item_substs: &'tcx Substs<'tcx>)
                     -> Ty<'tcx>;
 
     /// Invoked when we encounter an error from some prior pass
@@ -627,20 +642,21 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
 
     fn convert_ty_with_lifetime_elision(&self,
                                         elided_lifetime: ElidedLifetime,
-                                        ty: &hir::Ty)
+                                        ty: &hir::Ty,
+                                        anon_scope: Option<AnonTypeScope>)
                                         -> Ty<'tcx>
     {
         match elided_lifetime {
             Ok(implied_output_region) => {
                 let rb = ElidableRscope::new(implied_output_region);
-                self.ast_ty_to_ty(&rb, ty)
+                self.ast_ty_to_ty(&MaybeWithAnonTypes::new(rb, anon_scope), ty)
             }
             Err(param_lifetimes) => {
                 // All regions must be explicitly specified in the output
                 // if the lifetime elision rules do not apply. This saves
                 // the user from potentially-confusing errors.
                 let rb = UnelidableRscope::new(param_lifetimes);
-                self.ast_ty_to_ty(&rb, ty)
+                self.ast_ty_to_ty(&MaybeWithAnonTypes::new(rb, anon_scope), ty)
             }
         }
     }
@@ -671,7 +687,9 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
 
         let (output, output_span) = match data.output {
             Some(ref output_ty) => {
-                (self.convert_ty_with_lifetime_elision(implied_output_region, &output_ty),
+                (self.convert_ty_with_lifetime_elision(implied_output_region,
+                                                       &output_ty,
+                                                       None),
                  output_ty.span)
             }
             None => {
@@ -949,10 +967,12 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
 
         // Simple case: X is defined in the current trait.
         if self.trait_defines_associated_type_named(trait_ref.def_id(), binding.item_name) {
+            self.check_binding_not_generic(trait_ref.def_id(), binding)?;
             return Ok(ty::Binder(ty::ProjectionPredicate {      // <-------------------+
                 projection_ty: ty::ProjectionTy {               //                     |
                     trait_ref: trait_ref.skip_binder().clone(), // Binder moved here --+
                     item_name: binding.item_name,
+                    item_substs: tcx.mk_substs(Substs::empty()),
                 },
                 ty: binding.ty,
             }));
@@ -998,16 +1018,35 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
                                                       &trait_ref.to_string(),
                                                       &binding.item_name.as_str(),
                                                       binding.span)?;
+        self.check_binding_not_generic(candidate.def_id(), binding)?;
 
         Ok(ty::Binder(ty::ProjectionPredicate {             // <-------------------------+
             projection_ty: ty::ProjectionTy {               //                           |
                 trait_ref: candidate.skip_binder().clone(), // binder is moved up here --+
                 item_name: binding.item_name,
+                item_substs: tcx.mk_substs(Substs::empty()),
             },
             ty: binding.ty,
         }))
     }
 
+    /// A binding like `Trait<Item=X>` would have to hold for every
+    /// instantiation of a generic associated type, which cannot be
+    /// expressed yet.
+    fn check_binding_not_generic(&self,
+                                 trait_def_id: DefId,
+                                 binding: &ConvertedBinding<'tcx>)
+                                 -> Result<(), ErrorReported>
+    {
+        if self.associated_type_param_counts(trait_def_id, binding.item_name) != (0, 0) {
+            span_err!(self.tcx().sess, binding.span, E0572,
+                      "cannot bind generic associated type `{}` in a trait reference",
+                      binding.item_name);
+            return Err(ErrorReported);
+        }
+        Ok(())
+    }
+
     fn ast_path_to_ty(&self,
         rscope: &RegionScope,
         span: Span,
@@ -1289,6 +1328,71 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         Ok(bounds[0].clone())
     }
 
+    /// Converts the parameters given to the associated type `item_segment`
+    /// of the trait `trait_def_id` (e.g. the `'a` in `T::Item<'a>`) into
+    /// the `item_substs` of a projection. Only generic associated types
+    /// accept parameters; elided lifetimes are handled like those of any
+    /// other path.
+    fn associated_item_substs(&self,
+                              rscope: &RegionScope,
+                              span: Span,
+                              trait_def_id: DefId,
+                              item_segment: &hir::PathSegment)
+                              -> &'tcx Substs<'tcx>
+    {
+        let tcx = self.tcx();
+        let (expected_num_region_params, expected_num_type_params) =
+            self.associated_type_param_counts(trait_def_id, item_segment.name);
+
+        if expected_num_region_params == 0 && expected_num_type_params == 0 {
+            tcx.prohibit_type_params(slice::ref_slice(item_segment));
+            return tcx.mk_substs(Substs::empty());
+        }
+
+        if let hir::ParenthesizedParameters(..) = item_segment.parameters {
+            span_err!(tcx.sess, span, E0214,
+                      "parenthesized parameters may only be used with a trait");
+        }
+        for binding in item_segment.parameters.bindings() {
+            tcx.prohibit_projection(binding.span);
+            break;
+        }
+
+        let regions: Vec<_> = item_segment.parameters.lifetimes().iter()
+                                                     .map(|l| ast_region_to_region(tcx, l))
+                                                     .collect();
+        let supplied_num_region_params = regions.len();
+        let regions = if expected_num_region_params == supplied_num_region_params {
+            regions
+        } else {
+            let anon_regions =
+                rscope.anon_regions(span, expected_num_region_params);
+
+            if supplied_num_region_params != 0 || anon_regions.is_err() {
+                report_lifetime_number_error(tcx, span,
+                                             supplied_num_region_params,
+                                             expected_num_region_params);
+            }
+
+            match anon_regions {
+                Ok(anon_regions) => anon_regions,
+                Err(_) => (0..expected_num_region_params).map(|_| ty::ReStatic).collect()
+            }
+        };
+
+        let mut types: Vec<_> = item_segment.parameters.types().iter()
+                                                   .map(|t| self.ast_ty_to_ty(rscope, t))
+                                                   .collect();
+        check_type_argument_count(tcx, span, types.len(),
+                                  expected_num_type_params, expected_num_type_params);
+        types.truncate(expected_num_type_params);
+        while types.len() < expected_num_type_params {
+            types.push(tcx.types.err);
+        }
+
+        tcx.mk_substs(Substs::empty().with_method(types, regions))
+    }
+
     // Create a type from a path to an associated type.
     // For a path A::B::C::D, ty and ty_path_def are the type and def for A::B::C
     // and item_segment is the path segment for D. We return a type and a def for
@@ -1296,6 +1400,7 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
     // Will fail except for T::A and Self::A; i.e., if ty/ty_path_def are not a type
     // parameter or Self.
     fn associated_path_def_to_ty(&self,
+                                 rscope: &RegionScope,
                                  span: Span,
                                  ty: Ty<'tcx>,
                                  ty_path_def: Def,
@@ -1307,8 +1412,6 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
 
         debug!("associated_path_def_to_ty: {:?}::{}", ty, assoc_name);
 
-        tcx.prohibit_type_params(slice::ref_slice(item_segment));
-
         // Find the type of the associated item, and the trait where the associated
         // item is declared.
         let bound = match (&ty.sty, ty_path_def) {
@@ -1370,7 +1473,8 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         };
 
         let trait_did = bound.0.def_id;
-        let ty = self.projected_ty_from_poly_trait_ref(span, bound, assoc_name);
+        let item_substs = self.associated_item_substs(rscope, span, trait_did, item_segment);
+        let ty = self.projected_ty_from_poly_trait_ref(span, bound, assoc_name, item_substs);
 
         let item_did = if let Some(trait_id) = tcx.map.as_local_node_id(trait_did) {
             // `ty::trait_items` used below requires information generated
@@ -1405,8 +1509,6 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
     {
         let tcx = self.tcx();
 
-        tcx.prohibit_type_params(slice::ref_slice(item_segment));
-
         let self_ty = if let Some(ty) = opt_self_ty {
             ty
         } else {
@@ -1429,7 +1531,8 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
 
         debug!("qpath_to_ty: trait_ref={:?}", trait_ref);
 
-        self.projected_ty(span, trait_ref, item_segment.name)
+        let item_substs = self.associated_item_substs(rscope, span, trait_def_id, item_segment);
+        self.projected_ty(span, trait_ref, item_segment.name, item_substs)
     }
 
     /// Convert a type supplied as value for a type argument from AST into our
@@ -1457,6 +1560,9 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         let tcx = self.tcx();
 
         if let Some(def) = decl_generics.types.opt_get(TypeSpace, index) {
+            if !check_generic_arg_kind(tcx, def, ast_ty) {
+                return tcx.types.err;
+            }
             let object_lifetime_default = def.object_lifetime_default.subst(tcx, region_substs);
             let rscope1 = &ObjectLifetimeDefaultRscope::new(rscope, object_lifetime_default);
             self.ast_ty_to_ty(rscope1, ast_ty)
@@ -1603,7 +1709,7 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         for segment in assoc_segments {
             debug!("finish_resolving_def_to_ty: segment={:?}", segment);
             // This is pretty bad (it will fail except for T::A and Self::A).
-            let (new_ty, new_def) = self.associated_path_def_to_ty(span, ty, def, segment);
+            let (new_ty, new_def) = self.associated_path_def_to_ty(rscope, span, ty, def, segment);
             ty = new_ty;
             def = new_def;
 
@@ -1662,6 +1768,9 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
                 let t = self.ast_ty_to_ty(rscope1, &mt.ty);
                 tcx.mk_ref(tcx.mk_region(r), ty::TypeAndMut {ty: t, mutbl: mt.mutbl})
             }
+            hir::TyNever => {
+                tcx.types.never
+            }
             hir::TyTup(ref fields) => {
                 let flds = fields.iter()
                                  .map(|t| self.ast_ty_to_ty(rscope, &t))
@@ -1670,7 +1779,8 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
             }
             hir::TyBareFn(ref bf) => {
                 require_c_abi_if_variadic(tcx, &bf.decl, bf.abi, ast_ty.span);
-                let bare_fn_ty = self.ty_of_bare_fn(bf.unsafety, bf.abi, &bf.decl);
+                let anon_scope = rscope.anon_type_scope();
+                let bare_fn_ty = self.ty_of_bare_fn(bf.unsafety, bf.abi, &bf.decl, anon_scope);
 
                 // Find any late-bound regions declared in return type that do
                 // not appear in the arguments. These are not wellformed.
@@ -1737,10 +1847,46 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
                 ty
             }
             hir::TyFixedLengthVec(ref ty, ref e) => {
-                if let Ok(length) = eval_length(tcx.global_tcx(), &e, "array length") {
-                    tcx.mk_array(self.ast_ty_to_ty(rscope, &ty), length)
+                match tcx.const_param_def(&e) {
+                    Some(Def::TyParam(space, index, def_id, name)) => {
+                        // `[T; N]` where `N` is a `const` parameter.
+                        if is_const_ty_param(tcx, def_id) {
+                            tcx.mk_generic_array(self.ast_ty_to_ty(rscope, &ty),
+                                                 tcx.mk_param(space, index, name))
+                        } else {
+                            span_err!(tcx.sess, e.span, E0576,
+                                      "array length `{}` refers to a type parameter \
+                                       that is not `const`", name);
+                            tcx.types.err
+                        }
+                    }
+                    _ => {
+                        if let Ok(length) = eval_length(tcx.global_tcx(), &e, "array length") {
+                            tcx.mk_array(self.ast_ty_to_ty(rscope, &ty), length)
+                        } else {
+                            self.tcx().types.err
+                        }
+                    }
+                }
+            }
+            hir::TyConstArg(ref e) => {
+                if let Ok(value) = eval_length(tcx.global_tcx(), &e, "const argument") {
+                    tcx.mk_const(value)
                 } else {
-                    self.tcx().types.err
+                    tcx.types.err
+                }
+            }
+            hir::TyImplTrait(ref bounds) => {
+                match rscope.anon_type_scope() {
+                    Some(anon_scope) => {
+                        self.conv_anon_type(rscope, ast_ty, anon_scope, bounds)
+                    }
+                    None => {
+                        span_err!(tcx.sess, ast_ty.span, E0561,
+                                  "`impl Trait` not allowed outside of function \
+                                   and inherent method return types");
+                        tcx.types.err
+                    }
                 }
             }
             hir::TyTypeof(ref _e) => {
@@ -1762,6 +1908,75 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         result_ty
     }
 
+    /// Creates the anonymized type for `impl Bounds` and records its
+    /// bounds as the predicates of its def-id. The type shares the
+    /// generics of `anon_scope.enclosing_item`; its concrete type is
+    /// only filled in by writeback once that fn's body is checked.
+    fn conv_anon_type(&self,
+                      rscope: &RegionScope,
+                      ast_ty: &hir::Ty,
+                      anon_scope: AnonTypeScope,
+                      ast_bounds: &[hir::TyParamBound])
+                      -> Ty<'tcx>
+    {
+        let tcx = self.tcx();
+        let def_id = tcx.map.local_def_id(ast_ty.id);
+        let substs = self.get_anon_type_substs(ast_ty.span, anon_scope.enclosing_item);
+        let ty = tcx.mk_anon(def_id, substs);
+
+        let PartitionedBounds { mut builtin_bounds,
+                                trait_bounds,
+                                region_bounds } =
+            partition_bounds(tcx, ast_ty.span, ast_bounds);
+
+        let mut projection_bounds = vec![];
+        let trait_bounds = trait_bounds.into_iter().map(|trait_bound| {
+            self.instantiate_poly_trait_ref(rscope,
+                                            trait_bound,
+                                            Some(ty),
+                                            &mut projection_bounds)
+        }).collect();
+
+        let region_bounds = region_bounds.into_iter().map(|r| {
+            ast_region_to_region(tcx, r)
+        }).collect();
+
+        // Like type parameters, anonymized types are `Sized` unless
+        // they opt out with `?Sized`.
+        let is_unsized = ast_bounds.iter().any(|bound| match *bound {
+            hir::TraitTyParamBound(_, hir::TraitBoundModifier::Maybe) => true,
+            _ => false
+        });
+        if !is_unsized {
+            builtin_bounds.insert(ty::BoundSized);
+        }
+
+        let bounds = Bounds {
+            region_bounds: region_bounds,
+            builtin_bounds: builtin_bounds,
+            trait_bounds: trait_bounds,
+            projection_bounds: projection_bounds,
+        };
+        let predicates = bounds.predicates(tcx, ty);
+
+        // Late-bound regions of the fn signature (including elided
+        // ones) have no meaning outside of it, so they cannot appear
+        // in the bounds of a type that escapes the fn.
+        if predicates.iter().any(|p| p.has_escaping_regions()) {
+            span_err!(tcx.sess, ast_ty.span, E0562,
+                      "only named lifetimes are allowed in `impl Trait`, \
+                       but this type refers to a late-bound or elided lifetime");
+            return tcx.types.err;
+        }
+
+        let predicates = tcx.lift_to_global(&predicates).unwrap();
+        tcx.predicates.borrow_mut().insert(def_id, ty::GenericPredicates {
+            predicates: VecPerParamSpace::new(vec![], vec![], predicates)
+        });
+
+        ty
+    }
+
     pub fn ty_of_arg(&self,
                      rscope: &RegionScope,
                      a: &hir::Arg,
@@ -1777,29 +1992,33 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
 
     pub fn ty_of_method(&self,
                         sig: &hir::MethodSig,
-                        untransformed_self_ty: Ty<'tcx>)
+                        untransformed_self_ty: Ty<'tcx>,
+                        anon_scope: Option<AnonTypeScope>)
                         -> (&'tcx ty::BareFnTy<'tcx>, ty::ExplicitSelfCategory) {
         let (bare_fn_ty, optional_explicit_self_category) =
             self.ty_of_method_or_bare_fn(sig.unsafety,
                                          sig.abi,
                                          Some(untransformed_self_ty),
-                                         &sig.decl);
+                                         &sig.decl,
+                                         anon_scope);
         (bare_fn_ty, optional_explicit_self_category)
     }
 
     pub fn ty_of_bare_fn(&self,
                          unsafety: hir::Unsafety,
                          abi: abi::Abi,
-                         decl: &hir::FnDecl)
+                         decl: &hir::FnDecl,
+                         anon_scope: Option<AnonTypeScope>)
                          -> &'tcx ty::BareFnTy<'tcx> {
-        self.ty_of_method_or_bare_fn(unsafety, abi, None, decl).0
+        self.ty_of_method_or_bare_fn(unsafety, abi, None, decl, anon_scope).0
     }
 
     fn ty_of_method_or_bare_fn<'a>(&self,
                                    unsafety: hir::Unsafety,
                                    abi: abi::Abi,
                                    opt_untransformed_self_ty: Option<Ty<'tcx>>,
-                                   decl: &hir::FnDecl)
+                                   decl: &hir::FnDecl,
+                                   anon_scope: Option<AnonTypeScope>)
                                    -> (&'tcx ty::BareFnTy<'tcx>, ty::ExplicitSelfCategory)
     {
         debug!("ty_of_method_or_bare_fn");
@@ -1844,7 +2063,8 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         let output_ty = match decl.output {
             hir::Return(ref output) =>
                 ty::FnConverging(self.convert_ty_with_lifetime_elision(implied_output_region,
-                                                                       &output)),
+                                                                       &output,
+                                                                       anon_scope)),
             hir::DefaultReturn(..) => ty::FnConverging(self.tcx().mk_nil()),
             hir::NoReturn(..) => ty::FnDiverging
         };
@@ -2227,6 +2447,46 @@ pub fn partition_bounds<'a, 'b, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
     }
 }
 
+/// Whether `def_id` names a type parameter declared as `const N: usize`.
+pub fn is_const_ty_param(tcx: TyCtxt, def_id: DefId) -> bool {
+    match tcx.map.as_local_node_id(def_id).and_then(|id| tcx.map.find(id)) {
+        Some(hir_map::NodeTyParam(param)) => param.const_ty.is_some(),
+        _ => false
+    }
+}
+
+/// Checks that a generic argument is a constant exactly when the parameter
+/// it is supplied for is `const`, reporting an error otherwise. A path that
+/// resolves to a `const` parameter in scope counts as a constant, and `_`
+/// is accepted for either kind.
+pub fn check_generic_arg_kind(tcx: TyCtxt, def: &ty::TypeParameterDef, ast_ty: &hir::Ty)
+                              -> bool {
+    let is_const_arg = match ast_ty.node {
+        hir::TyInfer => return true,
+        hir::TyConstArg(_) => true,
+        hir::TyPath(None, _) => match tcx.expect_resolution(ast_ty.id) {
+            PathResolution { base_def: Def::TyParam(_, _, def_id, _), depth: 0 } => {
+                is_const_ty_param(tcx, def_id)
+            }
+            _ => false
+        },
+        _ => false
+    };
+    if is_const_arg == def.is_const {
+        return true;
+    }
+    let (expected, found) = if def.is_const {
+        ("a constant", "a type")
+    } else {
+        ("a type", "a constant")
+    };
+    struct_span_err!(tcx.sess, ast_ty.span, E0575,
+                     "mismatched kind of generic argument for parameter `{}`", def.name)
+        .span_label(ast_ty.span, &format!("expected {}, found {}", expected, found))
+        .emit();
+    false
+}
+
 fn check_type_argument_count(tcx: TyCtxt, span: Span, supplied: usize,
                              required: usize, accepted: usize) {
     if supplied < required {
@@ -502,11 +502,19 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         self.demand_eqtype(pat.span, expected, pat_ty);
 
         // Type check subpatterns.
-        let substs = match pat_ty.sty {
-            ty::TyStruct(_, substs) | ty::TyEnum(_, substs) => substs,
+        let (substs, is_union) = match pat_ty.sty {
+            ty::TyStruct(def, substs) => (substs, def.is_union()),
+            ty::TyEnum(_, substs) => (substs, false),
             _ => span_bug!(pat.span, "struct variant is not an ADT")
         };
-        self.check_struct_pat_fields(pat.span, fields, variant, substs, etc);
+
+        // A union pattern reads exactly one of the union's fields, the
+        // others are never reported as missing.
+        if is_union && fields.len() != 1 {
+            span_err!(self.tcx.sess, pat.span, E0565,
+                      "union patterns should have exactly one field");
+        }
+        self.check_struct_pat_fields(pat.span, fields, variant, substs, etc || is_union);
     }
 
     fn check_pat_path(&self,
@@ -115,7 +115,8 @@ impl<'a, 'gcx, 'tcx> Autoderef<'a, 'gcx, 'tcx> {
             &mut selcx,
             ty::ProjectionTy {
                 trait_ref: trait_ref,
-                item_name: token::intern("Target")
+                item_name: token::intern("Target"),
+                item_substs: tcx.mk_substs(Substs::empty())
             },
             cause,
             0
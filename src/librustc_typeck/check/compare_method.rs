@@ -498,3 +498,100 @@ pub fn compare_const_impl<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
         }
     });
 }
+
+/// Checks that an associated type from an impl declares the same number of
+/// parameters as the same type declared in the trait, and that its where
+/// clauses follow from those of the trait (as for methods above).
+pub fn compare_impl_type<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
+                                   impl_ty: &ty::AssociatedType<'tcx>,
+                                   impl_ty_span: Span,
+                                   trait_ty: &ty::AssociatedType<'tcx>,
+                                   impl_trait_ref: &ty::TraitRef<'tcx>) {
+    debug!("compare_impl_type(impl_trait_ref={:?})",
+           impl_trait_ref);
+
+    let tcx = ccx.tcx;
+
+    let num_impl_region_params = impl_ty.generics.regions.len(subst::FnSpace);
+    let num_trait_region_params = trait_ty.generics.regions.len(subst::FnSpace);
+    let num_impl_type_params = impl_ty.generics.types.len(subst::FnSpace);
+    let num_trait_type_params = trait_ty.generics.types.len(subst::FnSpace);
+    if num_impl_region_params != num_trait_region_params ||
+       num_impl_type_params != num_trait_type_params {
+        span_err!(tcx.sess, impl_ty_span, E0573,
+            "associated type `{}` has {} lifetime and {} type parameter(s) \
+             but its trait declaration has {} lifetime and {} type parameter(s)",
+            trait_ty.name,
+            num_impl_region_params,
+            num_impl_type_params,
+            num_trait_region_params,
+            num_trait_type_params);
+        return;
+    }
+
+    if impl_ty.predicates.predicates.is_empty_in(subst::FnSpace) {
+        return;
+    }
+
+    // Check the impl's where clauses in an environment made of the impl's
+    // own bounds and the trait's bounds on the associated type, with the
+    // parameters skolemized as in `compare_impl_method`.
+    let impl_ty_node_id = tcx.map.as_local_node_id(impl_ty.def_id).unwrap();
+    let impl_param_env = ty::ParameterEnvironment::for_item(tcx, impl_ty_node_id);
+    let impl_to_skol_substs = &impl_param_env.free_substs;
+    let trait_to_skol_substs =
+        impl_trait_ref.substs
+        .subst(tcx, impl_to_skol_substs).clone()
+        .with_method(impl_to_skol_substs.types.get_slice(subst::FnSpace).to_vec(),
+                     impl_to_skol_substs.regions.get_slice(subst::FnSpace).to_vec());
+    debug!("compare_impl_type: trait_to_skol_substs={:?}",
+           trait_to_skol_substs);
+
+    tcx.infer_ctxt(None, None, ProjectionMode::AnyFinal).enter(|mut infcx| {
+        let mut fulfillment_cx = traits::FulfillmentContext::new();
+
+        let trait_bounds = trait_ty.predicates.instantiate(tcx, &trait_to_skol_substs);
+        let impl_bounds = impl_ty.predicates.instantiate(tcx, impl_to_skol_substs);
+        let trait_pred = trait_bounds.predicates.split();
+        let impl_pred = impl_bounds.predicates.split();
+        let hybrid_preds = VecPerParamSpace::new(
+            impl_pred.types,
+            impl_pred.selfs,
+            trait_pred.fns
+        );
+
+        let normalize_cause = traits::ObligationCause::misc(impl_ty_span, impl_ty_node_id);
+        let trait_param_env = impl_param_env.with_caller_bounds(hybrid_preds.into_vec());
+        let trait_param_env = traits::normalize_param_env_or_error(tcx,
+                                                                   trait_param_env,
+                                                                   normalize_cause.clone());
+        infcx.parameter_environment = trait_param_env;
+
+        let mut selcx = traits::SelectionContext::new(&infcx);
+        for predicate in impl_pred.fns {
+            let traits::Normalized { value: predicate, .. } =
+                traits::normalize(&mut selcx, normalize_cause.clone(), &predicate);
+
+            let cause = traits::ObligationCause {
+                span: impl_ty_span,
+                body_id: impl_ty_node_id,
+                code: traits::ObligationCauseCode::CompareImplTypeObligation
+            };
+
+            fulfillment_cx.register_predicate_obligation(
+                &infcx,
+                traits::Obligation::new(cause, predicate));
+        }
+
+        if let Err(ref errors) = fulfillment_cx.select_all_or_error(&infcx) {
+            infcx.report_fulfillment_errors(errors);
+            return
+        }
+
+        let mut free_regions = FreeRegionMap::new();
+        free_regions.relate_free_regions_from_predicates(
+            &infcx.parameter_environment.caller_bounds);
+
+        infcx.resolve_regions_and_report_errors(&free_regions, impl_ty_node_id);
+    });
+}
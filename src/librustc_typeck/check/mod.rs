@@ -77,10 +77,11 @@ type parameter).
 */
 
 pub use self::Expectation::*;
-pub use self::compare_method::{compare_impl_method, compare_const_impl};
+pub use self::compare_method::{compare_impl_method, compare_const_impl, compare_impl_type};
 use self::TupleArgumentsFlag::*;
 
-use astconv::{AstConv, ast_region_to_region, PathParamMode};
+use astconv::{AstConv, ast_region_to_region, check_generic_arg_kind, is_const_ty_param,
+              PathParamMode};
 use dep_graph::DepNode;
 use fmt_macros::{Parser, Piece, Position};
 use middle::cstore::LOCAL_CRATE;
@@ -96,7 +97,7 @@ use rustc::ty::{LvaluePreference, NoPreference, PreferMutLvalue};
 use rustc::ty::{self, ToPolyTraitRef, Ty, TyCtxt, Visibility};
 use rustc::ty::{MethodCall, MethodCallee};
 use rustc::ty::adjustment;
-use rustc::ty::fold::TypeFoldable;
+use rustc::ty::fold::{BottomUpFolder, TypeFoldable};
 use rustc::ty::util::{Representability, IntTypeExt};
 use require_c_abi_if_variadic;
 use rscope::{ElisionFailureInfo, RegionScope};
@@ -172,6 +173,11 @@ pub struct Inherited<'a, 'gcx: 'a+'tcx, 'tcx: 'a> {
     deferred_call_resolutions: RefCell<DefIdMap<Vec<DeferredCallResolutionHandler<'gcx, 'tcx>>>>,
 
     deferred_cast_checks: RefCell<Vec<cast::CastCheck<'tcx>>>,
+
+    // Anonymized types found in the return type of the fn being checked,
+    // mapped to the inference variables standing in for them in the body.
+    // Writeback records the resolved types as their concrete types.
+    anon_types: RefCell<DefIdMap<Ty<'tcx>>>,
 }
 
 impl<'a, 'gcx, 'tcx> Deref for Inherited<'a, 'gcx, 'tcx> {
@@ -408,6 +414,7 @@ impl<'a, 'gcx, 'tcx> InheritedBuilder<'a, 'gcx, 'tcx> {
                 locals: RefCell::new(NodeMap()),
                 deferred_call_resolutions: RefCell::new(DefIdMap()),
                 deferred_cast_checks: RefCell::new(Vec::new()),
+                anon_types: RefCell::new(DefIdMap()),
             })
         })
     }
@@ -428,6 +435,51 @@ impl<'a, 'gcx, 'tcx> Inherited<'a, 'gcx, 'tcx> {
                                              value)
     }
 
+    /// Replaces the anonymized types (`impl Trait`) in `value` with
+    /// fresh inference variables, so that the body determines their
+    /// concrete types. The bounds of each anonymized type become
+    /// obligations on its variable.
+    fn instantiate_anon_types<T>(&self,
+                                 span: Span,
+                                 body_id: ast::NodeId,
+                                 value: &T)
+                                 -> T
+        where T : TypeFoldable<'tcx>
+    {
+        value.fold_with(&mut BottomUpFolder { tcx: self.tcx, fldop: |ty| {
+            let (def_id, substs) = match ty.sty {
+                ty::TyAnon(def_id, substs) => (def_id, substs),
+                _ => return ty
+            };
+
+            // The same anonymized type may appear more than once.
+            if let Some(&ty_var) = self.anon_types.borrow().get(&def_id) {
+                return ty_var;
+            }
+
+            let ty_var = self.next_ty_var();
+            self.anon_types.borrow_mut().insert(def_id, ty_var);
+
+            let predicates = self.tcx.lookup_predicates(def_id).instantiate(self.tcx, substs);
+            let cause = traits::ObligationCause::new(span, body_id, traits::MiscObligation);
+            for predicate in predicates.predicates.into_vec() {
+                // The anonymized type is the self type of its own bounds,
+                // which may in turn mention other anonymized types, as in
+                // `impl Iterator<Item=impl Debug>`.
+                let predicate = predicate.fold_with(&mut BottomUpFolder {
+                    tcx: self.tcx,
+                    fldop: |t| if t == ty { ty_var } else { t }
+                });
+                let predicate = self.instantiate_anon_types(span, body_id, &predicate);
+                let obligation = traits::Obligation::new(cause.clone(), predicate);
+                self.fulfillment_cx
+                    .borrow_mut()
+                    .register_predicate_obligation(self, obligation);
+            }
+
+            ty_var
+        }})
+    }
 }
 
 struct CheckItemTypesVisitor<'a, 'tcx: 'a> { ccx: &'a CrateCtxt<'a, 'tcx> }
@@ -441,8 +493,10 @@ impl<'a, 'tcx> Visitor<'tcx> for CheckItemTypesVisitor<'a, 'tcx> {
 
     fn visit_ty(&mut self, t: &'tcx hir::Ty) {
         match t.node {
-            hir::TyFixedLengthVec(_, ref expr) => {
-                check_const_in_type(self.ccx, &expr, self.ccx.tcx.types.usize);
+            hir::TyFixedLengthVec(_, ref expr) | hir::TyConstArg(ref expr) => {
+                if self.ccx.tcx.const_param_def(&expr).is_none() {
+                    check_const_in_type(self.ccx, &expr, self.ccx.tcx.types.usize);
+                }
             }
             _ => {}
         }
@@ -520,6 +574,8 @@ fn check_bare_fn<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
             inh.tcx.liberate_late_bound_regions(fn_scope, &fn_sig);
         let fn_sig =
             inh.normalize_associated_types_in(body.span, body.id, &fn_sig);
+        let fn_sig =
+            inh.instantiate_anon_types(decl.output.span(), body.id, &fn_sig);
 
         let fcx = check_fn(&inh, fn_ty.unsafety, fn_id, &fn_sig, decl, fn_id, body);
 
@@ -601,7 +657,12 @@ impl<'a, 'gcx, 'tcx> Visitor<'gcx> for GatherLocalsVisitor<'a, 'gcx, 'tcx> {
         match t.node {
             hir::TyFixedLengthVec(ref ty, ref count_expr) => {
                 self.visit_ty(&ty);
-                self.fcx.check_expr_with_hint(&count_expr, self.fcx.tcx.types.usize);
+                if self.fcx.tcx.const_param_def(&count_expr).is_none() {
+                    self.fcx.check_expr_with_hint(&count_expr, self.fcx.tcx.types.usize);
+                }
+            }
+            hir::TyConstArg(ref expr) => {
+                self.fcx.check_expr_with_hint(&expr, self.fcx.tcx.types.usize);
             }
             hir::TyBareFn(ref function_declaration) => {
                 intravisit::walk_fn_decl_nopat(self, &function_declaration.decl);
@@ -700,6 +761,10 @@ pub fn check_struct(ccx: &CrateCtxt, id: ast::NodeId, span: Span) {
     }
 }
 
+fn check_union(ccx: &CrateCtxt, id: ast::NodeId, span: Span) {
+    check_representable(ccx.tcx, span, id, "union");
+}
+
 pub fn check_item_type<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx hir::Item) {
     debug!("check_item_type(it.id={}, it.name={})",
            it.id,
@@ -716,7 +781,7 @@ pub fn check_item_type<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx hir::Item) {
                             it.id);
       }
       hir::ItemFn(..) => {} // entirely within check_item_body
-      hir::ItemImpl(_, _, _, _, _, ref impl_items) => {
+      hir::ItemImpl(_, _, _, _, _, _, ref impl_items) => {
           debug!("ItemImpl {} with id {}", it.name, it.id);
           let impl_def_id = ccx.tcx.map.local_def_id(it.id);
           match ccx.tcx.impl_trait_ref(impl_def_id) {
@@ -745,6 +810,9 @@ pub fn check_item_type<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx hir::Item) {
       hir::ItemStruct(..) => {
         check_struct(ccx, it.id, it.span);
       }
+      hir::ItemUnion(..) => {
+        check_union(ccx, it.id, it.span);
+      }
       hir::ItemTy(_, ref generics) => {
         let pty_ty = ccx.tcx.node_id_to_type(it.id);
         check_bounds_are_used(ccx, &generics.ty_params, pty_ty);
@@ -791,7 +859,7 @@ pub fn check_item_body<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx hir::Item) {
         let param_env = ParameterEnvironment::for_item(ccx.tcx, it.id);
         check_bare_fn(ccx, &decl, &body, it.id, it.span, fn_pty.ty, param_env);
       }
-      hir::ItemImpl(_, _, _, _, _, ref impl_items) => {
+      hir::ItemImpl(_, _, _, _, _, _, ref impl_items) => {
         debug!("ItemImpl {} with id {}", it.name, it.id);
 
         let impl_pty = ccx.tcx.lookup_item_type(ccx.tcx.map.local_def_id(it.id));
@@ -805,7 +873,7 @@ pub fn check_item_body<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx hir::Item) {
                     check_method_body(ccx, &impl_pty.generics, sig, body,
                                       impl_item.id, impl_item.span);
                 }
-                hir::ImplItemKind::Type(_) => {
+                hir::ImplItemKind::Type(..) => {
                     // Nothing to do here.
                 }
             }
@@ -960,7 +1028,7 @@ fn check_specialization_validity<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                 .map(|node_item| node_item.map(|parent| parent.defaultness))
 
         }
-        hir::ImplItemKind::Type(_) => {
+        hir::ImplItemKind::Type(..) => {
             ancestors.type_defs(tcx, impl_item.name).skip(1).next()
                 .map(|node_item| node_item.map(|parent| parent.defaultness))
         }
@@ -1044,13 +1112,18 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                   impl_trait_ref)
                     }
                 }
-                hir::ImplItemKind::Type(_) => {
+                hir::ImplItemKind::Type(..) => {
                     let impl_type = match ty_impl_item {
                         ty::TypeTraitItem(ref tti) => tti,
                         _ => span_bug!(impl_item.span, "non-type impl-item for type")
                     };
 
                     if let &ty::TypeTraitItem(ref at) = ty_trait_item {
+                        compare_impl_type(ccx,
+                                          &impl_type,
+                                          impl_item.span,
+                                          &at,
+                                          &impl_trait_ref);
                         if let Some(_) = at.ty {
                             overridden_associated_type = Some(impl_item);
                         }
@@ -1116,7 +1189,8 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
         }
     }
 
-    if !missing_items.is_empty() {
+    // A `default impl` may leave items to the impls specializing it.
+    if !missing_items.is_empty() && !tcx.impl_is_default(impl_id) {
         span_err!(tcx.sess, impl_span, E0046,
             "not all trait items implemented, missing: `{}`",
             missing_items.iter()
@@ -1290,6 +1364,10 @@ impl<'a, 'gcx, 'tcx> AstConv<'gcx, 'tcx> for FnCtxt<'a, 'gcx, 'tcx> {
         Some(&self.parameter_environment.free_substs)
     }
 
+    fn get_anon_type_substs(&self, span: Span, _: DefId) -> &'tcx Substs<'tcx> {
+        span_bug!(span, "anonymized types are only created in item signatures")
+    }
+
     fn get_type_parameter_bounds(&self,
                                  _: Span,
                                  node_id: ast::NodeId)
@@ -1326,6 +1404,17 @@ impl<'a, 'gcx, 'tcx> AstConv<'gcx, 'tcx> for FnCtxt<'a, 'gcx, 'tcx> {
         trait_def.associated_type_names.contains(&assoc_name)
     }
 
+    fn associated_type_param_counts(&self,
+                                    trait_def_id: DefId,
+                                    assoc_name: ast::Name)
+                                    -> (usize, usize)
+    {
+        self.tcx().associated_type_named(trait_def_id, assoc_name).map_or((0, 0), |assoc_ty| {
+            (assoc_ty.generics.regions.len(subst::FnSpace),
+             assoc_ty.generics.types.len(subst::FnSpace))
+        })
+    }
+
     fn ty_infer(&self,
                 ty_param_def: Option<ty::TypeParameterDef<'tcx>>,
                 substs: Option<&mut subst::Substs<'tcx>>,
@@ -1352,7 +1441,8 @@ impl<'a, 'gcx, 'tcx> AstConv<'gcx, 'tcx> for FnCtxt<'a, 'gcx, 'tcx> {
     fn projected_ty_from_poly_trait_ref(&self,
                                         span: Span,
                                         poly_trait_ref: ty::PolyTraitRef<'tcx>,
-                                        item_name: ast::Name)
+                                        item_name: ast::Name,
+                                        item_substs: &'tcx Substs<'tcx>)
                                         -> Ty<'tcx>
     {
         let (trait_ref, _) =
@@ -1361,16 +1451,17 @@ impl<'a, 'gcx, 'tcx> AstConv<'gcx, 'tcx> for FnCtxt<'a, 'gcx, 'tcx> {
                 infer::LateBoundRegionConversionTime::AssocTypeProjection(item_name),
                 &poly_trait_ref);
 
-        self.normalize_associated_type(span, trait_ref, item_name)
+        self.normalize_associated_type(span, trait_ref, item_name, item_substs)
     }
 
     fn projected_ty(&self,
                     span: Span,
                     trait_ref: ty::TraitRef<'tcx>,
-                    item_name: ast::Name)
+                    item_name: ast::Name,
+                    item_substs: &'tcx Substs<'tcx>)
                     -> Ty<'tcx>
     {
-        self.normalize_associated_type(span, trait_ref, item_name)
+        self.normalize_associated_type(span, trait_ref, item_name, item_substs)
     }
 
     fn set_tainted_by_errors(&self) {
@@ -1600,7 +1691,8 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
     fn normalize_associated_type(&self,
                                  span: Span,
                                  trait_ref: ty::TraitRef<'tcx>,
-                                 item_name: ast::Name)
+                                 item_name: ast::Name,
+                                 item_substs: &'tcx Substs<'tcx>)
                                  -> Ty<'tcx>
     {
         let cause = traits::ObligationCause::new(span,
@@ -1612,6 +1704,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                                        ty::ProjectionTy {
                                            trait_ref: trait_ref,
                                            item_name: item_name,
+                                           item_substs: item_substs,
                                        },
                                        cause)
     }
@@ -2216,7 +2309,11 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                 return Some(final_mt);
             }
 
-            if let ty::TyArray(element_ty, _) = adj_ty.sty {
+            let element_ty = match adj_ty.sty {
+                ty::TyArray(element_ty, _) | ty::TyGenericArray(element_ty, _) => Some(element_ty),
+                _ => None
+            };
+            if let Some(element_ty) = element_ty {
                 autoderef.finalize(lvalue_pref, Some(base_expr));
                 let adjusted_ty = self.tcx.mk_slice(element_ty);
                 return self.try_index_step(
@@ -3053,8 +3150,9 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                                 ast_fields: &'gcx [hir::Field],
                                 check_completeness: bool) {
         let tcx = self.tcx;
-        let substs = match adt_ty.sty {
-            ty::TyStruct(_, substs) | ty::TyEnum(_, substs) => substs,
+        let (substs, is_union) = match adt_ty.sty {
+            ty::TyStruct(def, substs) => (substs, def.is_union()),
+            ty::TyEnum(_, substs) => (substs, false),
             _ => span_bug!(span, "non-ADT passed to check_expr_struct_fields")
         };
 
@@ -3088,8 +3186,14 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
             self.check_expr_coercable_to_type(&field.expr, expected_field_type);
         }
 
-            // Make sure the programmer specified all the fields.
-        if check_completeness &&
+        // A union expression initializes exactly one of the union's fields.
+        if is_union {
+            if ast_fields.len() != 1 {
+                span_err!(tcx.sess, span, E0565,
+                          "union expressions should have exactly one field");
+            }
+        // Make sure the programmer specified all the fields.
+        } else if check_completeness &&
             !error_happened &&
             !remaining_fields.is_empty()
         {
@@ -3177,7 +3281,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         if let &Some(ref base_expr) = base_expr {
             self.check_expr_has_type(base_expr, expr_ty);
             match expr_ty.sty {
-                ty::TyStruct(adt, substs) => {
+                ty::TyStruct(adt, substs) if !adt.is_union() => {
                     self.tables.borrow_mut().fru_field_types.insert(
                         expr.id,
                         adt.struct_variant().fields.iter().map(|f| {
@@ -3339,6 +3443,20 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
             };
             self.write_ty(id, oprnd_t);
           }
+          hir::ExprPath(..) if tcx.const_param_def(expr).is_some() => {
+              // A `const` type parameter used as a value, e.g. the `N` in
+              // `fn len(&self) -> usize { N }`.
+              if let Some(Def::TyParam(_, _, def_id, name)) = tcx.const_param_def(expr) {
+                  if is_const_ty_param(tcx, def_id) {
+                      self.write_ty(id, tcx.types.usize);
+                  } else {
+                      span_err!(tcx.sess, expr.span, E0577,
+                                "type parameter `{}` is not `const` and cannot be used \
+                                 as a value", name);
+                      self.write_error(id);
+                  }
+              }
+          }
           hir::ExprPath(ref opt_qself, ref path) => {
               let opt_self_ty = opt_qself.as_ref().map(|qself| self.to_ty(&qself.ty));
               let (def, opt_ty, segments) = self.resolve_ty_and_def_ufcs(opt_self_ty, path,
@@ -3470,7 +3588,9 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
           }
           hir::ExprCast(ref e, ref t) => {
             if let hir::TyFixedLengthVec(_, ref count_expr) = t.node {
-                self.check_expr_with_hint(&count_expr, tcx.types.usize);
+                if tcx.const_param_def(&count_expr).is_none() {
+                    self.check_expr_with_hint(&count_expr, tcx.types.usize);
+                }
             }
 
             // Find the type of `e`. Supply hints based on the type we are casting to,
@@ -3509,7 +3629,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
           hir::ExprVec(ref args) => {
             let uty = expected.to_option(self).and_then(|uty| {
                 match uty.sty {
-                    ty::TyArray(ty, _) | ty::TySlice(ty) => Some(ty),
+                    ty::TyArray(ty, _) | ty::TyGenericArray(ty, _) | ty::TySlice(ty) => Some(ty),
                     _ => None
                 }
             });
@@ -3547,7 +3667,8 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
             let uty = match expected {
                 ExpectHasType(uty) => {
                     match uty.sty {
-                        ty::TyArray(ty, _) | ty::TySlice(ty) => Some(ty),
+                        ty::TyArray(ty, _) | ty::TyGenericArray(ty, _) |
+                        ty::TySlice(ty) => Some(ty),
                         _ => None
                     }
                 }
@@ -4316,7 +4437,12 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
             let type_count = type_defs.len(space);
             assert_eq!(substs.types.len(space), 0);
             for (i, typ) in data.types.iter().enumerate() {
-                let t = self.to_ty(&typ);
+                let t = match type_defs.opt_get(space, i) {
+                    Some(def) if !check_generic_arg_kind(self.tcx, def, typ) => {
+                        self.tcx.types.err
+                    }
+                    _ => self.to_ty(&typ)
+                };
                 if i < type_count {
                     substs.types.push(space, t);
                 } else if i == type_count {
@@ -15,6 +15,7 @@ use self::ResolveReason::*;
 
 use check::FnCtxt;
 use hir::def_id::DefId;
+use rustc::util::nodemap::DefIdMap;
 use rustc::ty::{self, Ty, TyCtxt, MethodCall, MethodCallee};
 use rustc::ty::adjustment;
 use rustc::ty::fold::{TypeFolder,TypeFoldable};
@@ -62,6 +63,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         wbcx.visit_closures();
         wbcx.visit_liberated_fn_sigs();
         wbcx.visit_fru_field_types();
+        wbcx.visit_anon_types();
     }
 }
 
@@ -202,7 +204,12 @@ impl<'cx, 'gcx, 'tcx, 'v> Visitor<'v> for WritebackCx<'cx, 'gcx, 'tcx> {
         match t.node {
             hir::TyFixedLengthVec(ref ty, ref count_expr) => {
                 self.visit_ty(&ty);
-                write_ty_to_tcx(self.fcx.ccx, count_expr.id, self.tcx().types.usize);
+                if self.tcx().const_param_def(&count_expr).is_none() {
+                    write_ty_to_tcx(self.fcx.ccx, count_expr.id, self.tcx().types.usize);
+                }
+            }
+            hir::TyConstArg(ref expr) => {
+                write_ty_to_tcx(self.fcx.ccx, expr.id, self.tcx().types.usize);
             }
             hir::TyBareFn(ref function_declaration) => {
                 intravisit::walk_fn_decl_nopat(self, &function_declaration.decl);
@@ -281,6 +288,10 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
 
             Some(adjustment) => {
                 let resolved_adjustment = match adjustment {
+                    adjustment::AdjustNeverToAny(ty) => {
+                        adjustment::AdjustNeverToAny(self.resolve(&ty, reason))
+                    }
+
                     adjustment::AdjustReifyFnPointer => {
                         adjustment::AdjustReifyFnPointer
                     }
@@ -353,6 +364,77 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
         }
     }
 
+    fn visit_anon_types(&self) {
+        if self.fcx.writeback_errors.get() {
+            return
+        }
+
+        let gcx = self.tcx().global_tcx();
+        for (&def_id, &concrete_ty) in self.fcx.anon_types.borrow().iter() {
+            let node_id = gcx.map.as_local_node_id(def_id).unwrap();
+            let reason = ResolvingAnonTy(node_id);
+            let inside_ty = self.resolve(&concrete_ty, reason);
+
+            // The concrete type is in terms of the free regions of the fn
+            // body; map those back to the early-bound regions the
+            // anonymized type is parameterized by.
+            let fn_def_id = gcx.map.get_parent_did(node_id);
+            let generics = gcx.lookup_item_type(fn_def_id).generics;
+            let early_bound: DefIdMap<ty::Region> = generics.regions.iter().map(|def| {
+                (def.def_id, def.to_early_bound_region())
+            }).collect();
+            let outside_ty = gcx.fold_regions(&inside_ty, &mut false, |r, _| {
+                match r {
+                    // 'static is valid everywhere.
+                    ty::ReStatic | ty::ReEmpty => r,
+
+                    // Free regions that come from early-bound regions are valid.
+                    ty::ReFree(ty::FreeRegion { bound_region: ty::BrNamed(region_def_id, ..), .. })
+                        if early_bound.contains_key(&region_def_id) => {
+                        early_bound[&region_def_id]
+                    }
+
+                    ty::ReFree(_) |
+                    ty::ReEarlyBound(_) |
+                    ty::ReLateBound(..) |
+                    ty::ReScope(_) |
+                    ty::ReSkolemized(..) => {
+                        let span = reason.span(gcx);
+                        span_err!(gcx.sess, span, E0563,
+                                  "only named lifetimes are allowed in `impl Trait`, \
+                                   but `{}` was found in the type `{}`", r, inside_ty);
+                        ty::ReStatic
+                    }
+
+                    ty::ReVar(_) |
+                    ty::ReErased => {
+                        let span = reason.span(gcx);
+                        span_bug!(span, "invalid region in impl Trait: {:?}", r);
+                    }
+                }
+            });
+
+            // Revealing a type which contains itself would never end.
+            let is_recursive = outside_ty.walk().any(|t| match t.sty {
+                ty::TyAnon(anon_def_id, _) => anon_def_id == def_id,
+                _ => false
+            });
+            let outside_ty = if is_recursive {
+                span_err!(gcx.sess, reason.span(gcx), E0564,
+                          "the concrete type of this `impl Trait` contains itself: `{}`",
+                          outside_ty);
+                gcx.types.err
+            } else {
+                outside_ty
+            };
+
+            gcx.register_item_type(def_id, ty::TypeScheme {
+                ty: outside_ty,
+                generics: generics
+            });
+        }
+    }
+
     fn resolve<T>(&self, x: &T, reason: ResolveReason) -> T::Lifted
         where T: TypeFoldable<'tcx> + ty::Lift<'gcx>
     {
@@ -377,7 +459,8 @@ enum ResolveReason {
     ResolvingUpvar(ty::UpvarId),
     ResolvingClosure(DefId),
     ResolvingFnSig(ast::NodeId),
-    ResolvingFieldTypes(ast::NodeId)
+    ResolvingFieldTypes(ast::NodeId),
+    ResolvingAnonTy(ast::NodeId),
 }
 
 impl<'a, 'gcx, 'tcx> ResolveReason {
@@ -392,7 +475,8 @@ impl<'a, 'gcx, 'tcx> ResolveReason {
             ResolvingFnSig(id) => {
                 tcx.map.span(id)
             }
-            ResolvingFieldTypes(id) => {
+            ResolvingFieldTypes(id) |
+            ResolvingAnonTy(id) => {
                 tcx.map.span(id)
             }
             ResolvingClosure(did) => {
@@ -468,7 +552,9 @@ impl<'cx, 'gcx, 'tcx> Resolver<'cx, 'gcx, 'tcx> {
                               "cannot determine a type for this closure")
                 }
 
-                ResolvingFnSig(id) | ResolvingFieldTypes(id) => {
+                ResolvingFnSig(id) |
+                ResolvingFieldTypes(id) |
+                ResolvingAnonTy(id) => {
                     // any failures here should also fail when
                     // resolving the patterns, closure types, or
                     // something else.
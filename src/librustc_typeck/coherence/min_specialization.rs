@@ -0,0 +1,182 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Minimal specialization checker: under `#![feature(min_specialization)]`
+//! (and without the full `specialization` feature) every specializing impl
+//! must be "always applicable", i.e. whether it applies may not depend on
+//! lifetimes or on trait bounds that the impl it specializes does not have.
+//!
+//! Concretely, if impl1 specializes impl2 with impl2's parameters
+//! instantiated to `S` in terms of impl1's parameters, then:
+//!
+//! - `S` may not mention `'static`;
+//! - no parameter of impl1 may appear more than once in `S`;
+//! - every where clause of impl1 must either follow from the where clauses
+//!   of impl2, be a `Sized` bound, or be a bound on a trait marked with
+//!   `#[rustc_specialization_trait]`.
+//!
+//! Since specializing impls may rely on bounds on a specialization trait,
+//! the impls of such a trait must be always applicable themselves: their
+//! trait reference may not mention `'static` or repeat a parameter, and
+//! their where clauses are restricted as above.
+
+use rustc::traits;
+use rustc::ty::{self, TyCtxt};
+use rustc::ty::subst::Substs;
+use rustc::hir::def_id::DefId;
+use rustc::hir::intravisit;
+use rustc::hir;
+use rustc::middle::region;
+use rustc::ty::subst::Subst;
+use syntax_pos::{Span, DUMMY_SP};
+use util::nodemap::FnvHashSet;
+
+pub fn check<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
+    {
+        let features = tcx.sess.features.borrow();
+        if !features.min_specialization || features.specialization {
+            return;
+        }
+    }
+    let mut checker = MinSpecializationChecker { tcx: tcx };
+    tcx.map.krate().visit_all_items(&mut checker);
+}
+
+struct MinSpecializationChecker<'cx, 'tcx:'cx> {
+    tcx: TyCtxt<'cx, 'tcx, 'tcx>
+}
+
+impl<'cx, 'tcx> MinSpecializationChecker<'cx, 'tcx> {
+    fn check_impl(&self, impl_def_id: DefId, span: Span) {
+        let tcx = self.tcx;
+        let trait_ref = match tcx.impl_trait_ref(impl_def_id) {
+            Some(trait_ref) => trait_ref,
+            None => return,
+        };
+        if tcx.has_attr(trait_ref.def_id, "rustc_specialization_trait") {
+            self.check_specialization_trait_impl(impl_def_id, trait_ref, span);
+        }
+
+        let trait_def = tcx.lookup_trait_def(trait_ref.def_id);
+        let parent = trait_def.specialization_graph.borrow().parent(impl_def_id);
+        if parent == trait_ref.def_id {
+            // Not a specializing impl.
+            return;
+        }
+
+        let (free_substs, parent_substs) =
+            match traits::specialized_impl_substs(tcx, impl_def_id, parent) {
+                Some(substs) => substs,
+                // Overlap checking has already reported the error.
+                None => return,
+            };
+
+        self.check_static_lifetimes(parent_substs, span);
+        self.check_repeated_parameters(parent_substs, span);
+
+        let parent_predicates = tcx.lookup_predicates(parent)
+                                   .instantiate(tcx, parent_substs)
+                                   .predicates
+                                   .into_vec();
+        let parent_predicates: Vec<_> =
+            traits::elaborate_predicates(tcx, parent_predicates).collect();
+        let predicates = tcx.lookup_predicates(impl_def_id)
+                            .instantiate(tcx, free_substs)
+                            .predicates;
+        for predicate in predicates.iter() {
+            if !parent_predicates.contains(predicate) &&
+               !self.is_always_applicable_predicate(predicate) {
+                span_err!(tcx.sess, span, E0570,
+                          "cannot specialize on `{}`", predicate);
+            }
+        }
+    }
+
+    fn check_specialization_trait_impl(&self,
+                                       impl_def_id: DefId,
+                                       trait_ref: ty::TraitRef<'tcx>,
+                                       span: Span) {
+        let tcx = self.tcx;
+        let scheme = tcx.lookup_item_type(impl_def_id);
+        let predicates = tcx.lookup_predicates(impl_def_id);
+        let penv = tcx.construct_parameter_environment(DUMMY_SP,
+                                                       &scheme.generics,
+                                                       &predicates,
+                                                       region::DUMMY_CODE_EXTENT);
+        let free_substs = penv.free_substs;
+        let trait_ref = trait_ref.subst(tcx, &free_substs);
+
+        self.check_static_lifetimes(trait_ref.substs, span);
+        self.check_repeated_parameters(trait_ref.substs, span);
+        for predicate in predicates.instantiate(tcx, &free_substs).predicates.iter() {
+            if !self.is_always_applicable_predicate(predicate) {
+                span_err!(tcx.sess, span, E0570,
+                          "impls of a specialization trait cannot depend on `{}`",
+                          predicate);
+            }
+        }
+    }
+
+    fn check_static_lifetimes(&self, substs: &Substs<'tcx>, span: Span) {
+        let mut regions = FnvHashSet();
+        self.tcx.collect_regions(substs, &mut regions);
+        if regions.contains(&ty::ReStatic) {
+            span_err!(self.tcx.sess, span, E0568,
+                      "cannot specialize on `'static` lifetime");
+        }
+    }
+
+    fn check_repeated_parameters(&self, substs: &Substs<'tcx>, span: Span) {
+        // Collect in order of appearance so that errors come out in source order.
+        let mut params = vec![];
+        for ty in substs.types.iter().flat_map(|ty| ty.walk()) {
+            if let ty::TyParam(..) = ty.sty {
+                params.push(ty.to_string());
+            }
+        }
+        let mut skipped_regions = false;
+        self.tcx.fold_regions(substs, &mut skipped_regions, |r, _| {
+            if let ty::ReFree(..) = r {
+                params.push(r.to_string());
+            }
+            r
+        });
+
+        let mut reported = vec![];
+        for (i, param) in params.iter().enumerate() {
+            if params[..i].contains(param) && !reported.contains(&param) {
+                span_err!(self.tcx.sess, span, E0569,
+                          "specializing impl repeats parameter `{}`", param);
+                reported.push(param);
+            }
+        }
+    }
+
+    /// Bounds that may be added by a specializing impl: `Sized` and bounds on
+    /// traits marked `#[rustc_specialization_trait]`.
+    fn is_always_applicable_predicate(&self, predicate: &ty::Predicate<'tcx>) -> bool {
+        match *predicate {
+            ty::Predicate::Trait(ref data) => {
+                let def_id = data.def_id();
+                Some(def_id) == self.tcx.lang_items.sized_trait() ||
+                    self.tcx.has_attr(def_id, "rustc_specialization_trait")
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'cx, 'tcx, 'v> intravisit::Visitor<'v> for MinSpecializationChecker<'cx, 'tcx> {
+    fn visit_item(&mut self, item: &'v hir::Item) {
+        if let hir::ItemImpl(_, _, _, _, Some(_), _, _) = item.node {
+            self.check_impl(self.tcx.map.local_def_id(item.id), item.span);
+        }
+    }
+}
@@ -360,10 +360,28 @@ impl<'a, 'tcx> AstConv<'tcx, 'tcx> for ItemCtxt<'a, 'tcx> {
         }
     }
 
+    fn associated_type_param_counts(&self,
+                                    trait_def_id: DefId,
+                                    assoc_name: ast::Name)
+                                    -> (usize, usize)
+    {
+        if let Some(trait_id) = self.tcx().map.as_local_node_id(trait_def_id) {
+            associated_type_param_counts(self.ccx, trait_id, assoc_name)
+        } else {
+            self.tcx().associated_type_named(trait_def_id, assoc_name).map_or((0, 0), |assoc_ty| {
+                (assoc_ty.generics.regions.len(FnSpace), assoc_ty.generics.types.len(FnSpace))
+            })
+        }
+    }
+
     fn get_free_substs(&self) -> Option<&Substs<'tcx>> {
         None
     }
 
+    fn get_anon_type_substs(&self, _: Span, enclosing_item: DefId) -> &'tcx Substs<'tcx> {
+        anon_type_substs(self.ccx, enclosing_item)
+    }
+
     fn ty_infer(&self,
                 _ty_param_def: Option<ty::TypeParameterDef<'tcx>>,
                 _substs: Option<&mut Substs<'tcx>>,
@@ -377,11 +395,12 @@ impl<'a, 'tcx> AstConv<'tcx, 'tcx> for ItemCtxt<'a, 'tcx> {
     fn projected_ty_from_poly_trait_ref(&self,
                                         span: Span,
                                         poly_trait_ref: ty::PolyTraitRef<'tcx>,
-                                        item_name: ast::Name)
+                                        item_name: ast::Name,
+                                        item_substs: &'tcx Substs<'tcx>)
                                         -> Ty<'tcx>
     {
         if let Some(trait_ref) = self.tcx().no_late_bound_regions(&poly_trait_ref) {
-            self.projected_ty(span, trait_ref, item_name)
+            self.projected_ty(span, trait_ref, item_name, item_substs)
         } else {
             // no late-bound regions, we can just ignore the binder
             span_err!(self.tcx().sess, span, E0212,
@@ -394,10 +413,11 @@ impl<'a, 'tcx> AstConv<'tcx, 'tcx> for ItemCtxt<'a, 'tcx> {
     fn projected_ty(&self,
                     _span: Span,
                     trait_ref: ty::TraitRef<'tcx>,
-                    item_name: ast::Name)
+                    item_name: ast::Name,
+                    item_substs: &'tcx Substs<'tcx>)
                     -> Ty<'tcx>
     {
-        self.tcx().mk_projection(trait_ref, item_name)
+        self.tcx().mk_projection(trait_ref, item_name, item_substs)
     }
 
     fn set_tainted_by_errors(&self) {
@@ -561,12 +581,23 @@ fn convert_method<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
     let ty_generic_predicates =
         ty_generic_predicates_for_fn(ccx, &sig.generics, rcvr_ty_predicates);
 
+    let def_id = ccx.tcx.map.local_def_id(id);
+
+    // `impl Trait` is only allowed in the return types of inherent
+    // methods, whose body is the only possible source of the type.
+    let anon_scope = match container {
+        ImplContainer(impl_def_id) if ccx.tcx.impl_trait_ref(impl_def_id).is_none() => {
+            Some(AnonTypeScope::new(def_id))
+        }
+        ImplContainer(_) | TraitContainer(_) => None
+    };
+
     let (fty, explicit_self_category) =
         AstConv::ty_of_method(&ccx.icx(&(rcvr_ty_predicates, &sig.generics)),
                               sig,
-                              untransformed_rcvr_ty);
+                              untransformed_rcvr_ty,
+                              anon_scope);
 
-    let def_id = ccx.tcx.map.local_def_id(id);
     let substs = mk_item_substs(ccx, &ty_generics);
 
     let ty_method = ty::Method::new(name,
@@ -650,10 +681,22 @@ fn convert_associated_type<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                      id: ast::NodeId,
                                      vis: &hir::Visibility,
                                      defaultness: hir::Defaultness,
-                                     ty: Option<Ty<'tcx>>)
+                                     generics: &hir::Generics,
+                                     base_generics: &ty::Generics<'tcx>,
+                                     base_predicates: &ty::GenericPredicates<'tcx>,
+                                     ty: Option<&hir::Ty>)
 {
+    let ty_generics = ty_generics_for_fn(ccx, generics, base_generics);
+    let ty_generic_predicates = ty_generic_predicates_for_fn(ccx, generics, base_predicates);
+
+    let ty = ty.map(|ty| {
+        ccx.icx(&(base_predicates, generics)).to_ty(&ExplicitRscope, ty)
+    });
+
     let associated_type = Rc::new(ty::AssociatedType {
         name: name,
+        generics: ty_generics,
+        predicates: ty_generic_predicates,
         vis: ty::Visibility::from_hir(vis, id, ccx.tcx),
         defaultness: defaultness,
         ty: ty,
@@ -726,7 +769,7 @@ fn convert_item(ccx: &CrateCtxt, it: &hir::Item) {
             tcx.impl_trait_refs.borrow_mut().insert(ccx.tcx.map.local_def_id(it.id),
                                                     Some(trait_ref));
         }
-        hir::ItemImpl(_, _,
+        hir::ItemImpl(_, _, defaultness,
                       ref generics,
                       ref opt_trait_ref,
                       ref selfty,
@@ -756,6 +799,10 @@ fn convert_item(ccx: &CrateCtxt, it: &hir::Item) {
             enforce_impl_params_are_constrained(ccx, generics, &mut ty_predicates, def_id);
             tcx.predicates.borrow_mut().insert(def_id, ty_predicates.clone());
 
+            // All the items of a `default impl` are `default` themselves.
+            let item_defaultness = |impl_item: &hir::ImplItem| {
+                if defaultness.is_default() { defaultness } else { impl_item.defaultness }
+            };
 
             // Convert all the associated consts.
             // Also, check if there are any duplicate associated items
@@ -764,7 +811,7 @@ fn convert_item(ccx: &CrateCtxt, it: &hir::Item) {
 
             for impl_item in impl_items {
                 let seen_items = match impl_item.node {
-                    hir::ImplItemKind::Type(_) => &mut seen_type_items,
+                    hir::ImplItemKind::Type(..) => &mut seen_type_items,
                     _                    => &mut seen_value_items,
                 };
                 match seen_items.entry(impl_item.name) {
@@ -796,24 +843,23 @@ fn convert_item(ccx: &CrateCtxt, it: &hir::Item) {
                     convert_associated_const(ccx, ImplContainer(def_id),
                                              impl_item.name, impl_item.id,
                                              visibility,
-                                             impl_item.defaultness,
+                                             item_defaultness(impl_item),
                                              ty, true /* has_value */);
                 }
             }
 
             // Convert all the associated types.
             for impl_item in impl_items {
-                if let hir::ImplItemKind::Type(ref ty) = impl_item.node {
+                if let hir::ImplItemKind::Type(ref generics, ref ty) = impl_item.node {
                     if opt_trait_ref.is_none() {
                         span_err!(tcx.sess, impl_item.span, E0202,
                                   "associated types are not allowed in inherent impls");
                     }
 
-                    let typ = ccx.icx(&ty_predicates).to_ty(&ExplicitRscope, ty);
-
                     convert_associated_type(ccx, ImplContainer(def_id),
                                             impl_item.name, impl_item.id, &impl_item.vis,
-                                            impl_item.defaultness, Some(typ));
+                                            item_defaultness(impl_item), generics,
+                                            &ty_generics, &ty_predicates, Some(ty));
                 }
             }
 
@@ -825,7 +871,7 @@ fn convert_item(ccx: &CrateCtxt, it: &hir::Item) {
 
                     convert_method(ccx, ImplContainer(def_id),
                                    impl_item.name, impl_item.id, method_vis,
-                                   sig, impl_item.defaultness, selfty, &ty_generics,
+                                   sig, item_defaultness(impl_item), selfty, &ty_generics,
                                    &ty_predicates);
                 }
             }
@@ -868,18 +914,17 @@ fn convert_item(ccx: &CrateCtxt, it: &hir::Item) {
 
             // Convert all the associated types.
             for trait_item in trait_items {
-                if let hir::TypeTraitItem(_, ref opt_ty) = trait_item.node {
-                    let typ = opt_ty.as_ref().map({
-                        |ty| ccx.icx(&trait_predicates).to_ty(&ExplicitRscope, &ty)
-                    });
-
+                if let hir::TypeTraitItem(ref generics, _, ref opt_ty) = trait_item.node {
                     convert_associated_type(ccx,
                                             container,
                                             trait_item.name,
                                             trait_item.id,
                                             &hir::Public,
                                             hir::Defaultness::Default,
-                                            typ);
+                                            generics,
+                                            &trait_def.generics,
+                                            &trait_predicates,
+                                            opt_ty.as_ref().map(|ty| &**ty));
                 }
             }
 
@@ -927,6 +972,17 @@ fn convert_item(ccx: &CrateCtxt, it: &hir::Item) {
                 convert_variant_ctor(ccx, struct_def.id(), variant, scheme, predicates);
             }
         },
+        hir::ItemUnion(ref struct_def, _) => {
+            let (scheme, predicates) = convert_typed_item(ccx, it);
+            write_ty_to_tcx(ccx, it.id, scheme.ty);
+
+            let it_def_id = ccx.tcx.map.local_def_id(it.id);
+            let variant = tcx.lookup_adt_def_master(it_def_id).struct_variant();
+
+            for (f, ty_f) in struct_def.fields().iter().zip(variant.fields.iter()) {
+                convert_field(ccx, &scheme.generics, &predicates, f, ty_f)
+            }
+        },
         hir::ItemTy(_, ref generics) => {
             ensure_no_ty_param_bounds(ccx, it.span, generics, "type");
             let (scheme, _) = convert_typed_item(ccx, it);
@@ -1052,6 +1108,16 @@ fn convert_struct_def<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
     adt
 }
 
+fn convert_union_def<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
+                               it: &hir::Item,
+                               def: &hir::VariantData)
+                               -> ty::AdtDefMaster<'tcx>
+{
+    let did = ccx.tcx.map.local_def_id(it.id);
+    let variants = vec![convert_struct_variant(ccx, did, it.name, ConstInt::Infer(0), def)];
+    ccx.tcx.intern_adt_def(did, ty::AdtKind::Union, variants)
+}
+
     fn evaluate_disr_expr(ccx: &CrateCtxt, repr_ty: attr::IntType, e: &hir::Expr)
                           -> Option<ty::Disr> {
         debug!("disr expr, checking {}", pprust::expr_to_string(e));
@@ -1329,6 +1395,26 @@ fn trait_defines_associated_type_named(ccx: &CrateCtxt,
     })
 }
 
+fn associated_type_param_counts(ccx: &CrateCtxt,
+                                trait_node_id: ast::NodeId,
+                                assoc_name: ast::Name)
+                                -> (usize, usize)
+{
+    let trait_items = match ccx.tcx.map.expect_item(trait_node_id).node {
+        hir::ItemTrait(_, _, _, ref trait_items) => trait_items,
+        _ => bug!("trait_node_id {} is not a trait", trait_node_id)
+    };
+
+    trait_items.iter().filter_map(|trait_item| {
+        match trait_item.node {
+            hir::TypeTraitItem(ref generics, _, _) if trait_item.name == assoc_name => {
+                Some((generics.lifetimes.len(), generics.ty_params.len()))
+            }
+            _ => None,
+        }
+    }).next().unwrap_or((0, 0))
+}
+
 fn convert_trait_predicates<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>, it: &hir::Item) {
     let tcx = ccx.tcx;
     let trait_def = trait_def_of_item(ccx, it);
@@ -1384,14 +1470,27 @@ fn convert_trait_predicates<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>, it: &hir::Item)
     {
         trait_items.iter().flat_map(|trait_item| {
             let bounds = match trait_item.node {
-                hir::TypeTraitItem(ref bounds, _) => bounds,
+                hir::TypeTraitItem(ref generics, ref bounds, _) => {
+                    if generics.is_parameterized() {
+                        // Bounds would have to hold for every instantiation
+                        // of the parameters, which we cannot express yet.
+                        if !bounds.is_empty() {
+                            span_err!(ccx.tcx.sess, trait_item.span, E0571,
+                                      "bounds on generic associated types are not \
+                                       yet supported");
+                        }
+                        return vec!().into_iter();
+                    }
+                    bounds
+                }
                 _ => {
                     return vec!().into_iter();
                 }
             };
 
             let assoc_ty = ccx.tcx.mk_projection(self_trait_ref,
-                                                 trait_item.name);
+                                                 trait_item.name,
+                                                 ccx.tcx.mk_substs(Substs::empty()));
 
             let bounds = compute_bounds(&ccx.icx(&(ast_generics, trait_predicates)),
                                         assoc_ty,
@@ -1453,8 +1552,10 @@ fn compute_type_scheme_of_item<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
         }
         hir::ItemFn(ref decl, unsafety, _, abi, ref generics, _) => {
             let ty_generics = ty_generics_for_fn(ccx, generics, &ty::Generics::empty());
-            let tofd = AstConv::ty_of_bare_fn(&ccx.icx(generics), unsafety, abi, &decl);
             let def_id = ccx.tcx.map.local_def_id(it.id);
+            let anon_scope = Some(AnonTypeScope::new(def_id));
+            let tofd = AstConv::ty_of_bare_fn(&ccx.icx(generics), unsafety, abi, &decl,
+                                              anon_scope);
             let substs = mk_item_substs(ccx, &ty_generics);
             let ty = tcx.mk_fn_def(def_id, substs, tofd);
             ty::TypeScheme { ty: ty, generics: ty_generics }
@@ -1478,6 +1579,13 @@ fn compute_type_scheme_of_item<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
             let t = tcx.mk_struct(def, substs);
             ty::TypeScheme { ty: t, generics: ty_generics }
         }
+        hir::ItemUnion(ref un, ref generics) => {
+            let def = convert_union_def(ccx, it, un);
+            let ty_generics = ty_generics_for_type(ccx, generics);
+            let substs = mk_item_substs(ccx, &ty_generics);
+            let t = tcx.mk_struct(def, substs);
+            ty::TypeScheme { ty: t, generics: ty_generics }
+        }
         hir::ItemDefaultImpl(..) |
         hir::ItemTrait(..) |
         hir::ItemImpl(..) |
@@ -1514,7 +1622,8 @@ fn convert_typed_item<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
         hir::ItemEnum(_, ref generics) => {
             ty_generic_predicates_for_type_or_impl(ccx, generics)
         }
-        hir::ItemStruct(_, ref generics) => {
+        hir::ItemStruct(_, ref generics) |
+        hir::ItemUnion(_, ref generics) => {
             ty_generic_predicates_for_type_or_impl(ccx, generics)
         }
         hir::ItemDefaultImpl(..) |
@@ -1660,6 +1769,7 @@ fn ty_generics_for_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
         default_def_id: ccx.tcx.map.local_def_id(parent),
         default: None,
         object_lifetime_default: ty::ObjectLifetimeDefault::BaseDefault,
+        is_const: false,
     };
 
     ccx.tcx.ty_param_defs.borrow_mut().insert(param_id, def.clone());
@@ -1763,6 +1873,13 @@ fn ty_generic_predicates<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
     for (index, param) in ast_generics.ty_params.iter().enumerate() {
         let index = index as u32;
         let param_ty = ty::ParamTy::new(space, index, param.name).to_ty(ccx.tcx);
+        if param.const_ty.is_some() {
+            // The value of a `const` parameter has no bounds and contains
+            // no references, so it outlives every region.
+            let outlives = ty::Binder(ty::OutlivesPredicate(param_ty, ty::ReStatic));
+            result.predicates.push(space, outlives.to_predicate());
+            continue;
+        }
         let bounds = compute_bounds(&ccx.icx(&(base_predicates, ast_generics)),
                                     param_ty,
                                     &param.bounds,
@@ -1932,6 +2049,14 @@ fn get_or_create_type_parameter_def<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
 
     let parent = tcx.map.get_parent(param.id);
 
+    if let Some(ref const_ty) = param.const_ty {
+        let ty = AstConv::ast_ty_to_ty(&ccx.icx(&()), &ExplicitRscope, &const_ty);
+        if ty != tcx.types.usize && !ty.references_error() {
+            span_err!(tcx.sess, const_ty.span, E0574,
+                      "the type of a const parameter must be `usize`, found `{}`", ty);
+        }
+    }
+
     if !allow_defaults && default.is_some() {
         if !tcx.sess.features.borrow().default_type_parameter_fallback {
             tcx.sess.add_lint(
@@ -1951,6 +2076,7 @@ fn get_or_create_type_parameter_def<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
         default_def_id: ccx.tcx.map.local_def_id(parent),
         default: default,
         object_lifetime_default: object_lifetime_default,
+        is_const: param.const_ty.is_some(),
     };
 
     tcx.ty_param_defs.borrow_mut().insert(param.id, def.clone());
@@ -2221,6 +2347,34 @@ fn mk_item_substs<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
     ccx.tcx.mk_substs(Substs::new(types, regions))
 }
 
+/// Returns the identity substitutions of the fn or inherent method
+/// `enclosing_item`, for use by the anonymized types in its return
+/// type. This has to recompute the generics, as it runs while the type
+/// scheme of the fn is still being computed.
+fn anon_type_substs<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
+                              enclosing_item: DefId)
+                              -> &'tcx Substs<'tcx>
+{
+    let tcx = ccx.tcx;
+    let node_id = tcx.map.as_local_node_id(enclosing_item).unwrap();
+    let ty_generics = match tcx.map.get(node_id) {
+        hir_map::NodeItem(&hir::Item {
+            node: hir::ItemFn(_, _, _, _, ref generics, _), ..
+        }) => {
+            ty_generics_for_fn(ccx, generics, &ty::Generics::empty())
+        }
+        hir_map::NodeImplItem(&hir::ImplItem {
+            node: hir::ImplItemKind::Method(ref sig, _), ..
+        }) => {
+            let impl_def_id = tcx.map.get_parent_did(node_id);
+            let impl_generics = tcx.lookup_item_type(impl_def_id).generics;
+            ty_generics_for_fn(ccx, &sig.generics, &impl_generics)
+        }
+        node => bug!("anon_type_substs: unexpected enclosing item {:?}", node)
+    };
+    mk_item_substs(ccx, &ty_generics)
+}
+
 /// Checks that all the type parameters on an impl
 fn enforce_impl_params_are_constrained<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                                  ast_generics: &hir::Generics,
@@ -63,16 +63,20 @@ fn parameters_for_type_shallow<'tcx>(ty: Ty<'tcx>) -> Vec<Parameter> {
         ty::TyRef(region, _) =>
             parameters_for_region(region).into_iter().collect(),
         ty::TyStruct(_, substs) |
-        ty::TyEnum(_, substs) =>
+        ty::TyEnum(_, substs) |
+        ty::TyAnon(_, substs) =>
             parameters_for_regions_in_substs(substs),
         ty::TyTrait(ref data) =>
             parameters_for_regions_in_substs(&data.principal.skip_binder().substs),
-        ty::TyProjection(ref pi) =>
-            parameters_for_regions_in_substs(&pi.trait_ref.substs),
+        ty::TyProjection(ref pi) => {
+            let mut parameters = parameters_for_regions_in_substs(&pi.trait_ref.substs);
+            parameters.extend(parameters_for_regions_in_substs(pi.item_substs));
+            parameters
+        }
         ty::TyBool | ty::TyChar | ty::TyInt(..) | ty::TyUint(..) |
-        ty::TyFloat(..) | ty::TyBox(..) | ty::TyStr |
-        ty::TyArray(..) | ty::TySlice(..) |
-        ty::TyFnDef(..) | ty::TyFnPtr(_) |
+        ty::TyFloat(..) | ty::TyBox(..) | ty::TyStr | ty::TyNever |
+        ty::TyArray(..) | ty::TyGenericArray(..) | ty::TySlice(..) |
+        ty::TyFnDef(..) | ty::TyFnPtr(_) | ty::TyConst(_) |
         ty::TyTuple(..) | ty::TyRawPtr(..) |
         ty::TyInfer(..) | ty::TyClosure(..) | ty::TyError =>
             vec![]
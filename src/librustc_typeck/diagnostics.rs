@@ -4031,6 +4031,69 @@ let s = Simba { mother: 1, father: 0 }; // ok!
 ```
 "##,
 
+E0561: r##"
+An `impl Trait` type was used somewhere other than the return type of a
+function or inherent method.
+
+Erroneous code example:
+
+```compile_fail,E0561
+#![feature(conservative_impl_trait)]
+
+fn show(x: impl ToString) -> String { // error: `impl Trait` not allowed here
+    x.to_string()
+}
+```
+
+The concrete type behind an `impl Trait` is inferred from the body of the
+function returning it, so it cannot appear in argument types, trait methods,
+or the types of fields, statics and locals. Use a type parameter instead:
+
+```
+fn show<T: ToString>(x: T) -> String {
+    x.to_string()
+}
+```
+"##,
+
+E0565: r##"
+A union expression or pattern did not name exactly one field.
+
+Erroneous code example:
+
+```compile_fail,E0565
+#![feature(untagged_unions)]
+
+union Value {
+    int: i32,
+    float: f32,
+}
+
+let v = Value { int: 1, float: 1.0 }; // error: two fields initialized
+```
+
+All the fields of a union share the same storage, so a union value is created
+by initializing one of them, and a pattern can only look at one field at a
+time:
+
+```
+#![feature(untagged_unions)]
+
+union Value {
+    int: i32,
+    float: f32,
+}
+
+let v = Value { int: 1 };
+unsafe {
+    match v {
+        Value { int: 1 } => {}
+        _ => {}
+    }
+}
+```
+"##,
+
 }
 
 register_diagnostics! {
@@ -4104,4 +4167,17 @@ register_diagnostics! {
     E0528, // expected at least {} elements, found {}
     E0529, // slice pattern expects array or slice, not `{}`
     E0533, // `{}` does not name a unit variant, unit struct or a constant
+    E0562, // only named lifetimes are allowed in `impl Trait` bounds
+    E0563, // only named lifetimes are allowed in `impl Trait` concrete types
+    E0564, // the concrete type of an `impl Trait` contains itself
+    E0568, // cannot specialize on `'static` lifetime
+    E0569, // specializing impl repeats parameter
+    E0570, // cannot specialize on a where clause the specialized impl lacks
+    E0571, // bounds on generic associated types are not yet supported
+    E0572, // cannot bind generic associated type in a trait reference
+    E0573, // associated type has a different number of parameters than in its trait
+    E0574, // the type of a const parameter must be `usize`
+    E0575, // mismatched kind of generic argument for a const parameter
+    E0576, // array length refers to a type parameter that is not `const`
+    E0577, // type parameter used as a value is not `const`
 }
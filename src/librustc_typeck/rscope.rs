@@ -9,6 +9,7 @@
 // except according to those terms.
 
 
+use rustc::hir::def_id::DefId;
 use rustc::ty;
 
 use std::cell::Cell;
@@ -50,6 +51,65 @@ pub trait RegionScope {
     /// computing `object_lifetime_default` (in particular, in legacy
     /// modes, it may not be relevant).
     fn base_object_lifetime_default(&self, span: Span) -> ty::Region;
+
+    /// If this scope allows anonymized types, return the fn or method
+    /// whose generics those types are parameterized by. Only the return
+    /// types of free functions and inherent methods provide such a scope.
+    fn anon_type_scope(&self) -> Option<AnonTypeScope> {
+        None
+    }
+}
+
+/// The enclosing item of an `impl Trait` type: the anonymized type has
+/// the same type and early-bound region parameters as this fn or method.
+#[derive(Copy, Clone)]
+pub struct AnonTypeScope {
+    pub enclosing_item: DefId
+}
+
+impl AnonTypeScope {
+    pub fn new(enclosing_item: DefId) -> AnonTypeScope {
+        AnonTypeScope {
+            enclosing_item: enclosing_item
+        }
+    }
+}
+
+/// A scope wrapper which optionally allows anonymized types.
+#[derive(Copy, Clone)]
+pub struct MaybeWithAnonTypes<R> {
+    base_scope: R,
+    anon_scope: Option<AnonTypeScope>
+}
+
+impl<R: RegionScope> MaybeWithAnonTypes<R> {
+    pub fn new(base_scope: R, anon_scope: Option<AnonTypeScope>) -> Self {
+        MaybeWithAnonTypes {
+            base_scope: base_scope,
+            anon_scope: anon_scope
+        }
+    }
+}
+
+impl<R: RegionScope> RegionScope for MaybeWithAnonTypes<R> {
+    fn object_lifetime_default(&self, span: Span) -> Option<ty::Region> {
+        self.base_scope.object_lifetime_default(span)
+    }
+
+    fn anon_regions(&self,
+                    span: Span,
+                    count: usize)
+                    -> Result<Vec<ty::Region>, Option<Vec<ElisionFailureInfo>>> {
+        self.base_scope.anon_regions(span, count)
+    }
+
+    fn base_object_lifetime_default(&self, span: Span) -> ty::Region {
+        self.base_scope.base_object_lifetime_default(span)
+    }
+
+    fn anon_type_scope(&self) -> Option<AnonTypeScope> {
+        self.anon_scope
+    }
 }
 
 // A scope in which all regions must be explicitly named. This is used
@@ -221,6 +281,10 @@ impl<'r> RegionScope for ObjectLifetimeDefaultRscope<'r> {
     {
         self.base_scope.anon_regions(span, count)
     }
+
+    fn anon_type_scope(&self) -> Option<AnonTypeScope> {
+        self.base_scope.anon_type_scope()
+    }
 }
 
 /// A scope which simply shifts the Debruijn index of other scopes
@@ -262,4 +326,8 @@ impl<'r> RegionScope for ShiftedRscope<'r> {
             }
         }
     }
+
+    fn anon_type_scope(&self) -> Option<AnonTypeScope> {
+        self.base_scope.anon_type_scope()
+    }
 }
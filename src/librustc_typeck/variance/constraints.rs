@@ -82,7 +82,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for ConstraintContext<'a, 'tcx> {
         debug!("visit_item item={}", tcx.map.node_to_string(item.id));
 
         match item.node {
-            hir::ItemEnum(..) | hir::ItemStruct(..) => {
+            hir::ItemEnum(..) | hir::ItemStruct(..) | hir::ItemUnion(..) => {
                 let scheme = tcx.lookup_item_type(did);
 
                 // Not entirely obvious: constraints on structs/enums do not
@@ -96,6 +96,14 @@ impl<'a, 'tcx, 'v> Visitor<'v> for ConstraintContext<'a, 'tcx> {
                                                  field.unsubst_ty(),
                                                  self.covariant);
                 }
+
+                // Two values of a `const` parameter are either equal or not,
+                // so such parameters are invariant even when no field uses them.
+                for def in scheme.generics.types.iter().filter(|def| def.is_const) {
+                    self.add_constraints_from_ty(&scheme.generics,
+                                                 tcx.mk_param_from_def(def),
+                                                 self.invariant);
+                }
             }
             hir::ItemTrait(..) => {
                 let trait_def = tcx.lookup_trait_def(did);
@@ -187,6 +195,7 @@ impl<'a, 'tcx> ConstraintContext<'a, 'tcx> {
                         hir::ItemTy(..) |
                         hir::ItemEnum(..) |
                         hir::ItemStruct(..) |
+                        hir::ItemUnion(..) |
                         hir::ItemTrait(..)   => is_inferred = true,
                         hir::ItemFn(..)      => is_inferred = false,
                         _                    => cannot_happen!(),
@@ -322,7 +331,7 @@ impl<'a, 'tcx> ConstraintContext<'a, 'tcx> {
         match ty.sty {
             ty::TyBool |
             ty::TyChar | ty::TyInt(_) | ty::TyUint(_) |
-            ty::TyFloat(_) | ty::TyStr => {
+            ty::TyFloat(_) | ty::TyStr | ty::TyNever | ty::TyConst(_) => {
                 /* leaf type -- noop */
             }
 
@@ -340,6 +349,12 @@ impl<'a, 'tcx> ConstraintContext<'a, 'tcx> {
                 self.add_constraints_from_ty(generics, typ, variance);
             }
 
+            ty::TyGenericArray(typ, len) => {
+                self.add_constraints_from_ty(generics, typ, variance);
+                let invariant = self.invariant(variance);
+                self.add_constraints_from_ty(generics, len, invariant);
+            }
+
 
             ty::TyRawPtr(ref mt) => {
                 self.add_constraints_from_mt(generics, mt, variance);
@@ -392,6 +407,15 @@ impl<'a, 'tcx> ConstraintContext<'a, 'tcx> {
                     trait_def.generics.regions.as_slice(),
                     trait_ref.substs,
                     variance);
+
+                // The parameters of a generic associated type are invariant.
+                let invar = self.invariant(variance);
+                for &ty in data.item_substs.types.iter() {
+                    self.add_constraints_from_ty(generics, ty, invar);
+                }
+                for &r in data.item_substs.regions.iter() {
+                    self.add_constraints_from_region(generics, r, invar);
+                }
             }
 
             ty::TyTrait(ref data) => {
@@ -438,7 +462,7 @@ impl<'a, 'tcx> ConstraintContext<'a, 'tcx> {
                 // types, where we use TyError as the Self type
             }
 
-            ty::TyInfer(..) => {
+            ty::TyInfer(..) | ty::TyAnon(..) => {
                 bug!("unexpected type encountered in \
                       variance inference: {}", ty);
             }
@@ -1300,7 +1300,7 @@ impl Clean<Item> for hir::TraitItem {
             hir::MethodTraitItem(ref sig, None) => {
                 TyMethodItem(sig.clean(cx))
             }
-            hir::TypeTraitItem(ref bounds, ref default) => {
+            hir::TypeTraitItem(_, ref bounds, ref default) => {
                 AssociatedTypeItem(bounds.clean(cx), default.clean(cx))
             }
         };
@@ -1327,13 +1327,9 @@ impl Clean<Item> for hir::ImplItem {
             hir::ImplItemKind::Method(ref sig, _) => {
                 MethodItem(sig.clean(cx))
             }
-            hir::ImplItemKind::Type(ref ty) => TypedefItem(Typedef {
+            hir::ImplItemKind::Type(ref generics, ref ty) => TypedefItem(Typedef {
                 type_: ty.clean(cx),
-                generics: Generics {
-                    lifetimes: Vec::new(),
-                    type_params: Vec::new(),
-                    where_predicates: Vec::new()
-                },
+                generics: generics.clean(cx),
             }, true),
         };
         Item {
@@ -1469,6 +1465,9 @@ pub enum Type {
 
     // for<'a> Foo(&'a)
     PolyTraitRef(Vec<TyParamBound>),
+
+    // impl TraitA+TraitB
+    ImplTrait(Vec<TyParamBound>),
 }
 
 #[derive(Clone, RustcEncodable, RustcDecodable, PartialEq, Eq, Hash, Copy, Debug)]
@@ -1625,21 +1624,23 @@ impl Clean<Type> for hir::Ty {
                              type_: box m.ty.clean(cx)},
             TyVec(ref ty) => Vector(box ty.clean(cx)),
             TyFixedLengthVec(ref ty, ref e) => {
-                let n = if let Some(tcx) = cx.tcx_opt() {
-                    use rustc_const_math::{ConstInt, ConstUsize};
-                    use rustc_const_eval::eval_const_expr;
-                    use rustc::middle::const_val::ConstVal;
-                    match eval_const_expr(tcx, e) {
-                        ConstVal::Integral(ConstInt::Usize(u)) => match u {
-                            ConstUsize::Us16(u) => u.to_string(),
-                            ConstUsize::Us32(u) => u.to_string(),
-                            ConstUsize::Us64(u) => u.to_string(),
-                        },
-                        // after type checking this can't fail
-                        _ => unreachable!(),
+                let n = match cx.tcx_opt() {
+                    // `[T; N]` with a `const` parameter `N` has no value to show.
+                    Some(tcx) if tcx.const_param_def(e).is_none() => {
+                        use rustc_const_math::{ConstInt, ConstUsize};
+                        use rustc_const_eval::eval_const_expr;
+                        use rustc::middle::const_val::ConstVal;
+                        match eval_const_expr(tcx, e) {
+                            ConstVal::Integral(ConstInt::Usize(u)) => match u {
+                                ConstUsize::Us16(u) => u.to_string(),
+                                ConstUsize::Us32(u) => u.to_string(),
+                                ConstUsize::Us64(u) => u.to_string(),
+                            },
+                            // after type checking this can't fail
+                            _ => unreachable!(),
+                        }
                     }
-                } else {
-                    pprust::expr_to_string(e)
+                    _ => pprust::expr_to_string(e)
                 };
                 FixedVector(box ty.clean(cx), n)
             },
@@ -1679,6 +1680,9 @@ impl Clean<Type> for hir::Ty {
             }
             TyBareFn(ref barefn) => BareFunction(box barefn.clean(cx)),
             TyPolyTraitRef(ref bounds) => PolyTraitRef(bounds.clean(cx)),
+            TyImplTrait(ref bounds) => ImplTrait(bounds.clean(cx)),
+            TyNever => Bottom,
+            TyConstArg(ref e) => Generic(pprust::expr_to_string(e)),
             TyInfer => Infer,
             TyTypeof(..) => panic!("Unimplemented type {:?}", self.node),
         }
@@ -1703,6 +1707,7 @@ impl<'tcx> Clean<Type> for ty::Ty<'tcx> {
             ty::TyFloat(ast::FloatTy::F32) => Primitive(F32),
             ty::TyFloat(ast::FloatTy::F64) => Primitive(F64),
             ty::TyStr => Primitive(Str),
+            ty::TyNever => Bottom,
             ty::TyBox(t) => {
                 let box_did = cx.tcx_opt().and_then(|tcx| {
                     tcx.lang_items.owned_box()
@@ -1712,6 +1717,9 @@ impl<'tcx> Clean<Type> for ty::Ty<'tcx> {
             ty::TySlice(ty) => Vector(box ty.clean(cx)),
             ty::TyArray(ty, i) => FixedVector(box ty.clean(cx),
                                               format!("{}", i)),
+            ty::TyGenericArray(ty, len) => FixedVector(box ty.clean(cx),
+                                                       format!("{}", len)),
+            ty::TyConst(value) => Generic(value.to_string()),
             ty::TyRawPtr(mt) => RawPointer(mt.mutbl.clean(cx), box mt.ty.clean(cx)),
             ty::TyRef(r, mt) => BorrowedRef {
                 lifetime: r.clean(cx),
@@ -1765,6 +1773,22 @@ impl<'tcx> Clean<Type> for ty::Ty<'tcx> {
 
             ty::TyParam(ref p) => Generic(p.name.to_string()),
 
+            ty::TyAnon(def_id, substs) => {
+                // Grab the "TraitA + TraitB" from `impl TraitA + TraitB`,
+                // by looking up the bounds associated with the def_id.
+                let tcx = cx.tcx();
+                let substs = tcx.lift(&substs).unwrap();
+                let bounds = tcx.lookup_predicates(def_id).instantiate(tcx, substs);
+                let sized_trait = tcx.lang_items.sized_trait();
+                ImplTrait(bounds.predicates.into_vec().into_iter().filter_map(|predicate| {
+                    predicate.to_opt_poly_trait_ref()
+                }).filter(|trait_ref| {
+                    Some(trait_ref.def_id()) != sized_trait
+                }).map(|trait_ref| {
+                    trait_ref.skip_binder().clean(cx)
+                }).collect())
+            }
+
             ty::TyClosure(..) => Tuple(vec![]), // FIXME(pcwalton)
 
             ty::TyInfer(..) => panic!("TyInfer"),
@@ -336,6 +336,8 @@ pub struct TyParam {
     pub id: NodeId,
     pub bounds: TyParamBounds,
     pub default: Option<P<Ty>>,
+    /// The type of a `const` parameter, e.g. `usize` in `const N: usize`.
+    pub const_ty: Option<P<Ty>>,
     pub span: Span
 }
 
@@ -391,6 +393,16 @@ pub enum WherePredicate {
     EqPredicate(WhereEqPredicate),
 }
 
+impl WherePredicate {
+    pub fn span(&self) -> Span {
+        match *self {
+            WherePredicate::BoundPredicate(ref p) => p.span,
+            WherePredicate::RegionPredicate(ref p) => p.span,
+            WherePredicate::EqPredicate(ref p) => p.span,
+        }
+    }
+}
+
 /// A type bound.
 ///
 /// E.g. `for<'c> Foo: Send+Clone+'c`
@@ -1011,6 +1023,10 @@ pub enum ExprKind {
 
     /// `expr?`
     Try(P<Expr>),
+
+    /// Placeholder for an expression that failed to parse. The error has
+    /// been reported already.
+    Err,
 }
 
 /// The explicit Self type in a "qualified path". The actual
@@ -1143,7 +1159,9 @@ pub struct TraitItem {
 pub enum TraitItemKind {
     Const(P<Ty>, Option<P<Expr>>),
     Method(MethodSig, Option<P<Block>>),
-    Type(TyParamBounds, Option<P<Ty>>),
+    /// An associated type with its own (possibly empty) generics and where
+    /// clause, bounds and an optional default, e.g. `type Item<'a>: Debug;`
+    Type(Generics, TyParamBounds, Option<P<Ty>>),
     Macro(Mac),
 }
 
@@ -1162,7 +1180,7 @@ pub struct ImplItem {
 pub enum ImplItemKind {
     Const(P<Ty>, P<Expr>),
     Method(MethodSig, P<Block>),
-    Type(P<Ty>),
+    Type(Generics, P<Ty>),
     Macro(Mac),
 }
 
@@ -1357,6 +1375,8 @@ pub enum TyKind {
     Rptr(Option<Lifetime>, MutTy),
     /// A bare function (e.g. `fn(usize) -> bool`)
     BareFn(P<BareFnTy>),
+    /// The never type (`!`)
+    Never,
     /// A tuple (`(A, B, C, D,...)`)
     Tup(Vec<P<Ty>> ),
     /// A path (`module::module::...::Type`), optionally
@@ -1368,10 +1388,16 @@ pub enum TyKind {
     ObjectSum(P<Ty>, TyParamBounds),
     /// A type like `for<'a> Foo<&'a Bar>`
     PolyTraitRef(TyParamBounds),
+    /// An `impl Bound1 + Bound2 + Bound3` type
+    /// where `Bound` is a trait or a lifetime.
+    ImplTrait(TyParamBounds),
     /// No-op; kept solely so that we can pretty-print faithfully
     Paren(P<Ty>),
     /// Unused for now
     Typeof(P<Expr>),
+    /// A constant argument to a `const` type parameter, e.g. the `3` in
+    /// `Buf<3>` or the `{ 2 * 8 }` in `Buf<{ 2 * 8 }>`
+    ConstArg(P<Expr>),
     /// TyKind::Infer means the type should be inferred instead of it having been
     /// specified. This can appear anywhere in a type.
     Infer,
@@ -1866,6 +1892,10 @@ pub enum ItemKind {
     ///
     /// E.g. `struct Foo<A> { x: A }`
     Struct(VariantData, Generics),
+    /// A union definition (`union` or `pub union`).
+    ///
+    /// E.g. `union Foo<A, B> { x: A, y: B }`
+    Union(VariantData, Generics),
     /// A Trait declaration (`trait` or `pub trait`).
     ///
     /// E.g. `trait Foo { .. }` or `trait Foo<T> { .. }`
@@ -1876,9 +1906,11 @@ pub enum ItemKind {
     DefaultImpl(Unsafety, TraitRef),
     /// An implementation.
     ///
-    /// E.g. `impl<A> Foo<A> { .. }` or `impl<A> Trait for Foo<A> { .. }`
+    /// E.g. `impl<A> Foo<A> { .. }`, `impl<A> Trait for Foo<A> { .. }`
+    /// or `default impl<A> Trait for Foo<A> { .. }`
     Impl(Unsafety,
              ImplPolarity,
+             Defaultness,
              Generics,
              Option<TraitRef>, // (optional) trait this impl implements
              P<Ty>, // self
@@ -1902,6 +1934,7 @@ impl ItemKind {
             ItemKind::Ty(..) => "type alias",
             ItemKind::Enum(..) => "enum",
             ItemKind::Struct(..) => "struct",
+            ItemKind::Union(..) => "union",
             ItemKind::Trait(..) => "trait",
             ItemKind::Mac(..) |
             ItemKind::Impl(..) |
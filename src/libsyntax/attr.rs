@@ -724,7 +724,8 @@ pub fn require_unique_names(diagnostic: &Handler, metas: &[P<MetaItem>]) {
 /// Valid repr contents: any of the primitive integral type names (see
 /// `int_type_of_word`, below) to specify enum discriminant type; `C`, to use
 /// the same discriminant size that the corresponding C enum would or C
-/// structure layout, and `packed` to remove padding.
+/// structure layout, `packed` to remove padding, `packed(N)` to align
+/// fields to at most `N` bytes and `align(N)` to align to at least `N` bytes.
 pub fn find_repr_attrs(diagnostic: &Handler, attr: &Attribute) -> Vec<ReprAttr> {
     let mut acc = Vec::new();
     match attr.node.value.node {
@@ -736,7 +737,7 @@ pub fn find_repr_attrs(diagnostic: &Handler, attr: &Attribute) -> Vec<ReprAttr>
                         let hint = match &word[..] {
                             // Can't use "extern" because it's not a lexical identifier.
                             "C" => Some(ReprExtern),
-                            "packed" => Some(ReprPacked),
+                            "packed" => Some(ReprPacked(1)),
                             "simd" => Some(ReprSimd),
                             _ => match int_type_of_word(&word) {
                                 Some(ity) => Some(ReprInt(item.span, ity)),
@@ -754,6 +755,15 @@ pub fn find_repr_attrs(diagnostic: &Handler, attr: &Attribute) -> Vec<ReprAttr>
                             None => { }
                         }
                     }
+                    ast::MetaItemKind::List(ref name, ref args)
+                            if name == "align" || name == "packed" => {
+                        // Invalid arguments are reported by attribute checking.
+                        match parse_repr_alignment(args) {
+                            Ok(align) if name == "align" => acc.push(ReprAlign(align)),
+                            Ok(align) => acc.push(ReprPacked(align)),
+                            Err(_) => {}
+                        }
+                    }
                     // Not a word:
                     _ => span_err!(diagnostic, item.span, E0553,
                                    "unrecognized enum representation hint"),
@@ -766,6 +776,27 @@ pub fn find_repr_attrs(diagnostic: &Handler, attr: &Attribute) -> Vec<ReprAttr>
     acc
 }
 
+/// Parse the `N` of `align(N)` or `packed(N)`, which must be a power of two
+/// that `ty::layout` can represent.
+pub fn parse_repr_alignment(args: &[P<MetaItem>]) -> Result<u32, &'static str> {
+    if args.len() != 1 {
+        return Err("expected a single integer");
+    }
+    let align = match args[0].node {
+        ast::MetaItemKind::Word(ref word) => {
+            word.parse::<u32>().map_err(|_| "expected a single integer")?
+        }
+        _ => return Err("expected a single integer"),
+    };
+    if !align.is_power_of_two() {
+        Err("not a power of two")
+    } else if align > 1 << 15 {
+        Err("larger than 2^15")
+    } else {
+        Ok(align)
+    }
+}
+
 fn int_type_of_word(s: &str) -> Option<IntType> {
     match s {
         "i8" => Some(SignedInt(ast::IntTy::I8)),
@@ -787,8 +818,9 @@ pub enum ReprAttr {
     ReprAny,
     ReprInt(Span, IntType),
     ReprExtern,
-    ReprPacked,
+    ReprPacked(u32),
     ReprSimd,
+    ReprAlign(u32),
 }
 
 impl ReprAttr {
@@ -797,8 +829,9 @@ impl ReprAttr {
             ReprAny => false,
             ReprInt(_sp, ity) => ity.is_ffi_safe(),
             ReprExtern => true,
-            ReprPacked => false,
+            ReprPacked(_) => false,
             ReprSimd => true,
+            ReprAlign(_) => false,
         }
     }
 }
@@ -19,6 +19,7 @@ use errors::DiagnosticBuilder;
 use ext;
 use ext::expand;
 use ext::tt::macro_rules;
+use ext::tt::macro_trace::MacroTraceFormat;
 use parse;
 use parse::parser;
 use parse::token;
@@ -217,6 +218,53 @@ impl<F> IdentMacroExpander for F
     }
 }
 
+/// A function-like procedural macro: maps the tokens of the invocation to the
+/// tokens of the expansion.
+pub trait ProcMacroExpander {
+    fn expand<'cx>(&self,
+                   ecx: &'cx mut ExtCtxt,
+                   span: Span,
+                   ts: tokenstream::TokenStream)
+                   -> tokenstream::TokenStream;
+}
+
+impl<F> ProcMacroExpander for F
+    where F: Fn(tokenstream::TokenStream) -> tokenstream::TokenStream
+{
+    fn expand<'cx>(&self,
+                   _ecx: &'cx mut ExtCtxt,
+                   _span: Span,
+                   ts: tokenstream::TokenStream)
+                   -> tokenstream::TokenStream {
+        (*self)(ts)
+    }
+}
+
+/// An attribute-like procedural macro: maps the tokens of the arguments of
+/// the attribute and of the annotated item to the tokens of the items that
+/// replace the annotated one.
+pub trait AttrProcMacroExpander {
+    fn expand<'cx>(&self,
+                   ecx: &'cx mut ExtCtxt,
+                   span: Span,
+                   annotation: tokenstream::TokenStream,
+                   annotated: tokenstream::TokenStream)
+                   -> tokenstream::TokenStream;
+}
+
+impl<F> AttrProcMacroExpander for F
+    where F: Fn(tokenstream::TokenStream, tokenstream::TokenStream) -> tokenstream::TokenStream
+{
+    fn expand<'cx>(&self,
+                   _ecx: &'cx mut ExtCtxt,
+                   _span: Span,
+                   annotation: tokenstream::TokenStream,
+                   annotated: tokenstream::TokenStream)
+                   -> tokenstream::TokenStream {
+        (*self)(annotation, annotated)
+    }
+}
+
 // Use a macro because forwarding to a simple function has type system issues
 macro_rules! make_stmts_default {
     ($me:expr) => {
@@ -475,19 +523,52 @@ pub enum SyntaxExtension {
 
     /// Represents `macro_rules!` itself.
     MacroRulesTT,
+
+    /// A function-like procedural macro working on token streams.
+    ///
+    /// Tokens it makes up without a span are given the span of the invocation.
+    ProcMacro(Box<ProcMacroExpander + 'static>),
+
+    /// An attribute-like procedural macro working on token streams, which
+    /// replaces the annotated item with the items it expands to.
+    AttrProcMacro(Box<AttrProcMacroExpander + 'static>),
+
+    /// A custom derive defined as a procedural macro, registered as
+    /// `derive_Name` for `#[derive(Name)]`.
+    ///
+    /// Unlike a custom derive registered as a `MultiDecorator`, it can be
+    /// used without `#![feature(custom_derive)]`.
+    CustomDerive(Box<MultiItemDecorator + 'static>),
 }
 
 pub type NamedSyntaxExtension = (Name, SyntaxExtension);
 
+/// A macro imported by a `use` item.
+#[derive(Clone, Debug)]
+pub struct MacroImport {
+    /// The macro, under the name it is imported as.
+    pub def: ast::MacroDef,
+    /// The span of the import, recorded as used when the macro is invoked.
+    pub span: Span,
+}
+
+/// The macros imported by `use` items in each module and block, by the span
+/// of the contents of the module or of the block.
+pub type MacroImports = HashMap<Span, Vec<MacroImport>>;
+
 pub struct BlockInfo {
     /// Should macros escape from this scope?
     pub macros_escape: bool,
+    /// Is this the scope of a module? Macros imported by `use` in a module
+    /// are not in scope in the modules nested in it.
+    pub is_module: bool,
 }
 
 impl BlockInfo {
     pub fn new() -> BlockInfo {
         BlockInfo {
             macros_escape: false,
+            is_module: false,
         }
     }
 }
@@ -576,6 +657,10 @@ fn initial_syntax_expander_table<'feat>(ecfg: &expand::ExpansionConfig<'feat>)
 
 pub trait MacroLoader {
     fn load_crate(&mut self, extern_crate: &ast::Item, allows_macros: bool) -> Vec<ast::MacroDef>;
+
+    /// Loads all the macros exported by the crate of `extern_crate`, which
+    /// can be imported by `use`.
+    fn load_exported_macros(&mut self, extern_crate: &ast::Item) -> Vec<ast::MacroDef>;
 }
 
 pub struct DummyMacroLoader;
@@ -583,6 +668,10 @@ impl MacroLoader for DummyMacroLoader {
     fn load_crate(&mut self, _: &ast::Item, _: bool) -> Vec<ast::MacroDef> {
         Vec::new()
     }
+
+    fn load_exported_macros(&mut self, _: &ast::Item) -> Vec<ast::MacroDef> {
+        Vec::new()
+    }
 }
 
 /// One of these is made during expansion and incrementally updated as we go;
@@ -598,6 +687,10 @@ pub struct ExtCtxt<'a> {
 
     pub mod_path: Vec<ast::Ident> ,
     pub exported_macros: Vec<ast::MacroDef>,
+    /// The macros imported by `use`, as resolved before expansion.
+    pub macro_imports: MacroImports,
+    /// The spans of the `use` imports whose macros were invoked.
+    pub used_macro_imports: HashSet<Span>,
 
     pub syntax_env: SyntaxEnv,
     pub recursion_count: usize,
@@ -621,6 +714,8 @@ impl<'a> ExtCtxt<'a> {
             ecfg: ecfg,
             crate_root: None,
             exported_macros: Vec::new(),
+            macro_imports: HashMap::new(),
+            used_macro_imports: HashSet::new(),
             loader: loader,
             syntax_env: env,
             recursion_count: 0,
@@ -721,6 +816,22 @@ impl<'a> ExtCtxt<'a> {
         }
     }
 
+    /// Brings the macros imported by `use` in the module or block whose
+    /// contents span `scope` into the current scope.
+    pub fn insert_macro_imports(&mut self, scope: Span) {
+        let imports = match self.macro_imports.remove(&scope) {
+            Some(imports) => imports,
+            None => return,
+        };
+        for import in imports {
+            if import.def.export {
+                self.exported_macros.push(import.def.clone());
+            }
+            let ext = macro_rules::compile(self, &import.def);
+            self.syntax_env.insert_import(import.def.ident.name, ext, import.span);
+        }
+    }
+
     pub fn struct_span_warn(&self,
                             sp: Span,
                             msg: &str)
@@ -781,6 +892,9 @@ impl<'a> ExtCtxt<'a> {
     pub fn set_trace_macros(&mut self, x: bool) {
         self.ecfg.trace_mac = x
     }
+    pub fn trace_macros_format(&self) -> Option<MacroTraceFormat> {
+        self.ecfg.trace_mac_format
+    }
     pub fn ident_of(&self, st: &str) -> ast::Ident {
         str_to_ident(st)
     }
@@ -914,6 +1028,8 @@ pub struct SyntaxEnv {
 struct MapChainFrame {
     info: BlockInfo,
     map: HashMap<Name, Rc<SyntaxExtension>>,
+    /// The spans of the imports of the macros of `map` imported by `use`.
+    imports: HashMap<Name, Span>,
 }
 
 impl SyntaxEnv {
@@ -927,6 +1043,7 @@ impl SyntaxEnv {
         self.chain.push(MapChainFrame {
             info: BlockInfo::new(),
             map: HashMap::new(),
+            imports: HashMap::new(),
         });
     }
 
@@ -945,9 +1062,23 @@ impl SyntaxEnv {
     }
 
     pub fn find(&self, k: Name) -> Option<Rc<SyntaxExtension>> {
+        self.find_with_import(k).map(|(v, _)| v)
+    }
+
+    /// Finds the extension `k`, along with the span of its import if it was
+    /// imported by `use`.
+    pub fn find_with_import(&self, k: Name) -> Option<(Rc<SyntaxExtension>, Option<Span>)> {
+        let mut in_current_module = true;
         for frame in self.chain.iter().rev() {
             if let Some(v) = frame.map.get(&k) {
-                return Some(v.clone());
+                match frame.imports.get(&k) {
+                    None => return Some((v.clone(), None)),
+                    Some(&span) if in_current_module => return Some((v.clone(), Some(span))),
+                    Some(_) => {}
+                }
+            }
+            if frame.info.is_module {
+                in_current_module = false;
             }
         }
         None
@@ -957,7 +1088,20 @@ impl SyntaxEnv {
         if let NormalTT(..) = v {
             self.names.insert(k);
         }
-        self.find_escape_frame().map.insert(k, Rc::new(v));
+        let frame = self.find_escape_frame();
+        frame.imports.remove(&k);
+        frame.map.insert(k, Rc::new(v));
+    }
+
+    /// Inserts the extension `k` imported by the `use` at `span`. Unlike
+    /// other extensions, it never escapes the current scope.
+    pub fn insert_import(&mut self, k: Name, v: SyntaxExtension, span: Span) {
+        if let NormalTT(..) = v {
+            self.names.insert(k);
+        }
+        let frame = self.chain.last_mut().unwrap();
+        frame.imports.insert(k, span);
+        frame.map.insert(k, Rc::new(v));
     }
 
     pub fn info(&mut self) -> &mut BlockInfo {
@@ -453,6 +453,7 @@ impl<'a> AstBuilder for ExtCtxt<'a> {
             id: ast::DUMMY_NODE_ID,
             bounds: bounds,
             default: default,
+            const_ty: None,
             span: span
         }
     }
@@ -18,18 +18,25 @@ use codemap::{dummy_spanned, ExpnInfo, NameAndSpan, MacroBang, MacroAttribute};
 use syntax_pos::{self, Span, ExpnId};
 use config::StripUnconfigured;
 use ext::base::*;
+use ext::tt::macro_rules::ParserAnyMacro;
+use ext::tt::macro_trace::MacroTraceFormat;
+use errors::FatalError;
 use feature_gate::{self, Features};
 use fold;
 use fold::*;
-use parse::token::{intern, keywords};
+use parse;
+use parse::parser::{Parser, Restrictions};
+use parse::token::{self, intern, keywords};
+use print::pprust;
 use ptr::P;
-use tokenstream::TokenTree;
+use tokenstream::{TokenStream, TokenTree};
 use util::small_vector::SmallVector;
 use visit;
 use visit::Visitor;
 use std_inject;
 
 use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
 
 // A trait for AST nodes and AST node lists into which macro invocations may expand.
 trait MacroGenerable: Sized {
@@ -148,7 +155,11 @@ fn expand_mac_invoc<T>(mac: ast::Mac, ident: Option<Ident>, attrs: Vec<ast::Attr
         }
 
         let extname = path.segments[0].identifier.name;
-        let extension = if let Some(extension) = fld.cx.syntax_env.find(extname) {
+        let extension = if let Some((extension, import)) =
+                fld.cx.syntax_env.find_with_import(extname) {
+            if let Some(import) = import {
+                fld.cx.used_macro_imports.insert(import);
+            }
             extension
         } else {
             let mut err = fld.cx.struct_span_err(path.span,
@@ -235,7 +246,39 @@ fn expand_mac_invoc<T>(mac: ast::Mac, ident: Option<Ident>, attrs: Vec<ast::Attr
                 Some(Box::new(MacroScopePlaceholder))
             }
 
-            MultiDecorator(..) | MultiModifier(..) => {
+            ProcMacro(ref expander) => {
+                if ident.name != keywords::Invalid.name() {
+                    let msg =
+                        format!("macro {}! expects no ident argument, given '{}'", extname, ident);
+                    fld.cx.span_err(path.span, &msg);
+                    return None;
+                }
+
+                fld.cx.bt_push(ExpnInfo {
+                    call_site: call_site,
+                    callee: NameAndSpan {
+                        format: MacroBang(extname),
+                        span: None,
+                        allow_internal_unstable: false,
+                    },
+                });
+
+                let name = format!("{}!", extname);
+                let output = match call_proc_macro(fld.cx, call_site, &name, |cx| {
+                    expander.expand(cx, call_site, TokenStream::from_tts(tts))
+                }) {
+                    Some(output) => output,
+                    None => return Some(DummyResult::any(call_site)),
+                };
+                // Procedural macros are unhygienic: the mark applied to the
+                // whole expansion below cancels out this one, so that all the
+                // identifiers of the expansion resolve at the invocation.
+                let tts = mark_tts(&respan_proc_macro_output(output, call_site), mark);
+                let parser = proc_macro_parser(fld.cx, &tts);
+                Some(Box::new(ParserAnyMacro::new(parser, call_site, path.segments[0].identifier)))
+            }
+
+            MultiDecorator(..) | MultiModifier(..) | AttrProcMacro(..) | CustomDerive(..) => {
                 fld.cx.span_err(path.span,
                                 &format!("`{}` can only be used in attributes", extname));
                 None
@@ -370,9 +413,13 @@ fn expand_multi_modified(a: Annotatable, fld: &mut MacroExpander) -> SmallVector
                     fld.cx.mod_push(it.ident);
                 }
                 let macro_use = contains_macro_use(fld, &it.attrs);
-                let result = with_exts_frame!(fld.cx.syntax_env,
-                                              macro_use,
-                                              noop_fold_item(it, fld));
+                let result = with_exts_frame!(fld.cx.syntax_env, macro_use, {
+                    if let ast::ItemKind::Mod(ref module) = it.node {
+                        fld.cx.syntax_env.info().is_module = true;
+                        fld.cx.insert_macro_imports(module.inner);
+                    }
+                    noop_fold_item(it, fld)
+                });
                 if valid_ident {
                     fld.cx.mod_pop();
                 }
@@ -399,7 +446,8 @@ fn expand_annotatable(mut item: Annotatable, fld: &mut MacroExpander) -> SmallVe
         for i in 0..attrs.len() {
             if let Some(extension) = fld.cx.syntax_env.find(intern(&attrs[i].name())) {
                 match *extension {
-                    MultiModifier(..) | MultiDecorator(..) => {
+                    MultiModifier(..) | MultiDecorator(..) | AttrProcMacro(..) |
+                    CustomDerive(..) => {
                         multi_modifier = Some((attrs.remove(i), extension));
                         break;
                     }
@@ -426,13 +474,14 @@ fn expand_annotatable(mut item: Annotatable, fld: &mut MacroExpander) -> SmallVe
 
             let modified = match *extension {
                 MultiModifier(ref mac) => mac.expand(fld.cx, attr.span, &attr.node.value, item),
-                MultiDecorator(ref mac) => {
+                MultiDecorator(ref mac) | CustomDerive(ref mac) => {
                     let mut items = Vec::new();
                     mac.expand(fld.cx, attr.span, &attr.node.value, &item,
                                &mut |item| items.push(item));
                     items.push(item);
                     items
                 }
+                AttrProcMacro(ref mac) => expand_attr_proc_macro(fld.cx, &attr, &**mac, item),
                 _ => unreachable!(),
             };
 
@@ -446,6 +495,199 @@ fn expand_annotatable(mut item: Annotatable, fld: &mut MacroExpander) -> SmallVe
     }
 }
 
+/// Expands the procedural macro `mac` invoked by the attribute `attr` on `item`.
+///
+/// The item and the arguments of the attribute are handed to the macro as
+/// tokens with their spans in the source, see `item_to_tts`.
+fn expand_attr_proc_macro(cx: &mut ExtCtxt,
+                          attr: &ast::Attribute,
+                          mac: &AttrProcMacroExpander,
+                          item: Annotatable)
+                          -> Vec<Annotatable> {
+    let name = format!("#[{}]", attr.name());
+    let item = match item {
+        Annotatable::Item(item) => item,
+        item => {
+            cx.span_err(attr.span, &format!("`{}` can only be applied to items", name));
+            return vec![item];
+        }
+    };
+
+    // Only the arguments of the attribute are passed, without the parentheses.
+    let meta = &attr.node.value;
+    let source = pprust::meta_item_to_string(meta);
+    let tts = match source_tts(cx, meta.span, &source, |parser| {
+        parser.parse_meta_item().map(|meta| pprust::meta_item_to_string(&meta))
+    }) {
+        Some(tts) => tts,
+        None => {
+            match pretty_tts(cx, format!("<{} annotation>", name), source, attr.span) {
+                Some(tts) => tts,
+                None => return vec![Annotatable::Item(item)],
+            }
+        }
+    };
+    let annotation = match tts.get(1) {
+        Some(&TokenTree::Delimited(_, ref delimed))
+            if tts.len() == 2 && delimed.delim == token::Paren => delimed.tts.clone(),
+        _ => tts.iter().skip(1).cloned().collect(),
+    };
+    let annotated = match item_to_tts(cx, &format!("<{} input>", name), &item) {
+        Some(tts) => tts,
+        None => return vec![Annotatable::Item(item)],
+    };
+
+    let (annotation, annotated) = (TokenStream::from_tts(annotation),
+                                   TokenStream::from_tts(annotated));
+    let output = match call_proc_macro(cx, attr.span, &name, |cx| {
+        mac.expand(cx, attr.span, annotation, annotated)
+    }) {
+        Some(output) => output,
+        None => return vec![Annotatable::Item(item)],
+    };
+
+    let tts = respan_proc_macro_output(output, attr.span);
+    let mut parser = proc_macro_parser(cx, &tts);
+    let mut items = Vec::new();
+    while parser.token != token::Eof {
+        match parser.parse_item() {
+            Ok(Some(item)) => items.push(Annotatable::Item(item)),
+            Ok(None) => {
+                let token_str = parser.this_token_to_string();
+                cx.struct_span_err(attr.span, &format!("procedural macro `{}` produced \
+                                                        something other than items",
+                                                       name))
+                  .span_note(parser.span, &format!("expected an item, found `{}`", token_str))
+                  .emit();
+                break;
+            }
+            Err(mut err) => {
+                err.emit();
+                break;
+            }
+        }
+    }
+    items
+}
+
+/// The tokens of `item`, for a procedural macro. If the item is still what
+/// its source text parses to, as far as the pretty-printer can tell, these
+/// are the tokens of that text, with their spans in the source. Otherwise,
+/// they are the tokens of the pretty-printed item, which all get the span of
+/// the item, and the pseudo-file they are lexed from is called `name`. Outer
+/// attributes are not part of the span of an item, so they are handed over in
+/// the latter way, each with its own span.
+pub fn item_to_tts(cx: &ExtCtxt, name: &str, item: &P<ast::Item>) -> Option<Vec<TokenTree>> {
+    let mut tts = vec![];
+    let (outer, inner): (Vec<_>, Vec<_>) = item.attrs.iter().cloned().partition(|attr| {
+        attr.node.style == ast::AttrStyle::Outer
+    });
+    for attr in &outer {
+        match pretty_tts(cx, name.to_string(), pprust::attribute_to_string(attr), attr.span) {
+            Some(attr_tts) => tts.extend(attr_tts),
+            None => return None,
+        }
+    }
+
+    let item = item.clone().map(|mut item| {
+        item.attrs = inner;
+        item
+    });
+    let source = pprust::item_to_string(&item);
+    match source_tts(cx, item.span, &source, |parser| {
+        parser.parse_item().map(|item| {
+            item.map_or(String::new(), |item| pprust::item_to_string(&item))
+        })
+    }) {
+        Some(item_tts) => tts.extend(item_tts),
+        None => {
+            match pretty_tts(cx, name.to_string(), source, item.span) {
+                Some(item_tts) => tts.extend(item_tts),
+                None => return None,
+            }
+        }
+    }
+    Some(tts)
+}
+
+/// The tokens of the source text that `sp` points to, with their spans in
+/// it, if what `parse` makes of that text pretty-prints as `source`. The node
+/// may have been changed since it was parsed, by an earlier attribute for
+/// example.
+fn source_tts<'a, F>(cx: &ExtCtxt<'a>, sp: Span, source: &str, parse: F) -> Option<Vec<TokenTree>>
+    where F: FnOnce(&mut Parser<'a>) -> parse::PResult<'a, String>
+{
+    let tts = match parse::parse_tts_from_span(cx.parse_sess(), sp) {
+        Some(tts) => tts,
+        None => return None,
+    };
+    let mut parser = cx.new_parser_from_tts(&tts);
+    match parse(&mut parser) {
+        Ok(ref parsed) if parsed == source && parser.token == token::Eof => Some(tts),
+        Ok(_) => None,
+        Err(mut err) => {
+            err.cancel();
+            None
+        }
+    }
+}
+
+/// The tokens of `source`, the pretty-printed source of a node with the span
+/// `sp`, which all get that span. Reports the error if the source does not
+/// lex.
+fn pretty_tts(cx: &ExtCtxt, name: String, source: String, sp: Span) -> Option<Vec<TokenTree>> {
+    match parse::parse_tts_with_span(name, source, cx.parse_sess(), sp) {
+        Ok(tts) => Some(tts),
+        Err(mut err) => {
+            err.emit();
+            None
+        }
+    }
+}
+
+/// Calls a procedural macro, reporting a panic in it as an error.
+fn call_proc_macro<F>(cx: &mut ExtCtxt, sp: Span, name: &str, f: F) -> Option<TokenStream>
+    where F: FnOnce(&mut ExtCtxt) -> TokenStream
+{
+    let payload = match panic::catch_unwind(AssertUnwindSafe(|| f(&mut *cx))) {
+        Ok(output) => return Some(output),
+        Err(payload) => payload,
+    };
+    // A fatal error the macro reported through `cx` is not a bug in the macro.
+    if payload.is::<FatalError>() {
+        panic::resume_unwind(payload);
+    }
+
+    let mut err = cx.struct_span_err(sp, &format!("procedural macro `{}` panicked", name));
+    if let Some(msg) = payload.downcast_ref::<String>() {
+        err.help(&format!("message: {}", msg));
+    } else if let Some(msg) = payload.downcast_ref::<&'static str>() {
+        err.help(&format!("message: {}", msg));
+    }
+    err.emit();
+    None
+}
+
+/// Gives the tokens a procedural macro invoked at `sp` made up without a span
+/// the span `sp`.
+fn respan_proc_macro_output(output: TokenStream, sp: Span) -> Vec<TokenTree> {
+    output.tts.iter().map(|tt| {
+        tt.map_spans(&mut |span| if span == syntax_pos::DUMMY_SP { sp } else { span })
+    }).collect()
+}
+
+/// Makes a parser for the output of a procedural macro.
+fn proc_macro_parser<'a>(cx: &ExtCtxt<'a>, tts: &[TokenTree]) -> Parser<'a> {
+    let mut parser = cx.new_parser_from_tts(tts);
+    parser.filename = cx.filename.clone();
+    parser.mod_path_stack = cx.mod_path_stack.clone();
+    parser.restrictions = match cx.in_block {
+        true => Restrictions::NO_NONINLINE_MOD,
+        false => Restrictions::empty(),
+    };
+    parser
+}
+
 fn expand_impl_item(ii: ast::ImplItem, fld: &mut MacroExpander)
                  -> SmallVector<ast::ImplItem> {
     match ii.node {
@@ -594,7 +836,10 @@ impl<'a, 'b> Folder for MacroExpander<'a, 'b> {
 
     fn fold_block(&mut self, block: P<Block>) -> P<Block> {
         let was_in_block = ::std::mem::replace(&mut self.cx.in_block, true);
-        let result = with_exts_frame!(self.cx.syntax_env, false, noop_fold_block(block, self));
+        let result = with_exts_frame!(self.cx.syntax_env, false, {
+            self.cx.insert_macro_imports(block.span);
+            noop_fold_block(block, self)
+        });
         self.cx.in_block = was_in_block;
         result
     }
@@ -634,6 +879,7 @@ pub struct ExpansionConfig<'feat> {
     pub features: Option<&'feat Features>,
     pub recursion_limit: usize,
     pub trace_mac: bool,
+    pub trace_mac_format: Option<MacroTraceFormat>,
     pub should_test: bool, // If false, strip `#[test]` nodes
 }
 
@@ -657,6 +903,7 @@ impl<'feat> ExpansionConfig<'feat> {
             features: None,
             recursion_limit: 64,
             trace_mac: false,
+            trace_mac_format: None,
             should_test: false,
         }
     }
@@ -670,12 +917,17 @@ impl<'feat> ExpansionConfig<'feat> {
         fn enable_allow_internal_unstable = allow_internal_unstable,
         fn enable_custom_derive = custom_derive,
         fn enable_pushpop_unsafe = pushpop_unsafe,
+        fn enable_macro_vis_matcher = macro_vis_matcher,
+        fn enable_macro_lifetime_matcher = macro_lifetime_matcher,
+        fn enable_macro_literal_matcher = macro_literal_matcher,
     }
 }
 
+/// Expands `c`, returning the expanded crate, the names of the macros
+/// encountered, and the spans of the `use` imports whose macros were invoked.
 pub fn expand_crate(mut cx: ExtCtxt,
                     user_exts: Vec<NamedSyntaxExtension>,
-                    mut c: Crate) -> (Crate, HashSet<Name>) {
+                    mut c: Crate) -> (Crate, HashSet<Name>, HashSet<Span>) {
     if std_inject::no_core(&c) {
         cx.crate_root = None;
     } else if std_inject::no_std(&c) {
@@ -704,7 +956,7 @@ pub fn expand_crate(mut cx: ExtCtxt,
 
         ret
     };
-    return (ret, cx.syntax_env.names);
+    return (ret, cx.syntax_env.names, cx.used_macro_imports);
 }
 
 // A Marker adds the given mark to the syntax context and
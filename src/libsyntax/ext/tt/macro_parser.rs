@@ -80,11 +80,11 @@ use self::TokenTreeOrTokenTreeVec::*;
 
 use ast;
 use ast::{Name, Ident};
-use syntax_pos::{self, BytePos, mk_sp, Span};
+use syntax_pos::{self, BytePos, mk_sp, MultiSpan, Span};
 use codemap::Spanned;
-use errors::FatalError;
+use errors::SubDiagnostic;
 use parse::lexer::*; //resolve bug?
-use parse::ParseSess;
+use parse::{ParseSess, PResult};
 use parse::parser::{PathStyle, Parser};
 use parse::token::{DocComment, MatchNt, SubstNt};
 use parse::token::{Token, Nonterminal};
@@ -254,7 +254,29 @@ pub enum ParseResult<T> {
     /// Arm failed to match
     Failure(syntax_pos::Span, String),
     /// Fatal error (malformed macro?). Abort compilation.
-    Error(syntax_pos::Span, String)
+    Error(syntax_pos::Span, String),
+    /// A fragment (`$e:expr`) failed to parse
+    FragmentFailure(FragmentError),
+}
+
+/// The error reported by the parser when a fragment fails to parse.
+pub struct FragmentError {
+    pub span: MultiSpan,
+    pub msg: String,
+    pub children: Vec<SubDiagnostic>,
+    /// The `$name:frag` matcher whose fragment failed to parse.
+    pub matcher_span: Span,
+    /// Whether another arm can be tried instead. This is not the case if the
+    /// parser reported errors of its own while parsing the fragment, since those
+    /// cannot be taken back.
+    pub recoverable: bool,
+}
+
+impl FragmentError {
+    /// The position in the macro input at which parsing failed.
+    pub fn primary_span(&self) -> Span {
+        self.span.primary_span().unwrap_or(self.matcher_span)
+    }
 }
 
 pub type NamedParseResult = ParseResult<HashMap<Name, Rc<NamedMatch>>>;
@@ -481,9 +503,26 @@ pub fn parse(sess: &ParseSess,
                 let mut ei = bb_eis.pop().unwrap();
                 match ei.top_elts.get_tt(ei.idx) {
                     TokenTree::Token(span, MatchNt(_, ident)) => {
+                        let err_count = sess.span_diagnostic.err_count();
+                        let nt = match parse_nt(&mut rust_parser, span, &ident.name.as_str()) {
+                            Ok(nt) => nt,
+                            Err(mut err) => {
+                                let error = FragmentError {
+                                    span: err.span.clone(),
+                                    msg: err.message.clone(),
+                                    children: err.children.clone(),
+                                    matcher_span: span,
+                                    recoverable: false,
+                                };
+                                sess.span_diagnostic.cancel(&mut err);
+                                return FragmentFailure(FragmentError {
+                                    recoverable: sess.span_diagnostic.err_count() == err_count,
+                                    ..error
+                                });
+                            }
+                        };
                         let match_cur = ei.match_cur;
-                        (&mut ei.matches[match_cur]).push(Rc::new(MatchedNonterminal(
-                            parse_nt(&mut rust_parser, span, &ident.name.as_str()))));
+                        (&mut ei.matches[match_cur]).push(Rc::new(MatchedNonterminal(nt)));
                         ei.idx += 1;
                         ei.match_cur += 1;
                     }
@@ -501,38 +540,31 @@ pub fn parse(sess: &ParseSess,
     }
 }
 
-pub fn parse_nt<'a>(p: &mut Parser<'a>, sp: Span, name: &str) -> Nonterminal {
+pub fn parse_nt<'a>(p: &mut Parser<'a>, sp: Span, name: &str) -> PResult<'a, Nonterminal> {
     match name {
         "tt" => {
             p.quote_depth += 1; //but in theory, non-quoted tts might be useful
-            let res: ::parse::PResult<'a, _> = p.parse_token_tree();
-            let res = token::NtTT(P(panictry!(res)));
+            let res = p.parse_token_tree();
             p.quote_depth -= 1;
-            return res;
+            return Ok(token::NtTT(P(res?)));
         }
         _ => {}
     }
     // check at the beginning and the parser checks after each bump
     p.check_unknown_macro_variable();
-    match name {
-        "item" => match panictry!(p.parse_item()) {
+    Ok(match name {
+        "item" => match p.parse_item()? {
             Some(i) => token::NtItem(i),
-            None => {
-                p.fatal("expected an item keyword").emit();
-                panic!(FatalError);
-            }
+            None => return Err(p.fatal("expected an item keyword")),
         },
-        "block" => token::NtBlock(panictry!(p.parse_block())),
-        "stmt" => match panictry!(p.parse_stmt()) {
+        "block" => token::NtBlock(p.parse_block()?),
+        "stmt" => match p.parse_stmt()? {
             Some(s) => token::NtStmt(P(s)),
-            None => {
-                p.fatal("expected a statement").emit();
-                panic!(FatalError);
-            }
+            None => return Err(p.fatal("expected a statement")),
         },
-        "pat" => token::NtPat(panictry!(p.parse_pat())),
-        "expr" => token::NtExpr(panictry!(p.parse_expr())),
-        "ty" => token::NtTy(panictry!(p.parse_ty())),
+        "pat" => token::NtPat(p.parse_pat()?),
+        "expr" => token::NtExpr(p.parse_expr()?),
+        "ty" => token::NtTy(p.parse_ty()?),
         // this could be handled like a token, since it is one
         "ident" => match p.token {
             token::Ident(sn) => {
@@ -541,17 +573,28 @@ pub fn parse_nt<'a>(p: &mut Parser<'a>, sp: Span, name: &str) -> Nonterminal {
             }
             _ => {
                 let token_str = pprust::token_to_string(&p.token);
-                p.fatal(&format!("expected ident, found {}",
-                                 &token_str[..])).emit();
-                panic!(FatalError)
+                return Err(p.fatal(&format!("expected ident, found {}", &token_str[..])));
             }
         },
         "path" => {
-            token::NtPath(Box::new(panictry!(p.parse_path(PathStyle::Type))))
+            token::NtPath(Box::new(p.parse_path(PathStyle::Type)?))
         },
-        "meta" => token::NtMeta(panictry!(p.parse_meta_item())),
+        "meta" => token::NtMeta(p.parse_meta_item()?),
+        "vis" => token::NtVis(p.parse_visibility(true)?),
+        "lifetime" => match p.token {
+            token::Lifetime(name) => {
+                let span = p.span;
+                p.bump();
+                token::NtLifetime(Box::new(Spanned::<Ident>{node: name, span: span}))
+            }
+            _ => {
+                let token_str = pprust::token_to_string(&p.token);
+                return Err(p.fatal(&format!("expected lifetime, found {}", &token_str[..])));
+            }
+        },
+        "literal" => token::NtLiteral(p.parse_pat_literal_maybe_minus()?),
         // this is not supposed to happen, since it has been checked
         // when compiling the macro.
         _ => p.span_bug(sp, "invalid fragment specifier")
-    }
+    })
 }
@@ -10,11 +10,14 @@
 
 use ast;
 use syntax_pos::{Span, DUMMY_SP};
+use errors::FatalError;
 use ext::base::{DummyResult, ExtCtxt, MacResult, SyntaxExtension};
 use ext::base::{NormalTT, TTMacroExpander};
-use ext::tt::macro_parser::{Success, Error, Failure};
+use ext::tt::macro_parser::{Success, Error, Failure, FragmentFailure, FragmentError};
 use ext::tt::macro_parser::{MatchedSeq, MatchedNonterminal};
 use ext::tt::macro_parser::parse;
+use ext::tt::macro_trace;
+use feature_gate;
 use parse::lexer::new_tt_reader;
 use parse::parser::{Parser, Restrictions};
 use parse::token::{self, gensym_ident, NtTT, Token};
@@ -30,7 +33,9 @@ use std::collections::{HashMap};
 use std::collections::hash_map::{Entry};
 use std::rc::Rc;
 
-struct ParserAnyMacro<'a> {
+/// Parses the expansion of a macro as whatever kind of node the invocation
+/// is expected to produce.
+pub struct ParserAnyMacro<'a> {
     parser: RefCell<Parser<'a>>,
 
     /// Span of the expansion site of the macro this parser is for
@@ -40,6 +45,14 @@ struct ParserAnyMacro<'a> {
 }
 
 impl<'a> ParserAnyMacro<'a> {
+    pub fn new(parser: Parser<'a>, site_span: Span, macro_ident: ast::Ident) -> ParserAnyMacro<'a> {
+        ParserAnyMacro {
+            parser: RefCell::new(parser),
+            site_span: site_span,
+            macro_ident: macro_ident,
+        }
+    }
+
     /// Make sure we don't have any tokens left to parse, so we don't
     /// silently drop anything. `allow_semi` is so that "optional"
     /// semicolons at the end of normal expressions aren't complained
@@ -191,6 +204,7 @@ fn generic_extension<'cx>(cx: &'cx ExtCtxt,
     // Which arm's failure should we report? (the one furthest along)
     let mut best_fail_spot = DUMMY_SP;
     let mut best_fail_msg = "internal error: ran no matchers".to_string();
+    let mut best_fragment_err = None;
 
     for (i, lhs) in lhses.iter().enumerate() { // try each arm's matchers
         let lhs_tt = match *lhs {
@@ -205,6 +219,10 @@ fn generic_extension<'cx>(cx: &'cx ExtCtxt,
                     TokenTree::Delimited(_, ref delimed) => delimed.tts.clone(),
                     _ => cx.span_bug(sp, "malformed macro rhs"),
                 };
+                if let Some(format) = cx.trace_macros_format() {
+                    macro_trace::trace_expansion(cx, format, sp, name, imported_from, arg,
+                                                 i, lhs, &named_matches, &rhs);
+                }
                 // rhs has holes ( `$id` and `$(...)` that need filled)
                 let trncbr = new_tt_reader(&cx.parse_sess().span_diagnostic,
                                            Some(named_matches),
@@ -220,27 +238,60 @@ fn generic_extension<'cx>(cx: &'cx ExtCtxt,
                 p.check_unknown_macro_variable();
                 // Let the context choose how to interpret the result.
                 // Weird, but useful for X-macros.
-                return Box::new(ParserAnyMacro {
-                    parser: RefCell::new(p),
-
-                    // Pass along the original expansion site and the name of the macro
-                    // so we can print a useful error message if the parse of the expanded
-                    // macro leaves unparsed tokens.
-                    site_span: sp,
-                    macro_ident: name
-                })
+                // Pass along the original expansion site and the name of the macro
+                // so we can print a useful error message if the parse of the expanded
+                // macro leaves unparsed tokens.
+                return Box::new(ParserAnyMacro::new(p, sp, name))
             }
-            Failure(sp, ref msg) => if sp.lo >= best_fail_spot.lo {
+            Failure(sp, ref msg) => if sp.lo > best_fail_spot.lo ||
+                                        sp.lo == best_fail_spot.lo && best_fragment_err.is_none() {
                 best_fail_spot = sp;
                 best_fail_msg = (*msg).clone();
+                best_fragment_err = None;
             },
             Error(err_sp, ref msg) => {
-                cx.span_fatal(err_sp.substitute_dummy(sp), &msg[..])
+                cx.span_err(err_sp.substitute_dummy(sp), &msg[..]);
+                return DummyResult::any(sp);
+            }
+            // Another arm may still match, unless the parser already reported
+            // errors. Failing to parse a fragment is preferred over a failure
+            // to match at the same position, since its message is more precise.
+            FragmentFailure(err) => {
+                if !err.recoverable {
+                    fragment_error(cx, sp, name, err);
+                    return DummyResult::any(sp);
+                }
+                let err_sp = err.primary_span();
+                if err_sp.lo >= best_fail_spot.lo {
+                    best_fail_spot = err_sp;
+                    best_fragment_err = Some(err);
+                }
             }
         }
     }
 
-     cx.span_fatal(best_fail_spot.substitute_dummy(sp), &best_fail_msg[..]);
+    // Report the error and carry on expanding, so that the errors of
+    // other invocations are reported as well.
+    if let Some(err) = best_fragment_err {
+        fragment_error(cx, sp, name, err);
+        return DummyResult::any(sp);
+    }
+    let mut db = cx.struct_span_err(best_fail_spot.substitute_dummy(sp), &best_fail_msg[..]);
+    if best_fail_spot != DUMMY_SP {
+        db.span_note(sp, &format!("in this invocation of `{}!`", name));
+    }
+    db.emit();
+    DummyResult::any(sp)
+}
+
+/// Reports a fragment that failed to parse, pointing at both the
+/// invocation and the matcher of the fragment.
+fn fragment_error(cx: &ExtCtxt, sp: Span, name: ast::Ident, err: FragmentError) {
+    let mut db = cx.parse_sess().span_diagnostic.struct_span_err(err.span, &err.msg);
+    db.children.extend(err.children);
+    db.span_note(err.matcher_span, "while parsing this fragment")
+      .span_note(sp, &format!("in this invocation of `{}!`", name))
+      .emit();
 }
 
 // Note that macro-by-example's input is also matched against a token tree:
@@ -297,6 +348,12 @@ pub fn compile<'cx>(cx: &'cx mut ExtCtxt,
             panic!(cx.parse_sess().span_diagnostic
                      .span_fatal(sp.substitute_dummy(def.span), &str[..]));
         }
+        FragmentFailure(err) => {
+            let mut db = cx.parse_sess().span_diagnostic.struct_span_fatal(err.span, &err.msg);
+            db.children.extend(err.children);
+            db.emit();
+            panic!(FatalError);
+        }
     };
 
     let mut valid = true;
@@ -307,6 +364,10 @@ pub fn compile<'cx>(cx: &'cx mut ExtCtxt,
             s.iter().map(|m| match **m {
                 MatchedNonterminal(NtTT(ref tt)) => {
                     valid &= check_lhs_nt_follows(cx, tt);
+                    // Macros from other crates were checked where they were defined.
+                    if def.imported_from.is_none() {
+                        check_fragment_specifier_gates(cx, tt);
+                    }
                     (**tt).clone()
                 }
                 _ => cx.span_bug(def.span, "wrong-structured lhs")
@@ -640,8 +701,8 @@ fn check_matcher_core(cx: &mut ExtCtxt,
                 if let Err(bad_frag) = has_legal_fragment_specifier(tok) {
                     cx.struct_span_err(sp, &format!("invalid fragment specifier `{}`", bad_frag))
                         .help("valid fragment specifiers are `ident`, `block`, \
-                               `stmt`, `expr`, `pat`, `ty`, `path`, `meta`, `tt` \
-                               and `item`")
+                               `stmt`, `expr`, `pat`, `ty`, `path`, `meta`, `tt`, \
+                               `item`, `vis`, `lifetime` and `literal`")
                         .emit();
                     // (This eliminates false positives and duplicates
                     // from error messages.)
@@ -769,11 +830,13 @@ fn token_can_be_followed_by_any(tok: &Token) -> bool {
 /// ANYTHING without fear of future compatibility hazards).
 fn frag_can_be_followed_by_any(frag: &str) -> bool {
     match frag {
-        "item"  | // always terminated by `}` or `;`
-        "block" | // exactly one token tree
-        "ident" | // exactly one token tree
-        "meta"  | // exactly one token tree
-        "tt" =>   // exactly one token tree
+        "item"     | // always terminated by `}` or `;`
+        "block"    | // exactly one token tree
+        "ident"    | // exactly one token tree
+        "meta"     | // exactly one token tree
+        "lifetime" | // exactly one token tree
+        "literal"  | // exactly one token tree, optionally preceded by `-`
+        "tt" =>      // exactly one token tree
             true,
 
         _ =>
@@ -838,10 +901,31 @@ fn is_in_follow(_: &ExtCtxt, tok: &Token, frag: &str) -> Result<bool, (String, &
                 // harmless
                 Ok(true)
             },
+            "lifetime" | "literal" => {
+                // a lifetime is a single token, and a literal is a single token
+                // with an optional leading `-`, so both are harmless
+                Ok(true)
+            },
+            "vis" => {
+                // `vis` may match nothing, in which case the next token must not
+                // be one that could extend the visibility
+                match *tok {
+                    Comma => Ok(true),
+                    Ident(i) if i.name.as_str() != "priv" => Ok(true),
+                    // tokens that can begin a type; `(` is left out since it
+                    // could continue a `pub(restricted)` visibility
+                    OpenDelim(token::DelimToken::Bracket) | Not | BinOp(token::Star) |
+                    BinOp(token::And) | AndAnd | Lt | ModSep | Underscore | Question => Ok(true),
+                    MatchNt(_, ref frag) if frag.name.as_str() == "ident" ||
+                                            frag.name.as_str() == "ty" ||
+                                            frag.name.as_str() == "path" => Ok(true),
+                    _ => Ok(false)
+                }
+            },
             _ => Err((format!("invalid fragment specifier `{}`", frag),
                      "valid fragment specifiers are `ident`, `block`, \
-                      `stmt`, `expr`, `pat`, `ty`, `path`, `meta`, `tt` \
-                      and `item`"))
+                      `stmt`, `expr`, `pat`, `ty`, `path`, `meta`, `tt`, \
+                      `item`, `vis`, `lifetime` and `literal`"))
         }
     }
 }
@@ -857,10 +941,44 @@ fn has_legal_fragment_specifier(tok: &Token) -> Result<(), String> {
     Ok(())
 }
 
+/// Reports the uses of unstable fragment specifiers in a matcher whose
+/// features are not enabled.
+fn check_fragment_specifier_gates(cx: &ExtCtxt, matcher: &TokenTree) {
+    match *matcher {
+        TokenTree::Token(sp, MatchNt(_, ref frag_spec)) => {
+            let (feature, enabled) = match &*frag_spec.name.as_str() {
+                "vis" => ("macro_vis_matcher", cx.ecfg.enable_macro_vis_matcher()),
+                "lifetime" => ("macro_lifetime_matcher",
+                               cx.ecfg.enable_macro_lifetime_matcher()),
+                "literal" => ("macro_literal_matcher", cx.ecfg.enable_macro_literal_matcher()),
+                _ => return,
+            };
+            if !enabled {
+                feature_gate::emit_feature_err(&cx.parse_sess.span_diagnostic, feature, sp,
+                                               feature_gate::GateIssue::Language,
+                                               &format!("the `{}` fragment specifier is \
+                                                         experimental", frag_spec));
+            }
+        }
+        TokenTree::Token(..) => {}
+        TokenTree::Delimited(_, ref delimed) => {
+            for tt in &delimed.tts {
+                check_fragment_specifier_gates(cx, tt);
+            }
+        }
+        TokenTree::Sequence(_, ref seq) => {
+            for tt in &seq.tts {
+                check_fragment_specifier_gates(cx, tt);
+            }
+        }
+    }
+}
+
 fn is_legal_fragment_specifier(frag: &str) -> bool {
     match frag {
         "item" | "block" | "stmt" | "expr" | "pat" |
-        "path" | "ty" | "ident" | "meta" | "tt" => true,
+        "path" | "ty" | "ident" | "meta" | "tt" |
+        "vis" | "lifetime" | "literal" => true,
         _ => false,
     }
 }
@@ -0,0 +1,145 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Structured tracing of `macro_rules!` expansions (`-Z trace-macros-format`).
+//!
+//! For every expansion we record which arm matched, what each `$var` was
+//! bound to, and the tokens the arm expanded to. The record is printed either
+//! as a tree, where the expansions of macros invoked by another macro are
+//! indented below it, or as one JSON object per line. Arms are numbered from 1.
+
+use ast;
+use ext::base::ExtCtxt;
+use ext::tt::macro_parser::{NamedMatch, MatchedSeq, MatchedNonterminal};
+use ext::tt::transcribe::{new_tt_reader, tt_next_token};
+use parse::token;
+use print::pprust;
+use syntax_pos::Span;
+use tokenstream::TokenTree;
+
+use rustc_serialize::json::Json;
+use std::collections::{BTreeMap, HashMap};
+use std::iter;
+use std::rc::Rc;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MacroTraceFormat {
+    /// An indented tree, for reading in a terminal.
+    Tree,
+    /// One JSON object per expansion.
+    Json,
+}
+
+/// What a `$var` was bound to.
+enum Binding {
+    Fragment(String),
+    Seq(Vec<Binding>),
+}
+
+impl Binding {
+    fn new(m: &NamedMatch) -> Binding {
+        match *m {
+            MatchedNonterminal(ref nt) => {
+                Binding::Fragment(pprust::token_to_string(&token::Interpolated(nt.clone())))
+            }
+            MatchedSeq(ref ms, _) => Binding::Seq(ms.iter().map(|m| Binding::new(m)).collect()),
+        }
+    }
+
+    fn to_string(&self) -> String {
+        match *self {
+            Binding::Fragment(ref s) => s.clone(),
+            Binding::Seq(ref bs) => {
+                let bs: Vec<_> = bs.iter().map(|b| b.to_string()).collect();
+                format!("[{}]", bs.join(", "))
+            }
+        }
+    }
+
+    fn to_json(&self) -> Json {
+        match *self {
+            Binding::Fragment(ref s) => Json::String(s.clone()),
+            Binding::Seq(ref bs) => Json::Array(bs.iter().map(|b| b.to_json()).collect()),
+        }
+    }
+}
+
+/// Prints the expansion of the macro `name` invoked at `sp` with `arg`, for
+/// which arm number `arm` (counting from 0) matched.
+pub fn trace_expansion(cx: &ExtCtxt,
+                       format: MacroTraceFormat,
+                       sp: Span,
+                       name: ast::Ident,
+                       imported_from: Option<ast::Ident>,
+                       arg: &[TokenTree],
+                       arm: usize,
+                       lhs: &TokenTree,
+                       named_matches: &HashMap<ast::Name, Rc<NamedMatch>>,
+                       rhs: &[TokenTree]) {
+    let matcher = match *lhs {
+        TokenTree::Delimited(_, ref delim) => pprust::tts_to_string(&delim.tts),
+        _ => pprust::tt_to_string(lhs),
+    };
+
+    let mut bindings: Vec<_> = named_matches.iter().map(|(name, m)| {
+        (name.as_str().to_string(), Binding::new(m))
+    }).collect();
+    bindings.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // The expansion proper is transcribed lazily by the parser, so the
+    // right-hand side is transcribed once more for the trace.
+    let mut rdr = new_tt_reader(&cx.parse_sess().span_diagnostic,
+                                Some(named_matches.clone()),
+                                imported_from,
+                                rhs.to_vec());
+    let mut output = vec![];
+    loop {
+        let tok = tt_next_token(&mut rdr).tok;
+        if tok == token::Eof {
+            break;
+        }
+        output.push(pprust::token_to_string(&tok));
+    }
+    let output = output.join(" ");
+
+    let call_site = cx.codemap().span_to_string(sp);
+    let arm_site = cx.codemap().span_to_string(lhs.get_span());
+    // Expansions performed while expanding another macro are nested below it.
+    let depth = cx.recursion_count.saturating_sub(1);
+
+    match format {
+        MacroTraceFormat::Tree => {
+            let indent: String = iter::repeat("    ").take(depth).collect();
+            println!("{}{}! {{ {} }} at {}",
+                     indent, name, pprust::tts_to_string(arg), call_site);
+            println!("{}  arm {} at {}: {}", indent, arm + 1, arm_site, matcher);
+            for &(ref name, ref binding) in &bindings {
+                println!("{}  ${} = {}", indent, name, binding.to_string());
+            }
+            println!("{}  => {}", indent, output);
+        }
+        MacroTraceFormat::Json => {
+            let mut obj = BTreeMap::new();
+            obj.insert("macro".to_string(), Json::String(name.to_string()));
+            obj.insert("call_site".to_string(), Json::String(call_site));
+            obj.insert("depth".to_string(), Json::U64(depth as u64));
+            obj.insert("input".to_string(), Json::String(pprust::tts_to_string(arg)));
+            obj.insert("arm".to_string(), Json::U64(arm as u64 + 1));
+            obj.insert("arm_site".to_string(), Json::String(arm_site));
+            obj.insert("matcher".to_string(), Json::String(matcher));
+            let bindings = bindings.iter().map(|&(ref name, ref binding)| {
+                (name.clone(), binding.to_json())
+            }).collect();
+            obj.insert("bindings".to_string(), Json::Object(bindings));
+            obj.insert("output".to_string(), Json::String(output));
+            println!("{}", Json::Object(obj));
+        }
+    }
+}
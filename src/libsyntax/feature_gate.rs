@@ -277,7 +277,47 @@ declare_features! (
     (active, cfg_target_has_atomic, "1.9.0", Some(32976)),
 
     // Allows `..` in tuple (struct) patterns
-    (active, dotdot_in_tuple_patterns, "1.10.0", Some(33627))
+    (active, dotdot_in_tuple_patterns, "1.10.0", Some(33627)),
+
+    // Allows `impl Trait` in function return types.
+    (active, conservative_impl_trait, "1.12.0", Some(34511)),
+
+    // Allows untagged unions `union U { ... }`
+    (active, untagged_unions, "1.12.0", Some(32836)),
+
+    // Allows the `!` type to be used outside of function return types.
+    (active, never_type, "1.12.0", Some(35121)),
+
+    // Allows `#[repr(align(N))]` on structs
+    (active, repr_align, "1.12.0", Some(33626)),
+
+    // Allows `#[repr(packed(N))]` on structs
+    (active, repr_packed, "1.12.0", Some(33158)),
+
+    // The sound subset of impl specialization: `default` items and impls,
+    // with specializing impls restricted to "always applicable" ones.
+    (active, min_specialization, "1.12.0", Some(31844)),
+
+    // Allows associated types to have generic parameters and where clauses
+    (active, generic_associated_types, "1.12.0", Some(44265)),
+
+    // Allows `const N: usize` type parameters and constant type arguments
+    (active, const_generics, "1.12.0", Some(44580)),
+
+    // Allows the `vis` fragment specifier in `macro_rules!`
+    (active, macro_vis_matcher, "1.12.0", Some(41022)),
+
+    // Allows the `lifetime` fragment specifier in `macro_rules!`
+    (active, macro_lifetime_matcher, "1.12.0", Some(46895)),
+
+    // Allows the `literal` fragment specifier in `macro_rules!`
+    (active, macro_literal_matcher, "1.12.0", Some(35625)),
+
+    // Allows defining and importing procedural macros over token streams
+    (active, proc_macro, "1.12.0", Some(38356)),
+
+    // Allows importing macros exported by other crates with `use`
+    (active, use_extern_macros, "1.12.0", Some(35896))
 );
 
 declare_features! (
@@ -407,6 +447,16 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeGat
     // Not used any more, but we can't feature gate it
     ("no_stack_check", Normal, Ungated),
 
+    ("proc_macro", Normal, Gated("proc_macro",
+                                 EXPLAIN_PROC_MACRO,
+                                 cfg_fn!(proc_macro))),
+    ("proc_macro_attribute", Normal, Gated("proc_macro",
+                                           EXPLAIN_PROC_MACRO,
+                                           cfg_fn!(proc_macro))),
+    ("proc_macro_derive", Normal, Gated("proc_macro",
+                                        EXPLAIN_PROC_MACRO,
+                                        cfg_fn!(proc_macro))),
+
     ("plugin", CrateLevel, Gated("plugin",
                                  "compiler plugins are experimental \
                                   and possibly buggy",
@@ -514,6 +564,13 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeGat
                                               EXPLAIN_ALLOW_INTERNAL_UNSTABLE,
                                               cfg_fn!(allow_internal_unstable))),
 
+    ("rustc_specialization_trait", Whitelisted, Gated("rustc_attrs",
+                                                      "the `#[rustc_specialization_trait]` \
+                                                       attribute is used internally to \
+                                                       mark traits that specializing impls \
+                                                       may depend on",
+                                                      cfg_fn!(rustc_attrs))),
+
     ("fundamental", Whitelisted, Gated("fundamental",
                                        "the `#[fundamental]` attribute \
                                         is an experimental feature",
@@ -781,12 +838,25 @@ pub const EXPLAIN_ALLOW_INTERNAL_UNSTABLE: &'static str =
 pub const EXPLAIN_CUSTOM_DERIVE: &'static str =
     "`#[derive]` for custom traits is not stable enough for use and is subject to change";
 
+pub const EXPLAIN_PROC_MACRO: &'static str =
+    "procedural macros are experimental";
+
+pub const EXPLAIN_USE_EXTERN_MACROS: &'static str =
+    "importing macros with `use` is experimental";
+
 pub const EXPLAIN_DERIVE_UNDERSCORE: &'static str =
     "attributes of the form `#[derive_*]` are reserved for the compiler";
 
 pub const EXPLAIN_PLACEMENT_IN: &'static str =
     "placement-in expression syntax is experimental and subject to change.";
 
+fn is_method(ii: &ast::ImplItem) -> bool {
+    match ii.node {
+        ast::ImplItemKind::Method(..) => true,
+        _ => false,
+    }
+}
+
 struct PostExpansionVisitor<'a> {
     context: &'a Context<'a>,
 }
@@ -800,6 +870,15 @@ macro_rules! gate_feature_post {
     }}
 }
 
+impl<'a> PostExpansionVisitor<'a> {
+    fn check_associated_type_generics(&self, generics: &ast::Generics, span: Span) {
+        if generics.is_parameterized() || !generics.where_clause.predicates.is_empty() {
+            gate_feature_post!(&self, generic_associated_types, span,
+                               "generic associated types are unstable");
+        }
+    }
+}
+
 impl<'a> Visitor for PostExpansionVisitor<'a> {
     fn visit_attribute(&mut self, attr: &ast::Attribute) {
         if !self.context.cm.span_allows_unstable(attr.span) {
@@ -884,11 +963,27 @@ impl<'a> Visitor for PostExpansionVisitor<'a> {
                                                     and possibly buggy");
 
                             }
+                            if item.name() == "align" {
+                                gate_feature_post!(&self, repr_align, i.span,
+                                                   "the `#[repr(align(N))]` attribute \
+                                                    is experimental");
+                            }
+                            if item.name() == "packed" && item.meta_item_list().is_some() {
+                                gate_feature_post!(&self, repr_packed, i.span,
+                                                   "the `#[repr(packed(N))]` attribute \
+                                                    is experimental");
+                            }
                         }
                     }
                 }
             }
 
+            ast::ItemKind::Union(..) => {
+                gate_feature_post!(&self, untagged_unions,
+                                   i.span,
+                                   "unions are unstable and possibly buggy");
+            }
+
             ast::ItemKind::DefaultImpl(..) => {
                 gate_feature_post!(&self, optin_builtin_traits,
                                    i.span,
@@ -896,7 +991,7 @@ impl<'a> Visitor for PostExpansionVisitor<'a> {
                                     and possibly buggy");
             }
 
-            ast::ItemKind::Impl(_, polarity, _, _, _, _) => {
+            ast::ItemKind::Impl(_, polarity, defaultness, _, _, _, ref impl_items) => {
                 match polarity {
                     ast::ImplPolarity::Negative => {
                         gate_feature_post!(&self, optin_builtin_traits,
@@ -906,6 +1001,26 @@ impl<'a> Visitor for PostExpansionVisitor<'a> {
                     },
                     _ => {}
                 }
+
+                if defaultness == ast::Defaultness::Default {
+                    if !self.context.features.min_specialization {
+                        gate_feature_post!(&self, specialization,
+                                           i.span,
+                                           "specialization is unstable");
+                    } else {
+                        // Everything in a `default impl` is `default`, so the
+                        // same restriction as in `visit_impl_item` applies.
+                        // (Items that are `default` themselves are gated there.)
+                        for ii in impl_items.iter().filter(|ii| {
+                            !is_method(ii) && ii.defaultness == ast::Defaultness::Final
+                        }) {
+                            gate_feature_post!(&self, specialization,
+                                               ii.span,
+                                               "specializing associated types and \
+                                                constants is unstable");
+                        }
+                    }
+                }
             }
 
             _ => {}
@@ -995,6 +1110,35 @@ impl<'a> Visitor for PostExpansionVisitor<'a> {
         visit::walk_pat(self, pattern)
     }
 
+    fn visit_ty(&mut self, ty: &ast::Ty) {
+        match ty.node {
+            ast::TyKind::ImplTrait(..) => {
+                gate_feature_post!(&self, conservative_impl_trait, ty.span,
+                                   "`impl Trait` is experimental");
+            }
+            ast::TyKind::Never => {
+                gate_feature_post!(&self, never_type, ty.span,
+                                   "the `!` type is experimental");
+            }
+            ast::TyKind::ConstArg(..) => {
+                gate_feature_post!(&self, const_generics, ty.span,
+                                   "const generics are unstable");
+            }
+            _ => {}
+        }
+        visit::walk_ty(self, ty);
+    }
+
+    fn visit_generics(&mut self, generics: &ast::Generics) {
+        for ty_param in generics.ty_params.iter() {
+            if ty_param.const_ty.is_some() {
+                gate_feature_post!(&self, const_generics, ty_param.span,
+                                   "const generics are unstable");
+            }
+        }
+        visit::walk_generics(self, generics);
+    }
+
     fn visit_fn(&mut self,
                 fn_kind: FnKind,
                 fn_decl: &ast::FnDecl,
@@ -1049,9 +1193,12 @@ impl<'a> Visitor for PostExpansionVisitor<'a> {
                     gate_feature_post!(&self, const_fn, ti.span, "const fn is unstable");
                 }
             }
-            ast::TraitItemKind::Type(_, Some(_)) => {
-                gate_feature_post!(&self, associated_type_defaults, ti.span,
-                                  "associated type defaults are unstable");
+            ast::TraitItemKind::Type(ref generics, _, ref default) => {
+                if default.is_some() {
+                    gate_feature_post!(&self, associated_type_defaults, ti.span,
+                                      "associated type defaults are unstable");
+                }
+                self.check_associated_type_generics(generics, ti.span);
             }
             _ => {}
         }
@@ -1060,9 +1207,17 @@ impl<'a> Visitor for PostExpansionVisitor<'a> {
 
     fn visit_impl_item(&mut self, ii: &ast::ImplItem) {
         if ii.defaultness == ast::Defaultness::Default {
-            gate_feature_post!(&self, specialization,
-                              ii.span,
-                              "specialization is unstable");
+            // `min_specialization` only covers `default fn`: specializing
+            // associated types and constants is not sound yet.
+            if !self.context.features.min_specialization {
+                gate_feature_post!(&self, specialization,
+                                  ii.span,
+                                  "specialization is unstable");
+            } else if !is_method(ii) {
+                gate_feature_post!(&self, specialization,
+                                  ii.span,
+                                  "specializing associated types and constants is unstable");
+            }
         }
 
         match ii.node {
@@ -1076,6 +1231,9 @@ impl<'a> Visitor for PostExpansionVisitor<'a> {
                     gate_feature_post!(&self, const_fn, ii.span, "const fn is unstable");
                 }
             }
+            ast::ImplItemKind::Type(ref generics, _) => {
+                self.check_associated_type_generics(generics, ii.span);
+            }
             _ => {}
         }
         visit::walk_impl_item(self, ii);
@@ -135,6 +135,7 @@ pub mod ext {
         pub mod transcribe;
         pub mod macro_parser;
         pub mod macro_rules;
+        pub mod macro_trace;
     }
 }
 
@@ -197,6 +197,12 @@ impl<'a> Parser<'a> {
     /// | IDENT = lit
     /// | IDENT meta_seq
     pub fn parse_meta_item(&mut self) -> PResult<'a, P<ast::MetaItem>> {
+        self.parse_nested_meta_item(None)
+    }
+
+    /// Parses a meta item inside the list of the meta item named `parent`.
+    fn parse_nested_meta_item(&mut self, parent: Option<&str>)
+                              -> PResult<'a, P<ast::MetaItem>> {
         let nt_meta = match self.token {
             token::Interpolated(token::NtMeta(ref e)) => Some(e.clone()),
             _ => None,
@@ -227,7 +233,10 @@ impl<'a> Parser<'a> {
                 Ok(P(spanned(lo, hi, ast::MetaItemKind::NameValue(name, lit))))
             }
             token::OpenDelim(token::Paren) => {
-                let inner_items = self.parse_meta_seq()?;
+                // Integers are only allowed in `repr(align(N))` and `repr(packed(N))`.
+                let allow_integers = parent == Some("repr") &&
+                                     (name == "align" || name == "packed");
+                let inner_items = self.parse_meta_seq(&name, allow_integers)?;
                 let hi = self.span.hi;
                 Ok(P(spanned(lo, hi, ast::MetaItemKind::List(name, inner_items))))
             }
@@ -238,11 +247,28 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// matches meta_seq = ( COMMASEP(meta_item) )
-    fn parse_meta_seq(&mut self) -> PResult<'a, Vec<P<ast::MetaItem>>> {
+    /// matches meta_seq = ( COMMASEP(meta_seq_item) )
+    fn parse_meta_seq(&mut self, name: &str, allow_integers: bool)
+                      -> PResult<'a, Vec<P<ast::MetaItem>>> {
         self.parse_unspanned_seq(&token::OpenDelim(token::Paren),
                                  &token::CloseDelim(token::Paren),
                                  SeqSep::trailing_allowed(token::Comma),
-                                 |p: &mut Parser<'a>| p.parse_meta_item())
+                                 |p: &mut Parser<'a>| p.parse_meta_seq_item(name, allow_integers))
+    }
+
+    /// matches meta_seq_item = meta_item | UNSUFFIXED_INTEGER
+    ///
+    /// An integer, as in `#[repr(align(8))]`, is kept as a word of its digits.
+    fn parse_meta_seq_item(&mut self, parent: &str, allow_integers: bool)
+                           -> PResult<'a, P<ast::MetaItem>> {
+        if let token::Literal(token::Integer(digits), None) = self.token {
+            if allow_integers {
+                let lo = self.span.lo;
+                self.bump();
+                let hi = self.last_span.hi;
+                return Ok(P(spanned(lo, hi, ast::MetaItemKind::Word(digits.as_str()))));
+            }
+        }
+        self.parse_nested_meta_item(Some(parent))
     }
 }
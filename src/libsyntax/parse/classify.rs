@@ -30,7 +30,9 @@ pub fn expr_requires_semi_to_be_stmt(e: &ast::Expr) -> bool {
         ast::ExprKind::While(..) |
         ast::ExprKind::WhileLet(..) |
         ast::ExprKind::Loop(..) |
-        ast::ExprKind::ForLoop(..) => false,
+        ast::ExprKind::ForLoop(..) |
+        // A placeholder left by error recovery took the `;` with it.
+        ast::ExprKind::Err => false,
         _ => true,
     }
 }
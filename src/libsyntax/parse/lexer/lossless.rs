@@ -0,0 +1,236 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Lossless lexing, for tools that rewrite source code.
+//!
+//! The parser only gets the tokens it cares about: `Reader::real_token`
+//! drops whitespace and comments, apart from doc comments. Here every token
+//! keeps the whitespace and comments in front of it, its *trivia*, and
+//! every token and piece of trivia keeps its text, so that the source can
+//! be put back together byte for byte with `tokens_to_source`. Trivia at
+//! the end of the file goes with the final `Eof` token.
+//!
+//! The only thing that does not come back is a byte order mark, which
+//! `CodeMap::new_filemap` strips before lexing.
+
+use codemap::CodeMap;
+use errors::Handler;
+use parse::lexer::{Reader, StringReader, TokenAndSpan};
+use parse::token;
+use syntax_pos::{FileMap, Pos, Span};
+
+use std::io::Read;
+use std::rc::Rc;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TriviaKind {
+    Whitespace,
+    /// A comment which is not a doc comment.
+    Comment,
+    /// `#!...` on the first line.
+    Shebang,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+    pub sp: Span,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LosslessToken {
+    /// The whitespace and comments between the previous token and this one.
+    pub leading_trivia: Vec<Trivia>,
+    pub tok: token::Token,
+    /// The source text of the token; empty for `Eof`.
+    pub text: String,
+    pub sp: Span,
+}
+
+/// Lexes `filemap`, keeping all of its trivia. The spans are those of the
+/// codemap that `filemap` belongs to, so they match the spans of an AST
+/// parsed from the same file.
+///
+/// Lexical errors are reported through `span_diagnostic`. As in the parser,
+/// fatal ones end lexing, either by making this return `Err(())` or by
+/// panicking with `FatalError`.
+pub fn lex_lossless(span_diagnostic: &Handler,
+                    filemap: Rc<FileMap>)
+                    -> Result<Vec<LosslessToken>, ()> {
+    let start_pos = filemap.start_pos;
+    let src = match filemap.src {
+        Some(ref src) => src.clone(),
+        None => {
+            span_diagnostic.bug(&format!("cannot lex filemap without source: {}",
+                                         filemap.name))
+        }
+    };
+    let text = |sp: Span| {
+        src[(sp.lo - start_pos).to_usize()..(sp.hi - start_pos).to_usize()].to_string()
+    };
+
+    let mut rdr = StringReader::new_raw(span_diagnostic, filemap.clone());
+    let mut tokens = vec![];
+    let mut trivia = vec![];
+    let mut next = rdr.advance_token().and_then(|()| rdr.try_next_token());
+    loop {
+        let TokenAndSpan { tok, sp } = match next {
+            Ok(t) => t,
+            Err(()) => {
+                rdr.emit_fatal_errors();
+                return Err(());
+            }
+        };
+        let kind = match tok {
+            token::Whitespace => Some(TriviaKind::Whitespace),
+            token::Comment => Some(TriviaKind::Comment),
+            token::Shebang(_) => Some(TriviaKind::Shebang),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            trivia.push(Trivia {
+                kind: kind,
+                text: text(sp),
+                sp: sp,
+            });
+        } else {
+            let is_eof = tok == token::Eof;
+            tokens.push(LosslessToken {
+                leading_trivia: trivia,
+                tok: tok,
+                text: if is_eof { String::new() } else { text(sp) },
+                sp: sp,
+            });
+            if is_eof {
+                return Ok(tokens);
+            }
+            trivia = vec![];
+        }
+        next = rdr.try_next_token();
+    }
+}
+
+/// Lexes the source read from `srdr`, keeping all of its trivia, like
+/// `comments::gather_comments_and_literals` does for comments. The spans
+/// are byte offsets into the source.
+pub fn gather_lossless_tokens(span_diagnostic: &Handler,
+                              path: String,
+                              srdr: &mut Read)
+                              -> Result<Vec<LosslessToken>, ()> {
+    let mut src = String::new();
+    srdr.read_to_string(&mut src).unwrap();
+    let cm = CodeMap::new();
+    let filemap = cm.new_filemap(path, None, src);
+    lex_lossless(span_diagnostic, filemap)
+}
+
+/// Puts the source back together from its tokens and trivia.
+pub fn tokens_to_source(tokens: &[LosslessToken]) -> String {
+    let mut src = String::new();
+    for t in tokens {
+        for trivia in &t.leading_trivia {
+            src.push_str(&trivia.text);
+        }
+        src.push_str(&t.text);
+    }
+    src
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use errors;
+    use parse::token;
+    use std::io;
+
+    fn mk_sh() -> errors::Handler {
+        let emitter = errors::emitter::EmitterWriter::new(Box::new(io::sink()),
+                                                None,
+                                                None,
+                                                errors::snippet::FormatMode::EnvironmentSelected);
+        errors::Handler::with_emitter(true, false, Box::new(emitter))
+    }
+
+    fn lex(src: &str) -> Vec<LosslessToken> {
+        let sh = mk_sh();
+        gather_lossless_tokens(&sh, "zebra.rs".to_string(), &mut src.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let src = "#!/usr/bin/env run-cargo-script\n\
+                   // A comment.\n\
+                   \n\
+                   /// Docs.\n\
+                   fn main() {\r\n\
+                   \tlet s = r#\"raw /* not a comment */\"#; /* block /* nested */ */\n\
+                   \x20   println!(\"{}\", s); // trailing\n\
+                   }\n\
+                   \n\
+                   // The end.\n";
+        let tokens = lex(src);
+        assert_eq!(tokens_to_source(&tokens), src);
+
+        // The pieces are contiguous.
+        let mut pos = 0;
+        for t in &tokens {
+            for trivia in &t.leading_trivia {
+                assert_eq!(trivia.sp.lo.0, pos);
+                pos = trivia.sp.hi.0;
+            }
+            assert_eq!(t.sp.lo.0, pos);
+            pos = t.sp.hi.0;
+        }
+        assert_eq!(pos as usize, src.len());
+    }
+
+    #[test]
+    fn trivia_goes_with_the_next_token() {
+        let tokens = lex("a /* b */\n// c\nd // e\n");
+        let toks: Vec<_> = tokens.iter().map(|t| t.text.clone()).collect();
+        assert_eq!(toks, ["a", "d", ""]);
+
+        assert!(tokens[0].leading_trivia.is_empty());
+        let trivia: Vec<_> = tokens[1].leading_trivia.iter().map(|t| {
+            (t.kind, &t.text[..])
+        }).collect();
+        assert_eq!(trivia, [(TriviaKind::Whitespace, " "),
+                            (TriviaKind::Comment, "/* b */"),
+                            (TriviaKind::Whitespace, "\n"),
+                            (TriviaKind::Comment, "// c"),
+                            (TriviaKind::Whitespace, "\n")]);
+
+        assert_eq!(tokens[2].tok, token::Eof);
+        let trivia: Vec<_> = tokens[2].leading_trivia.iter().map(|t| &t.text[..]).collect();
+        assert_eq!(trivia, [" ", "// e", "\n"]);
+    }
+
+    #[test]
+    fn doc_comments_are_tokens() {
+        let tokens = lex("//! Inner.\n/** Outer. */ struct S;");
+        match tokens[0].tok {
+            token::DocComment(_) => {}
+            ref tok => panic!("expected a doc comment, found {:?}", tok),
+        }
+        assert_eq!(tokens[0].text, "//! Inner.");
+        assert_eq!(tokens[1].text, "/** Outer. */");
+        assert_eq!(tokens[1].leading_trivia[0].text, "\n");
+    }
+
+    #[test]
+    fn fatal_errors_are_returned() {
+        let sh = mk_sh();
+        let res = gather_lossless_tokens(&sh, "zebra.rs".to_string(),
+                                         &mut "fn f() { \u{7} }".as_bytes());
+        assert!(res.is_err());
+    }
+}
@@ -25,6 +25,7 @@ use std::rc::Rc;
 pub use ext::tt::transcribe::{TtReader, new_tt_reader, new_tt_reader_with_doc_flag};
 
 pub mod comments;
+pub mod lossless;
 mod unicode_chars;
 
 pub trait Reader {
@@ -262,12 +262,60 @@ fn file_to_filemap(sess: &ParseSess, path: &Path, spanopt: Option<Span>)
 /// Given a filemap, produce a sequence of token-trees
 pub fn filemap_to_tts(sess: &ParseSess, filemap: Rc<FileMap>)
     -> Vec<tokenstream::TokenTree> {
+    panictry!(try_filemap_to_tts(sess, filemap))
+}
+
+/// Given a filemap, produce a sequence of token-trees, or the error that
+/// prevented it.
+pub fn try_filemap_to_tts<'a>(sess: &'a ParseSess, filemap: Rc<FileMap>)
+                              -> PResult<'a, Vec<tokenstream::TokenTree>> {
     // it appears to me that the cfg doesn't matter here... indeed,
     // parsing tt's probably shouldn't require a parser at all.
     let cfg = Vec::new();
     let srdr = lexer::StringReader::new(&sess.span_diagnostic, filemap);
     let mut p1 = Parser::new(sess, cfg, Box::new(srdr));
-    panictry!(p1.parse_all_token_trees())
+    p1.parse_all_token_trees()
+}
+
+/// Parses the source text that `sp` points to into token trees, which keep
+/// the spans of that text. Returns `None` if `sp` comes from a macro
+/// expansion, or its source text is not available.
+pub fn parse_tts_from_span(sess: &ParseSess, sp: Span) -> Option<Vec<tokenstream::TokenTree>> {
+    if sp.expn_id != syntax_pos::NO_EXPANSION {
+        return None;
+    }
+    let source = match sess.codemap().span_to_snippet(sp) {
+        Ok(source) => source,
+        Err(_) => return None,
+    };
+    let name = format!("<{}>", sess.codemap().span_to_string(sp));
+    let filemap = sess.codemap().new_filemap(name, None, source);
+    let start = filemap.start_pos;
+    let tts = match try_filemap_to_tts(sess, filemap) {
+        Ok(tts) => tts,
+        Err(mut err) => {
+            err.cancel();
+            return None;
+        }
+    };
+    // The text was copied verbatim, so its tokens are as far from the start
+    // of `sp` as they are from the start of the copy.
+    Some(tts.iter().map(|tt| {
+        tt.map_spans(&mut |span| syntax_pos::mk_sp(sp.lo + (span.lo - start),
+                                                   sp.lo + (span.hi - start)))
+    }).collect())
+}
+
+/// Parses `source`, text made up for a node with the span `sp` (such as its
+/// pretty-printed source), into token trees that all get the span `sp`.
+pub fn parse_tts_with_span<'a>(name: String,
+                               source: String,
+                               sess: &'a ParseSess,
+                               sp: Span)
+                               -> PResult<'a, Vec<tokenstream::TokenTree>> {
+    let filemap = sess.codemap().new_filemap(name, None, source);
+    let tts = try_filemap_to_tts(sess, filemap)?;
+    Ok(tts.iter().map(|tt| tt.map_spans(&mut |_| sp)).collect())
 }
 
 /// Given tts and cfg, produce a parser
@@ -58,6 +58,7 @@ use parse::PResult;
 use tokenstream::{self, Delimited, SequenceRepetition, TokenTree};
 use util::ThinVec;
 
+use std::cmp;
 use std::collections::HashSet;
 use std::mem;
 use std::path::{Path, PathBuf};
@@ -111,7 +112,8 @@ macro_rules! maybe_whole_expr {
     ($p:expr) => (
         {
             let found = match $p.token {
-                token::Interpolated(token::NtExpr(ref e)) => {
+                token::Interpolated(token::NtExpr(ref e)) |
+                token::Interpolated(token::NtLiteral(ref e)) => {
                     Some((*e).clone())
                 }
                 token::Interpolated(token::NtPath(_)) => {
@@ -276,6 +278,9 @@ pub struct Parser<'a> {
     /// into modules, and sub-parsers have new values for this name.
     pub root_module_name: Option<String>,
     pub expected_tokens: Vec<TokenType>,
+    /// The number of delimiters bumped past and not closed yet. Used for
+    /// error recovery.
+    delim_depth: usize,
 }
 
 #[derive(PartialEq, Eq, Clone)]
@@ -383,6 +388,7 @@ impl<'a> Parser<'a> {
             owns_directory: true,
             root_module_name: None,
             expected_tokens: Vec::new(),
+            delim_depth: 0,
         }
     }
 
@@ -805,6 +811,35 @@ impl<'a> Parser<'a> {
                                         |mut e| e.cancel());
     }
 
+    /// Whether to recover from syntax errors that would otherwise end the
+    /// parse, see `-Z continue-parse-after-error`.
+    pub fn recover_from_errors(&self) -> bool {
+        self.sess.span_diagnostic.continue_after_error()
+    }
+
+    /// Eat tokens until one of `kets` is encountered at the current nesting
+    /// level, or until an unmatched closing delimiter or the end of the file.
+    /// Used for error recovery.
+    fn recover_to_tokens(&mut self, kets: &[&token::Token]) {
+        loop {
+            if kets.contains(&&self.token) {
+                return;
+            }
+            match self.token {
+                token::CloseDelim(_) | token::Eof => return,
+                token::OpenDelim(_) => self.skip_token_tree(),
+                _ => self.bump(),
+            }
+        }
+    }
+
+    /// Eat a whole delimited token tree.
+    fn skip_token_tree(&mut self) {
+        if let Err(mut e) = self.parse_token_tree() {
+            e.emit();
+        }
+    }
+
     /// Parse a sequence, including the closing delimiter. The function
     /// f must consume tokens until reaching the next separator or
     /// closing bracket.
@@ -830,7 +865,13 @@ impl<'a> Parser<'a> {
                                          -> Vec<T>
         where F: FnMut(&mut Parser<'a>) -> PResult<'a,  T>
     {
-        self.parse_seq_to_before_tokens(&[ket], sep, f, |mut e| e.emit())
+        let v = self.parse_seq_to_before_tokens(&[ket], sep, f, |mut e| e.emit());
+        if self.recover_from_errors() {
+            // Skip what is left of an element that failed to parse, so that
+            // the caller finds the closing delimiter.
+            self.recover_to_tokens(&[ket]);
+        }
+        v
     }
 
     // `fe` is an error handler.
@@ -919,8 +960,11 @@ impl<'a> Parser<'a> {
             self.bug("attempted to bump the parser past EOF (may be stuck in a loop)");
         }
 
-        if self.token == token::Eof {
-            self.last_token_eof = true;
+        match self.token {
+            token::Eof => self.last_token_eof = true,
+            token::OpenDelim(_) => self.delim_depth += 1,
+            token::CloseDelim(_) => self.delim_depth = self.delim_depth.saturating_sub(1),
+            _ => {}
         }
 
         self.last_span = self.span;
@@ -1178,9 +1222,17 @@ impl<'a> Parser<'a> {
         let lo = self.span.lo;
 
         let (name, node) = if self.eat_keyword(keywords::Type) {
-            let TyParam {ident, bounds, default, ..} = self.parse_ty_param()?;
+            let ident = self.parse_ident()?;
+            let mut generics = self.parse_generics()?;
+            let bounds = self.parse_colon_then_ty_param_bounds(BoundParsingMode::Modified)?;
+            generics.where_clause = self.parse_where_clause()?;
+            let default = if self.eat(&token::Eq) {
+                Some(self.parse_ty_sum()?)
+            } else {
+                None
+            };
             self.expect(&token::Semi)?;
-            (ident, TraitItemKind::Type(bounds, default))
+            (ident, TraitItemKind::Type(generics, bounds, default))
         } else if self.is_const_item() {
                 self.expect_keyword(keywords::Const)?;
             let ident = self.parse_ident()?;
@@ -1296,13 +1348,40 @@ impl<'a> Parser<'a> {
 
     /// Parse the items in a trait declaration
     pub fn parse_trait_items(&mut self) -> PResult<'a,  Vec<TraitItem>> {
-        self.parse_unspanned_seq(
-            &token::OpenDelim(token::Brace),
-            &token::CloseDelim(token::Brace),
-            SeqSep::none(),
-            |p| -> PResult<'a, TraitItem> {
-                p.parse_trait_item()
-            })
+        if !self.recover_from_errors() {
+            return self.parse_unspanned_seq(
+                &token::OpenDelim(token::Brace),
+                &token::CloseDelim(token::Brace),
+                SeqSep::none(),
+                |p| -> PResult<'a, TraitItem> {
+                    p.parse_trait_item()
+                });
+        }
+        self.expect(&token::OpenDelim(token::Brace))?;
+        self.parse_assoc_items(|p| p.parse_trait_item())
+    }
+
+    /// Parse the items of a trait or impl with `f`, up to and including
+    /// the closing brace. When recovering from errors, an item that fails
+    /// to parse is skipped.
+    fn parse_assoc_items<T, F>(&mut self, mut f: F) -> PResult<'a, Vec<T>>
+        where F: FnMut(&mut Parser<'a>) -> PResult<'a, T>
+    {
+        let mut items = vec![];
+        while !self.eat(&token::CloseDelim(token::Brace)) {
+            let (lo, depth) = (self.tokens_consumed, self.delim_depth);
+            match f(self) {
+                Ok(item) => items.push(item),
+                Err(mut e) => {
+                    if !self.recover_from_errors() || self.token == token::Eof {
+                        return Err(e);
+                    }
+                    e.emit();
+                    self.recover_item(lo, depth);
+                }
+            }
+        }
+        Ok(items)
     }
 
     /// Parse a possibly mutable type
@@ -1382,6 +1461,9 @@ impl<'a> Parser<'a> {
             } else {
                 TyKind::Tup(ts)
             }
+        } else if self.eat(&token::Not) {
+            // NEVER TYPE
+            TyKind::Never
         } else if self.check(&token::BinOp(token::Star)) {
             // STAR POINTER (bare pointer?)
             self.bump();
@@ -1406,6 +1488,18 @@ impl<'a> Parser<'a> {
             self.parse_borrowed_pointee()?
         } else if self.check_keyword(keywords::For) {
             self.parse_for_in_type()?
+        } else if self.eat_keyword(keywords::Impl) {
+            // IMPL TRAIT
+            let bounds = self.parse_ty_param_bounds(BoundParsingMode::Bare)?;
+            let has_trait = bounds.iter().any(|bound| match *bound {
+                TraitTyParamBound(..) => true,
+                RegionTyParamBound(..) => false,
+            });
+            if !has_trait {
+                let last_span = self.last_span;
+                self.span_err(last_span, "at least one trait must be specified");
+            }
+            TyKind::ImplTrait(bounds)
         } else if self.token_is_bare_fn_keyword() {
             // BARE FUNCTION
             self.parse_ty_bare_fn(Vec::new())?
@@ -1622,6 +1716,18 @@ impl<'a> Parser<'a> {
 
     /// Matches lit = true | false | token_lit
     pub fn parse_lit(&mut self) -> PResult<'a, Lit> {
+        let found = match self.token {
+            token::Interpolated(token::NtLiteral(ref e)) => match e.node {
+                ExprKind::Lit(ref lit) => Some((**lit).clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(lit) = found {
+            self.bump();
+            return Ok(lit);
+        }
+
         let lo = self.span.lo;
         let lit = if self.eat_keyword(keywords::True) {
             LitKind::Bool(true)
@@ -1636,6 +1742,8 @@ impl<'a> Parser<'a> {
 
     /// matches '-' lit | lit
     pub fn parse_pat_literal_maybe_minus(&mut self) -> PResult<'a, P<Expr>> {
+        maybe_whole!(self, NtLiteral);
+
         let minus_lo = self.span.lo;
         let minus_present = self.eat(&token::BinOp(token::Minus));
         let lo = self.span.lo;
@@ -2136,7 +2244,7 @@ impl<'a> Parser<'a> {
                 let mut es = vec![];
                 let mut trailing_comma = false;
                 while self.token != token::CloseDelim(token::Paren) {
-                    es.push(self.parse_expr()?);
+                    es.push(self.parse_expr_list_elt(&token::CloseDelim(token::Paren))?);
                     self.expect_one_of(&[], &[token::Comma, token::CloseDelim(token::Paren)])?;
                     if self.check(&token::Comma) {
                         trailing_comma = true;
@@ -2174,7 +2282,7 @@ impl<'a> Parser<'a> {
                     ex = ExprKind::Vec(Vec::new());
                 } else {
                     // Nonempty vector.
-                    let first_expr = self.parse_expr()?;
+                    let first_expr = self.parse_expr_list_elt(&token::CloseDelim(token::Bracket))?;
                     if self.check(&token::Semi) {
                         // Repeating array syntax: [ 0; 512 ]
                         self.bump();
@@ -2187,7 +2295,7 @@ impl<'a> Parser<'a> {
                         let remaining_exprs = self.parse_seq_to_end(
                             &token::CloseDelim(token::Bracket),
                             SeqSep::trailing_allowed(token::Comma),
-                            |p| Ok(p.parse_expr()?)
+                            |p| p.parse_expr_list_elt(&token::CloseDelim(token::Bracket))
                         )?;
                         let mut exprs = vec!(first_expr);
                         exprs.extend(remaining_exprs);
@@ -2375,7 +2483,7 @@ impl<'a> Parser<'a> {
                             ex = ExprKind::Lit(P(lit));
                         }
                         Err(mut err) => {
-                            err.cancel();
+                            self.diagnostic().cancel(&mut err);
                             let msg = format!("expected expression, found {}",
                                               self.this_token_descr());
                             return Err(self.fatal(&msg));
@@ -2481,7 +2589,7 @@ impl<'a> Parser<'a> {
                     &token::OpenDelim(token::Paren),
                     &token::CloseDelim(token::Paren),
                     SeqSep::trailing_allowed(token::Comma),
-                    |p| Ok(p.parse_expr()?)
+                    |p| p.parse_expr_list_elt(&token::CloseDelim(token::Paren))
                 )?;
                 let hi = self.last_span.hi;
 
@@ -2588,7 +2696,7 @@ impl<'a> Parser<'a> {
                     &token::OpenDelim(token::Paren),
                     &token::CloseDelim(token::Paren),
                     SeqSep::trailing_allowed(token::Comma),
-                    |p| Ok(p.parse_expr()?)
+                    |p| p.parse_expr_list_elt(&token::CloseDelim(token::Paren))
                 )?;
                 hi = self.last_span.hi;
 
@@ -3335,6 +3443,26 @@ impl<'a> Parser<'a> {
         self.parse_expr_res(Restrictions::empty(), None)
     }
 
+    /// Parse an element of a comma-separated list of expressions closed by
+    /// `ket`, like the arguments of a call. When recovering from errors, an
+    /// element that fails to parse is skipped and replaced by a placeholder,
+    /// so that the list keeps its length.
+    fn parse_expr_list_elt(&mut self, ket: &token::Token) -> PResult<'a, P<Expr>> {
+        let lo = self.span.lo;
+        match self.parse_expr() {
+            Ok(e) => Ok(e),
+            Err(mut e) => {
+                if !self.recover_from_errors() {
+                    return Err(e);
+                }
+                e.emit();
+                self.recover_to_tokens(&[&token::Comma, ket]);
+                let hi = cmp::max(lo, self.last_span.hi);
+                Ok(self.mk_expr(lo, hi, ExprKind::Err, ThinVec::new()))
+            }
+        }
+    }
+
     /// Evaluate the closure with restrictions in place.
     ///
     /// After the closure is evaluated, restrictions are reset.
@@ -3691,7 +3819,7 @@ impl<'a> Parser<'a> {
                         }
                     }
                     Err(mut err) => {
-                        err.cancel();
+                        self.diagnostic().cancel(&mut err);
                         let msg = format!("expected pattern, found {}", self.this_token_descr());
                         return Err(self.fatal(&msg));
                     }
@@ -3859,10 +3987,22 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_stmt_(&mut self, macro_expanded: bool) -> Option<Stmt> {
+        let (lo, start) = (self.span.lo, self.tokens_consumed);
         self.parse_stmt_without_recovery(macro_expanded).unwrap_or_else(|mut e| {
             e.emit();
             self.recover_stmt_(SemiColonMode::Break);
-            None
+            // A placeholder takes the place of the statement, so that a block
+            // whose value failed to parse is not taken to be of type `()`.
+            if self.recover_from_errors() && self.tokens_consumed != start {
+                let hi = self.last_span.hi;
+                Some(Stmt {
+                    id: ast::DUMMY_NODE_ID,
+                    node: StmtKind::Expr(self.mk_expr(lo, hi, ExprKind::Err, ThinVec::new())),
+                    span: mk_sp(lo, hi),
+                })
+            } else {
+                None
+            }
         })
     }
 
@@ -4090,6 +4230,8 @@ impl<'a> Parser<'a> {
             None => return Ok(None),
         };
 
+        // Whether to carry on as if a missing `;` was there.
+        let mut assume_semi = false;
         match stmt.node {
             StmtKind::Expr(ref expr) if self.token != token::Eof => {
                 // expression without semicolon
@@ -4099,7 +4241,11 @@ impl<'a> Parser<'a> {
                         self.expect_one_of(&[], &[token::Semi, token::CloseDelim(token::Brace)])
                     {
                         e.emit();
-                        self.recover_stmt();
+                        if self.recover_from_errors() && self.is_on_new_line() {
+                            assume_semi = true;
+                        } else {
+                            self.recover_stmt();
+                        }
                     }
                 }
             }
@@ -4107,14 +4253,20 @@ impl<'a> Parser<'a> {
                 // We used to incorrectly allow a macro-expanded let statement to lack a semicolon.
                 if macro_expanded && self.token != token::Semi {
                     self.warn_missing_semicolon();
-                } else {
-                    self.expect_one_of(&[token::Semi], &[])?;
+                } else if let Err(mut e) = self.expect_one_of(&[token::Semi], &[]) {
+                    if !self.recover_from_errors() {
+                        return Err(e);
+                    }
+                    e.emit();
+                    if !self.is_on_new_line() {
+                        self.recover_stmt_(SemiColonMode::Break);
+                    }
                 }
             }
             _ => {}
         }
 
-        if self.eat(&token::Semi) {
+        if self.eat(&token::Semi) || assume_semi {
             stmt = stmt.add_trailing_semicolon();
         }
 
@@ -4122,6 +4274,13 @@ impl<'a> Parser<'a> {
         Ok(Some(stmt))
     }
 
+    /// Whether the current token starts a new line. A statement that is
+    /// followed by one is most likely only missing its `;`.
+    fn is_on_new_line(&self) -> bool {
+        let cm = self.sess.codemap();
+        cm.lookup_char_pos(self.last_span.hi).line != cm.lookup_char_pos(self.span.lo).line
+    }
+
     fn warn_missing_semicolon(&self) {
         self.diagnostic().struct_span_warn(self.span, {
             &format!("expected `;`, found `{}`", self.this_token_to_string())
@@ -4195,8 +4354,23 @@ impl<'a> Parser<'a> {
     }
 
     /// Matches typaram = IDENT (`?` unbound)? optbounds ( EQ ty )?
+    ///                 | `const` IDENT `:` ty
     fn parse_ty_param(&mut self) -> PResult<'a, TyParam> {
         let span = self.span;
+        if self.eat_keyword(keywords::Const) {
+            let ident = self.parse_ident()?;
+            self.expect(&token::Colon)?;
+            let const_ty = self.parse_ty()?;
+            return Ok(TyParam {
+                ident: ident,
+                id: ast::DUMMY_NODE_ID,
+                bounds: P::new(),
+                default: None,
+                const_ty: Some(const_ty),
+                span: span,
+            });
+        }
+
         let ident = self.parse_ident()?;
 
         let bounds = self.parse_colon_then_ty_param_bounds(BoundParsingMode::Modified)?;
@@ -4213,6 +4387,7 @@ impl<'a> Parser<'a> {
             id: ast::DUMMY_NODE_ID,
             bounds: bounds,
             default: default,
+            const_ty: None,
             span: span,
         })
     }
@@ -4278,7 +4453,7 @@ impl<'a> Parser<'a> {
             let span_hi = match self.parse_ty() {
                 Ok(..) => self.span.hi,
                 Err(ref mut err) => {
-                    err.cancel();
+                    self.diagnostic().cancel(err);
                     span_hi
                 }
             };
@@ -4298,7 +4473,7 @@ impl<'a> Parser<'a> {
                 if p.look_ahead(1, |t| t == &token::Eq) {
                     Ok(None)
                 } else {
-                    Ok(Some(p.parse_ty_sum()?))
+                    Ok(Some(p.parse_generic_arg()?))
                 }
             }
         )?;
@@ -4329,6 +4504,26 @@ impl<'a> Parser<'a> {
         Ok((lifetimes, types.into_vec(), bindings.into_vec()))
     }
 
+    /// Parses a type argument, or a constant argument to a `const` type
+    /// parameter. Constant arguments must be literals or blocks, as in
+    /// `Buf<3>` or `Buf<{ 2 * 8 }>`.
+    fn parse_generic_arg(&mut self) -> PResult<'a, P<Ty>> {
+        let lo = self.span.lo;
+        let expr = match self.token {
+            token::Literal(..) => {
+                let literal = P(self.parse_lit()?);
+                let hi = self.last_span.hi;
+                self.mk_expr(lo, hi, ExprKind::Lit(literal), ThinVec::new())
+            }
+            token::OpenDelim(token::Brace) => {
+                self.parse_block_expr(lo, BlockCheckMode::Default, ThinVec::new())?
+            }
+            _ => return self.parse_ty_sum(),
+        };
+        let sp = mk_sp(lo, self.last_span.hi);
+        Ok(P(Ty {id: ast::DUMMY_NODE_ID, node: TyKind::ConstArg(expr), span: sp}))
+    }
+
     fn forbid_lifetime(&mut self) -> PResult<'a, ()> {
         if self.token.is_lifetime() {
             let span = self.span;
@@ -4754,10 +4949,12 @@ impl<'a> Parser<'a> {
         let defaultness = self.parse_defaultness()?;
         let (name, node) = if self.eat_keyword(keywords::Type) {
             let name = self.parse_ident()?;
+            let mut generics = self.parse_generics()?;
+            generics.where_clause = self.parse_where_clause()?;
             self.expect(&token::Eq)?;
             let typ = self.parse_ty_sum()?;
             self.expect(&token::Semi)?;
-            (name, ast::ImplItemKind::Type(typ))
+            (name, ast::ImplItemKind::Type(generics, typ))
         } else if self.is_const_item() {
             self.expect_keyword(keywords::Const)?;
             let name = self.parse_ident()?;
@@ -4873,7 +5070,10 @@ impl<'a> Parser<'a> {
     ///    impl<T> Foo { ... }
     ///    impl<T> ToString for &'static T { ... }
     ///    impl Send for .. {}
-    fn parse_item_impl(&mut self, unsafety: ast::Unsafety) -> PResult<'a, ItemInfo> {
+    ///    default impl<T> ToString for T { ... }
+    fn parse_item_impl(&mut self,
+                       unsafety: ast::Unsafety,
+                       defaultness: Defaultness) -> PResult<'a, ItemInfo> {
         let impl_span = self.span;
 
         // First, parse type parameters if necessary.
@@ -4925,6 +5125,10 @@ impl<'a> Parser<'a> {
                 self.span_err(impl_span, "default trait implementations are not \
                                           allowed to have generics");
             }
+            if defaultness == Defaultness::Default {
+                self.span_err(impl_span, "default trait implementations cannot \
+                                          be `default`");
+            }
 
             self.expect(&token::OpenDelim(token::Brace))?;
             self.expect(&token::CloseDelim(token::Brace))?;
@@ -4939,13 +5143,10 @@ impl<'a> Parser<'a> {
             self.expect(&token::OpenDelim(token::Brace))?;
             let attrs = self.parse_inner_attributes()?;
 
-            let mut impl_items = vec![];
-            while !self.eat(&token::CloseDelim(token::Brace)) {
-                impl_items.push(self.parse_impl_item()?);
-            }
+            let impl_items = self.parse_assoc_items(|p| p.parse_impl_item())?;
 
             Ok((keywords::Invalid.ident(),
-             ItemKind::Impl(unsafety, polarity, generics, opt_trait, ty, impl_items),
+             ItemKind::Impl(unsafety, polarity, defaultness, generics, opt_trait, ty, impl_items),
              Some(attrs)))
         }
     }
@@ -5030,6 +5231,25 @@ impl<'a> Parser<'a> {
         Ok((class_name, ItemKind::Struct(vdata, generics), None))
     }
 
+    /// Parse union Foo { ... }
+    fn parse_item_union(&mut self) -> PResult<'a, ItemInfo> {
+        let class_name = self.parse_ident()?;
+        let mut generics = self.parse_generics()?;
+
+        let vdata = if self.token.is_keyword(keywords::Where) {
+            generics.where_clause = self.parse_where_clause()?;
+            VariantData::Struct(self.parse_record_struct_body()?, ast::DUMMY_NODE_ID)
+        } else if self.token == token::OpenDelim(token::Brace) {
+            VariantData::Struct(self.parse_record_struct_body()?, ast::DUMMY_NODE_ID)
+        } else {
+            let token_str = self.this_token_to_string();
+            return Err(self.fatal(&format!("expected `where` or `{{` after union \
+                                            name, found `{}`", token_str)))
+        };
+
+        Ok((class_name, ItemKind::Union(vdata, generics), None))
+    }
+
     pub fn parse_record_struct_body(&mut self) -> PResult<'a, Vec<StructField>> {
         let mut fields = Vec::new();
         if self.eat(&token::OpenDelim(token::Brace)) {
@@ -5121,7 +5341,9 @@ impl<'a> Parser<'a> {
     }
 
     // If `allow_path` is false, just parse the `pub` in `pub(path)` (but still parse `pub(crate)`)
-    fn parse_visibility(&mut self, allow_path: bool) -> PResult<'a, Visibility> {
+    pub fn parse_visibility(&mut self, allow_path: bool) -> PResult<'a, Visibility> {
+        maybe_whole!(self, NtVis);
+
         let pub_crate = |this: &mut Self| {
             let span = this.last_span;
             this.expect(&token::CloseDelim(token::Paren))?;
@@ -5162,13 +5384,34 @@ impl<'a> Parser<'a> {
     /// Given a termination token, parse all of the items in a module
     fn parse_mod_items(&mut self, term: &token::Token, inner_lo: BytePos) -> PResult<'a, Mod> {
         let mut items = vec![];
-        while let Some(item) = self.parse_item()? {
-            items.push(item);
-        }
-
-        if !self.eat(term) {
-            let token_str = self.this_token_to_string();
-            return Err(self.fatal(&format!("expected item, found `{}`", token_str)));
+        loop {
+            let (lo, depth) = (self.tokens_consumed, self.delim_depth);
+            match self.parse_item() {
+                Ok(Some(item)) => items.push(item),
+                Ok(None) => {
+                    if self.eat(term) {
+                        break;
+                    }
+                    let token_str = self.this_token_to_string();
+                    let mut e = self.fatal(&format!("expected item, found `{}`", token_str));
+                    if !self.recover_from_errors() || self.token == token::Eof {
+                        return Err(e);
+                    }
+                    e.emit();
+                    if let token::CloseDelim(_) = self.token {
+                        self.bump();
+                    } else {
+                        self.recover_item(lo, depth);
+                    }
+                }
+                Err(mut e) => {
+                    if !self.recover_from_errors() {
+                        return Err(e);
+                    }
+                    e.emit();
+                    self.recover_item(lo, depth);
+                }
+            }
         }
 
         let hi = if self.span == syntax_pos::DUMMY_SP {
@@ -5183,6 +5426,47 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Eat the rest of an item that failed to parse, so that parsing can go
+    /// on with the next one. `lo` is the number of tokens consumed and
+    /// `depth` the delimiter depth when the item started.
+    ///
+    /// Delimiters opened by the item are closed first. Back at `depth`, we
+    /// stop after a `;` or a `{ ... }` block, and before a token that can
+    /// start an item. If the item did not get past `lo`, at least one token
+    /// is eaten. We never go past an unmatched closing delimiter.
+    fn recover_item(&mut self, lo: usize, depth: usize) {
+        let mut progress = self.tokens_consumed != lo;
+        loop {
+            match self.token {
+                token::Eof => return,
+                token::CloseDelim(delim) => {
+                    if self.delim_depth <= depth {
+                        return;
+                    }
+                    self.bump();
+                    if delim == token::Brace && self.delim_depth == depth {
+                        return;
+                    }
+                }
+                token::Semi if self.delim_depth == depth => {
+                    self.bump();
+                    return;
+                }
+                _ if progress && self.delim_depth == depth && self.token_starts_item() => return,
+                _ => self.bump(),
+            }
+            progress = true;
+        }
+    }
+
+    /// Whether the current token is a good place to resume parsing items.
+    fn token_starts_item(&self) -> bool {
+        self.token == token::Pound ||
+        [keywords::Pub, keywords::Fn, keywords::Struct, keywords::Enum, keywords::Impl,
+         keywords::Trait, keywords::Mod, keywords::Use, keywords::Extern, keywords::Static,
+         keywords::Const, keywords::Type].iter().any(|&kw| self.token.is_keyword(kw))
+    }
+
     fn parse_item_const(&mut self, m: Option<Mutability>) -> PResult<'a, ItemInfo> {
         let id = self.parse_ident()?;
         self.expect(&token::Colon)?;
@@ -5734,7 +6018,8 @@ impl<'a> Parser<'a> {
             // IMPL ITEM
             self.expect_keyword(keywords::Unsafe)?;
             self.expect_keyword(keywords::Impl)?;
-            let (ident, item_, extra_attrs) = self.parse_item_impl(ast::Unsafety::Unsafe)?;
+            let (ident, item_, extra_attrs) =
+                self.parse_item_impl(ast::Unsafety::Unsafe, Defaultness::Final)?;
             let last_span = self.last_span;
             let item = self.mk_item(lo,
                                     last_span.hi,
@@ -5831,7 +6116,8 @@ impl<'a> Parser<'a> {
         }
         if self.eat_keyword(keywords::Impl) {
             // IMPL ITEM
-            let (ident, item_, extra_attrs) = self.parse_item_impl(ast::Unsafety::Normal)?;
+            let (ident, item_, extra_attrs) =
+                self.parse_item_impl(ast::Unsafety::Normal, Defaultness::Final)?;
             let last_span = self.last_span;
             let item = self.mk_item(lo,
                                     last_span.hi,
@@ -5853,6 +6139,43 @@ impl<'a> Parser<'a> {
                                     maybe_append(attrs, extra_attrs));
             return Ok(Some(item));
         }
+        if self.token.is_keyword(keywords::Default) &&
+           (self.look_ahead(1, |t| t.is_keyword(keywords::Impl)) ||
+            self.look_ahead(1, |t| t.is_keyword(keywords::Unsafe)) &&
+            self.look_ahead(2, |t| t.is_keyword(keywords::Impl))) {
+            // DEFAULT IMPL ITEM
+            self.bump();
+            let unsafety = if self.eat_keyword(keywords::Unsafe) {
+                ast::Unsafety::Unsafe
+            } else {
+                ast::Unsafety::Normal
+            };
+            self.expect_keyword(keywords::Impl)?;
+            let (ident, item_, extra_attrs) =
+                self.parse_item_impl(unsafety, Defaultness::Default)?;
+            let last_span = self.last_span;
+            let item = self.mk_item(lo,
+                                    last_span.hi,
+                                    ident,
+                                    item_,
+                                    visibility,
+                                    maybe_append(attrs, extra_attrs));
+            return Ok(Some(item));
+        }
+        if self.token.is_keyword(keywords::Union) &&
+           self.look_ahead(1, |t| t.is_ident() && !t.is_any_keyword()) {
+            // UNION ITEM
+            self.bump();
+            let (ident, item_, extra_attrs) = self.parse_item_union()?;
+            let last_span = self.last_span;
+            let item = self.mk_item(lo,
+                                    last_span.hi,
+                                    ident,
+                                    item_,
+                                    visibility,
+                                    maybe_append(attrs, extra_attrs));
+            return Ok(Some(item));
+        }
         self.parse_macro_use_or_failure(attrs,macros_allowed,attributes_allowed,lo,visibility)
     }
 
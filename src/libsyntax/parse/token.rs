@@ -194,6 +194,7 @@ impl Token {
             Interpolated(NtIdent(..))   => true,
             Interpolated(NtBlock(..))   => true,
             Interpolated(NtPath(..))    => true,
+            Interpolated(NtLiteral(..)) => true,
             Pound                       => true, // for expression attributes
             _                           => false,
         }
@@ -330,6 +331,10 @@ pub enum Nonterminal {
     NtMeta(P<ast::MetaItem>),
     NtPath(Box<ast::Path>),
     NtTT(P<tokenstream::TokenTree>), // needs P'ed to break a circularity
+    NtVis(ast::Visibility),
+    NtLifetime(Box<ast::SpannedIdent>),
+    /// A literal, optionally preceded by `-`
+    NtLiteral(P<ast::Expr>),
     // These are not exposed to macros, but are used by quasiquote.
     NtArm(ast::Arm),
     NtImplItem(P<ast::ImplItem>),
@@ -352,6 +357,9 @@ impl fmt::Debug for Nonterminal {
             NtMeta(..) => f.pad("NtMeta(..)"),
             NtPath(..) => f.pad("NtPath(..)"),
             NtTT(..) => f.pad("NtTT(..)"),
+            NtVis(..) => f.pad("NtVis(..)"),
+            NtLifetime(..) => f.pad("NtLifetime(..)"),
+            NtLiteral(..) => f.pad("NtLiteral(..)"),
             NtArm(..) => f.pad("NtArm(..)"),
             NtImplItem(..) => f.pad("NtImplItem(..)"),
             NtTraitItem(..) => f.pad("NtTraitItem(..)"),
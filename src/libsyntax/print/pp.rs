@@ -179,7 +179,8 @@ pub fn mk_printer<'a>(out: Box<io::Write+'a>, linewidth: usize) -> Printer<'a> {
         right_total: 0,
         scan_stack: scan_stack,
         print_stack: Vec::new(),
-        pending_indentation: 0
+        pending_indentation: 0,
+        track_printed: false,
     }
 }
 
@@ -288,6 +289,9 @@ pub struct Printer<'a> {
     print_stack: Vec<PrintStackElem> ,
     /// Buffered indentation to avoid writing trailing whitespace
     pending_indentation: isize,
+    /// Whether tokens printed without being buffered are still reported by
+    /// `last_token`, see `track_printed_tokens`
+    track_printed: bool,
 }
 
 impl<'a> Printer<'a> {
@@ -298,6 +302,21 @@ impl<'a> Printer<'a> {
     pub fn replace_last_token(&mut self, t: Token) {
         self.token[self.right] = t;
     }
+    /// Makes `last_token` report the last token even when it was printed
+    /// straight away rather than buffered. The source formatter needs this
+    /// to tell where a line ends; the other pretty modes do not ask for it,
+    /// to keep their output as it is.
+    pub fn track_printed_tokens(&mut self) {
+        self.track_printed = true;
+    }
+    /// Turns the last token, a break that has not been printed yet, into a
+    /// hard break with the same offset.
+    pub fn harden_last_break(&mut self) {
+        if let Token::Break(b) = self.token[self.right].clone() {
+            self.token[self.right] = hardbreak_tok_offset(b.offset);
+            self.right_total += SIZE_INFINITY - b.blank_space;
+        }
+    }
     pub fn pretty_print(&mut self, token: Token) -> io::Result<()> {
         debug!("pp Vec<{},{}>", self.left, self.right);
         match token {
@@ -327,6 +346,10 @@ impl<'a> Printer<'a> {
           Token::End => {
             if self.scan_stack.is_empty() {
                 debug!("pp End/print Vec<{},{}>", self.left, self.right);
+                if self.track_printed {
+                    // Nothing is buffered, so the slot only serves `last_token`.
+                    self.token[self.right] = token.clone();
+                }
                 self.print(token, 0)
             } else {
                 debug!("pp End/buffer Vec<{},{}>", self.left, self.right);
@@ -359,6 +382,9 @@ impl<'a> Printer<'a> {
             if self.scan_stack.is_empty() {
                 debug!("pp String('{}')/print Vec<{},{}>",
                        s, self.left, self.right);
+                if self.track_printed {
+                    self.token[self.right] = Token::String(s.clone(), len);
+                }
                 self.print(Token::String(s, len), len)
             } else {
                 debug!("pp String('{}')/buffer Vec<{},{}>",
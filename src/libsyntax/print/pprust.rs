@@ -31,6 +31,7 @@ use std_inject;
 use tokenstream::{self, TokenTree};
 
 use std::ascii;
+use std::cmp;
 use std::io::{self, Write, Read};
 use std::iter;
 
@@ -68,6 +69,10 @@ pub struct State<'a> {
     cur_cmnt_and_lit: CurrentCommentAndLiteral,
     boxes: Vec<pp::Breaks>,
     ann: &'a (PpAnn+'a),
+    /// Whether source is being formatted (see `format_crate`). Modules
+    /// loaded from other files are then printed as `mod foo;` rather than
+    /// inline, and comments and blank lines keep the lines they had.
+    formatting: bool,
 }
 
 pub fn rust_printer<'a>(writer: Box<Write+'a>) -> State<'a> {
@@ -88,6 +93,7 @@ pub fn rust_printer_annotated<'a>(writer: Box<Write+'a>,
         },
         boxes: Vec::new(),
         ann: ann,
+        formatting: false,
     }
 }
 
@@ -137,6 +143,32 @@ pub fn print_crate<'a>(cm: &'a CodeMap,
     eof(&mut s.s)
 }
 
+/// Formats the source of an unexpanded crate, wrapping lines at `width`
+/// columns (`--unpretty format`).
+///
+/// Like `print_crate`, this copies comments, blank lines and literals
+/// forward from `input`. Unlike `print_crate`, modules loaded from other
+/// files are kept as `mod foo;` declarations, so the output can replace
+/// the original file.
+pub fn format_crate<'a>(cm: &'a CodeMap,
+                        span_diagnostic: &errors::Handler,
+                        krate: &ast::Crate,
+                        filename: String,
+                        input: &mut Read,
+                        out: Box<Write+'a>,
+                        width: usize) -> io::Result<()> {
+    static NO_ANN: NoAnn = NoAnn;
+    let (cmnts, lits) = comments::gather_comments_and_literals(span_diagnostic,
+                                                               filename,
+                                                               input);
+    let mut s = State::new_with_width(cm, out, &NO_ANN, Some(cmnts), Some(lits), width);
+    s.formatting = true;
+    s.s.track_printed_tokens();
+    try!(s.print_mod(&krate.module, &krate.attrs));
+    try!(s.print_remaining_comments());
+    eof(&mut s.s)
+}
+
 impl<'a> State<'a> {
     pub fn new_from_input(cm: &'a CodeMap,
                           span_diagnostic: &errors::Handler,
@@ -166,8 +198,19 @@ impl<'a> State<'a> {
                ann: &'a PpAnn,
                comments: Option<Vec<comments::Comment>>,
                literals: Option<Vec<comments::Literal>>) -> State<'a> {
+        State::new_with_width(cm, out, ann, comments, literals, DEFAULT_COLUMNS)
+    }
+
+    /// Like `new`, but wraps lines at `width` columns rather than at
+    /// `DEFAULT_COLUMNS`.
+    pub fn new_with_width(cm: &'a CodeMap,
+                          out: Box<Write+'a>,
+                          ann: &'a PpAnn,
+                          comments: Option<Vec<comments::Comment>>,
+                          literals: Option<Vec<comments::Literal>>,
+                          width: usize) -> State<'a> {
         State {
-            s: pp::mk_printer(out, DEFAULT_COLUMNS),
+            s: pp::mk_printer(out, width),
             cm: Some(cm),
             comments: comments,
             literals: literals,
@@ -177,6 +220,7 @@ impl<'a> State<'a> {
             },
             boxes: Vec::new(),
             ann: ann,
+            formatting: false,
         }
     }
 }
@@ -299,6 +343,9 @@ pub fn token_to_string(tok: &Token) -> String {
             token::NtPat(ref e)         => pat_to_string(&e),
             token::NtIdent(ref e)       => ident_to_string(e.node),
             token::NtTT(ref e)          => tt_to_string(&e),
+            token::NtVis(ref e)         => vis_to_string(&e),
+            token::NtLifetime(ref e)    => e.node.to_string(),
+            token::NtLiteral(ref e)     => expr_to_string(&e),
             token::NtArm(ref e)         => arm_to_string(&e),
             token::NtImplItem(ref e)    => impl_item_to_string(&e),
             token::NtTraitItem(ref e)   => trait_item_to_string(&e),
@@ -430,6 +477,10 @@ pub fn mac_to_string(arg: &ast::Mac) -> String {
     to_string(|s| s.print_mac(arg, ::parse::token::Paren))
 }
 
+pub fn vis_to_string(vis: &ast::Visibility) -> String {
+    to_string(|s| s.print_visibility(vis))
+}
+
 pub fn visibility_qualified(vis: &ast::Visibility, s: &str) -> String {
     match *vis {
         ast::Visibility::Public => format!("pub {}", s),
@@ -456,6 +507,10 @@ pub trait PrintState<'a> {
     fn cur_cmnt_and_lit(&mut self) -> &mut CurrentCommentAndLiteral;
     fn literals(&self) -> &Option<Vec<comments::Literal>>;
 
+    /// Whether source is being formatted, which changes how lines are ended
+    /// around comments.
+    fn formatting(&self) -> bool { false }
+
     fn word_space(&mut self, w: &str) -> io::Result<()> {
         try!(word(self.writer(), w));
         space(self.writer())
@@ -491,6 +546,20 @@ pub trait PrintState<'a> {
         Ok(())
     }
 
+    // Ends the current line, for a comment or blank line that has lines of its
+    // own, when formatting. A pending break is made hard rather than followed by
+    // a hardbreak, which would otherwise leave an extra empty line behind it.
+    fn end_line_for_comment(&mut self) -> io::Result<()> {
+        match self.writer().last_token() {
+            pp::Token::Eof => Ok(()),
+            pp::Token::Break(_) => {
+                self.writer().harden_last_break();
+                Ok(())
+            }
+            _ => hardbreak(self.writer()),
+        }
+    }
+
     // "raw box"
     fn rbox(&mut self, u: usize, b: pp::Breaks) -> io::Result<()> {
         self.boxes().push(b);
@@ -566,7 +635,11 @@ pub trait PrintState<'a> {
                 zerobreak(self.writer())
             }
             comments::Isolated => {
-                try!(self.hardbreak_if_not_bol());
+                if self.formatting() {
+                    try!(self.end_line_for_comment());
+                } else {
+                    try!(self.hardbreak_if_not_bol());
+                }
                 for line in &cmnt.lines {
                     // Don't print empty lines because they will end up as trailing
                     // whitespace
@@ -594,13 +667,19 @@ pub trait PrintState<'a> {
                 }
             }
             comments::BlankLine => {
-                // We need to do at least one, possibly two hardbreaks.
-                let is_semi = match self.writer().last_token() {
-                    pp::Token::String(s, _) => ";" == s,
-                    _ => false
-                };
-                if is_semi || self.is_begin() || self.is_end() {
-                    try!(hardbreak(self.writer()));
+                // We need to do at least one, possibly two hardbreaks: one to
+                // end the current line, unless it is empty, and one for the
+                // blank line itself.
+                if self.formatting() {
+                    try!(self.end_line_for_comment());
+                } else {
+                    let is_semi = match self.writer().last_token() {
+                        pp::Token::String(s, _) => ";" == s,
+                        _ => false
+                    };
+                    if is_semi || self.is_begin() || self.is_end() {
+                        try!(hardbreak(self.writer()));
+                    }
                 }
                 hardbreak(self.writer())
             }
@@ -815,6 +894,10 @@ impl<'a> PrintState<'a> for State<'a> {
     fn literals(&self) -> &Option<Vec<comments::Literal>> {
         &self.literals
     }
+
+    fn formatting(&self) -> bool {
+        self.formatting
+    }
 }
 
 impl<'a> State<'a> {
@@ -985,6 +1068,9 @@ impl<'a> State<'a> {
                 }
                 try!(self.pclose());
             }
+            ast::TyKind::Never => {
+                try!(word(&mut self.s, "!"));
+            }
             ast::TyKind::Paren(ref typ) => {
                 try!(self.popen());
                 try!(self.print_type(&typ));
@@ -1018,6 +1104,9 @@ impl<'a> State<'a> {
             ast::TyKind::PolyTraitRef(ref bounds) => {
                 try!(self.print_bounds("", &bounds[..]));
             }
+            ast::TyKind::ImplTrait(ref bounds) => {
+                try!(self.print_bounds("impl", &bounds[..]));
+            }
             ast::TyKind::FixedLengthVec(ref ty, ref v) => {
                 try!(word(&mut self.s, "["));
                 try!(self.print_type(&ty));
@@ -1030,6 +1119,9 @@ impl<'a> State<'a> {
                 try!(self.print_expr(&e));
                 try!(word(&mut self.s, ")"));
             }
+            ast::TyKind::ConstArg(ref e) => {
+                try!(self.print_expr(&e));
+            }
             ast::TyKind::Infer => {
                 try!(word(&mut self.s, "_"));
             }
@@ -1096,14 +1188,17 @@ impl<'a> State<'a> {
 
     fn print_associated_type(&mut self,
                              ident: ast::Ident,
+                             generics: &ast::Generics,
                              bounds: Option<&ast::TyParamBounds>,
                              ty: Option<&ast::Ty>)
                              -> io::Result<()> {
         try!(self.word_space("type"));
         try!(self.print_ident(ident));
+        try!(self.print_generics(generics));
         if let Some(bounds) = bounds {
             try!(self.print_bounds(":", bounds));
         }
+        try!(self.print_where_clause(&generics.where_clause));
         if let Some(ty) = ty {
             try!(space(&mut self.s));
             try!(self.word_space("="));
@@ -1190,10 +1285,16 @@ impl<'a> State<'a> {
             ast::ItemKind::Mod(ref _mod) => {
                 try!(self.head(&visibility_qualified(&item.vis, "mod")));
                 try!(self.print_ident(item.ident));
-                try!(self.nbsp());
-                try!(self.bopen());
-                try!(self.print_mod(_mod, &item.attrs));
-                try!(self.bclose(item.span));
+                if self.formatting && !item.span.contains(_mod.inner) {
+                    try!(word(&mut self.s, ";"));
+                    try!(self.end()); // end inner head-block
+                    try!(self.end()); // end outer head-block
+                } else {
+                    try!(self.nbsp());
+                    try!(self.bopen());
+                    try!(self.print_mod(_mod, &item.attrs));
+                    try!(self.bclose(item.span));
+                }
             }
             ast::ItemKind::ForeignMod(ref nmod) => {
                 try!(self.head("extern"));
@@ -1230,6 +1331,10 @@ impl<'a> State<'a> {
                 try!(self.head(&visibility_qualified(&item.vis, "struct")));
                 try!(self.print_struct(&struct_def, generics, item.ident, item.span, true));
             }
+            ast::ItemKind::Union(ref struct_def, ref generics) => {
+                try!(self.head(&visibility_qualified(&item.vis, "union")));
+                try!(self.print_struct(&struct_def, generics, item.ident, item.span, true));
+            }
 
             ast::ItemKind::DefaultImpl(unsafety, ref trait_ref) => {
                 try!(self.head(""));
@@ -1245,12 +1350,16 @@ impl<'a> State<'a> {
             }
             ast::ItemKind::Impl(unsafety,
                           polarity,
+                          defaultness,
                           ref generics,
                           ref opt_trait,
                           ref ty,
                           ref impl_items) => {
                 try!(self.head(""));
                 try!(self.print_visibility(&item.vis));
+                if let ast::Defaultness::Default = defaultness {
+                    try!(self.word_nbsp("default"));
+                }
                 try!(self.print_unsafety(unsafety));
                 try!(self.word_nbsp("impl"));
 
@@ -1447,7 +1556,8 @@ impl<'a> State<'a> {
     /// expression arguments as expressions). It can be done! I think.
     pub fn print_tt(&mut self, tt: &tokenstream::TokenTree) -> io::Result<()> {
         match *tt {
-            TokenTree::Token(_, ref tk) => {
+            TokenTree::Token(sp, ref tk) => {
+                try!(self.maybe_print_comment(sp.lo));
                 try!(word(&mut self.s, &token_to_string(tk)));
                 match *tk {
                     parse::token::DocComment(..) => {
@@ -1457,10 +1567,12 @@ impl<'a> State<'a> {
                 }
             }
             TokenTree::Delimited(_, ref delimed) => {
+                try!(self.maybe_print_comment(delimed.open_span.lo));
                 try!(word(&mut self.s, &token_to_string(&delimed.open_token())));
                 try!(space(&mut self.s));
                 try!(self.print_tts(&delimed.tts));
                 try!(space(&mut self.s));
+                try!(self.maybe_print_comment(delimed.close_span.lo));
                 word(&mut self.s, &token_to_string(&delimed.close_token()))
             },
             TokenTree::Sequence(_, ref seq) => {
@@ -1543,8 +1655,8 @@ impl<'a> State<'a> {
                     try!(word(&mut self.s, ";"));
                 }
             }
-            ast::TraitItemKind::Type(ref bounds, ref default) => {
-                try!(self.print_associated_type(ti.ident, Some(bounds),
+            ast::TraitItemKind::Type(ref generics, ref bounds, ref default) => {
+                try!(self.print_associated_type(ti.ident, generics, Some(bounds),
                                            default.as_ref().map(|ty| &**ty)));
             }
             ast::TraitItemKind::Macro(codemap::Spanned { ref node, .. }) => {
@@ -1580,8 +1692,8 @@ impl<'a> State<'a> {
                 try!(self.nbsp());
                 try!(self.print_block_with_attrs(body, &ii.attrs));
             }
-            ast::ImplItemKind::Type(ref ty) => {
-                try!(self.print_associated_type(ii.ident, None, Some(ty)));
+            ast::ImplItemKind::Type(ref generics, ref ty) => {
+                try!(self.print_associated_type(ii.ident, generics, None, Some(ty)));
             }
             ast::ImplItemKind::Macro(codemap::Spanned { ref node, .. }) => {
                 // code copied from ItemKind::Mac:
@@ -1782,6 +1894,9 @@ impl<'a> State<'a> {
             token::NoDelim => {}
         }
         try!(self.print_tts(&m.node.tts));
+        if delim != token::Brace {
+            try!(self.maybe_print_comment(m.span.hi));
+        }
         match delim {
             token::Paren => self.pclose(),
             token::Bracket => word(&mut self.s, "]"),
@@ -1791,9 +1906,11 @@ impl<'a> State<'a> {
     }
 
 
-    fn print_call_post(&mut self, args: &[P<ast::Expr>]) -> io::Result<()> {
+    fn print_call_post(&mut self, args: &[P<ast::Expr>],
+                       span: syntax_pos::Span) -> io::Result<()> {
         try!(self.popen());
         try!(self.commasep_exprs(Inconsistent, args));
+        try!(self.maybe_print_comment(span.hi));
         self.pclose()
     }
 
@@ -1834,11 +1951,13 @@ impl<'a> State<'a> {
     }
 
     fn print_expr_vec(&mut self, exprs: &[P<ast::Expr>],
-                      attrs: &[Attribute]) -> io::Result<()> {
+                      attrs: &[Attribute],
+                      span: syntax_pos::Span) -> io::Result<()> {
         try!(self.ibox(INDENT_UNIT));
         try!(word(&mut self.s, "["));
         try!(self.print_inner_attributes_inline(attrs));
         try!(self.commasep_exprs(Inconsistent, &exprs[..]));
+        try!(self.maybe_print_comment(span.hi));
         try!(word(&mut self.s, "]"));
         self.end()
     }
@@ -1861,7 +1980,8 @@ impl<'a> State<'a> {
                          path: &ast::Path,
                          fields: &[ast::Field],
                          wth: &Option<P<ast::Expr>>,
-                         attrs: &[Attribute]) -> io::Result<()> {
+                         attrs: &[Attribute],
+                         span: syntax_pos::Span) -> io::Result<()> {
         try!(self.print_path(path, true, 0));
         try!(word(&mut self.s, "{"));
         try!(self.print_inner_attributes_inline(attrs));
@@ -1891,32 +2011,37 @@ impl<'a> State<'a> {
                 try!(word(&mut self.s, ","))
             }
         }
+        try!(self.maybe_print_comment(span.hi));
         try!(word(&mut self.s, "}"));
         Ok(())
     }
 
     fn print_expr_tup(&mut self, exprs: &[P<ast::Expr>],
-                      attrs: &[Attribute]) -> io::Result<()> {
+                      attrs: &[Attribute],
+                      span: syntax_pos::Span) -> io::Result<()> {
         try!(self.popen());
         try!(self.print_inner_attributes_inline(attrs));
         try!(self.commasep_exprs(Inconsistent, &exprs[..]));
         if exprs.len() == 1 {
             try!(word(&mut self.s, ","));
         }
+        try!(self.maybe_print_comment(span.hi));
         self.pclose()
     }
 
     fn print_expr_call(&mut self,
                        func: &ast::Expr,
-                       args: &[P<ast::Expr>]) -> io::Result<()> {
+                       args: &[P<ast::Expr>],
+                       span: syntax_pos::Span) -> io::Result<()> {
         try!(self.print_expr_maybe_paren(func));
-        self.print_call_post(args)
+        self.print_call_post(args, span)
     }
 
     fn print_expr_method_call(&mut self,
                               ident: ast::SpannedIdent,
                               tys: &[P<ast::Ty>],
-                              args: &[P<ast::Expr>]) -> io::Result<()> {
+                              args: &[P<ast::Expr>],
+                              span: syntax_pos::Span) -> io::Result<()> {
         let base_args = &args[1..];
         try!(self.print_expr(&args[0]));
         try!(word(&mut self.s, "."));
@@ -1927,7 +2052,7 @@ impl<'a> State<'a> {
                           |s, ty| s.print_type(&ty)));
             try!(word(&mut self.s, ">"));
         }
-        self.print_call_post(base_args)
+        self.print_call_post(base_args, span)
     }
 
     fn print_expr_binary(&mut self,
@@ -1990,22 +2115,22 @@ impl<'a> State<'a> {
                 try!(self.print_expr_in_place(place, expr));
             }
             ast::ExprKind::Vec(ref exprs) => {
-                try!(self.print_expr_vec(&exprs[..], attrs));
+                try!(self.print_expr_vec(&exprs[..], attrs, expr.span));
             }
             ast::ExprKind::Repeat(ref element, ref count) => {
                 try!(self.print_expr_repeat(&element, &count, attrs));
             }
             ast::ExprKind::Struct(ref path, ref fields, ref wth) => {
-                try!(self.print_expr_struct(path, &fields[..], wth, attrs));
+                try!(self.print_expr_struct(path, &fields[..], wth, attrs, expr.span));
             }
             ast::ExprKind::Tup(ref exprs) => {
-                try!(self.print_expr_tup(&exprs[..], attrs));
+                try!(self.print_expr_tup(&exprs[..], attrs, expr.span));
             }
             ast::ExprKind::Call(ref func, ref args) => {
-                try!(self.print_expr_call(&func, &args[..]));
+                try!(self.print_expr_call(&func, &args[..], expr.span));
             }
             ast::ExprKind::MethodCall(ident, ref tys, ref args) => {
-                try!(self.print_expr_method_call(ident, &tys[..], &args[..]));
+                try!(self.print_expr_method_call(ident, &tys[..], &args[..], expr.span));
             }
             ast::ExprKind::Binary(op, ref lhs, ref rhs) => {
                 try!(self.print_expr_binary(op, &lhs, &rhs));
@@ -2085,11 +2210,11 @@ impl<'a> State<'a> {
                 try!(space(&mut self.s));
                 try!(self.print_block_with_attrs(&blk, attrs));
             }
-            ast::ExprKind::Match(ref expr, ref arms) => {
+            ast::ExprKind::Match(ref scrutinee, ref arms) => {
                 try!(self.cbox(INDENT_UNIT));
                 try!(self.ibox(4));
                 try!(self.word_nbsp("match"));
-                try!(self.print_expr(&expr));
+                try!(self.print_expr(&scrutinee));
                 try!(space(&mut self.s));
                 try!(self.bopen());
                 try!(self.print_inner_attributes_no_trailing_hardbreak(attrs));
@@ -2286,6 +2411,9 @@ impl<'a> State<'a> {
                 try!(self.print_expr(e));
                 try!(word(&mut self.s, "?"))
             }
+            ast::ExprKind::Err => {
+                try!(word(&mut self.s, "(/*ERROR*/)"))
+            }
         }
         try!(self.ann.post(self, NodeExpr(expr)));
         self.end()
@@ -2576,7 +2704,10 @@ impl<'a> State<'a> {
         // I have no idea why this check is necessary, but here it
         // is :(
         if arm.attrs.is_empty() {
-            try!(space(&mut self.s));
+            // Print the comments before the arm here rather than with its
+            // pattern, so that they are not indented as part of the arm.
+            try!(self.maybe_print_comment(arm.pats[0].span.lo));
+            try!(self.space_if_not_bol());
         }
         try!(self.cbox(INDENT_UNIT));
         try!(self.ibox(0));
@@ -2661,10 +2792,12 @@ impl<'a> State<'a> {
     pub fn print_fn_args_and_ret(&mut self, decl: &ast::FnDecl)
         -> io::Result<()> {
         try!(self.popen());
-        try!(self.commasep(Inconsistent, &decl.inputs, |s, arg| s.print_arg(arg, false)));
+        try!(self.commasep_cmnt(Inconsistent, &decl.inputs,
+                                |s, arg| s.print_arg(arg, false), arg_span));
         if decl.variadic {
             try!(word(&mut self.s, ", ..."));
         }
+        try!(self.maybe_print_comment(decl.output.span().lo));
         try!(self.pclose());
 
         self.print_fn_output(decl)
@@ -2675,7 +2808,8 @@ impl<'a> State<'a> {
             decl: &ast::FnDecl)
             -> io::Result<()> {
         try!(word(&mut self.s, "|"));
-        try!(self.commasep(Inconsistent, &decl.inputs, |s, arg| s.print_arg(arg, true)));
+        try!(self.commasep_cmnt(Inconsistent, &decl.inputs,
+                                |s, arg| s.print_arg(arg, true), arg_span));
         try!(word(&mut self.s, "|"));
 
         if let ast::FunctionRetTy::Default(..) = decl.output {
@@ -2780,7 +2914,7 @@ impl<'a> State<'a> {
             ints.push(i);
         }
 
-        try!(self.commasep(Inconsistent, &ints[..], |s, &idx| {
+        try!(self.commasep_cmnt(Inconsistent, &ints[..], |s, &idx| {
             if idx < generics.lifetimes.len() {
                 let lifetime_def = &generics.lifetimes[idx];
                 s.print_lifetime_bounds(&lifetime_def.lifetime, &lifetime_def.bounds)
@@ -2789,6 +2923,12 @@ impl<'a> State<'a> {
                 let param = &generics.ty_params[idx];
                 s.print_ty_param(param)
             }
+        }, |&idx| {
+            if idx < generics.lifetimes.len() {
+                generics.lifetimes[idx].lifetime.span
+            } else {
+                generics.ty_params[idx - generics.lifetimes.len()].span
+            }
         }));
 
         try!(word(&mut self.s, ">"));
@@ -2796,6 +2936,12 @@ impl<'a> State<'a> {
     }
 
     pub fn print_ty_param(&mut self, param: &ast::TyParam) -> io::Result<()> {
+        if let Some(ref const_ty) = param.const_ty {
+            try!(self.word_nbsp("const"));
+            try!(self.print_ident(param.ident));
+            try!(self.word_space(":"));
+            return self.print_type(&const_ty);
+        }
         try!(self.print_ident(param.ident));
         try!(self.print_bounds(":", &param.bounds));
         match param.default {
@@ -2819,8 +2965,12 @@ impl<'a> State<'a> {
 
         for (i, predicate) in where_clause.predicates.iter().enumerate() {
             if i != 0 {
-                try!(self.word_space(","));
+                try!(word(&mut self.s, ","));
+                let prev = where_clause.predicates[i - 1].span();
+                try!(self.maybe_print_trailing_comment(prev, Some(predicate.span().hi)));
+                try!(self.space_if_not_bol());
             }
+            try!(self.maybe_print_comment(predicate.span().lo));
 
             match *predicate {
                 ast::WherePredicate::BoundPredicate(ast::WhereBoundPredicate{ref bound_lifetimes,
@@ -2875,7 +3025,7 @@ impl<'a> State<'a> {
                     try!(self.print_path(path, false, 0));
                     try!(word(&mut self.s, "::{"));
                 }
-                try!(self.commasep(Inconsistent, &idents[..], |s, w| {
+                try!(self.commasep_cmnt(Inconsistent, &idents[..], |s, w| {
                     match w.node {
                         ast::PathListItemKind::Ident { name, rename, .. } => {
                             try!(s.print_ident(name));
@@ -2896,7 +3046,8 @@ impl<'a> State<'a> {
                             Ok(())
                         }
                     }
-                }));
+                }, |w| w.span));
+                try!(self.maybe_print_comment(vp.span.hi));
                 word(&mut self.s, "}")
             }
         }
@@ -3091,6 +3242,12 @@ impl<'a> State<'a> {
 
 fn repeat(s: &str, n: usize) -> String { iter::repeat(s).take(n).collect() }
 
+// The type of `self` arguments has a dummy span, so this only extends the span of
+// the pattern when the type comes after it.
+fn arg_span(arg: &ast::Arg) -> syntax_pos::Span {
+    syntax_pos::mk_sp(arg.pat.span.lo, cmp::max(arg.pat.span.hi, arg.ty.span.hi))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
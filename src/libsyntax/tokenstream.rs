@@ -30,11 +30,13 @@ use parse::lexer;
 use parse;
 use parse::token::{self, Token, Lit, InternedString, Nonterminal};
 use parse::token::Lit as TokLit;
+use print::pprust;
 
 use std::fmt;
 use std::mem;
 use std::ops::Index;
 use std::ops;
+use std::str;
 use std::iter::*;
 
 use std::rc::Rc;
@@ -253,6 +255,28 @@ impl TokenTree {
         }
     }
 
+    /// Applies `f` to the spans of this token tree and of all the trees nested in it.
+    pub fn map_spans<F: FnMut(Span) -> Span>(&self, f: &mut F) -> TokenTree {
+        match *self {
+            TokenTree::Token(sp, ref tok) => TokenTree::Token(f(sp), tok.clone()),
+            TokenTree::Delimited(sp, ref delimed) => {
+                TokenTree::Delimited(f(sp), Rc::new(Delimited {
+                    delim: delimed.delim,
+                    open_span: f(delimed.open_span),
+                    tts: delimed.tts.iter().map(|tt| tt.map_spans(f)).collect(),
+                    close_span: f(delimed.close_span),
+                }))
+            }
+            TokenTree::Sequence(sp, ref seq) => {
+                TokenTree::Sequence(f(sp), Rc::new(SequenceRepetition {
+                    tts: seq.tts.iter().map(|tt| tt.map_spans(f)).collect(),
+                    separator: seq.separator.clone(),
+                    ..**seq
+                }))
+            }
+        }
+    }
+
     /// Indicates if the stream is a token that is equal to the provided token.
     pub fn eq_token(&self, t: Token) -> bool {
         match *self {
@@ -355,6 +379,40 @@ impl fmt::Debug for TokenStream {
     }
 }
 
+/// Prints the tokens of the stream as source code.
+impl fmt::Display for TokenStream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&pprust::tts_to_string(&self.tts))
+    }
+}
+
+/// Lexes source code into a TokenStream. The tokens are lexed in a session of
+/// their own, so they are given `DUMMY_SP` rather than spans that do not point
+/// into the crate being compiled. Procedural macros give such tokens the span
+/// of the macro invocation.
+impl str::FromStr for TokenStream {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<TokenStream, String> {
+        let sess = parse::ParseSess::new();
+        let tts = parse::parse_tts_from_source_str("<token stream>".to_string(),
+                                                   src.to_string(),
+                                                   Vec::new(),
+                                                   &sess);
+        match tts {
+            Ok(tts) => {
+                let tts = tts.iter().map(|tt| tt.map_spans(&mut |_| DUMMY_SP)).collect();
+                Ok(TokenStream::from_tts(tts))
+            }
+            Err(mut err) => {
+                let msg = err.message().to_string();
+                err.cancel();
+                Err(msg)
+            }
+        }
+    }
+}
+
 /// Checks if two TokenStreams are equivalent (including spans). For unspanned
 /// equality, see `eq_unspanned`.
 impl PartialEq<TokenStream> for TokenStream {
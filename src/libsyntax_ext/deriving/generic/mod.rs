@@ -484,7 +484,8 @@ impl<'a> TraitDef<'a> {
                 vis: ast::Visibility::Inherited,
                 defaultness: ast::Defaultness::Final,
                 attrs: Vec::new(),
-                node: ast::ImplItemKind::Type(type_def.to_ty(cx, self.span, type_ident, generics)),
+                node: ast::ImplItemKind::Type(ast::Generics::default(),
+                                              type_def.to_ty(cx, self.span, type_ident, generics)),
             }
         });
 
@@ -497,6 +498,11 @@ impl<'a> TraitDef<'a> {
 
         // Create the type parameters.
         ty_params.extend(generics.ty_params.iter().map(|ty_param| {
+            // `const` parameters are values, so they cannot carry trait bounds
+            if ty_param.const_ty.is_some() {
+                return ty_param.clone();
+            }
+
             // I don't think this can be moved out of the loop, since
             // a TyParamBound requires an ast id
             let mut bounds: Vec<_> =
@@ -639,6 +645,7 @@ impl<'a> TraitDef<'a> {
                 a,
                 ast::ItemKind::Impl(unsafety,
                                     ast::ImplPolarity::Positive,
+                                    ast::Defaultness::Final,
                                     trait_generics,
                                     opt_trait_ref,
                                     self_type,
@@ -752,7 +759,8 @@ fn find_repr_type_name(diagnostic: &Handler, type_attrs: &[ast::Attribute]) -> &
     for a in type_attrs {
         for r in &attr::find_repr_attrs(diagnostic, a) {
             repr_type_name = match *r {
-                attr::ReprAny | attr::ReprPacked | attr::ReprSimd => continue,
+                attr::ReprAny | attr::ReprPacked(_) | attr::ReprAlign(_) |
+                attr::ReprSimd => continue,
                 attr::ReprExtern => "i32",
 
                 attr::ReprInt(_, attr::SignedInt(ast::IntTy::Is)) => "isize",
@@ -12,7 +12,7 @@
 
 use syntax::ast::{self, MetaItem, MetaItemKind};
 use syntax::attr::AttrMetaMethods;
-use syntax::ext::base::{Annotatable, ExtCtxt, SyntaxEnv};
+use syntax::ext::base::{Annotatable, CustomDerive, ExtCtxt, SyntaxEnv};
 use syntax::ext::base::{MultiDecorator, MultiItemDecorator, MultiModifier};
 use syntax::ext::build::AstBuilder;
 use syntax::feature_gate;
@@ -106,7 +106,8 @@ fn expand_derive(cx: &mut ExtCtxt,
                     }
                 };
 
-                if !(is_builtin_trait(tname) || cx.ecfg.enable_custom_derive()) {
+                if !(is_builtin_trait(tname) || is_proc_macro_derive(cx, tname) ||
+                     cx.ecfg.enable_custom_derive()) {
                     feature_gate::emit_feature_err(&cx.parse_sess.span_diagnostic,
                                                    "custom_derive",
                                                    titem.span,
@@ -160,6 +161,19 @@ fn expand_derive(cx: &mut ExtCtxt,
     annot
 }
 
+/// Whether `#[derive(tname)]` refers to a custom derive defined as a
+/// procedural macro, which is gated on importing it rather than on
+/// `custom_derive`.
+fn is_proc_macro_derive(cx: &ExtCtxt, tname: &str) -> bool {
+    match cx.syntax_env.find(intern(&format!("derive_{}", tname))) {
+        Some(ext) => match *ext {
+            CustomDerive(..) => true,
+            _ => false,
+        },
+        None => false,
+    }
+}
+
 macro_rules! derive_traits {
     ($( $name:expr => $func:path, )+) => {
         pub fn register_all(env: &mut SyntaxEnv) {
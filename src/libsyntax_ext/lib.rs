@@ -46,6 +46,8 @@ mod trace_macros;
 // for custom_derive
 pub mod deriving;
 
+pub mod proc_macro_registrar;
+
 pub fn register_builtins(env: &mut SyntaxEnv) {
     // utility function to simplify creating NormalTT syntax extensions
     fn builtin_normal_expander(f: MacroExpanderFn) -> SyntaxExtension {
@@ -0,0 +1,218 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Generates the plugin registrar of a crate defining procedural macros with
+//! `#[proc_macro]`, `#[proc_macro_attribute]` and `#[proc_macro_derive]`, so
+//! that such a crate needs neither a `#[plugin_registrar]` of its own nor
+//! `rustc_private`.
+//!
+//! For a crate defining `#[proc_macro] fn sum`,
+//! `#[proc_macro_attribute] fn rename` and
+//! `#[proc_macro_derive(Answer)] fn derive_answer`, the generated module is:
+//!
+//! ```ignore
+//! #[doc(hidden)]
+//! pub mod __rustc_proc_macro_registrar {
+//!     extern crate rustc_plugin;
+//!
+//!     #[plugin_registrar]
+//!     pub fn registrar(registry: &mut rustc_plugin::Registry) {
+//!         registry.register_proc_macro("sum", ::sum);
+//!         registry.register_attr_proc_macro("rename", ::rename);
+//!         registry.register_derive("Answer", ::derive_answer);
+//!     }
+//! }
+//! ```
+
+use std::mem;
+
+use errors;
+use syntax::ast::{self, Ident};
+use syntax::attr::AttrMetaMethods;
+use syntax::codemap::{ExpnInfo, NameAndSpan, MacroAttribute};
+use syntax::ext::base::{ExtCtxt, DummyMacroLoader};
+use syntax::ext::build::AstBuilder;
+use syntax::ext::expand::ExpansionConfig;
+use syntax::parse::ParseSess;
+use syntax::parse::token::{self, intern, InternedString};
+use syntax::ptr::P;
+use syntax::visit::{self, Visitor};
+use syntax_pos::{Span, DUMMY_SP};
+
+struct ProcMacroDef {
+    /// The method of `rustc_plugin::Registry` that registers the macro.
+    register: &'static str,
+    /// The name of the macro, or of the trait for a custom derive.
+    name: InternedString,
+    function: Ident,
+    span: Span,
+}
+
+struct CollectProcMacros<'a> {
+    defs: Vec<ProcMacroDef>,
+    in_root: bool,
+    handler: &'a errors::Handler,
+}
+
+impl<'a, 'v> Visitor<'v> for CollectProcMacros<'a> {
+    fn visit_item(&mut self, item: &'v ast::Item) {
+        let register = item.attrs.iter().filter_map(|attr| {
+            if attr.check_name("proc_macro") {
+                Some((attr, "register_proc_macro"))
+            } else if attr.check_name("proc_macro_attribute") {
+                Some((attr, "register_attr_proc_macro"))
+            } else if attr.check_name("proc_macro_derive") {
+                Some((attr, "register_derive"))
+            } else {
+                None
+            }
+        }).next();
+
+        if let Some((attr, register)) = register {
+            if let ast::ItemKind::Fn(..) = item.node {
+                if self.in_root {
+                    let name = if register == "register_derive" {
+                        derived_trait(attr, self.handler)
+                    } else {
+                        Some(item.ident.name.as_str())
+                    };
+                    if let Some(name) = name {
+                        self.defs.push(ProcMacroDef {
+                            register: register,
+                            name: name,
+                            function: item.ident,
+                            span: item.span,
+                        });
+                    }
+                } else {
+                    self.handler.span_err(attr.span,
+                                          &format!("functions tagged with `#[{}]` must be \
+                                                    defined at the root of the crate",
+                                                   attr.name()));
+                }
+            } else {
+                self.handler.span_err(attr.span,
+                                      &format!("the `#[{}]` attribute may only be used \
+                                                on functions",
+                                               attr.name()));
+            }
+        }
+
+        let in_root = mem::replace(&mut self.in_root, false);
+        visit::walk_item(self, item);
+        self.in_root = in_root;
+    }
+}
+
+/// The name of the trait in `#[proc_macro_derive(Trait)]`.
+fn derived_trait(attr: &ast::Attribute, handler: &errors::Handler) -> Option<InternedString> {
+    match attr.meta_item_list() {
+        Some(list) if list.len() == 1 => {
+            if let ast::MetaItemKind::Word(ref name) = list[0].node {
+                return Some(name.clone());
+            }
+        }
+        _ => {}
+    }
+    handler.span_err(attr.span, "expected `#[proc_macro_derive(Trait)]`");
+    None
+}
+
+/// Adds the plugin registrar for the procedural macros defined in `krate`,
+/// if there are any.
+pub fn modify(sess: &ParseSess,
+              mut krate: ast::Crate,
+              handler: &errors::Handler) -> ast::Crate {
+    let defs = {
+        let mut collect = CollectProcMacros {
+            defs: Vec::new(),
+            in_root: true,
+            handler: handler,
+        };
+        visit::walk_crate(&mut collect, &krate);
+        collect.defs
+    };
+    if defs.is_empty() {
+        return krate;
+    }
+
+    let mut loader = DummyMacroLoader;
+    let cx = ExtCtxt::new(sess,
+                          krate.config.clone(),
+                          ExpansionConfig::default("proc_macro".to_string()),
+                          &mut loader);
+    let registrar = mk_registrar(&cx, &defs);
+    krate.module.items.push(registrar);
+    krate
+}
+
+fn mk_registrar(cx: &ExtCtxt, defs: &[ProcMacroDef]) -> P<ast::Item> {
+    // Neither `rustc_plugin` nor `#[plugin_registrar]` are stable, so the
+    // registrar gets a span that allows unstable code.
+    let info = ExpnInfo {
+        call_site: DUMMY_SP,
+        callee: NameAndSpan {
+            format: MacroAttribute(intern("proc_macro")),
+            span: None,
+            allow_internal_unstable: true,
+        }
+    };
+    let mut sp = DUMMY_SP;
+    sp.expn_id = cx.codemap().record_expansion(info);
+
+    let rustc_plugin = token::str_to_ident("rustc_plugin");
+    let registry = token::str_to_ident("registry");
+
+    // extern crate rustc_plugin;
+    let extern_crate = cx.item(sp, rustc_plugin, vec![], ast::ItemKind::ExternCrate(None));
+
+    // registry.register_proc_macro("sum", ::sum);
+    let stmts = defs.iter().map(|def| {
+        let name = cx.expr_str(sp, def.name.clone());
+        let function = cx.expr_path(cx.path_global(def.span, vec![def.function]));
+        let call = cx.expr_method_call(sp,
+                                       cx.expr_ident(sp, registry),
+                                       token::str_to_ident(def.register),
+                                       vec![name, function]);
+        cx.stmt_expr(call)
+    }).collect();
+
+    // #[plugin_registrar]
+    // pub fn registrar(registry: &mut rustc_plugin::Registry) { ... }
+    let registry_ty = cx.ty_rptr(sp,
+                                 cx.ty_path(cx.path(sp, vec![rustc_plugin,
+                                                             token::str_to_ident("Registry")])),
+                                 None,
+                                 ast::Mutability::Mutable);
+    let registrar_attr = cx.meta_word(sp, InternedString::new("plugin_registrar"));
+    let registrar_attr = cx.attribute(sp, registrar_attr);
+    let registrar = cx.item_fn(sp,
+                               token::str_to_ident("registrar"),
+                               vec![cx.arg(sp, registry, registry_ty)],
+                               cx.ty(sp, ast::TyKind::Tup(vec![])),
+                               cx.block(sp, stmts)).map(|mut item| {
+        item.attrs.push(registrar_attr);
+        item.vis = ast::Visibility::Public;
+        item
+    });
+
+    // #[doc(hidden)]
+    // pub mod __rustc_proc_macro_registrar { ... }
+    let hidden = cx.meta_word(sp, InternedString::new("hidden"));
+    let doc_hidden = cx.attribute(sp, cx.meta_list(sp, InternedString::new("doc"), vec![hidden]));
+    cx.item_mod(sp,
+                sp,
+                token::gensym_ident("__rustc_proc_macro_registrar"),
+                vec![doc_hidden],
+                vec![extern_crate, registrar]).map(|mut item| {
+        item.vis = ast::Visibility::Public;
+        item
+    })
+}
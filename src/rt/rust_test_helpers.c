@@ -247,3 +247,33 @@ double rust_interesting_average(uint64_t n, ...) {
 int32_t rust_int8_to_int32(int8_t x) {
     return (int32_t)x;
 }
+
+union FloatBits {
+    double d;
+    float f;
+};
+
+double rust_dbg_union_get_double(union FloatBits u) {
+    return u.d;
+}
+
+union FloatBits rust_dbg_union_make_double(double d) {
+    union FloatBits u;
+    u.d = d;
+    return u;
+}
+
+union IntOrFloat {
+    uint32_t i;
+    float f;
+};
+
+uint32_t rust_dbg_union_get_int(union IntOrFloat u) {
+    return u.i;
+}
+
+union IntOrFloat rust_dbg_union_make_int(uint32_t i) {
+    union IntOrFloat u;
+    u.i = i;
+    return u;
+}
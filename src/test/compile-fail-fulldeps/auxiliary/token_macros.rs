@@ -0,0 +1,42 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// force-host
+
+#![feature(proc_macro, proc_macro_lib)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+#[proc_macro]
+pub fn panics(_: TokenStream) -> TokenStream {
+    panic!("`panics!` always panics")
+}
+
+#[proc_macro_attribute]
+pub fn not_items(_: TokenStream, _: TokenStream) -> TokenStream {
+    "1 + 1".parse().unwrap()
+}
+
+#[proc_macro_attribute]
+pub fn keep(_: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+#[proc_macro_derive(Panic)]
+pub fn derive_panic(_: TokenStream) -> TokenStream {
+    panic!("cannot derive `Panic`")
+}
+
+#[proc_macro_derive(NotAnItem)]
+pub fn derive_not_an_item(_: TokenStream) -> TokenStream {
+    "1 + 1".parse().unwrap()
+}
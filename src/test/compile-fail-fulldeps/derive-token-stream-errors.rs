@@ -0,0 +1,25 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:token_macros.rs
+// ignore-stage1
+
+#![feature(proc_macro)]
+
+#[macro_use(Panic, NotAnItem)] #[no_link]
+extern crate token_macros;
+
+#[derive(Panic)] //~ ERROR custom derive `Panic` panicked
+struct A;
+
+#[derive(NotAnItem)] //~ ERROR custom derive `NotAnItem` produced something other than items
+struct B;
+
+fn main() {}
@@ -0,0 +1,34 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-stage1
+
+#![feature(proc_macro_lib)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+#[proc_macro] //~ ERROR procedural macros are experimental
+pub fn foo(input: TokenStream) -> TokenStream {
+    input
+}
+
+#[proc_macro_attribute] //~ ERROR procedural macros are experimental
+pub fn bar(_: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+#[proc_macro_derive(Baz)] //~ ERROR procedural macros are experimental
+pub fn baz(input: TokenStream) -> TokenStream {
+    input
+}
+
+fn main() {}
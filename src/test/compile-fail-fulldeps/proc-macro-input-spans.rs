@@ -0,0 +1,39 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// aux-build:token_macros.rs
+// ignore-stage1
+
+// Errors in the output of a procedural macro that hands its input back point
+// into this file.
+
+#![feature(proc_macro)]
+
+#[macro_use] #[no_link]
+extern crate token_macros;
+
+#[keep]
+fn foo() -> u32 {
+    "not a number" //~ ERROR mismatched types
+}
+
+#[keep(with, arguments)]
+/// Outer attributes are handed over too.
+struct S {
+    #[allow(dead_code)]
+    x: u32,
+}
+
+fn bar() -> S {
+    S { x: 1, y: 2 } //~ ERROR structure `S` has no field named `y`
+}
+
+fn main() {}
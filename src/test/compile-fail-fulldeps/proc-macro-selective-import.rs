@@ -0,0 +1,22 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:token_macros.rs
+// ignore-stage1
+
+#![feature(proc_macro)]
+
+#[macro_use(panics, missing)] #[no_link] //~ ERROR imported macro not found
+extern crate token_macros;
+
+#[keep] //~ ERROR The attribute `keep` is currently unknown to the compiler
+fn foo() {}
+
+fn main() {}
@@ -0,0 +1,36 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(const_generics)]
+
+struct Buf<const N: usize> {
+    data: [u8; N],
+}
+
+struct Wrapper<T> {
+    value: T,
+}
+
+struct Signed<const N: i32>; //~ ERROR E0574
+
+struct NotConst<T> {
+    value: T,
+    data: [T; T], //~ ERROR E0576
+}
+
+fn size_of_value<T>() -> usize {
+    T //~ ERROR E0577
+}
+
+fn main() {
+    let _: Buf<u8>; //~ ERROR E0575
+    let _: Wrapper<3>; //~ ERROR E0575
+    let _: Buf<_> = Buf { data: [0; 5] };
+}
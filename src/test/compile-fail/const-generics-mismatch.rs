@@ -0,0 +1,23 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(const_generics)]
+
+struct Buf<const N: usize> {
+    data: [u8; N],
+}
+
+fn takes_three(_: Buf<3>) {}
+
+fn main() {
+    takes_three(Buf { data: [0; 4] }); //~ ERROR mismatched types
+    let _: Buf<{1 + 2}> = Buf { data: [0; 3] };
+    let _: Buf<2> = Buf { data: [0; 3] }; //~ ERROR mismatched types
+}
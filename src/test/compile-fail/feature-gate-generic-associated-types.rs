@@ -0,0 +1,25 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+trait PointerFamily {
+    type Pointer<T>; //~ ERROR generic associated types are unstable
+}
+
+trait StreamingIterator {
+    type Item<'a> where Self: 'a; //~ ERROR generic associated types are unstable
+}
+
+struct Foo;
+
+impl StreamingIterator for Foo {
+    type Item<'a> where Self: 'a = &'a Foo; //~ ERROR generic associated types are unstable
+}
+
+fn main() {}
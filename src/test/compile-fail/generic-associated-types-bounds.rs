@@ -0,0 +1,35 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(generic_associated_types)]
+
+use std::fmt::Debug;
+
+trait StreamingIterator {
+    type Item<'a>: Debug; //~ ERROR bounds on generic associated types are not yet supported
+}
+
+trait Family {
+    type Member<T>;
+}
+
+fn bound<F: Family<Member=Vec<u8>>>() {}
+//~^ ERROR cannot bind generic associated type `Member` in a trait reference
+
+trait Windows {
+    type Window<'a> where Self: 'a;
+}
+
+impl<T> Windows for Vec<T> {
+    type Window<'a> where T: Copy + 'a = &'a [T];
+    //~^ ERROR the requirement `T: std::marker::Copy` appears on the impl associated type
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(generic_associated_types)]
+
+trait Family {
+    type Member<'a, T>;
+}
+
+struct VecFamily;
+
+impl Family for VecFamily {
+    type Member<T> = Vec<T>;
+    //~^ ERROR associated type `Member` has 0 lifetime and 1 type parameter(s) but its trait
+}
+
+fn too_few_types<'a, F: Family>(_: F::Member<'a>) {}
+//~^ ERROR wrong number of type arguments: expected 1, found 0
+
+fn too_many_types<'a, F: Family>(_: F::Member<'a, u8, u8>) {}
+//~^ ERROR wrong number of type arguments: expected 1, found 2
+
+fn too_many_lifetimes<'a, F: Family>(_: F::Member<'a, 'a, u8>) {}
+//~^ ERROR wrong number of lifetime parameters: expected 1, found 2
+
+trait Plain {
+    type Item;
+}
+
+fn not_generic<T: Plain>(_: T::Item<u8>) {}
+//~^ ERROR type parameters are not allowed on this type
+
+fn main() {}
@@ -0,0 +1,34 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(conservative_impl_trait)]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+fn send<T: Send>(_: T) {}
+
+// Auto traits like `Send` are not inferred from the concrete type, so
+// they have to be named in the bounds to be usable by callers.
+fn before() -> impl Fn(i32) {
+    let p = Rc::new(Cell::new(0));
+    move |x| p.set(x)
+}
+
+fn after() -> impl Fn(i32) + Send {
+    |x| println!("{}", x)
+}
+
+fn main() {
+    send(before());
+    //~^ ERROR the trait bound `impl std::ops::Fn<(i32,)>: std::marker::Send` is not satisfied
+
+    send(after());
+}
@@ -0,0 +1,34 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Check the follow set of `vis` (see `macro-vis-lifetime-literal.rs` for the
+// accepted cases).
+
+#![feature(macro_vis_matcher)]
+
+// FOLLOW(vis) = {Comma, Ident(except priv), tokens that begin a type except `(`,
+//                $i:ident, $t:ty, $p:path}
+macro_rules! follow_vis {
+    ($v:vis ()) => {};       //~ERROR `$v:vis` is followed by `(`
+    ($v:vis {}) => {};       //~ERROR `$v:vis` is followed by `{`
+    ($v:vis priv) => {};     //~ERROR `$v:vis` is followed by `priv`
+    ($v:vis =) => {};        //~ERROR `$v:vis` is followed by `=`
+    ($v:vis ;) => {};        //~ERROR `$v:vis` is followed by `;`
+    ($v:vis $e:expr) => {};  //~ERROR `$v:vis` is followed by `$e:expr`
+    ($v:vis $i:item) => {};  //~ERROR `$v:vis` is followed by `$i:item`
+    ($v:vis $t:tt) => {};    //~ERROR `$v:vis` is followed by `$t:tt`
+    ($v:vis $w:vis) => {};   //~ERROR `$v:vis` is followed by `$w:vis`
+}
+
+macro_rules! bad_fragment {
+    ($v:visibility) => {}; //~ERROR invalid fragment specifier `visibility`
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a fragment which fails to parse in every arm is reported with
+// notes pointing at the matcher and at the invocation.
+
+macro_rules! pair {
+    ($a:expr, $b:expr) => (($a, $b)); //~ NOTE while parsing this fragment
+    ($a:ident) => ($a);
+}
+
+fn main() {
+    let _ = pair!(1, =); //~ ERROR expected expression, found `=`
+    //~^ NOTE in this invocation of `pair!`
+}
@@ -0,0 +1,36 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:macro_by_path_a.rs
+
+// A macro imported by `use` is only visible in the module importing it, and
+// not in the modules nested in it.
+
+#![feature(use_extern_macros)]
+
+extern crate macro_by_path_a;
+
+mod m {
+    use macro_by_path_a::which;
+
+    pub fn f() -> &'static str {
+        which!()
+    }
+
+    mod nested {
+        fn g() {
+            which!(); //~ ERROR macro undefined: 'which!'
+        }
+    }
+}
+
+fn main() {
+    which!(); //~ ERROR macro undefined: 'which!'
+}
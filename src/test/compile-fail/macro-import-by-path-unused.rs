@@ -0,0 +1,46 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:macro_by_path_a.rs
+
+#![feature(use_extern_macros)]
+#![deny(unused_imports)]
+
+extern crate macro_by_path_a;
+
+use macro_by_path_a::which; //~ ERROR unused import
+use macro_by_path_a::try_opt;
+
+mod reexports {
+    pub use macro_by_path_a::which;
+}
+
+mod unused {
+    use macro_by_path_a::*; //~ ERROR unused import
+    use reexports::which; //~ ERROR unused import
+}
+
+mod used {
+    use macro_by_path_a::*;
+    use reexports::which as reexported_which;
+
+    pub fn f() -> Option<&'static str> {
+        Some(try_opt!(Some(reexported_which!())))
+    }
+}
+
+fn f(x: Option<u32>) -> Option<u32> {
+    Some(try_opt!(x))
+}
+
+fn main() {
+    f(None);
+    used::f();
+}
@@ -0,0 +1,26 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that only variants with an uninhabited field may be left out of a match.
+
+#![feature(never_type)]
+
+enum Foo {
+    A(u32),
+    B(!),
+    C(u32, ()),
+}
+
+fn main() {
+    let x = Foo::A(1);
+    match x { //~ ERROR non-exhaustive patterns: `C(_, _)` not covered
+        Foo::A(_) => {}
+    }
+}
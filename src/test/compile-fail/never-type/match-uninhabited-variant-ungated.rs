@@ -0,0 +1,26 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Without `never_type`, variants with uninhabited fields still have to be
+// matched.
+
+enum Void {}
+
+enum Foo {
+    A(u32),
+    B(Void),
+}
+
+fn main() {
+    let x = Foo::A(1);
+    match x { //~ ERROR non-exhaustive patterns: `B(_)` not covered
+        Foo::A(_) => {}
+    }
+}
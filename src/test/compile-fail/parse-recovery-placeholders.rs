@@ -0,0 +1,37 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z continue-parse-after-error
+
+// Expressions that fail to parse are replaced by placeholders that do not
+// cause any errors of their own: `add` is still called with three arguments
+// and `broken_tail` still returns a `u32`. Missing semicolons at the end of
+// a line are assumed. Type checking still reports the real error in `main`.
+
+fn add(a: u32, b: u32, c: u32) -> u32 { a + b + c }
+
+fn broken_argument() -> u32 {
+    add(1, +, 3) //~ ERROR expected expression, found `+`
+}
+
+fn broken_tail() -> u32 {
+    1 + + //~ ERROR expected expression, found `+`
+}
+
+fn missing_semicolons() -> u32 {
+    let x = 1
+    let y = x + 1; //~ ERROR found `let`
+    add(x, y, 0)
+    y //~ ERROR found `y`
+}
+
+fn main() {
+    let _: String = broken_argument(); //~ ERROR mismatched types
+}
@@ -0,0 +1,45 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(dead_code)]
+#![feature(repr_align, repr_packed)]
+
+#[repr(align(16))]
+struct Ok16(u8);
+
+#[repr(align(3))] //~ ERROR: invalid `repr(align)` attribute: not a power of two
+struct NotPowerOfTwo(u8);
+
+#[repr(packed(6))] //~ ERROR: invalid `repr(packed)` attribute: not a power of two
+struct PackedNotPowerOfTwo(u8);
+
+#[repr(align(65536))] //~ ERROR: invalid `repr(align)` attribute: larger than 2^15
+struct TooLarge(u8);
+
+#[repr(align(x))] //~ ERROR: invalid `repr(align)` attribute: expected a single integer
+struct NotAnInteger(u8);
+
+#[repr(align(4, 8))] //~ ERROR: invalid `repr(align)` attribute: expected a single integer
+struct TwoIntegers(u8);
+
+#[repr(packed, align(8))] //~ ERROR: conflicting representation hints
+struct Conflicting(u8);
+
+#[repr(align(8))] //~ ERROR: attribute should be applied to struct
+enum AlignedEnum { A, B }
+
+fn main() {
+    let _ = Ok16(0);
+    let _ = NotPowerOfTwo(0);
+    let _ = PackedNotPowerOfTwo(0);
+    let _ = TooLarge(0);
+    let _ = NotAnInteger(0);
+    let _ = TwoIntegers(0);
+}
@@ -0,0 +1,39 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(min_specialization, associated_consts)]
+
+// `min_specialization` only allows `default fn`: specializing associated
+// types and constants still needs the full `specialization` feature.
+
+trait Foo {
+    type Assoc;
+    const VALUE: u32;
+    fn foo(&self);
+}
+
+impl<T> Foo for T {
+    default type Assoc = (); //~ ERROR specializing associated types and constants is unstable
+    default const VALUE: u32 = 0; //~ ERROR specializing associated types and constants
+    default fn foo(&self) {}
+}
+
+trait Bar {
+    type Assoc;
+    fn bar(&self);
+}
+
+default impl<T> Bar for T {
+    type Assoc = (); //~ ERROR specializing associated types and constants is unstable
+    fn bar(&self) {}
+}
+
+fn main() {}
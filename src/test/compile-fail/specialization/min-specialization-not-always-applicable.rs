@@ -0,0 +1,36 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(min_specialization)]
+
+// Check that with `min_specialization` a specializing impl must apply
+// whenever the impl it specializes applies to the same types.
+
+trait Foo {
+    fn foo(&self);
+}
+
+impl<T> Foo for T {
+    default fn foo(&self) {}
+}
+
+impl Foo for &'static str { //~ ERROR cannot specialize on `'static` lifetime
+    fn foo(&self) {}
+}
+
+impl<U> Foo for (U, U) { //~ ERROR specializing impl repeats parameter `U`
+    fn foo(&self) {}
+}
+
+impl<U: Clone> Foo for Vec<U> { //~ ERROR cannot specialize on `U: std::clone::Clone`
+    fn foo(&self) {}
+}
+
+fn main() {}
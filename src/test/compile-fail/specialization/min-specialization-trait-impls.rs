@@ -0,0 +1,29 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(min_specialization, rustc_attrs)]
+
+// Specializing impls may rely on bounds on a specialization trait, so the
+// impls of such a trait have to be always applicable themselves.
+
+#[rustc_specialization_trait]
+trait Special {}
+
+impl Special for &'static str {} //~ ERROR cannot specialize on `'static` lifetime
+
+impl<T> Special for (T, T) {} //~ ERROR specializing impl repeats parameter `T`
+
+impl<T: Clone> Special for Vec<T> {}
+//~^ ERROR impls of a specialization trait cannot depend on `T: std::clone::Clone`
+
+impl<T: Special> Special for Option<T> {}
+
+fn main() {}
@@ -0,0 +1,62 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// revisions: rpass1 rpass2
+
+#![allow(warnings)]
+#![feature(rustc_attrs, untagged_unions)]
+
+// Here we turn a struct into a union with the same fields, and change
+// the `#[repr]` of another struct. Neither change shows up in the names
+// or types of the fields, but both change the signature of the item,
+// so the change must propagate to the users of the types.
+
+fn main() { }
+
+mod x {
+    #[cfg(rpass1)]
+    pub struct X {
+        pub a: u32,
+        pub b: u64,
+    }
+
+    #[cfg(rpass2)]
+    pub union X {
+        pub a: u32,
+        pub b: u64,
+    }
+
+    #[cfg(rpass1)]
+    pub struct Y {
+        pub a: u8,
+        pub b: u32,
+    }
+
+    #[cfg(rpass2)]
+    #[repr(packed)]
+    pub struct Y {
+        pub a: u8,
+        pub b: u32,
+    }
+}
+
+mod y {
+    use x::{X, Y};
+
+    #[rustc_dirty(label="TypeckItemBody", cfg="rpass2")]
+    pub fn use_x(x: X) -> u32 {
+        unsafe { x.a }
+    }
+
+    #[rustc_dirty(label="TypeckItemBody", cfg="rpass2")]
+    pub fn use_y(y: Y) -> u8 {
+        y.a
+    }
+}
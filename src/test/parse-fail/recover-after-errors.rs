@@ -0,0 +1,51 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z parse-only -Z continue-parse-after-error
+
+// The parser picks up after each of these errors, so all of them are
+// reported.
+
+fn missing_semicolons() {
+    let x = 1
+    let y = 2; //~ ERROR found `let`
+    foo()
+    bar(); //~ ERROR found `bar`
+}
+
+struct Fields {
+    a: u32
+    b: u32, //~ ERROR expected `,`, or `}`, found `b`
+}
+
+fn calls() {
+    foo(1, +, 3); //~ ERROR expected expression, found `+`
+    [1, 2, +]; //~ ERROR expected expression, found `+`
+}
+
+impl Thing {
+    fn a(&self) -> u32 { 1 }
+    fn b(&self) -> { 2 } //~ ERROR expected type, found `{`
+    fn c(&self) -> u32 { 3 }
+}
+
+trait Trait {
+    fn a(&self) -> ;  //~ ERROR expected type, found `;`
+    fn b(&self);
+}
+
+const C: u32 = 1
+fn after_const() {} //~ ERROR found `fn`
+
+let stray = 1; //~ ERROR expected item, found `let`
+
+fn last() {
+    let z = ; //~ ERROR expected expression, found `;`
+}
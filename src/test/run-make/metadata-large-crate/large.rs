@@ -0,0 +1,59 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A crate with a few thousand modules and over ten thousand items, of
+// which `use_large.rs` uses a handful.
+
+macro_rules! leaf {
+    () => {
+        pub struct S(pub u32);
+
+        impl S {
+            pub fn get(&self) -> u32 { self.0 }
+        }
+
+        pub enum E { A(u32), B }
+
+        pub trait T {
+            fn t(&self) -> u32 { 1 }
+        }
+
+        impl T for S {}
+
+        pub const C: u32 = 1;
+
+        pub fn f(e: E) -> u32 {
+            match e {
+                E::A(x) => x,
+                E::B => 0,
+            }
+        }
+    }
+}
+
+macro_rules! tens {
+    ($inner:ident) => {
+        pub mod m0 { $inner!(); }
+        pub mod m1 { $inner!(); }
+        pub mod m2 { $inner!(); }
+        pub mod m3 { $inner!(); }
+        pub mod m4 { $inner!(); }
+        pub mod m5 { $inner!(); }
+        pub mod m6 { $inner!(); }
+        pub mod m7 { $inner!(); }
+        pub mod m8 { $inner!(); }
+        pub mod m9 { $inner!(); }
+    }
+}
+
+macro_rules! hundreds { () => { tens!(leaf); } }
+macro_rules! thousands { () => { tens!(hundreds); } }
+
+tens!(thousands);
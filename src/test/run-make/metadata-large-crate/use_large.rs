@@ -0,0 +1,19 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+extern crate large;
+
+use large::m7::m3::m9::{f, E, S, T, C};
+
+fn main() {
+    let s = S(20);
+    let answer = s.get() + f(E::A(20)) + f(E::B) + s.t() + C;
+    println!("{}", answer);
+}
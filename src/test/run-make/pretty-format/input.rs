@@ -0,0 +1,86 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// before the module
+mod foo;
+
+use std::ops::{
+    // inside the use list
+    Add,
+    Mul, // after the last import
+};
+
+
+// after two blank lines
+struct Pair<
+    // before the parameter
+    T>
+    where
+    // in the where clause
+    T: Copy
+{
+    first: T,
+    // between the fields
+    second: T, // trailing
+    // before the end of the struct
+}
+
+enum Op {
+    // before the first variant
+    Add,
+
+
+    // between the variants
+    Mul, // after the last variant
+}
+
+trait Apply {
+    // before the trait method
+    fn apply(&self, a: u32, b: u32) -> u32;
+    // after the trait method
+}
+
+impl Apply for Op {
+    // before the impl method
+    fn apply(&self,
+             // before an argument
+             a: u32,
+             b: u32 // after the last argument
+             ) -> u32 {
+        match *self {
+            // before the first arm
+            Op::Add => Add::add(a, b),
+            // before the second arm
+            Op::Mul => Mul::mul(a, b),
+            // after the last arm
+        }
+    }
+}
+
+macro_rules! add {
+    // inside the macro
+    ($a:expr, $b:expr) => { $a + $b }
+}
+
+fn main() {
+    let pair = Pair {
+        // before a field
+        first: 20,
+        second: 22, // after the last field
+    };
+
+
+    let answer = add!(pair.first, foo::half(pair.second * 2)) + pair.first * 0 + pair.second * 0;
+    let answer = Op::Add.apply(Op::Mul.apply(answer, 1), 0 // before the paren
+                               );
+    // the answer
+    println!("{}", answer);
+    // at the end
+}
@@ -0,0 +1,46 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+pub use inner::Reexported;
+
+mod inner {
+    pub struct Reexported;
+}
+
+pub struct Public {
+    pub x: u32,
+    y: u32,
+}
+
+struct Private;
+
+impl Public {
+    pub fn new() -> Public {
+        helper();
+        Public { x: 0, y: 0 }
+    }
+}
+
+fn helper() {}
+
+pub trait Base {}
+
+/// A documented trait.
+pub trait Derived: Base {
+    fn method(&self);
+}
+
+impl Base for Public {}
+
+impl Derived for Public {
+    fn method(&self) {}
+}
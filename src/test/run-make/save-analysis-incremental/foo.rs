@@ -0,0 +1,38 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+extern crate bar;
+
+pub struct Foo {
+    pub x: u32,
+}
+
+impl Foo {
+    pub fn new() -> Foo {
+        Foo { x: bar::answer() }
+    }
+}
+
+pub fn f() -> u64 {
+    let x = g();
+    x as u64
+}
+
+#[cfg(not(changed))]
+pub fn g() -> u32 {
+    42
+}
+
+#[cfg(changed)]
+pub fn g() -> u64 {
+    42
+}
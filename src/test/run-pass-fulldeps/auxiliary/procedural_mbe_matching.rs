@@ -24,7 +24,7 @@ use syntax::tokenstream::{TokenTree};
 use syntax::ext::base::{ExtCtxt, MacResult, DummyResult, MacEager};
 use syntax::ext::build::AstBuilder;
 use syntax::ext::tt::macro_parser::{MatchedSeq, MatchedNonterminal};
-use syntax::ext::tt::macro_parser::{Success, Failure, Error};
+use syntax::ext::tt::macro_parser::{Success, Failure, Error, FragmentFailure};
 use syntax::ptr::P;
 use syntax_pos::Span;
 use rustc_plugin::Registry;
@@ -61,6 +61,9 @@ fn expand_mbe_matches(cx: &mut ExtCtxt, sp: Span, args: &[TokenTree])
         Failure(_, s) | Error(_, s) => {
             panic!("expected Success, but got Error/Failure: {}", s);
         }
+        FragmentFailure(err) => {
+            panic!("expected Success, but got FragmentFailure: {}", err.msg);
+        }
     };
 
     MacEager::expr(mac_expr)
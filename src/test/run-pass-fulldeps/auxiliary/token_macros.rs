@@ -0,0 +1,68 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// force-host
+
+#![feature(proc_macro, proc_macro_lib)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+// `sum!(a, b, c)` expands to `a + b + c`.
+#[proc_macro]
+pub fn sum(input: TokenStream) -> TokenStream {
+    input.to_string().replace(",", "+").parse().unwrap()
+}
+
+// `first!(a, ...)` expands to `a`, keeping the tokens of the invocation.
+#[proc_macro]
+pub fn first(input: TokenStream) -> TokenStream {
+    input.trees().into_iter().take_while(|tree| tree.to_string() != ",").collect()
+}
+
+// `make_answer_fn!(name)` defines `fn name() -> u32`, returning 42.
+#[proc_macro]
+pub fn make_answer_fn(input: TokenStream) -> TokenStream {
+    format!("fn {}() -> u32 {{ 42 }}", input).parse().unwrap()
+}
+
+// `#[rename(new_name)]` renames the annotated function.
+#[proc_macro_attribute]
+pub fn rename(annotation: TokenStream, item: TokenStream) -> TokenStream {
+    let item = item.to_string();
+    let start = item.find("fn ").unwrap() + "fn ".len();
+    let len = item[start..].find(|c: char| !c.is_alphanumeric() && c != '_').unwrap();
+    format!("{}{}{}", &item[..start], annotation, &item[start + len..]).parse().unwrap()
+}
+
+// Finds the name of the annotated struct or enum, and the source before it.
+fn split_item(input: &TokenStream) -> (String, String) {
+    let source = input.to_string();
+    let start = source.find("struct ").or_else(|| source.find("enum ")).unwrap();
+    let name = source[start..].split_whitespace().nth(1).unwrap();
+    (source[..start].split_whitespace().collect::<Vec<_>>().join(" "),
+     name.trim_right_matches(';').to_string())
+}
+
+// `#[derive(Answer)]` implements `Answer`, returning 42.
+#[proc_macro_derive(Answer)]
+pub fn derive_answer(input: TokenStream) -> TokenStream {
+    let (_, name) = split_item(&input);
+    format!("impl Answer for {} {{ fn answer() -> u32 {{ 42 }} }}", name).parse().unwrap()
+}
+
+// `#[derive(Attrs)]` defines `fn attrs()`, returning the attributes the
+// derive was given.
+#[proc_macro_derive(Attrs)]
+pub fn derive_attrs(input: TokenStream) -> TokenStream {
+    let (attrs, name) = split_item(&input);
+    format!("impl {} {{ fn attrs() -> &'static str {{ {:?} }} }}", name, attrs).parse().unwrap()
+}
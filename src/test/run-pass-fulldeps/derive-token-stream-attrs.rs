@@ -0,0 +1,36 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:token_macros.rs
+// ignore-stage1
+
+// A custom derive sees the attributes written on the item, including those
+// whose name starts with `derive_`, but not the ones left by the expansion
+// of `#[derive]`.
+
+#![feature(proc_macro, custom_derive)]
+#![allow(unused_attributes)]
+
+#[macro_use(Attrs)] #[no_link]
+extern crate token_macros;
+
+#[derive_seen]
+#[derive(Debug, Attrs)]
+struct S;
+
+#[derive(Attrs)]
+#[derive_too]
+struct T;
+
+fn main() {
+    assert_eq!(S::attrs(), "#[derive_seen]");
+    assert_eq!(T::attrs(), "#[derive_too]");
+    assert_eq!(format!("{:?}", S), "S");
+}
@@ -0,0 +1,48 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:token_macros.rs
+// ignore-stage1
+
+// Custom derives defined as procedural macros need neither a plugin nor
+// `custom_derive`.
+#![feature(proc_macro)]
+
+#[macro_use] #[no_link]
+extern crate token_macros;
+
+trait Answer {
+    fn answer() -> u32;
+}
+
+#[derive(Answer)]
+struct Unit;
+
+#[derive(Clone, Answer, Debug)]
+struct Named {
+    x: u32,
+}
+
+#[derive(Answer)]
+enum Enum {
+    A,
+    B(u32),
+}
+
+fn main() {
+    assert_eq!(Unit::answer(), 42);
+    assert_eq!(Named::answer(), 42);
+    assert_eq!(Enum::answer(), 42);
+
+    // The builtin derives still apply.
+    let named = Named { x: 1 }.clone();
+    assert_eq!(format!("{:?}", named), "Named { x: 1 }");
+    let _ = (Enum::A, Enum::B(0));
+}
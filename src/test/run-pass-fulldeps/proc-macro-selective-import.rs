@@ -0,0 +1,34 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:token_macros.rs
+// ignore-stage1
+
+// Only the procedural macros listed in `#[macro_use(...)]` are imported.
+
+#![feature(proc_macro)]
+
+#[macro_use(sum, rename)] #[no_link]
+extern crate token_macros;
+
+// Not shadowed by the macro of the same name in `token_macros`.
+macro_rules! first {
+    ($($t:tt)*) => (0)
+}
+
+#[rename(renamed)]
+fn original() -> u32 {
+    sum!(1, 2)
+}
+
+fn main() {
+    assert_eq!(renamed(), 3);
+    assert_eq!(first!(1, 2), 0);
+}
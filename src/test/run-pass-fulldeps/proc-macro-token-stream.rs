@@ -0,0 +1,40 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:token_macros.rs
+// ignore-stage1
+
+// Procedural macros are imported with `#[macro_use]`, not as plugins.
+#![feature(proc_macro)]
+
+#[macro_use] #[no_link]
+extern crate token_macros;
+
+make_answer_fn!(answer);
+
+#[rename(renamed)]
+fn original() -> u32 {
+    7
+}
+
+struct S;
+
+impl S {
+    make_answer_fn!(method_answer);
+}
+
+fn main() {
+    let x = 1;
+    assert_eq!(sum!(x, 2, 3), 6);
+    assert_eq!(first!(x, these tokens are dropped), 1);
+    assert_eq!(answer(), 42);
+    assert_eq!(S::method_answer(), 42);
+    assert_eq!(renamed(), 7);
+}
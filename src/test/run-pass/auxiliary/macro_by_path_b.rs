@@ -0,0 +1,28 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub fn helper() -> u32 {
+    3
+}
+
+#[macro_export]
+macro_rules! try_opt {
+    ($e:expr) => (match $e { Some(x) => x, None => return Some(0) })
+}
+
+#[macro_export]
+macro_rules! which {
+    () => ("b")
+}
+
+#[macro_export]
+macro_rules! call_helper {
+    () => ($crate::helper())
+}
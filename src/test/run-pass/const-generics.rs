@@ -0,0 +1,87 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(const_generics)]
+
+trait Sum {
+    fn sum(&self) -> u32;
+}
+
+// One impl for arrays of every length.
+impl<const N: usize> Sum for [u32; N] {
+    fn sum(&self) -> u32 {
+        self.iter().fold(0, |a, &b| a + b)
+    }
+}
+
+trait Length {
+    fn length(&self) -> usize;
+}
+
+// `N` can be used as a value.
+impl<T, const N: usize> Length for [T; N] {
+    fn length(&self) -> usize {
+        N
+    }
+}
+
+struct Buf<const N: usize> {
+    data: [u8; N],
+}
+
+impl<const N: usize> Buf<N> {
+    fn new(data: [u8; N]) -> Buf<N> {
+        Buf { data: data }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn capacity() -> usize {
+        N
+    }
+
+    fn half_full(&self) -> bool {
+        let half = || N / 2;
+        self.data.iter().filter(|&&b| b != 0).count() >= half()
+    }
+
+    fn first(&self) -> u8 {
+        self.data[0]
+    }
+}
+
+fn first<T: Copy, const N: usize>(array: &[T; N]) -> T {
+    array[0]
+}
+
+fn main() {
+    assert_eq!([1u32, 2, 3].sum(), 6);
+    assert_eq!([5u32; 4].sum(), 20);
+    assert_eq!([1u8, 2, 3].length(), 3);
+    assert_eq!([(); 0].length(), 0);
+
+    let inferred = Buf::new([1, 2, 3]);
+    assert_eq!(inferred.len(), 3);
+    assert_eq!(inferred.first(), 1);
+
+    let explicit = Buf::<2>::new([4, 5]);
+    assert_eq!(explicit.len(), 2);
+    assert_eq!(Buf::<2>::capacity(), 2);
+    assert!(explicit.half_full());
+
+    let computed: Buf<{2 * 8}> = Buf::new([7; 16]);
+    assert_eq!(computed.len(), 16);
+    assert_eq!(computed.first(), 7);
+
+    assert_eq!(first(&['a', 'b']), 'a');
+    assert_eq!(first::<_, 1>(&[9u64]), 9);
+}
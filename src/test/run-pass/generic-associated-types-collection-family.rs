@@ -0,0 +1,62 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A family of collections, parameterized over the element type.
+
+#![feature(generic_associated_types)]
+
+use std::collections::VecDeque;
+
+trait CollectionFamily {
+    type Member<T>;
+    fn empty<T>() -> Self::Member<T>;
+    fn push<T>(c: &mut Self::Member<T>, value: T);
+    fn len<T>(c: &Self::Member<T>) -> usize;
+}
+
+struct VecFamily;
+
+impl CollectionFamily for VecFamily {
+    type Member<T> = Vec<T>;
+    fn empty<T>() -> Vec<T> { Vec::new() }
+    fn push<T>(c: &mut Vec<T>, value: T) { c.push(value) }
+    fn len<T>(c: &Vec<T>) -> usize { c.len() }
+}
+
+struct DequeFamily;
+
+impl CollectionFamily for DequeFamily {
+    type Member<T> = VecDeque<T>;
+    fn empty<T>() -> VecDeque<T> { VecDeque::new() }
+    fn push<T>(c: &mut VecDeque<T>, value: T) { c.push_front(value) }
+    fn len<T>(c: &VecDeque<T>) -> usize { c.len() }
+}
+
+fn fill<F: CollectionFamily>(n: u32) -> F::Member<u32> {
+    let mut c = F::empty::<u32>();
+    for i in 0..n {
+        F::push(&mut c, i);
+    }
+    c
+}
+
+fn lengths<F: CollectionFamily>() -> (usize, usize) {
+    let numbers = fill::<F>(3);
+    let mut strings = F::empty::<String>();
+    F::push(&mut strings, "hello".to_string());
+    (F::len(&numbers), F::len(&strings))
+}
+
+fn main() {
+    assert_eq!(fill::<VecFamily>(3), vec![0, 1, 2]);
+    assert_eq!(fill::<DequeFamily>(3).into_iter().collect::<Vec<_>>(), vec![2, 1, 0]);
+    assert_eq!(lengths::<VecFamily>(), (3, 1));
+    assert_eq!(lengths::<DequeFamily>(), (3, 1));
+}
@@ -0,0 +1,56 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A streaming iterator hands out items borrowing from the iterator itself.
+
+#![feature(generic_associated_types)]
+
+trait StreamingIterator {
+    type Item<'a> where Self: 'a;
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+}
+
+struct Windows {
+    buf: Vec<u8>,
+    pos: usize,
+    size: usize,
+}
+
+impl StreamingIterator for Windows {
+    type Item<'a> where Self: 'a = &'a mut [u8];
+
+    fn next<'a>(&'a mut self) -> Option<&'a mut [u8]> {
+        if self.pos + self.size > self.buf.len() {
+            return None;
+        }
+        let start = self.pos;
+        self.pos += 1;
+        Some(&mut self.buf[start..start + self.size])
+    }
+}
+
+fn count<I: StreamingIterator>(iter: &mut I) -> usize {
+    let mut n = 0;
+    while let Some(_) = iter.next() {
+        n += 1;
+    }
+    n
+}
+
+fn main() {
+    let mut windows = Windows { buf: vec![1, 2, 3, 4], pos: 0, size: 2 };
+    while let Some(window) = windows.next() {
+        window[1] += window[0];
+    }
+    assert_eq!(windows.buf, vec![1, 3, 6, 10]);
+
+    let mut windows = Windows { buf: vec![0; 5], pos: 0, size: 3 };
+    assert_eq!(count(&mut windows), 3);
+}
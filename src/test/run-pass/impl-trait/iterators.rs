@@ -0,0 +1,57 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(conservative_impl_trait)]
+
+use std::fmt::Debug;
+
+fn evens(limit: u32) -> impl Iterator<Item=u32> {
+    (0..limit).filter(|x| x % 2 == 0)
+}
+
+fn squares<I: Iterator<Item=u32>>(iter: I) -> impl Iterator<Item=u32> {
+    iter.map(|x| x * x)
+}
+
+fn adder(n: i32) -> impl Fn(i32) -> i32 {
+    move |x| x + n
+}
+
+fn boxed_debug(x: u8) -> Box<impl Debug + Clone> {
+    Box::new((x, "impl Trait"))
+}
+
+fn nested() -> impl Iterator<Item=impl Iterator<Item=u32>> {
+    (1..4).map(|n| 0..n)
+}
+
+struct Counter(u32);
+
+impl Counter {
+    fn upto<'a>(&'a self, n: u32) -> impl Iterator<Item=u32> + 'a {
+        (self.0..n).chain(Some(self.0))
+    }
+}
+
+fn main() {
+    let v: Vec<u32> = squares(evens(7)).collect();
+    assert_eq!(v, [0, 4, 16, 36]);
+
+    assert_eq!(adder(3)(4), 7);
+
+    let b = boxed_debug(5);
+    assert_eq!(format!("{:?}", b.clone()), "(5, \"impl Trait\")");
+
+    let lengths: Vec<usize> = nested().map(|it| it.count()).collect();
+    assert_eq!(lengths, [1, 2, 3]);
+
+    let c = Counter(2);
+    assert_eq!(c.upto(4).collect::<Vec<_>>(), [2, 3, 2]);
+}
@@ -0,0 +1,35 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that an arm whose fragment fails to parse is skipped in favour of
+// the next arm.
+
+macro_rules! kind {
+    ($e:expr) => ("expr");
+    ($t:ty) => ("ty");
+    ($i:item) => ("item");
+    ($($t:tt)*) => ("tts");
+}
+
+macro_rules! pat_or_op {
+    ($p:pat) => ("pat");
+    (+ $e:expr) => ("plus");
+}
+
+fn main() {
+    assert_eq!(kind!(1 + 2), "expr");
+    assert_eq!(kind!(&'static str), "ty");
+    assert_eq!(kind!(fn foo() {}), "item");
+    assert_eq!(kind!(+ -), "tts");
+    assert_eq!(kind!(=>), "tts");
+
+    assert_eq!(pat_or_op!(Some(_)), "pat");
+    assert_eq!(pat_or_op!(+ 1), "plus");
+}
@@ -0,0 +1,113 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:macro_by_path_a.rs
+// aux-build:macro_by_path_b.rs
+// aux-build:macro_by_path_c.rs
+
+// Both crates export `try_opt!` and `which!`; each module sees the one it imports.
+
+#![feature(use_extern_macros)]
+
+extern crate macro_by_path_a;
+extern crate macro_by_path_b;
+extern crate macro_by_path_c;
+
+use macro_by_path_a::which;
+use macro_by_path_b::{which as which_b, call_helper};
+
+mod b {
+    pub fn try_b(x: Option<u32>) -> Option<u32> {
+        Some(try_opt!(x) + 1)
+    }
+
+    use macro_by_path_b::try_opt;
+}
+
+mod glob {
+    use macro_by_path_b::*;
+
+    pub fn g() -> u32 {
+        call_helper!() + helper()
+    }
+}
+
+mod reexported {
+    use macro_by_path_c::which;
+
+    pub fn which_c() -> &'static str {
+        which!()
+    }
+}
+
+mod reexports {
+    pub use macro_by_path_a::which as which_a;
+
+    pub mod nested {
+        pub use macro_by_path_b::which;
+    }
+}
+
+mod by_self {
+    mod inner {
+        pub use macro_by_path_b::which;
+    }
+
+    use self::inner::which;
+
+    pub fn f() -> &'static str {
+        which!()
+    }
+
+    pub mod by_super {
+        use super::inner::which as which_super;
+
+        pub fn f() -> &'static str {
+            which_super!()
+        }
+    }
+}
+
+mod by_nested_path {
+    use reexports::nested::which;
+
+    pub fn f() -> &'static str {
+        which!()
+    }
+}
+
+mod by_reexport_glob {
+    use reexports::*;
+
+    pub fn f() -> &'static str {
+        which_a!()
+    }
+}
+
+fn try_a(x: Option<u32>) -> Option<u32> {
+    use macro_by_path_a::try_opt;
+    Some(try_opt!(x) + 1)
+}
+
+fn main() {
+    assert_eq!(which!(), "a");
+    assert_eq!(which_b!(), "b");
+    assert_eq!(reexported::which_c(), "a");
+    assert_eq!(call_helper!(), 3);
+    assert_eq!(glob::g(), 6);
+    assert_eq!(try_a(Some(1)), Some(2));
+    assert_eq!(try_a(None), None);
+    assert_eq!(b::try_b(Some(1)), Some(2));
+    assert_eq!(b::try_b(None), Some(0));
+    assert_eq!(by_self::f(), "b");
+    assert_eq!(by_self::by_super::f(), "b");
+    assert_eq!(by_nested_path::f(), "b");
+    assert_eq!(by_reexport_glob::f(), "a");
+}
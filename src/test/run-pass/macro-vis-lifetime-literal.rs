@@ -0,0 +1,92 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test the `vis`, `lifetime` and `literal` fragment specifiers.
+
+#![feature(macro_vis_matcher, macro_lifetime_matcher, macro_literal_matcher)]
+
+macro_rules! new_struct {
+    ($v:vis struct $name:ident { $($fv:vis $field:ident: $t:ty),* }) => {
+        $v struct $name { $($fv $field: $t),* }
+    }
+}
+
+macro_rules! vis_fn {
+    ($v:vis $name:ident) => {
+        $v fn $name() -> u32 { 42 }
+    }
+}
+
+macro_rules! borrow {
+    ($l:lifetime, $t:ty) => {
+        struct Borrowed<$l> { inner: &$l $t }
+        fn first<$l>(x: &$l $t, _: &$t) -> &$l $t { x }
+    }
+}
+
+macro_rules! classify {
+    ($x:expr; $($lit:literal => $name:expr),*) => {
+        match $x {
+            $($lit => $name,)*
+            _ => "other",
+        }
+    }
+}
+
+macro_rules! doc_string {
+    ($doc:literal) => {
+        #[doc = $doc]
+        pub struct Documented;
+    }
+}
+
+macro_rules! literals {
+    ($($l:literal)*) => { [$(stringify!($l)),*] }
+}
+
+mod m {
+    new_struct!(pub struct Public { pub a: u32, b: u32 });
+    new_struct!(struct Private { a: u32 });
+    new_struct!(pub(crate) struct Crate { pub(crate) a: u32 });
+    vis_fn!(pub answer);
+    vis_fn!(private);
+
+    impl Public {
+        pub fn new(a: u32) -> Public { Public { a: a, b: private() } }
+        pub fn b(&self) -> u32 { self.b }
+    }
+
+    pub fn make_private() -> u32 { Private { a: 1 }.a }
+    pub fn make_crate() -> Crate { Crate { a: 2 } }
+}
+
+borrow!('a, str);
+doc_string!("A documented struct.");
+
+fn main() {
+    let p = m::Public::new(1);
+    assert_eq!(p.a, 1);
+    assert_eq!(p.b(), 42);
+    assert_eq!(m::answer(), 42);
+    assert_eq!(m::make_private(), 1);
+    assert_eq!(m::make_crate().a, 2);
+
+    let s = String::from("hello");
+    let b = Borrowed { inner: &s[..] };
+    assert_eq!(first(b.inner, "world"), "hello");
+
+    assert_eq!(classify!(-1; -1 => "minus one", 0 => "zero", 1 => "one"), "minus one");
+    assert_eq!(classify!(1; -1 => "minus one", 0 => "zero", 1 => "one"), "one");
+    assert_eq!(classify!(7; -1 => "minus one", 0 => "zero"), "other");
+    assert_eq!(classify!('x'; 'x' => "x", 'y' => "y"), "x");
+
+    assert_eq!(literals!(1 -2 "three" 'f' true 5.0),
+               ["1", "-2", "\"three\"", "'f'", "true", "5.0"]);
+}
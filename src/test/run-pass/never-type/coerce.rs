@@ -0,0 +1,31 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a value of type `!` coerces to any type.
+
+#![feature(never_type)]
+
+fn unwrap_never<T>(r: Result<T, !>) -> T {
+    match r {
+        Ok(t) => t,
+        Err(e) => {
+            let _: String = e;
+            let _: (u8, Vec<u32>) = e;
+            e
+        }
+    }
+}
+
+fn main() {
+    let s: Result<&str, !> = Ok("hello");
+    assert_eq!(unwrap_never(s), "hello");
+    let v: Result<Vec<u8>, !> = Ok(vec![1, 2, 3]);
+    assert_eq!(unwrap_never(v), [1, 2, 3]);
+}
@@ -0,0 +1,41 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that `!` can be used as a generic argument and an associated type.
+
+#![feature(never_type)]
+
+trait Parse {
+    type Error;
+    fn parse(s: &str) -> Result<Self, Self::Error> where Self: Sized;
+}
+
+struct Raw(String);
+
+impl Parse for Raw {
+    type Error = !;
+    fn parse(s: &str) -> Result<Raw, !> {
+        Ok(Raw(s.to_string()))
+    }
+}
+
+fn parse_infallible<T: Parse<Error=!>>(s: &str) -> T {
+    match T::parse(s) {
+        Ok(t) => t,
+    }
+}
+
+fn main() {
+    let Raw(s) = parse_infallible::<Raw>("raw");
+    assert_eq!(s, "raw");
+
+    let v: Vec<!> = Vec::new();
+    assert!(v.is_empty());
+}
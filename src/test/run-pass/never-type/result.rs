@@ -0,0 +1,30 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that `Result<T, !>` can be matched on without an `Err` arm.
+
+#![feature(never_type)]
+
+fn infallible(x: u32) -> Result<u32, !> {
+    Ok(x + 1)
+}
+
+fn main() {
+    let n = match infallible(1) {
+        Ok(n) => n,
+    };
+    assert_eq!(n, 2);
+
+    let m = match infallible(2) {
+        Ok(n) => n,
+        Err(e) => e,
+    };
+    assert_eq!(m, 3);
+}
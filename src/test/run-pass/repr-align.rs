@@ -0,0 +1,81 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(repr_align)]
+
+use std::mem;
+
+// Raising the alignment also pads the size up to it.
+#[repr(align(16))]
+struct Align16(i32);
+
+#[repr(align(64))]
+#[derive(Clone, Copy)]
+struct Align64 {
+    a: u8,
+    b: u64,
+}
+
+// A hint lower than the natural alignment has no effect.
+#[repr(align(1))]
+struct Align1(u32);
+
+// The alignment carries over to structs containing the aligned one.
+struct Nested {
+    a: u8,
+    b: Align16,
+}
+
+#[repr(C, align(8))]
+struct CAlign8 {
+    a: u8,
+    b: u16,
+}
+
+fn is_aligned_to<T>(ptr: *const T, align: usize) -> bool {
+    ptr as usize % align == 0
+}
+
+fn main() {
+    assert_eq!(mem::align_of::<Align16>(), 16);
+    assert_eq!(mem::size_of::<Align16>(), 16);
+
+    assert_eq!(mem::align_of::<Align64>(), 64);
+    assert_eq!(mem::size_of::<Align64>(), 64);
+
+    assert_eq!(mem::align_of::<Align1>(), 4);
+    assert_eq!(mem::size_of::<Align1>(), 4);
+
+    assert_eq!(mem::align_of::<Nested>(), 16);
+    assert_eq!(mem::size_of::<Nested>(), 32);
+
+    assert_eq!(mem::align_of::<CAlign8>(), 8);
+    assert_eq!(mem::size_of::<CAlign8>(), 8);
+
+    // Values on the stack and on the heap are placed at aligned addresses.
+    let a = Align16(7);
+    assert!(is_aligned_to(&a, 16));
+    assert_eq!(a.0, 7);
+
+    let b = Box::new(Align64 { a: 1, b: 2 });
+    assert!(is_aligned_to(&*b, 64));
+    assert_eq!((b.a, b.b), (1, 2));
+
+    let n = Nested { a: 3, b: Align16(4) };
+    assert!(is_aligned_to(&n.b, 16));
+    assert_eq!((n.a, n.b.0), (3, 4));
+
+    let arr = [Align64 { a: 5, b: 6 }; 3];
+    assert_eq!(mem::size_of_val(&arr), 192);
+    for x in &arr {
+        assert!(is_aligned_to(x, 64));
+        assert_eq!((x.a, x.b), (5, 6));
+    }
+}
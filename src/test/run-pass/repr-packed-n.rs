@@ -0,0 +1,74 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(repr_packed)]
+
+use std::mem;
+
+#[repr(packed(2))]
+struct Packed2 {
+    a: u8,
+    b: u32,
+    c: u16,
+}
+
+#[repr(packed(4))]
+struct Packed4 {
+    a: u8,
+    b: u64,
+    c: u8,
+}
+
+// Fields aligned less than the limit are laid out as usual.
+#[repr(packed(8))]
+struct Packed8 {
+    a: u8,
+    b: u16,
+    c: u32,
+}
+
+#[repr(packed(2))]
+struct Packed2Tuple(u8, f64, u8);
+
+fn offset_of<T, U>(base: &T, field: &U) -> usize {
+    field as *const U as usize - base as *const T as usize
+}
+
+fn main() {
+    assert_eq!(mem::align_of::<Packed2>(), 2);
+    assert_eq!(mem::size_of::<Packed2>(), 8);
+
+    assert_eq!(mem::align_of::<Packed4>(), 4);
+    assert_eq!(mem::size_of::<Packed4>(), 16);
+
+    assert_eq!(mem::align_of::<Packed8>(), 4);
+    assert_eq!(mem::size_of::<Packed8>(), 8);
+
+    assert_eq!(mem::align_of::<Packed2Tuple>(), 2);
+    assert_eq!(mem::size_of::<Packed2Tuple>(), 12);
+
+    let p2 = Packed2 { a: 1, b: 0x12345678, c: 3 };
+    assert_eq!(offset_of(&p2, &p2.b), 2);
+    assert_eq!(offset_of(&p2, &p2.c), 6);
+    assert_eq!((p2.a, p2.b, p2.c), (1, 0x12345678, 3));
+
+    let mut p4 = Packed4 { a: 1, b: 0x123456789abcdef0, c: 3 };
+    assert_eq!(offset_of(&p4, &p4.b), 4);
+    assert_eq!(offset_of(&p4, &p4.c), 12);
+    p4.b += 1;
+    assert_eq!((p4.a, p4.b, p4.c), (1, 0x123456789abcdef1, 3));
+
+    let t = Packed2Tuple(1, 2.5, 3);
+    assert_eq!(offset_of(&t, &t.1), 2);
+    assert_eq!((t.0, t.1, t.2), (1, 2.5, 3));
+
+    static P: Packed2 = Packed2 { a: 4, b: 5, c: 6 };
+    assert_eq!((P.a, P.b, P.c), (4, 5, 6));
+}
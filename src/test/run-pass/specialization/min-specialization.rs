@@ -0,0 +1,55 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(min_specialization, rustc_attrs)]
+
+// Test the specializations `min_specialization` accepts: those with no
+// additional requirements on lifetimes, or trait bounds beyond `Sized` and
+// traits marked `#[rustc_specialization_trait]`.
+
+#[rustc_specialization_trait]
+trait Special {}
+
+impl Special for u32 {}
+
+trait Describe {
+    fn describe(&self) -> &'static str;
+}
+
+impl<T: ?Sized> Describe for T {
+    default fn describe(&self) -> &'static str {
+        "anything"
+    }
+}
+
+impl<T> Describe for T {
+    default fn describe(&self) -> &'static str {
+        "sized"
+    }
+}
+
+impl<T: Special> Describe for T {
+    default fn describe(&self) -> &'static str {
+        "special"
+    }
+}
+
+impl<'a, T> Describe for &'a [T] {
+    fn describe(&self) -> &'static str {
+        "slice"
+    }
+}
+
+fn main() {
+    assert_eq!(<str as Describe>::describe("str"), "anything");
+    assert_eq!(<u8 as Describe>::describe(&1), "sized");
+    assert_eq!(<u32 as Describe>::describe(&1), "special");
+    assert_eq!(<&[u8] as Describe>::describe(&&[1][..]), "slice");
+}
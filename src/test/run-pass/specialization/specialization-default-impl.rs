@@ -0,0 +1,57 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(specialization)]
+
+// Test that a `default impl` may leave items to the impls specializing it,
+// and that those impls pick up the items it does provide.
+
+trait Foo {
+    fn foo_one(&self) -> &'static str;
+    fn foo_two(&self) -> &'static str;
+}
+
+struct MyStruct;
+
+default impl<T> Foo for T {
+    fn foo_one(&self) -> &'static str {
+        "generic"
+    }
+}
+
+impl Foo for MyStruct {
+    fn foo_two(&self) -> &'static str {
+        "specific"
+    }
+}
+
+unsafe trait Bar {
+    fn bar(&self) -> u8;
+    fn baz(&self) -> u8;
+}
+
+default unsafe impl<T: Clone> Bar for T {
+    fn bar(&self) -> u8 {
+        0
+    }
+}
+
+unsafe impl Bar for u8 {
+    fn baz(&self) -> u8 {
+        *self
+    }
+}
+
+fn main() {
+    assert!(MyStruct.foo_one() == "generic");
+    assert!(MyStruct.foo_two() == "specific");
+    assert!(7u8.bar() == 0);
+    assert!(7u8.baz() == 7);
+}
@@ -0,0 +1,60 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Unions are passed to and returned from C functions the way C does it.
+
+#![feature(untagged_unions)]
+
+#[repr(C)]
+union FloatBits {
+    d: f64,
+    f: f32,
+}
+
+impl Clone for FloatBits {
+    fn clone(&self) -> Self { *self }
+}
+
+impl Copy for FloatBits {}
+
+// C classifies this by both fields, so it is passed as an integer.
+#[repr(C)]
+union IntOrFloat {
+    i: u32,
+    f: f32,
+}
+
+impl Clone for IntOrFloat {
+    fn clone(&self) -> Self { *self }
+}
+
+impl Copy for IntOrFloat {}
+
+#[link(name = "rust_test_helpers")]
+extern {
+    fn rust_dbg_union_get_double(u: FloatBits) -> f64;
+    fn rust_dbg_union_make_double(d: f64) -> FloatBits;
+    fn rust_dbg_union_get_int(u: IntOrFloat) -> u32;
+    fn rust_dbg_union_make_int(i: u32) -> IntOrFloat;
+}
+
+fn main() {
+    unsafe {
+        let u = FloatBits { d: 2.5 };
+        assert_eq!(rust_dbg_union_get_double(u), 2.5);
+        let u = rust_dbg_union_make_double(4.0);
+        assert_eq!(u.d, 4.0);
+
+        let u = IntOrFloat { f: 1.0 };
+        assert_eq!(rust_dbg_union_get_int(u), 0x3f80_0000);
+        let u = rust_dbg_union_make_int(0x4048_0000);
+        assert_eq!(u.f, 3.125);
+    }
+}
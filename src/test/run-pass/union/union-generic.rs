@@ -0,0 +1,38 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(untagged_unions)]
+
+union MaybeItem<T: Iterator> where T::Item: Copy {
+    elem: T::Item,
+    none: (),
+}
+
+union W<A: Copy, B: Copy> {
+    a: A,
+    b: B,
+}
+
+fn main() {
+    let w = W::<u32, [u8; 4]> { b: [0, 0, 0, 0] };
+    unsafe {
+        assert_eq!(w.a, 0);
+    }
+    let w = W::<u32, f32> { b: 1.0 };
+    unsafe {
+        assert_eq!(w.a, 0x3f80_0000);
+    }
+
+    let i = MaybeItem::<std::vec::IntoIter<u8>> { elem: 10 };
+    unsafe {
+        assert_eq!(i.elem, 10);
+    }
+    let _ = MaybeItem::<std::vec::IntoIter<u8>> { none: () };
+}